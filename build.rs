@@ -1,11 +1,19 @@
-#[cfg(target_os = "windows")]
 fn main() {
-    let mut res = winres::WindowsResource::new();
-    res.set_icon("assets/icon/app.ico");
-    res.compile().unwrap();
-}
+    // Plugins are only loaded when built by the same compiler (see `declare_plugin!`)
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = std::process::Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| "unknown rustc".to_string());
+    println!("cargo:rustc-env=GTEXVIEWER_RUSTC_VERSION={version}");
 
-#[cfg(not(target_os = "windows"))]
-fn main() {
-    // Do nothing for non-Windows platforms
+    #[cfg(target_os = "windows")]
+    {
+        let mut res = winres::WindowsResource::new();
+        res.set_icon("assets/icon/app.ico");
+        res.compile().unwrap();
+    }
 }
@@ -0,0 +1,167 @@
+/// Relative coverage loss (compared to mip 0) above which a texture is flagged as thinning
+pub const COVERAGE_LOSS_THRESHOLD: f32 = 0.25;
+
+/// Mips smaller than this (in either dimension) are too coarse to judge and are ignored
+const MIN_EVALUATED_MIP_SIZE: u32 = 4;
+
+/// Alpha histogram for a single mip level
+#[derive(Debug, Clone)]
+struct MipAlphaHistogram {
+    width: u32,
+    height: u32,
+    histogram: [u32; 256],
+}
+
+/// Alpha-test coverage for every level of a box-filtered mip chain
+///
+/// Only histograms are stored, so coverage can be re-evaluated for any cutoff
+/// without keeping the decoded pixels around.
+#[derive(Debug, Clone)]
+pub struct AlphaCoverage {
+    levels: Vec<MipAlphaHistogram>,
+}
+
+/// Worst coverage drop found down the mip chain
+#[derive(Debug, Clone, Copy)]
+pub struct CoverageLoss {
+    pub base_coverage: f32,
+    pub mip_level: usize,
+    pub mip_coverage: f32,
+    pub relative_loss: f32,
+}
+
+impl AlphaCoverage {
+    /// Build the mip chain histograms from tightly packed RGBA8 data
+    /// Returns None for fully opaque images since there is nothing to alpha-test
+    pub fn from_rgba(width: u32, height: u32, rgba: &[u8]) -> Option<Self> {
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(4))?;
+        if width == 0 || height == 0 || rgba.len() < len {
+            return None;
+        }
+
+        let mut alpha: Vec<u8> = rgba.chunks_exact(4).map(|px| px[3]).collect();
+        if alpha.iter().all(|&a| a == 255) {
+            return None;
+        }
+
+        let mut levels = Vec::new();
+        let (mut w, mut h) = (width, height);
+
+        loop {
+            let mut histogram = [0u32; 256];
+            for &a in &alpha {
+                histogram[a as usize] += 1;
+            }
+            levels.push(MipAlphaHistogram {
+                width: w,
+                height: h,
+                histogram,
+            });
+
+            if w == 1 && h == 1 {
+                break;
+            }
+
+            // 2x2 box filter, clamping at odd edges like most runtime mip generators
+            let next_w = (w / 2).max(1);
+            let next_h = (h / 2).max(1);
+            let mut next = Vec::with_capacity(next_w as usize * next_h as usize);
+            for y in 0..next_h {
+                let y0 = (y * 2).min(h - 1);
+                let y1 = (y * 2 + 1).min(h - 1);
+                for x in 0..next_w {
+                    let x0 = (x * 2).min(w - 1);
+                    let x1 = (x * 2 + 1).min(w - 1);
+                    let texel = |x: u32, y: u32| alpha[y as usize * w as usize + x as usize] as u32;
+                    let sum = texel(x0, y0) + texel(x1, y0) + texel(x0, y1) + texel(x1, y1);
+                    next.push(((sum + 2) / 4) as u8);
+                }
+            }
+
+            alpha = next;
+            w = next_w;
+            h = next_h;
+        }
+
+        Some(Self { levels })
+    }
+
+    /// Number of mip levels analysed (including mip 0)
+    pub fn mip_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Fraction of texels passing the alpha test (alpha >= cutoff) at each mip level
+    pub fn coverage_at(&self, cutoff: f32) -> Vec<f32> {
+        let threshold = (cutoff.clamp(0.0, 1.0) * 255.0).ceil() as usize;
+
+        self.levels
+            .iter()
+            .map(|level| {
+                let total = level.width as f32 * level.height as f32;
+                let passing: u32 = level.histogram[threshold..].iter().sum();
+                passing as f32 / total
+            })
+            .collect()
+    }
+
+    /// Find the mip level with the largest coverage drop relative to mip 0
+    pub fn worst_loss(&self, cutoff: f32) -> Option<CoverageLoss> {
+        let coverage = self.coverage_at(cutoff);
+        let base_coverage = *coverage.first()?;
+
+        if base_coverage <= 0.0 {
+            return None;
+        }
+
+        self.levels
+            .iter()
+            .zip(coverage.iter())
+            .enumerate()
+            .skip(1)
+            .filter(|(_, (level, _))| {
+                level.width >= MIN_EVALUATED_MIP_SIZE && level.height >= MIN_EVALUATED_MIP_SIZE
+            })
+            .map(|(mip_level, (_, &mip_coverage))| CoverageLoss {
+                base_coverage,
+                mip_level,
+                mip_coverage,
+                relative_loss: (base_coverage - mip_coverage) / base_coverage,
+            })
+            .max_by(|a, b| a.relative_loss.total_cmp(&b.relative_loss))
+    }
+
+    /// True when coverage thins out down the chain (the classic disappearing-foliage problem)
+    pub fn is_thinning(&self, cutoff: f32) -> bool {
+        self.worst_loss(cutoff)
+            .is_some_and(|loss| loss.relative_loss > COVERAGE_LOSS_THRESHOLD)
+    }
+
+    /// Short human-readable summary for the info panel
+    pub fn summary(&self, cutoff: f32) -> String {
+        match self.worst_loss(cutoff) {
+            Some(loss) => {
+                let warning = if loss.relative_loss > COVERAGE_LOSS_THRESHOLD {
+                    " - THINNING"
+                } else {
+                    ""
+                };
+                format!(
+                    "@{:.2}: mip0 {:.0}% -> mip{} {:.0}% ({:+.0}%){}",
+                    cutoff,
+                    loss.base_coverage * 100.0,
+                    loss.mip_level,
+                    loss.mip_coverage * 100.0,
+                    -loss.relative_loss * 100.0,
+                    warning
+                )
+            }
+            None => {
+                let base = self.coverage_at(cutoff).first().copied().unwrap_or(0.0);
+                format!("@{:.2}: mip0 {:.0}%", cutoff, base * 100.0)
+            }
+        }
+    }
+}
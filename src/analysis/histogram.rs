@@ -0,0 +1,44 @@
+/// Number of bins per channel, one per 8-bit value
+pub const HISTOGRAM_BINS: usize = 256;
+
+/// Per-channel value counts of the decoded RGBA8 pixels
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    pub channels: [[u32; HISTOGRAM_BINS]; 4], // R, G, B, A
+    pub has_alpha: bool,                      // Any pixel below full opacity
+}
+
+impl Histogram {
+    /// Count every pixel of tightly packed RGBA8 data
+    /// Returns None for empty images
+    pub fn from_rgba(rgba: &[u8]) -> Option<Self> {
+        if rgba.len() < 4 {
+            return None;
+        }
+
+        let mut channels = [[0u32; HISTOGRAM_BINS]; 4];
+        for pixel in rgba.chunks_exact(4) {
+            for (channel, &value) in pixel.iter().enumerate() {
+                channels[channel][value as usize] += 1;
+            }
+        }
+
+        let has_alpha = channels[3][..HISTOGRAM_BINS - 1]
+            .iter()
+            .any(|&count| count > 0);
+        Some(Self {
+            channels,
+            has_alpha,
+        })
+    }
+
+    /// Largest bin over the colour channels (and alpha when present), for normalizing plots
+    pub fn peak(&self) -> u32 {
+        let channels = if self.has_alpha { 4 } else { 3 };
+        self.channels[..channels]
+            .iter()
+            .flat_map(|bins| bins.iter().copied())
+            .max()
+            .unwrap_or(0)
+    }
+}
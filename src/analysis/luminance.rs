@@ -0,0 +1,68 @@
+/// Fraction of pixels ignored at each end when computing the robust low/high values
+const PERCENTILE_TAIL: f32 = 0.01;
+
+/// Pixels with alpha at or below this are treated as empty and skipped
+const MIN_COUNTED_ALPHA: u8 = 8;
+
+/// Luma statistics in display-encoded (sRGB) 0-255 units, the scale PBR albedo
+/// guidelines are usually written in
+#[derive(Debug, Clone, Copy)]
+pub struct LuminanceStats {
+    pub min: u8,
+    pub max: u8,
+    pub mean: f32,
+    pub low: u8,  // 1st percentile
+    pub high: u8, // 99th percentile
+}
+
+impl LuminanceStats {
+    /// Compute Rec.709 luma statistics from tightly packed RGBA8 data
+    /// Returns None when the image has no visible pixels
+    pub fn from_rgba(rgba: &[u8]) -> Option<Self> {
+        let mut histogram = [0u64; 256];
+        let mut total = 0u64;
+
+        for pixel in rgba.chunks_exact(4) {
+            if pixel[3] <= MIN_COUNTED_ALPHA {
+                continue;
+            }
+            let luma =
+                0.2126 * pixel[0] as f32 + 0.7152 * pixel[1] as f32 + 0.0722 * pixel[2] as f32;
+            histogram[(luma.round() as usize).min(255)] += 1;
+            total += 1;
+        }
+
+        if total == 0 {
+            return None;
+        }
+
+        let min = histogram.iter().position(|&count| count > 0)? as u8;
+        let max = histogram.iter().rposition(|&count| count > 0)? as u8;
+        let mean = histogram
+            .iter()
+            .enumerate()
+            .map(|(value, &count)| value as f64 * count as f64)
+            .sum::<f64>()
+            / total as f64;
+
+        Some(Self {
+            min,
+            max,
+            mean: mean as f32,
+            low: Self::percentile(&histogram, total, PERCENTILE_TAIL),
+            high: Self::percentile(&histogram, total, 1.0 - PERCENTILE_TAIL),
+        })
+    }
+
+    fn percentile(histogram: &[u64; 256], total: u64, fraction: f32) -> u8 {
+        let target = (total as f64 * fraction as f64).ceil().max(1.0) as u64;
+        let mut cumulative = 0u64;
+        for (value, &count) in histogram.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return value as u8;
+            }
+        }
+        255
+    }
+}
@@ -0,0 +1,43 @@
+pub mod alpha_coverage;
+pub mod histogram;
+pub mod luminance;
+pub mod premultiplied;
+pub mod streaming;
+
+pub use alpha_coverage::AlphaCoverage;
+pub use histogram::Histogram;
+pub use luminance::LuminanceStats;
+pub use premultiplied::{PremultipliedAlpha, UnpremultiplyMode};
+pub use streaming::{StreamingConfig, StreamingCost};
+
+use macroquad::prelude::Image;
+
+use crate::texture_pipeline::LoadedImageData;
+
+/// Default alpha-test cutoff used when analysing newly loaded textures
+pub const DEFAULT_ALPHA_CUTOFF: f32 = 0.5;
+
+/// CPU-side analysis computed once per texture on the loader thread,
+/// while the decoded pixels are still available
+#[derive(Debug, Clone, Default)]
+pub struct TextureAnalysis {
+    pub alpha_coverage: Option<AlphaCoverage>,
+    pub histogram: Option<Histogram>,
+    pub luminance: Option<LuminanceStats>,
+    pub premultiplied: Option<PremultipliedAlpha>,
+}
+
+impl TextureAnalysis {
+    pub fn analyze(data: &LoadedImageData, image: &Image) -> Self {
+        Self {
+            alpha_coverage: AlphaCoverage::from_rgba(
+                image.width as u32,
+                image.height as u32,
+                &image.bytes,
+            ),
+            histogram: Histogram::from_rgba(&image.bytes),
+            luminance: LuminanceStats::from_rgba(&image.bytes),
+            premultiplied: PremultipliedAlpha::detect(data, &image.bytes),
+        }
+    }
+}
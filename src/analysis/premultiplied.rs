@@ -0,0 +1,128 @@
+use imagesize::ImageType;
+
+use crate::texture_pipeline::LoadedImageData;
+
+/// KTX2 data format descriptor flag marking premultiplied colour (KHR_DF_FLAG_ALPHA_PREMULTIPLIED)
+const KTX2_DF_FLAG_ALPHA_PREMULTIPLIED: u8 = 1;
+
+/// Offset of `dfdByteOffset` in the KTX2 header
+const KTX2_DFD_OFFSET_FIELD: usize = 48;
+
+/// Offset of the flags byte from the start of the DFD: total size, then the basic
+/// block's vendor/type and version/size words, then model, primaries, transfer, flags
+const KTX2_DFD_FLAGS_OFFSET: usize = 4 + 8 + 3;
+
+/// Minimum share of partially transparent pixels for the heuristic to decide; images
+/// with only hard edges look the same either way
+const MIN_TRANSLUCENT_SHARE: f32 = 0.01;
+
+/// Rounding slack when checking colour against alpha in 8-bit data
+const CHANNEL_TOLERANCE: u8 = 1;
+
+/// Which premultiplied images are divided by alpha for display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnpremultiplyMode {
+    Authoritative, // Only verdicts from file metadata
+    All,           // Also images the pixel heuristic flags
+    Off,           // Everything shown as stored
+}
+
+impl UnpremultiplyMode {
+    pub fn next(self) -> Self {
+        match self {
+            UnpremultiplyMode::Authoritative => UnpremultiplyMode::All,
+            UnpremultiplyMode::All => UnpremultiplyMode::Off,
+            UnpremultiplyMode::Off => UnpremultiplyMode::Authoritative,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            UnpremultiplyMode::Authoritative => "un-premultiplied when the file says so",
+            UnpremultiplyMode::All => "un-premultiplied when flagged or detected",
+            UnpremultiplyMode::Off => "shown as stored",
+        }
+    }
+
+    pub fn applies_to(self, verdict: PremultipliedAlpha) -> bool {
+        match self {
+            UnpremultiplyMode::Authoritative => verdict.is_authoritative(),
+            UnpremultiplyMode::All => true,
+            UnpremultiplyMode::Off => false,
+        }
+    }
+}
+
+/// Where a premultiplied-alpha verdict came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PremultipliedAlpha {
+    Ktx2Flag,  // Premultiplied flag in the KTX2 data format descriptor
+    Exr,       // OpenEXR stores associated alpha by definition
+    Heuristic, // No colour channel ever exceeds alpha
+}
+
+impl PremultipliedAlpha {
+    /// Container metadata first, then the pixel heuristic on tightly packed RGBA8 data
+    /// Returns None for straight alpha and images without usable transparency
+    pub fn detect(data: &LoadedImageData, rgba: &[u8]) -> Option<Self> {
+        match data.format {
+            // The flag is authoritative; unset means straight alpha
+            ImageType::Ktx2 => Self::ktx2_flag(&data.data).then_some(PremultipliedAlpha::Ktx2Flag),
+            ImageType::Exr => Self::has_transparency(rgba).then_some(PremultipliedAlpha::Exr),
+            _ => Self::heuristic(rgba).then_some(PremultipliedAlpha::Heuristic),
+        }
+    }
+
+    /// Verdicts from file metadata; the pixel heuristic can't tell straight alpha with
+    /// dark translucent content (smoke, shadows) apart from premultiplied
+    pub fn is_authoritative(self) -> bool {
+        self != PremultipliedAlpha::Heuristic
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PremultipliedAlpha::Ktx2Flag => "Premultiplied (KTX2 flag)",
+            PremultipliedAlpha::Exr => "Premultiplied (EXR)",
+            PremultipliedAlpha::Heuristic => "Premultiplied (detected)",
+        }
+    }
+
+    fn ktx2_flag(bytes: &[u8]) -> bool {
+        let flags = bytes
+            .get(KTX2_DFD_OFFSET_FIELD..KTX2_DFD_OFFSET_FIELD + 4)
+            .map(|field| u32::from_le_bytes([field[0], field[1], field[2], field[3]]) as usize)
+            .and_then(|dfd_offset| bytes.get(dfd_offset + KTX2_DFD_FLAGS_OFFSET));
+        flags.is_some_and(|&flags| flags & KTX2_DF_FLAG_ALPHA_PREMULTIPLIED != 0)
+    }
+
+    fn has_transparency(rgba: &[u8]) -> bool {
+        rgba.chunks_exact(4).any(|pixel| pixel[3] < 255)
+    }
+
+    /// Premultiplied colour can never exceed alpha; straight alpha usually does somewhere,
+    /// at least in the partially transparent fringe
+    fn heuristic(rgba: &[u8]) -> bool {
+        let mut translucent = 0usize;
+        let mut coloured = false;
+
+        for pixel in rgba.chunks_exact(4) {
+            let alpha = pixel[3];
+            if alpha == 255 {
+                continue;
+            }
+            if pixel[..3]
+                .iter()
+                .any(|&channel| channel > alpha.saturating_add(CHANNEL_TOLERANCE))
+            {
+                return false;
+            }
+            if alpha > 0 {
+                translucent += 1;
+                coloured |= pixel[..3].iter().any(|&channel| channel > 0);
+            }
+        }
+
+        let pixels = rgba.len() / 4;
+        coloured && pixels > 0 && translucent as f32 / pixels as f32 >= MIN_TRANSLUCENT_SHARE
+    }
+}
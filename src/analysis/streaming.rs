@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::ops::AddAssign;
+use std::path::{Path, PathBuf};
+
+use crate::loading::LoadedImage;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Environment variable pointing at a streaming configuration file
+pub const STREAMING_CONFIG_ENV: &str = "GTEXVIEWER_STREAMING";
+
+/// Streaming configuration picked up from the working directory when no override is given
+pub const DEFAULT_STREAMING_CONFIG_FILE: &str = "gtexviewer-streaming.toml";
+
+/// Target engine texture-streaming setup used for budget estimates
+///
+/// ```toml
+/// streamed_mips = true
+/// resident_mips = 7            # smallest mips that never stream out
+/// opaque_bits_per_pixel = 4.0  # e.g. BC1
+/// alpha_bits_per_pixel = 8.0   # e.g. BC3/BC7
+/// disk_compression_ratio = 0.6 # package compression
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreamingConfig {
+    pub streamed_mips: bool,
+    pub resident_mips: u32,
+    pub opaque_bits_per_pixel: f32,
+    pub alpha_bits_per_pixel: f32,
+    pub disk_compression_ratio: f32,
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            streamed_mips: true,
+            resident_mips: 7,
+            opaque_bits_per_pixel: 4.0,
+            alpha_bits_per_pixel: 8.0,
+            disk_compression_ratio: 1.0,
+        }
+    }
+}
+
+impl StreamingConfig {
+    /// Config from `$GTEXVIEWER_STREAMING` or the working directory, else engine defaults
+    /// Relative paths fall back to the executable's folder in portable mode
+    pub fn load_default() -> Self {
+        let path = crate::portable::resolve_config_path(
+            std::env::var(STREAMING_CONFIG_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(DEFAULT_STREAMING_CONFIG_FILE)),
+        );
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::load(&path) {
+            Ok(config) => {
+                log::info!("💾 Streaming config loaded from {}", path.display());
+                config
+            }
+            Err(e) => {
+                log::warn!(
+                    "⚠️ Failed to load streaming config {}: {e:#}",
+                    path.display()
+                );
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read streaming config: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid streaming config: {}", path.display()))
+    }
+
+    /// Estimated GPU and disk cost of a texture with a full mip chain in the target format
+    pub fn estimate(&self, width: u32, height: u32, has_alpha: bool) -> StreamingCost {
+        let bits_per_pixel = if has_alpha {
+            self.alpha_bits_per_pixel
+        } else {
+            self.opaque_bits_per_pixel
+        };
+
+        let mip_sizes: Vec<u64> = mip_chain(width, height)
+            .map(|(w, h)| {
+                // Block-compressed formats store whole 4x4 blocks
+                let texels = (w.div_ceil(4) * 4) as u64 * (h.div_ceil(4) * 4) as u64;
+                (texels as f64 * bits_per_pixel as f64 / 8.0).ceil() as u64
+            })
+            .collect();
+        let total: u64 = mip_sizes.iter().sum();
+
+        let resident = if self.streamed_mips {
+            let resident_count = (self.resident_mips as usize).min(mip_sizes.len());
+            mip_sizes[mip_sizes.len() - resident_count..].iter().sum()
+        } else {
+            total
+        };
+
+        StreamingCost {
+            resident_bytes: resident,
+            pooled_bytes: total - resident,
+            disk_bytes: (total as f64 * self.disk_compression_ratio as f64).round() as u64,
+            textures: 1,
+        }
+    }
+}
+
+/// Mip dimensions from the base level down to 1x1
+fn mip_chain(width: u32, height: u32) -> impl Iterator<Item = (u32, u32)> {
+    let levels = 32 - width.max(height).max(1).leading_zeros();
+    (0..levels).map(move |level| ((width >> level).max(1), (height >> level).max(1)))
+}
+
+/// Estimated memory a texture (or a whole container) costs under a streaming config
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamingCost {
+    pub resident_bytes: u64, // Always in memory (non-streamed mips)
+    pub pooled_bytes: u64,   // Streaming pool usage when fully streamed in
+    pub disk_bytes: u64,
+    pub textures: usize,
+}
+
+impl AddAssign for StreamingCost {
+    fn add_assign(&mut self, other: Self) {
+        self.resident_bytes += other.resident_bytes;
+        self.pooled_bytes += other.pooled_bytes;
+        self.disk_bytes += other.disk_bytes;
+        self.textures += other.textures;
+    }
+}
+
+impl StreamingCost {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} resident + {} pool, {} disk",
+            format_bytes(self.resident_bytes),
+            format_bytes(self.pooled_bytes),
+            format_bytes(self.disk_bytes)
+        )
+    }
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    if mb >= 1.0 {
+        format!("{mb:.1} MB")
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+impl GTexViewerApp {
+    pub fn image_streaming_cost(&self, image: &LoadedImage) -> StreamingCost {
+        self.streaming_config.estimate(
+            image.info.width,
+            image.info.height,
+            image.analysis.alpha_coverage.is_some(),
+        )
+    }
+
+    /// Total cost of every loaded texture that came from the same container file
+    pub fn container_streaming_cost(&self, source_path: &Path) -> StreamingCost {
+        let mut total = StreamingCost::default();
+        for slot in &self.image_slots {
+            if let ImageState::Loaded { image } = &slot.state
+                && image.metadata.source_path == source_path
+            {
+                total += self.image_streaming_cost(image);
+            }
+        }
+        total
+    }
+}
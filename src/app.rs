@@ -6,12 +6,15 @@ use crate::types::GTexViewerApp;
 impl GTexViewerApp {
     pub async fn new(initial_file: Option<String>) -> Self {
         use crate::loading::AsyncImageLoader;
-        use crate::types::ChannelMode;
+        use crate::timeline::FrameCompare;
+        use crate::types::{ChannelMode, FilterOverride};
         use macroquad::math::Rect as MacroRect;
         use taffy::prelude::TaffyTree;
 
         let mut app = Self {
             image_slots: Vec::new(),
+            tabs: vec![crate::tabs::Session::default()],
+            active_tab: 0,
             initial_file_path: None,
             metadata_receivers: Vec::new(),
             async_loader: AsyncImageLoader::new(),
@@ -24,12 +27,71 @@ impl GTexViewerApp {
             taffy_tree: TaffyTree::new(),
             channel_switch_material: None,
             channel_mode: ChannelMode::Normal,
+            false_color: crate::false_color::FalseColor::Off,
+            filter_override: FilterOverride::from_env(),
             hovered_image_info: None,
             ui_text_queue: Vec::new(),
             pending_metadata: Vec::new(),
             burst_render_until: Some(std::time::Instant::now() + std::time::Duration::from_secs(1)), // Force 1 second of rendering on startup
             ui_font: None,
             metadata_cancel_flag: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            workers: crate::loading::WorkerTracker::new(),
+            alpha_cutoff: crate::analysis::DEFAULT_ALPHA_CUTOFF,
+            levels: crate::levels::LevelsCurve::default(),
+            exposure_gamma: crate::exposure::ExposureGamma::default(),
+            color_lut: crate::lut::ColorLut::load_default(),
+            levels_drag: None,
+            sampling: crate::sampling::SamplingSettings::default(),
+            sampling_drag: false,
+            minimap_dragging: false,
+            display_views: crate::display_transform::available_views(),
+            display_view_index: 0,
+            color_managed: true,
+            unpremultiply: crate::analysis::UnpremultiplyMode::Authoritative,
+            monitor_profile: crate::color_profile::MonitorProfile::load_default(),
+            monitor_profile_enabled: true,
+            gamut_overlay: crate::gamut::GamutOverlay::default(),
+            guides: crate::guides::GuideOverlay::default(),
+            color_vision: crate::color_vision::ColorVision::Normal,
+            surface_preview: crate::surface_preview::SurfacePreview::default(),
+            validation_rules: crate::validation::ValidationRules::load_default(),
+            streaming_config: crate::analysis::StreamingConfig::load_default(),
+            directory_scan: None,
+            container_watcher: None,
+            dropped_roots: Vec::new(),
+            highlighted_slots: Vec::new(),
+            highlight_until: None,
+            selected_slots: Vec::new(),
+            focus: None,
+            last_click: None,
+            slideshow: None,
+            pixel_diff: None,
+            pixel_diff_pending: None,
+            sync_compare: None,
+            password_prompt: None,
+            animation_focus: None,
+            timeline_dragging: false,
+            frame_compare: FrameCompare::Off,
+            expand_frames: false,
+            cubemap_layout: crate::cubemap_view::CubemapLayout::Face,
+            offset_map_container: None,
+            histogram_target: None,
+            details_target: None,
+            breadcrumb_target: None,
+            pixel_probe: crate::pixel_inspector::PixelProbe::default(),
+            eyedropper: crate::eyedropper::Eyedropper::default(),
+            jump_palette: None,
+            slot_filter: crate::slot_filter::SlotFilter::default(),
+            sort_mode: crate::slot_sort::SortMode::default(),
+            show_captions: false,
+            slot_groups: Vec::new(),
+            collapsed_groups: std::collections::HashSet::new(),
+            group_press: None,
+            crop_drag: None,
+            workspace: crate::workspace::WorkspaceLayout::load_default(),
+            texture_cache: crate::texture_cache::TextureCache::from_env(),
+            full_resolution_pending: std::collections::HashSet::new(),
+            placeholder_previews: std::collections::HashMap::new(),
         };
 
         // Load initial file if provided (from file association)
@@ -59,33 +121,203 @@ impl GTexViewerApp {
             }
         }
 
+        // Feed paths discovered by the background directory scan into the pipeline
+        self.update_directory_scan();
+
         // Check for completed metadata extraction
         self.check_metadata_results();
 
         // Update async image loading from Rayon
         self.update_async_loading();
 
+        // Swap between proxy and full-resolution textures as slots grow and shrink on screen
+        self.update_resolution_tiers();
+
+        // Keep uploaded textures within the VRAM budget
+        self.update_texture_cache();
+
+        // Reload entries of containers edited on disk since they were scanned
+        self.update_container_watch();
+
         // Load initial file if provided via command line
         self.load_initial_file_if_needed();
 
         // Handle drag and drop for multiple files
         self.handle_file_drops();
 
+        // Handle http(s) URLs pasted from the clipboard
+        self.handle_paste_input();
+
+        // Password entry for encrypted containers takes over the keyboard while open
+        if self.handle_password_prompt_input() {
+            self.update_hover_info();
+            return;
+        }
+
+        // Jump palette captures typing while open; animations keep playing underneath
+        if self.handle_jump_palette_input() {
+            self.update_animations();
+            self.update_hover_info();
+            return;
+        }
+
+        // So does the `/` filter box while typing
+        if self.handle_slot_filter_input() {
+            self.update_animations();
+            self.update_hover_info();
+            return;
+        }
+
+        // The pixel diff view covers the canvas while open
+        if self.handle_pixel_diff_input() {
+            return;
+        }
+
+        // The slideshow takes over navigation too; animations keep playing in it
+        if self.handle_slideshow_input() {
+            self.update_animations();
+            self.update_pixel_probe();
+            self.update_hover_info();
+            return;
+        }
+
+        // As does the synchronized side-by-side compare, which keeps animations playing
+        if self.handle_sync_compare_input() {
+            self.update_animations();
+            return;
+        }
+
+        // Advance animated images
+        self.update_animations();
+
+        // Toggle animated images between playback and one slot per frame
+        self.handle_frame_expansion_input();
+
+        // Panel visibility, docking and workspace presets
+        self.handle_workspace_input();
+
+        // Cycle the canvas background
+        self.handle_background_input();
+
+        // Tab strip clicks and Ctrl+T/W/PageUp/PageDown switch sessions
+        let tabs_captured_mouse = self.handle_tab_input();
+        // Clicking the minimap jumps the view instead of panning
+        let minimap_captured_mouse = self.handle_minimap_input();
+        // Breadcrumb steps filter to their container when clicked
+        let breadcrumb_captured_mouse = self.handle_breadcrumb_input();
+        // Handle levels/sampling widgets and timeline first so dragging their handles doesn't pan the camera
+        let levels_captured_mouse = self.handle_levels_input();
+        let sampling_captured_mouse = self.handle_sampling_input();
+        let timeline_captured_mouse = self.handle_timeline_input();
+        // Shift+drag crop selection also replaces panning
+        let crop_captured_mouse = self.handle_crop_input();
+        // Double-click isolates an image; Escape leaves it unless the colour picker takes it first
+        let focus_captured_mouse = self.handle_focus_input();
+        // Clicks in colour picker mode copy the texel instead of panning
+        let eyedropper_captured_mouse = self.handle_eyedropper_input();
+        // Ctrl+click selects images for comparison
+        let selection_captured_mouse = self.handle_selection_input();
+        let ui_captured_mouse = tabs_captured_mouse
+            || minimap_captured_mouse
+            || breadcrumb_captured_mouse
+            || levels_captured_mouse
+            || sampling_captured_mouse
+            || timeline_captured_mouse
+            || crop_captured_mouse
+            || eyedropper_captured_mouse
+            || selection_captured_mouse
+            || focus_captured_mouse;
+
         // Handle camera input
-        self.handle_camera_input();
+        if !ui_captured_mouse {
+            self.handle_camera_input();
+        }
 
         // Handle channel switching input
         self.handle_channel_input();
+        self.handle_false_color_input();
+
+        // Handle texture filtering override
+        self.handle_filter_input();
 
-        // Handle layout recalculation input
+        // Handle cubemap layout (single face / cross / equirectangular)
+        self.handle_cubemap_input();
+
+        // Handle texture array layer browsing
+        self.handle_layer_input();
+
+        // Handle Basis Universal transcode target selection
+        self.handle_transcode_input();
+
+        // Handle per-image wrap mode / tiling preview
+        self.handle_tiling_input();
+
+        // Handle layout recalculation, grid sort order, group collapsing and caption input
         self.handle_layout_input();
+        self.handle_sort_input();
+        self.handle_group_input();
+        self.handle_caption_input();
+
+        // Handle alpha-test cutoff adjustment
+        self.handle_alpha_cutoff_input();
+
+        // Handle premultiplied alpha display
+        self.handle_unpremultiply_input();
+
+        // Handle exposure/gamma adjustment
+        self.handle_exposure_input();
+
+        // Handle LUT toggle
+        self.handle_lut_input();
+
+        // Handle display/view transform selection
+        self.handle_display_transform_input();
+        self.handle_monitor_profile_input();
+        self.handle_color_management_input();
+
+        // Handle out-of-gamut overlay selection
+        self.handle_gamut_input();
+
+        // Handle colour vision deficiency simulation
+        self.handle_color_vision_input();
+
+        // Handle normal map lighting preview
+        self.handle_surface_preview_input();
 
-        // Update hover info
+        // Handle validation report export
+        self.handle_report_export_input();
+
+        // Handle saving metadata-stripped copies
+        self.handle_strip_export_input();
+
+        // Handle hint manifest export
+        self.handle_manifest_export_input();
+
+        // Handle container offset map
+        self.handle_offset_map_input();
+
+        // Handle histogram panel
+        self.handle_histogram_input();
+
+        // Toggle the details sidebar and follow the hovered image
+        self.handle_details_input();
+
+        // Handle alignment guides
+        self.handle_guides_input();
+
+        // Update hover info, with the exact value of the texel under the cursor
+        self.update_pixel_probe();
         self.update_hover_info();
     }
 
     pub async fn draw(&mut self) {
-        clear_background(BLACK);
+        clear_background(self.workspace.background.color());
+
+        // Synchronized compare replaces the grid and its UI with one pane per image
+        if self.sync_compare.is_some() {
+            self.draw_sync_compare();
+            return;
+        }
 
         // Clear UI text queue for this frame
         self.ui_text_queue.clear();
@@ -116,6 +348,12 @@ impl GTexViewerApp {
             draw_text_ex(&ui_text.text, ui_text.x, ui_text.y, ui_text_params);
         }
 
+        // The slideshow shows only its caption over the image
+        if self.slideshow.is_some() {
+            self.draw_slideshow();
+            return;
+        }
+
         // Draw UI elements
         self.draw_ui();
     }
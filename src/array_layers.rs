@@ -0,0 +1,69 @@
+use macroquad::prelude::*;
+
+use crate::sampling::upload_texture;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Uploaded layers of a KTX2/DDS texture array
+#[derive(Clone)]
+pub struct LayerStack {
+    pub layers: Vec<Texture2D>,
+    pub current: usize,
+}
+
+impl LayerStack {
+    pub fn from_images(images: &[Image]) -> Self {
+        let layers = images
+            .iter()
+            .map(|image| {
+                let texture = upload_texture(image);
+                texture.set_filter(FilterMode::Linear);
+                texture
+            })
+            .collect();
+
+        Self { layers, current: 0 }
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Step layers relative to the current one, wrapping around
+    pub fn step(&mut self, delta: isize) -> &Texture2D {
+        let count = self.layer_count() as isize;
+        self.current = (self.current as isize + delta).rem_euclid(count) as usize;
+        &self.layers[self.current]
+    }
+}
+
+impl GTexViewerApp {
+    /// , and . step through the layers of the texture array under the cursor
+    pub fn handle_layer_input(&mut self) {
+        let delta = if is_key_pressed(KeyCode::Comma) {
+            -1
+        } else if is_key_pressed(KeyCode::Period) {
+            1
+        } else {
+            return;
+        };
+
+        let mouse_screen = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+        let Some(index) = self.slot_at(mouse_world) else {
+            return;
+        };
+
+        if let ImageState::Loaded { image } = &mut self.image_slots[index].state
+            && let Some(layers) = &mut image.layers
+        {
+            image.texture = layers.step(delta).clone();
+            log::info!(
+                "🗂️ Layer {}/{} of {}",
+                layers.current + 1,
+                layers.layer_count(),
+                image.metadata.name
+            );
+            macroquad::miniquad::window::schedule_update();
+        }
+    }
+}
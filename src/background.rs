@@ -0,0 +1,59 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::GTexViewerApp;
+
+/// Canvas colour behind the images; contrasting colours expose premultiplication
+/// mistakes and colour bleeding into transparent edges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Background {
+    #[default]
+    Black,
+    Gray, // 18% linear middle gray
+    White,
+    Magenta,
+}
+
+impl Background {
+    pub fn name(self) -> &'static str {
+        match self {
+            Background::Black => "black",
+            Background::Gray => "18% gray",
+            Background::White => "white",
+            Background::Magenta => "magenta",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Background::Black => Background::Gray,
+            Background::Gray => Background::White,
+            Background::White => Background::Magenta,
+            Background::Magenta => Background::Black,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Background::Black => BLACK,
+            // 0.18 linear encoded as sRGB
+            Background::Gray => Color::from_rgba(118, 118, 118, 255),
+            Background::White => WHITE,
+            Background::Magenta => MAGENTA,
+        }
+    }
+}
+
+impl GTexViewerApp {
+    /// B cycles the canvas background: black, 18% gray, white, magenta
+    pub fn handle_background_input(&mut self) {
+        if !is_key_pressed(KeyCode::B) {
+            return;
+        }
+
+        self.workspace.background = self.workspace.background.next();
+        log::info!("🎨 Background {}", self.workspace.background.name());
+        self.save_workspace();
+    }
+}
@@ -0,0 +1,155 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+
+use crate::slot_sort::slot_metadata;
+use crate::types::GTexViewerApp;
+
+const CRUMB_SEPARATOR: &str = " › ";
+const CRUMB_FONT_SIZE: u16 = 16;
+
+/// One step of the breadcrumb and the filter a click on it applies
+struct Crumb {
+    text: String,
+    rect: MacroRect,        // Screen rect of the text
+    filter: Option<String>, // Glob for entries inside this container, None for the entry itself
+}
+
+impl GTexViewerApp {
+    /// Follow the hovered image, or the last selected one over empty space; clicking a
+    /// container step filters the grid to it. Returns true then so the click doesn't also pan
+    pub fn handle_breadcrumb_input(&mut self) -> bool {
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse = vec2(mouse_x, mouse_y);
+
+        if is_mouse_button_pressed(MouseButton::Left)
+            && let Some(filter) = self
+                .breadcrumb_crumbs()
+                .into_iter()
+                .find(|crumb| crumb.rect.contains(mouse))
+                .and_then(|crumb| crumb.filter)
+        {
+            log::info!("🔍 Filter \"{filter}\"");
+            self.slot_filter.query = filter;
+            self.slot_filter.editing = false;
+            self.layout_needs_update = true;
+            self.camera.target = Vec2::ZERO;
+            return true;
+        }
+
+        let target = self
+            .slot_at(self.screen_to_world(mouse))
+            .or_else(|| self.selected_slots.last().copied())
+            .and_then(|index| slot_metadata(self.image_slots.get(index)?))
+            .map(|metadata| metadata.entry_key());
+        if target.is_some() {
+            self.breadcrumb_target = target;
+        }
+        false
+    }
+
+    /// Source file, nested containers and entry of the breadcrumb target, laid out along the
+    /// top (under the tab strip when shown)
+    fn breadcrumb_crumbs(&self) -> Vec<Crumb> {
+        let Some(target) = &self.breadcrumb_target else {
+            return Vec::new();
+        };
+        let Some(metadata) = self
+            .image_slots
+            .iter()
+            .filter_map(slot_metadata)
+            .find(|metadata| metadata.entry_key() == *target)
+        else {
+            return Vec::new();
+        };
+
+        let file_name = metadata
+            .source_path
+            .file_name()
+            .unwrap_or(metadata.source_path.as_os_str())
+            .to_string_lossy()
+            .into_owned();
+        let mut parts = vec![file_name];
+        parts.extend(metadata.parents.iter().map(|segment| segment.name.clone()));
+        // Loose files are named after themselves
+        if parts.last() != Some(&metadata.name) {
+            parts.push(metadata.name.clone());
+        }
+
+        let y = if self.tabs.len() > 1 { 34.0 } else { 5.0 };
+        let separator_width =
+            measure_text(CRUMB_SEPARATOR, self.ui_font.as_ref(), CRUMB_FONT_SIZE, 1.0).width;
+        let mut x = 10.0;
+        let last = parts.len() - 1;
+        parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                let width = measure_text(part, self.ui_font.as_ref(), CRUMB_FONT_SIZE, 1.0).width;
+                let rect = MacroRect::new(x, y, width, 25.0);
+                x += width + separator_width;
+                // Same " / " path `display_path` gives, which the filter matches against
+                let filter = (i < last).then(|| format!("{} / *", parts[..=i].join(" / ")));
+                Crumb {
+                    text: part.clone(),
+                    rect,
+                    filter,
+                }
+            })
+            .collect()
+    }
+
+    /// Breadcrumb of the hovered or selected image; container steps are links
+    pub fn draw_breadcrumb(&self) {
+        let crumbs = self.breadcrumb_crumbs();
+        let (Some(first), Some(last)) = (crumbs.first(), crumbs.last()) else {
+            return;
+        };
+
+        draw_rectangle(
+            first.rect.x - 5.0,
+            first.rect.y,
+            last.rect.x + last.rect.w - first.rect.x + 10.0,
+            first.rect.h,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+
+        let (mouse_x, mouse_y) = mouse_position();
+        for (i, crumb) in crumbs.iter().enumerate() {
+            let hovered = crumb.filter.is_some() && crumb.rect.contains(vec2(mouse_x, mouse_y));
+            let color = match (&crumb.filter, hovered) {
+                (None, _) => WHITE,
+                (Some(_), true) => Color::new(0.5, 0.8, 1.0, 1.0),
+                (Some(_), false) => LIGHTGRAY,
+            };
+            let text_params = TextParams {
+                font: self.ui_font.as_ref(),
+                font_size: CRUMB_FONT_SIZE,
+                color,
+                ..Default::default()
+            };
+            let baseline = crumb.rect.y + 17.0;
+            draw_text_ex(&crumb.text, crumb.rect.x, baseline, text_params.clone());
+            if hovered {
+                draw_line(
+                    crumb.rect.x,
+                    baseline + 2.0,
+                    crumb.rect.x + crumb.rect.w,
+                    baseline + 2.0,
+                    1.0,
+                    color,
+                );
+            }
+            if i + 1 < crumbs.len() {
+                draw_text_ex(
+                    CRUMB_SEPARATOR,
+                    crumb.rect.x + crumb.rect.w,
+                    baseline,
+                    TextParams {
+                        color: GRAY,
+                        ..text_params
+                    },
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use macroquad::prelude::*;
+
+use crate::slot_sort::slot_metadata;
+use crate::types::GTexViewerApp;
+
+/// Band under each row left for captions while they're on, in layout pixels
+pub const CAPTION_HEIGHT: f32 = 18.0;
+/// Captions are skipped once their band is shorter than this on screen
+const MIN_CAPTION_PIXELS: f32 = 9.0;
+
+impl GTexViewerApp {
+    /// Alt+N shows or hides file names under the thumbnails
+    pub fn handle_caption_input(&mut self) {
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        if !(alt_down && is_key_pressed(KeyCode::N)) {
+            return;
+        }
+        self.show_captions = !self.show_captions;
+        self.layout_needs_update = true;
+        log::info!(
+            "🏷️ Captions {}",
+            if self.show_captions { "on" } else { "off" }
+        );
+    }
+
+    /// Entry name under each visible slot, shortened to its width; hidden when zoomed out
+    /// too far to read
+    pub fn draw_captions(&self) {
+        if !self.show_captions {
+            return;
+        }
+
+        // The band's world height is fixed by the layout; on screen it follows the zoom
+        let band_world = CAPTION_HEIGHT / (screen_width().max(screen_height()) / 2.0);
+        let band =
+            self.world_to_screen(vec2(0.0, band_world)).y - self.world_to_screen(Vec2::ZERO).y;
+        if band < MIN_CAPTION_PIXELS {
+            return;
+        }
+        let font_size = (band * 0.8).min(16.0) as u16;
+
+        let view = self.visible_world_rect();
+        for slot in self
+            .image_slots
+            .iter()
+            .filter(|slot| Self::slot_overlaps(slot, &view))
+        {
+            let Some(metadata) = slot_metadata(slot) else {
+                continue;
+            };
+            let top_left = self.world_to_screen(slot.position);
+            let bottom_right = self.world_to_screen(slot.position + slot.size);
+            let width = bottom_right.x - top_left.x;
+
+            let name = self.ellipsize(&metadata.name, font_size, width);
+            if name.is_empty() {
+                continue;
+            }
+            let text_width = measure_text(&name, self.ui_font.as_ref(), font_size, 1.0).width;
+            draw_text_ex(
+                &name,
+                (top_left.x + (width - text_width) / 2.0).round(),
+                (bottom_right.y + band * 0.75).round(),
+                TextParams {
+                    font: self.ui_font.as_ref(),
+                    font_size,
+                    color: LIGHTGRAY,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    /// `text` cut to fit `max_width` with a trailing ellipsis, empty when not even that fits
+    fn ellipsize(&self, text: &str, font_size: u16, max_width: f32) -> String {
+        let width = |text: &str| measure_text(text, self.ui_font.as_ref(), font_size, 1.0).width;
+        if width(text) <= max_width {
+            return text.to_string();
+        }
+        let mut chars: Vec<char> = text.chars().collect();
+        while !chars.is_empty() {
+            chars.pop();
+            let shortened: String = chars.iter().chain(['…'].iter()).collect();
+            if width(&shortened) <= max_width {
+                return shortened;
+            }
+        }
+        String::new()
+    }
+}
@@ -0,0 +1,283 @@
+use anyhow::{Context, Result};
+use macroquad::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::types::GTexViewerApp;
+
+/// Environment variable naming the monitor profile to apply
+/// The profile the OS assigned to the display isn't queried, so there's no fallback
+pub const ICC_PROFILE_ENV: &str = "GTEXVIEWER_ICC_PROFILE";
+
+/// Linear sRGB to PCS XYZ (D50, Bradford adapted)
+const SRGB_TO_XYZ_D50: [[f32; 3]; 3] = [
+    [0.436_074_7, 0.385_064_9, 0.143_080_4],
+    [0.222_504_5, 0.716_878_6, 0.060_616_9],
+    [0.013_932_2, 0.097_104_5, 0.714_173_3],
+];
+
+/// Matrix/TRC monitor profile reduced to what the display shader can apply:
+/// a linear sRGB to monitor RGB matrix followed by per-channel gamma encoding
+#[derive(Debug, Clone)]
+pub struct MonitorProfile {
+    pub name: String,
+    pub srgb_to_display: [[f32; 3]; 3],
+    pub gamma: [f32; 3],
+}
+
+impl MonitorProfile {
+    /// Parse the profile named by `$GTEXVIEWER_ICC_PROFILE`; None when it's unset
+    pub fn load_default() -> Option<Self> {
+        let path = PathBuf::from(std::env::var_os(ICC_PROFILE_ENV)?);
+        match Self::from_file(&path) {
+            Ok(profile) => {
+                log::info!(
+                    "🖥️ Monitor profile loaded: {} ({})",
+                    profile.name,
+                    path.display()
+                );
+                Some(profile)
+            }
+            Err(e) => {
+                log::warn!("⚠️ Failed to load monitor profile {}: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read ICC profile: {}", path.display()))?;
+        Self::from_icc_bytes(&data)
+    }
+
+    pub fn from_icc_bytes(data: &[u8]) -> Result<Self> {
+        if data.len() < 132 || &data[36..40] != b"acsp" {
+            anyhow::bail!("Not an ICC profile");
+        }
+        if &data[16..20] != b"RGB " {
+            anyhow::bail!("Only RGB monitor profiles are supported");
+        }
+
+        let tags = IccTags::parse(data)?;
+
+        let red = tags.xyz(b"rXYZ")?;
+        let green = tags.xyz(b"gXYZ")?;
+        let blue = tags.xyz(b"bXYZ")?;
+        let display_to_xyz = [
+            [red[0], green[0], blue[0]],
+            [red[1], green[1], blue[1]],
+            [red[2], green[2], blue[2]],
+        ];
+        let xyz_to_display =
+            invert3(&display_to_xyz).context("Monitor profile matrix is not invertible")?;
+
+        let gamma = [
+            tags.trc_gamma(b"rTRC")?,
+            tags.trc_gamma(b"gTRC")?,
+            tags.trc_gamma(b"bTRC")?,
+        ];
+
+        Ok(Self {
+            name: tags.description().unwrap_or_else(|| "Monitor".to_string()),
+            srgb_to_display: multiply3(&xyz_to_display, &SRGB_TO_XYZ_D50),
+            gamma,
+        })
+    }
+
+    /// Matrix padded to a `mat4` uniform (column-major, as GLSL expects)
+    pub fn shader_matrix(&self) -> Mat4 {
+        let m = &self.srgb_to_display;
+        Mat4::from_cols(
+            vec4(m[0][0], m[1][0], m[2][0], 0.0),
+            vec4(m[0][1], m[1][1], m[2][1], 0.0),
+            vec4(m[0][2], m[1][2], m[2][2], 0.0),
+            vec4(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+}
+
+/// ICC tag table lookup
+struct IccTags<'a> {
+    data: &'a [u8],
+    entries: Vec<([u8; 4], usize, usize)>,
+}
+
+impl<'a> IccTags<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        let count = read_u32(data, 128)? as usize;
+        // 12 bytes per entry; a count past the end of the data is corrupt
+        if count > data.len().saturating_sub(132) / 12 {
+            anyhow::bail!("Truncated ICC tag table");
+        }
+        let mut entries = Vec::with_capacity(count);
+        for i in 0..count {
+            let base = 132 + i * 12;
+            let signature: [u8; 4] = data
+                .get(base..base + 4)
+                .context("Truncated ICC tag table")?
+                .try_into()?;
+            let offset = read_u32(data, base + 4)? as usize;
+            let size = read_u32(data, base + 8)? as usize;
+            entries.push((signature, offset, size));
+        }
+        Ok(Self { data, entries })
+    }
+
+    fn tag(&self, signature: &[u8; 4]) -> Result<&'a [u8]> {
+        let (_, offset, size) = self
+            .entries
+            .iter()
+            .find(|(sig, _, _)| sig == signature)
+            .with_context(|| format!("Missing ICC tag {}", String::from_utf8_lossy(signature)))?;
+        self.data
+            .get(*offset..offset + size)
+            .context("ICC tag out of bounds")
+    }
+
+    fn xyz(&self, signature: &[u8; 4]) -> Result<[f32; 3]> {
+        let tag = self.tag(signature)?;
+        if tag.get(0..4) != Some(b"XYZ ") {
+            anyhow::bail!("Unexpected ICC tag type for XYZ");
+        }
+        Ok([
+            read_s15_fixed16(tag, 8)?,
+            read_s15_fixed16(tag, 12)?,
+            read_s15_fixed16(tag, 16)?,
+        ])
+    }
+
+    /// Approximate a tone response curve with a single gamma exponent
+    fn trc_gamma(&self, signature: &[u8; 4]) -> Result<f32> {
+        let tag = self.tag(signature)?;
+        let gamma = match tag.get(0..4) {
+            Some(b"curv") => {
+                let count = read_u32(tag, 8)? as usize;
+                match count {
+                    0 => 1.0,
+                    1 => read_u16(tag, 12)? as f32 / 256.0,
+                    _ => {
+                        // Fit the exponent through the curve midpoint
+                        let mid = count / 2;
+                        let x = mid as f32 / (count - 1) as f32;
+                        let y = read_u16(tag, 12 + mid * 2)? as f32 / 65535.0;
+                        if y > 0.0 && y < 1.0 {
+                            y.ln() / x.ln()
+                        } else {
+                            2.2
+                        }
+                    }
+                }
+            }
+            Some(b"para") => read_s15_fixed16(tag, 12)?,
+            _ => anyhow::bail!("Unsupported ICC curve type"),
+        };
+        Ok(gamma.clamp(1.0, 3.0))
+    }
+
+    fn description(&self) -> Option<String> {
+        let tag = self.tag(b"desc").ok()?;
+        match tag.get(0..4)? {
+            b"desc" => {
+                let len = read_u32(tag, 8).ok()? as usize;
+                let text = tag.get(12..12 + len)?;
+                Some(
+                    String::from_utf8_lossy(text)
+                        .trim_end_matches('\0')
+                        .to_string(),
+                )
+            }
+            b"mluc" => {
+                let len = read_u32(tag, 20).ok()? as usize;
+                let offset = read_u32(tag, 24).ok()? as usize;
+                let units: Vec<u16> = tag
+                    .get(offset..offset + len)?
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect();
+                Some(String::from_utf16_lossy(&units))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    let bytes = data.get(offset..offset + 4).context("Truncated ICC data")?;
+    Ok(u32::from_be_bytes(bytes.try_into()?))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data.get(offset..offset + 2).context("Truncated ICC data")?;
+    Ok(u16::from_be_bytes(bytes.try_into()?))
+}
+
+fn read_s15_fixed16(data: &[u8], offset: usize) -> Result<f32> {
+    Ok(read_u32(data, offset)? as i32 as f32 / 65536.0)
+}
+
+pub(crate) fn multiply3(a: &[[f32; 3]; 3], b: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let mut out = [[0.0; 3]; 3];
+    for (row, out_row) in out.iter_mut().enumerate() {
+        for (col, value) in out_row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[row][k] * b[k][col]).sum();
+        }
+    }
+    out
+}
+
+pub(crate) fn invert3(m: &[[f32; 3]; 3]) -> Option<[[f32; 3]; 3]> {
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    if det.abs() < 1e-8 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    Some([
+        [
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det,
+        ],
+        [
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det,
+        ],
+        [
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det,
+        ],
+    ])
+}
+
+impl GTexViewerApp {
+    /// The monitor profile, if one was loaded and is enabled
+    pub fn active_monitor_profile(&self) -> Option<&MonitorProfile> {
+        self.monitor_profile
+            .as_ref()
+            .filter(|_| self.monitor_profile_enabled)
+    }
+
+    pub fn handle_monitor_profile_input(&mut self) {
+        // Shift+V toggles the monitor profile as the final display transform
+        if is_key_pressed(KeyCode::V)
+            && (is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift))
+        {
+            if self.monitor_profile.is_none() {
+                log::info!("🖥️ No monitor profile set (point {ICC_PROFILE_ENV} at one)");
+                return;
+            }
+            self.monitor_profile_enabled = !self.monitor_profile_enabled;
+            log::info!(
+                "🖥️ Monitor profile {}",
+                if self.monitor_profile_enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+        }
+    }
+}
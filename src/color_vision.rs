@@ -0,0 +1,76 @@
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+
+/// Simulated colour vision deficiency, applied to the displayed colour
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorVision {
+    Normal,
+    Protanopia,   // No long-wavelength (red) cones
+    Deuteranopia, // No medium-wavelength (green) cones
+    Tritanopia,   // No short-wavelength (blue) cones
+}
+
+impl ColorVision {
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorVision::Normal => "Normal",
+            ColorVision::Protanopia => "Protanopia",
+            ColorVision::Deuteranopia => "Deuteranopia",
+            ColorVision::Tritanopia => "Tritanopia",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ColorVision::Normal => ColorVision::Protanopia,
+            ColorVision::Protanopia => ColorVision::Deuteranopia,
+            ColorVision::Deuteranopia => ColorVision::Tritanopia,
+            ColorVision::Tritanopia => ColorVision::Normal,
+        }
+    }
+
+    /// Row-major simulation matrix on linear sRGB, Machado et al. 2009 at full severity
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            ColorVision::Normal => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            ColorVision::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVision::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVision::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// The simulation matrix as a `mat4` uniform (column-major)
+    pub fn shader_matrix(self) -> Mat4 {
+        let m = self.matrix();
+        Mat4::from_cols(
+            vec4(m[0][0], m[1][0], m[2][0], 0.0),
+            vec4(m[0][1], m[1][1], m[2][1], 0.0),
+            vec4(m[0][2], m[1][2], m[2][2], 0.0),
+            vec4(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+}
+
+impl GTexViewerApp {
+    /// Alt+C cycles the simulated colour vision deficiency
+    pub fn handle_color_vision_input(&mut self) {
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        if alt_down && is_key_pressed(KeyCode::C) {
+            self.color_vision = self.color_vision.next();
+            log::info!("👁️ Colour vision: {}", self.color_vision.name());
+        }
+    }
+}
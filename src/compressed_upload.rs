@@ -0,0 +1,146 @@
+use macroquad::miniquad::{RawId, TextureAccess, TextureFormat, TextureParams, TextureSource, gl};
+use macroquad::prelude::*;
+use std::sync::{Arc, OnceLock};
+
+use crate::sampling::OwnedTexture;
+use crate::texture_pipeline::parsers::{GpuFormat, GpuPayload};
+
+/// Environment variable that turns compressed uploads off (`0`), e.g. to compare
+/// against the CPU decoders
+pub const COMPRESSED_UPLOAD_ENV: &str = "GTEXVIEWER_COMPRESSED_UPLOAD";
+
+// GL enums from EXT_texture_compression_s3tc, ARB_texture_compression_bptc,
+// GL 4.3/ES 3.0 (ETC2) and KHR_texture_compression_astc_ldr
+const GL_COMPRESSED_RGBA_S3TC_DXT1_EXT: u32 = 0x83F1;
+const GL_COMPRESSED_RGBA_S3TC_DXT5_EXT: u32 = 0x83F3;
+const GL_COMPRESSED_RGBA_BPTC_UNORM: u32 = 0x8E8C;
+const GL_COMPRESSED_RGB8_ETC2: u32 = 0x9274;
+const GL_COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2: u32 = 0x9276;
+const GL_COMPRESSED_RGBA8_ETC2_EAC: u32 = 0x9278;
+const GL_COMPRESSED_RGBA_ASTC_4X4_KHR: u32 = 0x93B0;
+const GL_NUM_COMPRESSED_TEXTURE_FORMATS: u32 = 0x86A2;
+const GL_COMPRESSED_TEXTURE_FORMATS: u32 = 0x86A3;
+const GL_TEXTURE_BINDING_2D: u32 = 0x8069;
+const GL_TEXTURE_MAX_LEVEL: u32 = 0x813D;
+
+/// ASTC block footprints in GL enum order, starting at GL_COMPRESSED_RGBA_ASTC_4x4_KHR
+const ASTC_BLOCK_SIZES: [(u8, u8); 14] = [
+    (4, 4),
+    (5, 4),
+    (5, 5),
+    (6, 5),
+    (6, 6),
+    (8, 5),
+    (8, 6),
+    (8, 8),
+    (10, 5),
+    (10, 6),
+    (10, 8),
+    (10, 10),
+    (12, 10),
+    (12, 12),
+];
+
+/// Compressed formats the driver reports, queried once on the render thread
+static SUPPORTED_FORMATS: OnceLock<Vec<u32>> = OnceLock::new();
+
+fn gl_format(format: GpuFormat) -> Option<u32> {
+    match format {
+        GpuFormat::Bc1 => Some(GL_COMPRESSED_RGBA_S3TC_DXT1_EXT),
+        GpuFormat::Bc3 => Some(GL_COMPRESSED_RGBA_S3TC_DXT5_EXT),
+        GpuFormat::Bc7 => Some(GL_COMPRESSED_RGBA_BPTC_UNORM),
+        GpuFormat::Etc2Rgb => Some(GL_COMPRESSED_RGB8_ETC2),
+        GpuFormat::Etc2RgbA1 => Some(GL_COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2),
+        GpuFormat::Etc2Rgba8 => Some(GL_COMPRESSED_RGBA8_ETC2_EAC),
+        GpuFormat::Astc {
+            block_width,
+            block_height,
+        } => ASTC_BLOCK_SIZES
+            .iter()
+            .position(|&size| size == (block_width, block_height))
+            .map(|index| GL_COMPRESSED_RGBA_ASTC_4X4_KHR + index as u32),
+    }
+}
+
+fn supported_formats() -> &'static [u32] {
+    SUPPORTED_FORMATS.get_or_init(|| unsafe {
+        let mut count = 0;
+        gl::glGetIntegerv(GL_NUM_COMPRESSED_TEXTURE_FORMATS, &mut count);
+        let mut formats = vec![0i32; count.max(0) as usize];
+        if !formats.is_empty() {
+            gl::glGetIntegerv(GL_COMPRESSED_TEXTURE_FORMATS, formats.as_mut_ptr());
+        }
+        log::info!(
+            "🎮 GPU reports {} compressed texture formats",
+            formats.len()
+        );
+        formats.into_iter().map(|format| format as u32).collect()
+    })
+}
+
+/// Whether compressed uploads are enabled (on unless `$GTEXVIEWER_COMPRESSED_UPLOAD` is 0)
+pub fn compressed_upload_enabled() -> bool {
+    std::env::var(COMPRESSED_UPLOAD_ENV).map_or(true, |value| value != "0")
+}
+
+/// Upload block data as-is when the GPU samples the format natively, using a quarter
+/// to an eighth of the VRAM of decoded RGBA. None when unsupported, so callers fall back
+/// to uploading the CPU-decoded image
+/// Only the top mip is uploaded, so the texture is capped to level 0 to stay complete
+/// The returned owner must be kept alongside the texture; dropping it frees the VRAM
+pub fn upload_compressed(payload: &GpuPayload) -> Option<(Texture2D, Arc<OwnedTexture>)> {
+    let format = gl_format(payload.format)?;
+    if !supported_formats().contains(&format) {
+        return None;
+    }
+
+    let context = unsafe { get_internal_gl() }.quad_context;
+    let id = context.new_texture(
+        TextureAccess::Static,
+        TextureSource::Empty,
+        TextureParams {
+            width: payload.width,
+            height: payload.height,
+            format: TextureFormat::RGBA8,
+            ..Default::default()
+        },
+    );
+    let RawId::OpenGl(raw_id) = (unsafe { context.texture_raw_id(id) }) else {
+        context.delete_texture(id);
+        return None;
+    };
+
+    // Bind around miniquad's state cache and restore the previous binding afterwards
+    let error = unsafe {
+        let mut previous = 0;
+        gl::glGetIntegerv(GL_TEXTURE_BINDING_2D, &mut previous);
+        gl::glBindTexture(gl::GL_TEXTURE_2D, raw_id);
+        gl::glCompressedTexImage2D(
+            gl::GL_TEXTURE_2D,
+            0,
+            format,
+            payload.width as i32,
+            payload.height as i32,
+            0,
+            payload.data.len() as i32,
+            payload.data.as_ptr() as *const _,
+        );
+        gl::glTexParameteri(gl::GL_TEXTURE_2D, GL_TEXTURE_MAX_LEVEL, 0);
+        let error = gl::glGetError();
+        gl::glBindTexture(gl::GL_TEXTURE_2D, previous as u32);
+        error
+    };
+
+    if error != 0 {
+        log::warn!(
+            "⚠️ Compressed upload of {:?} failed (GL error {error:#x}), decoding on CPU",
+            payload.format
+        );
+        context.delete_texture(id);
+        return None;
+    }
+
+    let texture = Texture2D::from_miniquad_texture(id);
+    texture.set_filter(FilterMode::Linear);
+    Some((texture, Arc::new(OwnedTexture(id))))
+}
@@ -0,0 +1,214 @@
+use macroquad::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::types::GTexViewerApp;
+
+/// Service name under which passwords are stored in the OS keyring
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "gtexviewer";
+
+/// Passwords remembered per protected container
+/// Lives for the whole session; with the `keyring` feature, passwords also persist
+/// in the OS keyring so re-opening a delivery doesn't ask again
+#[derive(Default)]
+struct CredentialStore {
+    passwords: HashMap<String, String>,
+    requests: Vec<String>, // Containers waiting for the user to enter a password
+}
+
+fn store() -> &'static Mutex<CredentialStore> {
+    static STORE: OnceLock<Mutex<CredentialStore>> = OnceLock::new();
+    STORE.get_or_init(Default::default)
+}
+
+/// Credential key for a container on disk (canonical, so relative drops match)
+pub fn path_key(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Password for a container, from this session or the OS keyring
+pub fn password(key: &str) -> Option<String> {
+    let mut store = store().lock().ok()?;
+    if let Some(password) = store.passwords.get(key) {
+        return Some(password.clone());
+    }
+
+    let password = keyring_get(key)?;
+    store.passwords.insert(key.to_string(), password.clone());
+    Some(password)
+}
+
+/// Remember a password for a container
+pub fn remember(key: &str, password: &str) {
+    if let Ok(mut store) = store().lock() {
+        store
+            .passwords
+            .insert(key.to_string(), password.to_string());
+    }
+    keyring_set(key, password);
+}
+
+/// Forget a password that turned out to be wrong
+pub fn forget(key: &str) {
+    if let Ok(mut store) = store().lock() {
+        store.passwords.remove(key);
+    }
+    keyring_delete(key);
+}
+
+/// Ask the UI to prompt for a container's password (deduplicated)
+pub fn request_password(key: &str) {
+    if let Ok(mut store) = store().lock()
+        && !store.requests.iter().any(|pending| pending == key)
+    {
+        store.requests.push(key.to_string());
+    }
+}
+
+/// Next container waiting for a password prompt
+pub fn take_password_request() -> Option<String> {
+    let mut store = store().lock().ok()?;
+    (!store.requests.is_empty()).then(|| store.requests.remove(0))
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_get(key: &str) -> Option<String> {
+    // Portable runs leave nothing behind on the host machine
+    if crate::portable::is_portable() {
+        return None;
+    }
+    keyring::Entry::new(KEYRING_SERVICE, key)
+        .and_then(|entry| entry.get_password())
+        .ok()
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_set(key: &str, password: &str) {
+    if crate::portable::is_portable() {
+        return;
+    }
+    if let Err(e) =
+        keyring::Entry::new(KEYRING_SERVICE, key).and_then(|entry| entry.set_password(password))
+    {
+        log::warn!("Failed to store password in OS keyring: {e}");
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_delete(key: &str) {
+    if crate::portable::is_portable() {
+        return;
+    }
+    let _ = keyring::Entry::new(KEYRING_SERVICE, key).and_then(|entry| entry.delete_credential());
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_get(_key: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_set(_key: &str, _password: &str) {}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_delete(_key: &str) {}
+
+/// Password entry overlay for a protected container
+pub struct PasswordPrompt {
+    pub container: String, // Credential key of the container
+    pub input: String,
+}
+
+impl GTexViewerApp {
+    /// Handle the password prompt: Enter submits and reloads the container, Escape skips it
+    /// Returns true while the prompt is open so other keyboard shortcuts stay inactive
+    pub fn handle_password_prompt_input(&mut self) -> bool {
+        if self.password_prompt.is_none() {
+            let Some(container) = take_password_request() else {
+                return false;
+            };
+            log::info!("🔐 Password required for {container}");
+            self.password_prompt = Some(PasswordPrompt {
+                container,
+                input: String::new(),
+            });
+            macroquad::miniquad::window::schedule_update();
+        }
+
+        let Some(prompt) = self.password_prompt.as_mut() else {
+            return false;
+        };
+
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() {
+                prompt.input.push(c);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            prompt.input.pop();
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            log::info!("🔐 Skipped protected container {}", prompt.container);
+            self.password_prompt = None;
+        } else if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            remember(&prompt.container, &prompt.input);
+            let container = PathBuf::from(&prompt.container);
+            self.password_prompt = None;
+            self.load_images(vec![container]);
+        }
+
+        macroquad::miniquad::window::schedule_update();
+        true
+    }
+
+    pub fn draw_password_prompt(&self) {
+        let Some(ref prompt) = self.password_prompt else {
+            return;
+        };
+
+        let file_name = Path::new(&prompt.container)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&prompt.container);
+        let lines = [
+            format!("Password for {file_name}"),
+            format!("{}_", "*".repeat(prompt.input.chars().count())),
+            "Enter to unlock, Esc to skip".to_string(),
+        ];
+
+        let text_size = 18.0;
+        let line_height = 28.0;
+        let width = lines
+            .iter()
+            .map(|line| measure_text(line, self.ui_font.as_ref(), text_size as u16, 1.0).width)
+            .fold(260.0, f32::max)
+            + 32.0;
+        let height = line_height * lines.len() as f32 + 24.0;
+        let x = ((screen_width() - width) / 2.0).round();
+        let y = ((screen_height() - height) / 2.0).round();
+
+        draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.85));
+        draw_rectangle_lines(x, y, width, height, 1.0, GRAY);
+
+        for (i, line) in lines.iter().enumerate() {
+            draw_text_ex(
+                line,
+                x + 16.0,
+                y + 12.0 + line_height * (i as f32 + 0.75),
+                TextParams {
+                    font: self.ui_font.as_ref(),
+                    font_size: text_size as u16,
+                    color: if i == 2 { LIGHTGRAY } else { WHITE },
+                    ..Default::default()
+                },
+            );
+        }
+    }
+}
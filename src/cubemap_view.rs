@@ -0,0 +1,104 @@
+use macroquad::prelude::*;
+
+use crate::sampling::upload_texture;
+use crate::texture_pipeline::cubemap::CubemapImages;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// How cubemap textures are laid out on the canvas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubemapLayout {
+    Face,     // First face (+X) only, as stored
+    Cross,    // Unfolded horizontal cross
+    Equirect, // Equirectangular projection
+}
+
+impl CubemapLayout {
+    pub fn name(self) -> &'static str {
+        match self {
+            CubemapLayout::Face => "Single face",
+            CubemapLayout::Cross => "Cross",
+            CubemapLayout::Equirect => "Equirectangular",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            CubemapLayout::Face => CubemapLayout::Cross,
+            CubemapLayout::Cross => CubemapLayout::Equirect,
+            CubemapLayout::Equirect => CubemapLayout::Face,
+        }
+    }
+}
+
+/// Uploaded layouts of a cubemap, swapped into the slot's texture by `CubemapLayout`
+#[derive(Clone)]
+pub struct CubemapView {
+    face: (Texture2D, u32, u32),
+    cross: (Texture2D, u32, u32),
+    equirect: (Texture2D, u32, u32),
+}
+
+impl CubemapView {
+    pub fn new(face: &Texture2D, width: u32, height: u32, images: CubemapImages) -> Self {
+        let upload = |image: &Image| {
+            let texture = upload_texture(image);
+            texture.set_filter(FilterMode::Linear);
+            (texture, image.width as u32, image.height as u32)
+        };
+
+        Self {
+            face: (face.clone(), width, height),
+            cross: upload(&images.cross),
+            equirect: upload(&images.equirect),
+        }
+    }
+
+    /// Texture and dimensions shown for a layout
+    pub fn layout(&self, layout: CubemapLayout) -> &(Texture2D, u32, u32) {
+        match layout {
+            CubemapLayout::Face => &self.face,
+            CubemapLayout::Cross => &self.cross,
+            CubemapLayout::Equirect => &self.equirect,
+        }
+    }
+
+    /// Textures uploaded for the cross and equirectangular layouts
+    pub fn unfolded_textures(&self) -> [&Texture2D; 2] {
+        [&self.cross.0, &self.equirect.0]
+    }
+}
+
+impl GTexViewerApp {
+    /// H cycles cubemaps between a single face, the unfolded cross and equirectangular
+    pub fn handle_cubemap_input(&mut self) {
+        if !is_key_pressed(KeyCode::H) {
+            return;
+        }
+
+        self.cubemap_layout = self.cubemap_layout.next();
+        log::info!("🧊 Cubemap layout: {}", self.cubemap_layout.name());
+        self.apply_cubemap_layout();
+    }
+
+    /// Show every loaded cubemap in the current layout; slots resize to its aspect
+    pub fn apply_cubemap_layout(&mut self) {
+        let mut changed = false;
+
+        for slot in &mut self.image_slots {
+            if let ImageState::Loaded { image } = &mut slot.state
+                && let Some(cubemap) = &image.cubemap
+            {
+                let (texture, width, height) = cubemap.layout(self.cubemap_layout);
+                image.texture = texture.clone();
+                image.info.width = *width;
+                image.info.height = *height;
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.layout_needs_update = true;
+            macroquad::miniquad::window::schedule_update();
+        }
+    }
+}
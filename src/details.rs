@@ -0,0 +1,173 @@
+use macroquad::prelude::*;
+
+use crate::analysis::streaming::format_bytes;
+use crate::loading::{LoadedImage, ResolutionTier};
+use crate::texture_cache::image_vram_bytes;
+use crate::types::{GTexViewerApp, ImageState};
+use crate::workspace::Panel;
+
+pub const DETAILS_HEIGHT: f32 = 280.0;
+
+const LINE_HEIGHT: f32 = 18.0;
+const LABEL_COLOR: Color = Color::new(0.6, 0.6, 0.6, 1.0);
+
+impl GTexViewerApp {
+    /// Tab toggles the details sidebar for the image under the cursor
+    pub fn handle_details_input(&mut self) {
+        if is_key_pressed(KeyCode::Tab) {
+            self.toggle_panel(Panel::Details);
+        }
+
+        if !self.panel_shown(Panel::Details) {
+            return;
+        }
+
+        // Follow the hovered image; keep the last one when over empty space
+        let mouse_screen = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+        if let Some(index) = self.slot_at(mouse_world)
+            && let ImageState::Loaded { image } = &self.image_slots[index].state
+        {
+            self.details_target = Some(image.metadata.entry_key());
+        }
+    }
+
+    fn details_source(&self) -> Option<&LoadedImage> {
+        let target = self.details_target.as_ref()?;
+        self.image_slots.iter().find_map(|slot| match &slot.state {
+            ImageState::Loaded { image } if image.metadata.entry_key() == *target => Some(image),
+            _ => None,
+        })
+    }
+
+    /// Label/value rows for the sidebar, container chain outermost first
+    fn details_rows(&self, image: &LoadedImage) -> Vec<(&'static str, String)> {
+        let metadata = &image.metadata;
+        let mut rows = Vec::new();
+
+        let file_name = metadata
+            .source_path
+            .file_name()
+            .unwrap_or(metadata.source_path.as_os_str())
+            .to_string_lossy()
+            .into_owned();
+        if file_name != metadata.name || !metadata.parents.is_empty() {
+            rows.push(("File", file_name));
+            for segment in &metadata.parents {
+                rows.push(("In", segment.name.clone()));
+            }
+        }
+
+        rows.push(("Format", format!("{:?}", metadata.format)));
+        rows.push(("Encoding", image.info.color_space.clone()));
+        let upload = if image.gpu_compressed {
+            "Block-compressed"
+        } else {
+            "RGBA8"
+        };
+        rows.push((
+            "Upload",
+            match image.tier {
+                ResolutionTier::Proxy => format!("{upload} (proxy)"),
+                ResolutionTier::Full => upload.to_string(),
+            },
+        ));
+
+        let (width, height) = (image.info.width, image.info.height);
+        rows.push(("Size", format!("{width}×{height}")));
+        let mips = 32 - width.max(height).max(1).leading_zeros();
+        rows.push(("Mips", format!("{mips} levels")));
+        if let Some(animation) = &image.animation {
+            rows.push(("Frames", animation.frame_count().to_string()));
+        }
+        if let Some(layers) = &image.layers {
+            rows.push(("Layers", layers.layer_count().to_string()));
+        }
+
+        rows.push(("On disk", format_bytes(image.info.file_size)));
+        rows.push((
+            "VRAM",
+            format!("~{}", format_bytes(image_vram_bytes(image))),
+        ));
+        rows.push(("Streaming", self.image_streaming_cost(image).summary()));
+        rows
+    }
+
+    pub fn draw_details(&self) {
+        if !self.panel_shown(Panel::Details) {
+            return;
+        }
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 14,
+            color: WHITE,
+            ..Default::default()
+        };
+        let label_params = TextParams {
+            color: LABEL_COLOR,
+            ..text_params.clone()
+        };
+
+        let panel = self.panel_rect(Panel::Details);
+        draw_rectangle(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            Color::new(0.1, 0.1, 0.1, 0.9),
+        );
+
+        let Some(image) = self.details_source() else {
+            draw_text_ex(
+                "Details: hover an image",
+                panel.x + 10.0,
+                panel.y + 20.0,
+                text_params,
+            );
+            return;
+        };
+
+        let text_width = panel.w - 20.0;
+        draw_text_ex(
+            &self.fit_text(&image.metadata.name, text_width),
+            panel.x + 10.0,
+            panel.y + 20.0,
+            text_params.clone(),
+        );
+
+        let value_x = panel.x + 80.0;
+        let mut y = panel.y + 20.0 + LINE_HEIGHT + 4.0;
+        for (label, value) in self.details_rows(image) {
+            if y > panel.y + panel.h - 6.0 {
+                break;
+            }
+            draw_text_ex(label, panel.x + 10.0, y, label_params.clone());
+            draw_text_ex(
+                &self.fit_text(&value, panel.x + panel.w - 10.0 - value_x),
+                value_x,
+                y,
+                text_params.clone(),
+            );
+            y += LINE_HEIGHT;
+        }
+    }
+
+    /// Shorten text with an ellipsis until it fits `max_width`
+    pub(crate) fn fit_text(&self, text: &str, max_width: f32) -> String {
+        let width = |text: &str| measure_text(text, self.ui_font.as_ref(), 14, 1.0).width;
+        if width(text) <= max_width {
+            return text.to_string();
+        }
+
+        let mut chars: Vec<char> = text.chars().collect();
+        while !chars.is_empty() {
+            chars.pop();
+            let shortened = format!("{}…", chars.iter().collect::<String>());
+            if width(&shortened) <= max_width {
+                return shortened;
+            }
+        }
+        String::new()
+    }
+}
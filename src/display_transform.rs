@@ -0,0 +1,88 @@
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+
+/// Built-in display/view transforms evaluated in the display shader
+/// Scene-linear content is mapped to the display with the selected curve, then sRGB encoded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayTransform {
+    Standard, // Plain sRGB encode, values above 1.0 clip
+    Aces,     // ACES filmic RRT+ODT approximation
+    Filmic,   // Hable filmic curve
+}
+
+impl DisplayTransform {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DisplayTransform::Standard => "sRGB",
+            DisplayTransform::Aces => "ACES",
+            DisplayTransform::Filmic => "Filmic",
+        }
+    }
+
+    /// Value of the `display_transform` shader uniform
+    pub fn shader_index(&self) -> i32 {
+        match self {
+            DisplayTransform::Standard => 0,
+            DisplayTransform::Aces => 1,
+            DisplayTransform::Filmic => 2,
+        }
+    }
+}
+
+/// A selectable display/view entry
+#[derive(Debug, Clone)]
+pub struct DisplayView {
+    pub name: String,
+    pub transform: DisplayTransform,
+}
+
+/// Views offered for cycling with the V key
+/// Only the built-in transforms; OpenColorIO configs are not read
+pub fn available_views() -> Vec<DisplayView> {
+    [
+        DisplayTransform::Standard,
+        DisplayTransform::Aces,
+        DisplayTransform::Filmic,
+    ]
+    .into_iter()
+    .map(|transform| DisplayView {
+        name: transform.name().to_string(),
+        transform,
+    })
+    .collect()
+}
+
+impl GTexViewerApp {
+    pub fn current_display_view(&self) -> Option<&DisplayView> {
+        self.display_views.get(self.display_view_index)
+    }
+
+    pub fn display_transform(&self) -> DisplayTransform {
+        self.current_display_view()
+            .map(|view| view.transform)
+            .unwrap_or(DisplayTransform::Standard)
+    }
+
+    pub fn handle_display_transform_input(&mut self) {
+        // V cycles through the available display/view transforms (Shift+V is the monitor profile)
+        // Ctrl+V pastes URLs, Alt+V toggles colour management
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if is_key_pressed(KeyCode::V)
+            && !shift_down
+            && !ctrl_down
+            && !alt_down
+            && !self.display_views.is_empty()
+        {
+            self.display_view_index = (self.display_view_index + 1) % self.display_views.len();
+            if let Some(view) = self.current_display_view() {
+                log::info!("🎨 Display view: {}", view.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use macroquad::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::loading::{LoadedImage, ResolutionTier};
+use crate::texture_pipeline::{EmbeddedMetadata, Pipeline};
+use crate::types::{GTexViewerApp, ImageSlot, ImageState};
+
+/// Crop rectangle being dragged over an image, in that image's pixels
+#[derive(Debug, Clone, Copy)]
+pub struct CropDrag {
+    pub slot: usize, // Slot being cropped
+    pub start: Vec2, // Pixel where the drag started
+    pub end: Vec2,   // Pixel under the cursor
+}
+
+impl CropDrag {
+    /// Whole-pixel region covered by the drag as (x, y, width, height)
+    pub fn region(&self) -> (u32, u32, u32, u32) {
+        let min = self.start.min(self.end).floor();
+        let max = self.start.max(self.end).ceil();
+        (
+            min.x as u32,
+            min.y as u32,
+            (max.x - min.x) as u32,
+            (max.y - min.y) as u32,
+        )
+    }
+}
+
+/// Where a crop is written: next to its source, `<name>.crop-<x>_<y>-<w>x<h>.png`
+pub fn crop_export_path(
+    source_path: &Path,
+    entry_name: &str,
+    (x, y, width, height): (u32, u32, u32, u32),
+) -> PathBuf {
+    let stem = Path::new(entry_name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("image");
+    let directory = if source_path.is_dir() {
+        source_path
+    } else {
+        source_path.parent().unwrap_or(Path::new("."))
+    };
+    directory.join(format!("{stem}.crop-{x}_{y}-{width}x{height}.png"))
+}
+
+/// Image pixel under a world-space point, clamped to the image
+/// With the tiling preview on, only the center tile maps to the image
+pub fn world_to_pixel(slot: &ImageSlot, image: &LoadedImage, world: Vec2) -> Vec2 {
+    let mut uv = (world - slot.position) / slot.size;
+    if image.sampler_preview.tiling {
+        let preview = &image.sampler_preview;
+        uv = uv * preview.repeats as f32 - Vec2::splat(preview.center_offset());
+    }
+    uv.clamp(Vec2::ZERO, Vec2::ONE) * image.pixel_size()
+}
+
+fn pixel_to_world(slot: &ImageSlot, image: &LoadedImage, pixel: Vec2) -> Vec2 {
+    let mut uv = pixel / image.pixel_size();
+    if image.sampler_preview.tiling {
+        let preview = &image.sampler_preview;
+        uv = (uv + Vec2::splat(preview.center_offset())) / preview.repeats as f32;
+    }
+    slot.position + uv * slot.size
+}
+
+/// Decode an image from its source again, for textures whose pixels live only on the GPU
+pub fn decode_pixels(metadata: &EmbeddedMetadata) -> anyhow::Result<Image> {
+    let pipeline = Pipeline::new();
+    let data = pipeline.metadata_to_loaded_data(metadata)?;
+    Ok(pipeline.parse_image_data(&data)?.0)
+}
+
+impl GTexViewerApp {
+    /// Shift+drag over an image selects a region; releasing saves it as a PNG at native
+    /// resolution, Escape cancels. Returns true while dragging so the camera doesn't pan
+    pub fn handle_crop_input(&mut self) -> bool {
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_x, mouse_y));
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+
+        if self.crop_drag.is_none() && shift_down && is_mouse_button_pressed(MouseButton::Left) {
+            let Some(index) = self.slot_at(mouse_world) else {
+                return false;
+            };
+            let slot = &self.image_slots[index];
+            let ImageState::Loaded { image } = &slot.state else {
+                return false;
+            };
+            let pixel = world_to_pixel(slot, image, mouse_world);
+            self.crop_drag = Some(CropDrag {
+                slot: index,
+                start: pixel,
+                end: pixel,
+            });
+        }
+
+        let Some(mut drag) = self.crop_drag else {
+            return false;
+        };
+        let Some(slot) = self.image_slots.get(drag.slot) else {
+            self.crop_drag = None;
+            return false;
+        };
+        let ImageState::Loaded { image } = &slot.state else {
+            self.crop_drag = None;
+            return false;
+        };
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.crop_drag = None;
+            log::info!("✂️ Crop cancelled");
+            return true;
+        }
+
+        drag.end = world_to_pixel(slot, image, mouse_world);
+        self.crop_drag = Some(drag);
+
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.crop_drag = None;
+            self.export_crop(drag);
+        }
+        true
+    }
+
+    /// Save the dragged region of the image's current texture as a PNG next to its source
+    fn export_crop(&self, drag: CropDrag) {
+        let Some(ImageState::Loaded { image }) =
+            self.image_slots.get(drag.slot).map(|slot| &slot.state)
+        else {
+            return;
+        };
+
+        let (x, y, width, height) = drag.region();
+        if width == 0 || height == 0 {
+            log::info!("✂️ Crop region is empty, nothing saved");
+            return;
+        }
+
+        let path = crop_export_path(
+            &image.metadata.source_path,
+            &image.metadata.name,
+            (x, y, width, height),
+        );
+        // Read back the uploaded pixels so animations crop their current frame;
+        // block-compressed textures can't be read back and proxies are downscaled,
+        // so both are decoded again
+        let pixels = if image.gpu_compressed || image.tier == ResolutionTier::Proxy {
+            match decode_pixels(&image.metadata) {
+                Ok(pixels) => pixels,
+                Err(e) => {
+                    log::warn!(
+                        "⚠️ Failed to decode {} for cropping: {e:#}",
+                        image.metadata.name
+                    );
+                    return;
+                }
+            }
+        } else {
+            image.texture.get_texture_data()
+        };
+        let cropped = pixels.sub_image(Rect::new(x as f32, y as f32, width as f32, height as f32));
+        cropped.export_png(&path.to_string_lossy());
+        log::info!(
+            "✂️ Saved {width}x{height} crop at ({x}, {y}) of {} to {}",
+            image.metadata.name,
+            path.display()
+        );
+    }
+
+    /// Outline the crop rectangle and its pixel size while dragging
+    pub fn draw_crop_selection(&self) {
+        let Some(drag) = self.crop_drag else {
+            return;
+        };
+        let Some(slot) = self.image_slots.get(drag.slot) else {
+            return;
+        };
+        let ImageState::Loaded { image } = &slot.state else {
+            return;
+        };
+
+        let (x, y, width, height) = drag.region();
+        let top_left = self.world_to_screen(pixel_to_world(slot, image, vec2(x as f32, y as f32)));
+        let bottom_right = self.world_to_screen(pixel_to_world(
+            slot,
+            image,
+            vec2((x + width) as f32, (y + height) as f32),
+        ));
+        let size = bottom_right - top_left;
+
+        draw_rectangle(
+            top_left.x,
+            top_left.y,
+            size.x,
+            size.y,
+            Color::new(1.0, 1.0, 1.0, 0.12),
+        );
+        draw_rectangle_lines(top_left.x, top_left.y, size.x, size.y, 1.5, YELLOW);
+
+        let label = format!("{width} x {height}  @ {x}, {y}");
+        let text_size = 14.0;
+        let dims = measure_text(&label, self.ui_font.as_ref(), text_size as u16, 1.0);
+        let label_y = (top_left.y - 6.0).max(dims.height + 4.0).round();
+        draw_rectangle(
+            top_left.x.round() - 2.0,
+            label_y - dims.height - 2.0,
+            dims.width + 4.0,
+            dims.height + 6.0,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+        draw_text_ex(
+            &label,
+            top_left.x.round(),
+            label_y,
+            TextParams {
+                font: self.ui_font.as_ref(),
+                font_size: text_size as u16,
+                color: YELLOW,
+                ..Default::default()
+            },
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use macroquad::prelude::*;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::{ContainerSegment, EmbeddedHint, EmbeddedMetadata, Pipeline};
+use crate::types::{GTexViewerApp, ImageState};
+use crate::validation::format_name;
+
+/// Default file name for exported metadata manifests
+pub const DEFAULT_MANIFEST_FILE: &str = "gtexviewer-manifest.json";
+
+const USAGE: &str = "Usage: gtexviewer manifest [--out <manifest.json>] <paths>...";
+
+/// Stored bytes of an entry inside its container file
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ByteRange {
+    pub offset: u64,
+    pub length: u64,
+}
+
+/// One texture with the hint needed to pull it out of its container
+#[derive(Serialize)]
+pub struct ManifestEntry<'a> {
+    pub source: &'a Path,
+    pub name: &'a str,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub parents: &'a [ContainerSegment], // Nested containers, outermost first
+    pub format: String,
+    pub width: usize,
+    pub height: usize,
+    pub file_size: u64,
+    pub byte_range: Option<ByteRange>, // None when the entry can't be read straight from disk
+    pub hint: &'a dyn EmbeddedHint,
+}
+
+impl<'a> ManifestEntry<'a> {
+    pub fn new(metadata: &'a EmbeddedMetadata) -> Self {
+        Self {
+            source: &metadata.source_path,
+            name: &metadata.name,
+            parents: &metadata.parents,
+            format: format_name(metadata.format),
+            width: metadata.width,
+            height: metadata.height,
+            file_size: metadata.file_size,
+            byte_range: metadata
+                .embedded_hint
+                .byte_range()
+                .map(|(offset, length)| ByteRange { offset, length }),
+            hint: metadata.embedded_hint.as_ref(),
+        }
+    }
+}
+
+/// Exportable index of every entry the pipeline found
+#[derive(Serialize)]
+pub struct Manifest<'a> {
+    pub entries: Vec<ManifestEntry<'a>>,
+}
+
+impl<'a> Manifest<'a> {
+    pub fn new(metadata: impl IntoIterator<Item = &'a EmbeddedMetadata>) -> Self {
+        Self {
+            entries: metadata.into_iter().map(ManifestEntry::new).collect(),
+        }
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write manifest: {}", path.display()))
+    }
+}
+
+/// Headless `manifest` subcommand: index every entry under the given paths without
+/// decoding any pixels and write the manifest, returning the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let mut output_path = PathBuf::from(DEFAULT_MANIFEST_FILE);
+    let mut paths = Vec::new();
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--out" => {
+                if let Some(path) = args_iter.next() {
+                    output_path = PathBuf::from(path);
+                }
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return 0;
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("{USAGE}");
+        return 2;
+    }
+
+    let pipeline = Pipeline::new();
+    let mut metadata_list = pipeline.extract_all_metadata_recursive(paths);
+
+    // Entries of huge archives are listed lazily; the manifest needs their real format and size
+    metadata_list.par_iter_mut().for_each(|metadata| {
+        if let Err(e) = pipeline.resolve_deferred(metadata) {
+            log::warn!("Failed to probe {}: {e:#}", metadata.name);
+        }
+    });
+
+    let manifest = Manifest::new(&metadata_list);
+    if let Err(e) = manifest.write_json(&output_path) {
+        eprintln!("error: {e:#}");
+        return 2;
+    }
+
+    println!(
+        "{} entries written to {}",
+        manifest.entries.len(),
+        output_path.display()
+    );
+    0
+}
+
+impl GTexViewerApp {
+    pub fn handle_manifest_export_input(&mut self) {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+
+        // Ctrl+Shift+E exports the manifest of all entries
+        if ctrl_down && shift_down && is_key_pressed(KeyCode::E) {
+            self.export_manifest(Path::new(DEFAULT_MANIFEST_FILE));
+        }
+    }
+
+    /// Write the manifest for every slot whose metadata is known
+    pub fn export_manifest(&self, path: &Path) {
+        let metadata = self
+            .image_slots
+            .iter()
+            .filter_map(|slot| match &slot.state {
+                ImageState::Loaded { image } => Some(&image.metadata),
+                ImageState::Placeholder {
+                    original_metadata, ..
+                } => Some(original_metadata),
+                ImageState::Failed { metadata, .. } => metadata.as_ref(),
+            });
+
+        let manifest = Manifest::new(metadata);
+        match manifest.write_json(path) {
+            Ok(()) => log::info!(
+                "🗂️ Manifest written to {} ({} entries)",
+                path.display(),
+                manifest.entries.len()
+            ),
+            Err(e) => log::error!("Failed to export manifest: {e:#}"),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+pub mod crop;
+pub mod manifest;
+pub mod strip;
+
+pub use crop::{CropDrag, crop_export_path, decode_pixels, world_to_pixel};
+pub use manifest::{DEFAULT_MANIFEST_FILE, Manifest, ManifestEntry};
+pub use strip::{StripOptions, strip_metadata};
+
+use macroquad::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::Pipeline;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Where a stripped copy of an entry is written: next to its source, `<name>.stripped.<ext>`
+pub fn stripped_copy_path(source_path: &Path, entry_name: &str) -> PathBuf {
+    let entry = Path::new(entry_name);
+    let stem = entry
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("image");
+    let extension = entry
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("png");
+
+    let directory = if source_path.is_dir() {
+        source_path
+    } else {
+        source_path.parent().unwrap_or(Path::new("."))
+    };
+    directory.join(format!("{stem}.stripped.{extension}"))
+}
+
+impl GTexViewerApp {
+    pub fn handle_strip_export_input(&mut self) {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+
+        // Ctrl+S saves a copy of the hovered image without metadata (Ctrl+Shift+S keeps ICC)
+        if ctrl_down && is_key_pressed(KeyCode::S) {
+            let options = if shift_down {
+                StripOptions::KEEP_ICC
+            } else {
+                StripOptions::ALL
+            };
+            self.save_stripped_copy(options);
+        }
+    }
+
+    pub fn save_stripped_copy(&self, options: StripOptions) {
+        let mouse_screen = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+        let Some(ImageState::Loaded { image }) = self
+            .slot_at(mouse_world)
+            .map(|index| &self.image_slots[index].state)
+        else {
+            log::info!("🧹 Hover over a loaded image to save a stripped copy");
+            return;
+        };
+
+        let output_path = stripped_copy_path(&image.metadata.source_path, &image.metadata.name);
+        let result = Pipeline::new()
+            .load_bytes(&image.metadata)
+            .and_then(|data| strip_metadata(&data, options))
+            .and_then(|stripped| std::fs::write(&output_path, stripped).map_err(Into::into));
+
+        match result {
+            Ok(()) => log::info!("🧹 Saved stripped copy to {}", output_path.display()),
+            Err(e) => log::error!(
+                "Failed to save stripped copy of {}: {e:#}",
+                image.metadata.name
+            ),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+
+/// Which kinds of authoring metadata to drop when re-saving
+#[derive(Debug, Clone, Copy)]
+pub struct StripOptions {
+    pub exif: bool,
+    pub xmp: bool,
+    pub icc: bool,
+    pub text: bool, // PNG text chunks/timestamps, JPEG comments
+}
+
+impl StripOptions {
+    pub const ALL: Self = Self {
+        exif: true,
+        xmp: true,
+        icc: true,
+        text: true,
+    };
+
+    /// Everything except the color profile, so color-managed textures still display the same
+    pub const KEEP_ICC: Self = Self {
+        icc: false,
+        ..Self::ALL
+    };
+}
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const XMP_PNG_KEYWORD: &[u8] = b"XML:com.adobe.xmp\0";
+const EXIF_APP1_ID: &[u8] = b"Exif\0\0";
+const XMP_APP1_ID: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_EXTENSION_APP1_ID: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+const ICC_APP2_ID: &[u8] = b"ICC_PROFILE\0";
+
+/// Re-write a PNG or JPEG without the selected metadata
+/// Pixel data is copied byte for byte, nothing is re-encoded
+pub fn strip_metadata(data: &[u8], options: StripOptions) -> Result<Vec<u8>> {
+    if data.starts_with(PNG_SIGNATURE) {
+        strip_png(data, options)
+    } else if data.starts_with(&[0xFF, 0xD8]) {
+        strip_jpeg(data, options)
+    } else {
+        anyhow::bail!("Only PNG and JPEG files can be stripped")
+    }
+}
+
+fn strip_png(data: &[u8], options: StripOptions) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(PNG_SIGNATURE);
+
+    let mut offset = PNG_SIGNATURE.len();
+    while offset + 8 <= data.len() {
+        let length = u32::from_be_bytes(data[offset..offset + 4].try_into()?) as usize;
+        let chunk_type = &data[offset + 4..offset + 8];
+        let chunk_end = offset + 12 + length; // length + type + data + crc
+        let chunk = data.get(offset..chunk_end).context("Truncated PNG chunk")?;
+        let chunk_data = &chunk[8..8 + length];
+
+        let drop = match chunk_type {
+            b"eXIf" => options.exif,
+            b"iCCP" => options.icc,
+            b"iTXt" if chunk_data.starts_with(XMP_PNG_KEYWORD) => options.xmp,
+            b"tEXt" | b"zTXt" | b"iTXt" | b"tIME" => options.text,
+            _ => false,
+        };
+
+        if drop {
+            log::debug!(
+                "Stripping PNG {} chunk ({length} bytes)",
+                String::from_utf8_lossy(chunk_type)
+            );
+        } else {
+            output.extend_from_slice(chunk);
+        }
+
+        offset = chunk_end;
+        if chunk_type == b"IEND" {
+            break;
+        }
+    }
+
+    Ok(output)
+}
+
+fn strip_jpeg(data: &[u8], options: StripOptions) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(&data[..2]); // SOI
+
+    let mut offset = 2;
+    loop {
+        let marker = data
+            .get(offset..offset + 2)
+            .context("Truncated JPEG marker")?;
+        if marker[0] != 0xFF {
+            anyhow::bail!("Invalid JPEG marker at offset {offset}");
+        }
+
+        // Fill bytes before a marker are allowed
+        if marker[1] == 0xFF {
+            offset += 1;
+            continue;
+        }
+
+        // Start of scan: entropy-coded data and everything after is kept as is
+        if marker[1] == 0xDA {
+            output.extend_from_slice(&data[offset..]);
+            break;
+        }
+
+        let length = u16::from_be_bytes(
+            data.get(offset + 2..offset + 4)
+                .context("Truncated JPEG segment")?
+                .try_into()?,
+        ) as usize;
+        // The length counts its own two bytes
+        if length < 2 {
+            anyhow::bail!("Invalid JPEG segment length");
+        }
+        let segment_end = offset + 2 + length;
+        let segment = data
+            .get(offset..segment_end)
+            .context("Truncated JPEG segment")?;
+        let payload = &segment[4..];
+
+        let drop = match marker[1] {
+            0xE1 if payload.starts_with(EXIF_APP1_ID) => options.exif,
+            0xE1 if payload.starts_with(XMP_APP1_ID)
+                || payload.starts_with(XMP_EXTENSION_APP1_ID) =>
+            {
+                options.xmp
+            }
+            0xE2 if payload.starts_with(ICC_APP2_ID) => options.icc,
+            0xFE => options.text,
+            _ => false,
+        };
+
+        if drop {
+            log::debug!(
+                "Stripping JPEG segment 0x{:02X} ({length} bytes)",
+                marker[1]
+            );
+        } else {
+            output.extend_from_slice(segment);
+        }
+
+        offset = segment_end;
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_length_jpeg_segment_is_an_error() {
+        let data = [0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x00, 0xFF, 0xDA];
+        let error = strip_metadata(&data, StripOptions::ALL).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid JPEG segment length");
+    }
+
+    #[test]
+    fn truncated_jpeg_segment_is_an_error() {
+        let data = [0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x10, b'E', b'x'];
+        assert!(strip_metadata(&data, StripOptions::ALL).is_err());
+    }
+
+    #[test]
+    fn exif_segment_is_stripped() {
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x08];
+        data.extend_from_slice(EXIF_APP1_ID);
+        data.extend_from_slice(&[0xFF, 0xDA, 0x01, 0x02]);
+        let stripped = strip_metadata(&data, StripOptions::ALL).unwrap();
+        assert_eq!(stripped, [0xFF, 0xD8, 0xFF, 0xDA, 0x01, 0x02]);
+    }
+}
@@ -0,0 +1,69 @@
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+
+/// Exposure change per Ctrl+Up/Down press, in stops
+const EXPOSURE_STEP: f32 = 0.5;
+const EXPOSURE_RANGE: (f32, f32) = (-10.0, 10.0);
+
+/// Gamma change per Ctrl+Left/Right press
+const GAMMA_STEP: f32 = 0.1;
+const GAMMA_RANGE: (f32, f32) = (0.2, 5.0);
+
+/// Display-only exposure and gamma, evaluated in the display shader
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExposureGamma {
+    pub exposure: f32, // Stops applied to linear light before display encoding
+    pub gamma: f32,    // Extra display gamma on the encoded output
+}
+
+impl Default for ExposureGamma {
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            gamma: 1.0,
+        }
+    }
+}
+
+impl ExposureGamma {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Info bar text, e.g. "+1.5 EV · γ 1.2"
+    pub fn label(&self) -> String {
+        format!("{:+.1} EV · γ {:.1}", self.exposure, self.gamma)
+    }
+}
+
+impl GTexViewerApp {
+    /// Ctrl+Up/Down change exposure, Ctrl+Left/Right change gamma, Ctrl+Backspace resets both
+    pub fn handle_exposure_input(&mut self) {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if !ctrl_down {
+            return;
+        }
+
+        let before = self.exposure_gamma;
+        let adjust = &mut self.exposure_gamma;
+        if is_key_pressed(KeyCode::Up) {
+            adjust.exposure = (adjust.exposure + EXPOSURE_STEP).min(EXPOSURE_RANGE.1);
+        } else if is_key_pressed(KeyCode::Down) {
+            adjust.exposure = (adjust.exposure - EXPOSURE_STEP).max(EXPOSURE_RANGE.0);
+        } else if is_key_pressed(KeyCode::Right) {
+            adjust.gamma = (adjust.gamma + GAMMA_STEP).min(GAMMA_RANGE.1);
+        } else if is_key_pressed(KeyCode::Left) {
+            adjust.gamma = (adjust.gamma - GAMMA_STEP).max(GAMMA_RANGE.0);
+        } else if is_key_pressed(KeyCode::Backspace) {
+            *adjust = ExposureGamma::default();
+        }
+
+        if self.exposure_gamma != before {
+            log::info!("☀️ Exposure/gamma: {}", self.exposure_gamma.label());
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use macroquad::prelude::*;
+
+use crate::pixel_inspector::PixelValue;
+use crate::types::GTexViewerApp;
+
+const SWATCH_SIZE: f32 = 28.0;
+
+/// Colour picker mode: clicking an image copies the texel under the cursor to the clipboard
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Eyedropper {
+    pub active: bool,
+    pub picked: Option<PixelValue>, // Last colour copied
+}
+
+/// sRGB transfer function decode of one 8-bit value
+fn srgb_to_linear(value: u8) -> f32 {
+    let value = value as f32 / 255.0;
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+impl PixelValue {
+    /// e.g. "#FF8000FF"
+    pub fn hex(&self) -> String {
+        let [r, g, b, a] = self.rgba;
+        format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+    }
+
+    /// RGB decoded from sRGB to linear light, alpha as stored
+    pub fn linear(&self) -> [f32; 4] {
+        let [r, g, b, a] = self.rgba;
+        [
+            srgb_to_linear(r),
+            srgb_to_linear(g),
+            srgb_to_linear(b),
+            a as f32 / 255.0,
+        ]
+    }
+
+    /// Clipboard text: hex on the first line, linear floats on the second
+    fn clipboard_text(&self) -> String {
+        let [r, g, b, a] = self.linear();
+        format!("{}\n{r:.4}, {g:.4}, {b:.4}, {a:.4}", self.hex())
+    }
+
+    fn color(&self) -> Color {
+        let [r, g, b, a] = self.rgba;
+        Color::from_rgba(r, g, b, a)
+    }
+}
+
+impl GTexViewerApp {
+    /// Shift+I toggles picker mode, Esc leaves it; returns true when a click was used to
+    /// pick so it doesn't also pan the camera
+    pub fn handle_eyedropper_input(&mut self) -> bool {
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::I) && shift_down {
+            self.eyedropper.active = !self.eyedropper.active;
+            log::info!(
+                "💧 Color picker {}",
+                if self.eyedropper.active { "on" } else { "off" }
+            );
+        }
+
+        if !self.eyedropper.active {
+            return false;
+        }
+        if is_key_pressed(KeyCode::Escape) {
+            self.eyedropper.active = false;
+            log::info!("💧 Color picker off");
+            return false;
+        }
+        // Shift+drag stays a crop selection
+        if shift_down || !is_mouse_button_pressed(MouseButton::Left) {
+            return false;
+        }
+
+        let Some(value) = self.hovered_pixel() else {
+            return false;
+        };
+        macroquad::miniquad::window::clipboard_set(&value.clipboard_text());
+        log::info!("💧 Copied {} at ({}, {})", value.hex(), value.x, value.y);
+        self.eyedropper.picked = Some(value);
+        true
+    }
+
+    fn hovered_pixel(&self) -> Option<PixelValue> {
+        let (mouse_x, mouse_y) = mouse_position();
+        let world = self.screen_to_world(vec2(mouse_x, mouse_y));
+        self.probe_pixel(self.slot_at(world)?, world)
+    }
+
+    /// Swatches of the hovered and last picked colours next to the cursor
+    pub fn draw_eyedropper(&self) {
+        if !self.eyedropper.active {
+            return;
+        }
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 14,
+            color: WHITE,
+            ..Default::default()
+        };
+
+        let swatches: Vec<(PixelValue, &str)> = [
+            self.hovered_pixel().map(|value| (value, "")),
+            self.eyedropper.picked.map(|value| (value, " (copied)")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let mut y = mouse_y + 20.0;
+        for (value, suffix) in swatches {
+            let label = format!("{}{suffix}", value.hex());
+            let width = measure_text(&label, self.ui_font.as_ref(), 14, 1.0).width;
+            let x = mouse_x + 20.0;
+
+            draw_rectangle(
+                x,
+                y,
+                SWATCH_SIZE + width + 16.0,
+                SWATCH_SIZE + 8.0,
+                Color::new(0.0, 0.0, 0.0, 0.8),
+            );
+            draw_rectangle(x + 4.0, y + 4.0, SWATCH_SIZE, SWATCH_SIZE, value.color());
+            draw_rectangle_lines(x + 4.0, y + 4.0, SWATCH_SIZE, SWATCH_SIZE, 1.0, WHITE);
+            draw_text_ex(
+                &label,
+                x + SWATCH_SIZE + 10.0,
+                y + SWATCH_SIZE / 2.0 + 9.0,
+                text_params.clone(),
+            );
+            y += SWATCH_SIZE + 12.0;
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use macroquad::prelude::*;
+
+use crate::types::{ChannelMode, GTexViewerApp};
+
+/// Colour map for single-channel views, making subtle gradients easier to read than grey
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FalseColor {
+    Off,
+    Viridis, // Perceptually uniform, colour-blind safe
+    Turbo,   // High-contrast rainbow for the smallest differences
+}
+
+impl FalseColor {
+    pub fn name(self) -> &'static str {
+        match self {
+            FalseColor::Off => "Off",
+            FalseColor::Viridis => "Viridis",
+            FalseColor::Turbo => "Turbo",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            FalseColor::Off => FalseColor::Viridis,
+            FalseColor::Viridis => FalseColor::Turbo,
+            FalseColor::Turbo => FalseColor::Off,
+        }
+    }
+
+    pub fn shader_index(self) -> i32 {
+        match self {
+            FalseColor::Off => 0,
+            FalseColor::Viridis => 1,
+            FalseColor::Turbo => 2,
+        }
+    }
+
+    /// Whether the map applies to a channel mode; only single-value views have one
+    pub fn applies_to(channel_mode: ChannelMode) -> bool {
+        matches!(
+            channel_mode,
+            ChannelMode::Red
+                | ChannelMode::Green
+                | ChannelMode::Blue
+                | ChannelMode::Alpha
+                | ChannelMode::Luminance
+                | ChannelMode::Quadrants
+        )
+    }
+}
+
+impl GTexViewerApp {
+    /// Shift+C cycles the false-colour map used by single-channel views
+    pub fn handle_false_color_input(&mut self) {
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if shift_down && is_key_pressed(KeyCode::C) {
+            self.false_color = self.false_color.next();
+            log::info!("🌡️ False colour: {}", self.false_color.name());
+        }
+    }
+
+    /// The false-colour map in effect for the current channel mode
+    pub fn active_false_color(&self) -> FalseColor {
+        if FalseColor::applies_to(self.channel_mode) {
+            self.false_color
+        } else {
+            FalseColor::Off
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use macroquad::prelude::*;
+use std::time::{Duration, Instant};
+
+use crate::types::GTexViewerApp;
+
+/// Longest gap between the two clicks of a double-click
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(350);
+/// Farthest the cursor may move between them, in screen pixels
+const DOUBLE_CLICK_SLOP: f32 = 4.0;
+
+/// One image isolated full-viewport, with the grid camera to return to
+pub struct FocusMode {
+    pub slot: usize,
+    saved_target: Vec2,
+    saved_zoom: Vec2,
+}
+
+impl GTexViewerApp {
+    /// Double-clicking an image isolates it; Escape returns to the grid where it was left.
+    /// Both defer to the colour picker while it's on. Returns true when a click was used so
+    /// it doesn't also pan
+    pub fn handle_focus_input(&mut self) -> bool {
+        if self.eyedropper.active {
+            return false;
+        }
+        if self.focus.is_some() && is_key_pressed(KeyCode::Escape) {
+            self.exit_focus();
+            return false;
+        }
+
+        let modifier_down = [
+            KeyCode::LeftControl,
+            KeyCode::RightControl,
+            KeyCode::LeftSuper,
+            KeyCode::RightSuper,
+            KeyCode::LeftShift,
+            KeyCode::RightShift,
+        ]
+        .into_iter()
+        .any(is_key_down);
+        if modifier_down || !is_mouse_button_pressed(MouseButton::Left) {
+            return false;
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse = vec2(mouse_x, mouse_y);
+        let now = Instant::now();
+        let double_click = self.last_click.is_some_and(|(at, position)| {
+            now - at <= DOUBLE_CLICK_TIME && position.distance(mouse) <= DOUBLE_CLICK_SLOP
+        });
+        if !double_click {
+            self.last_click = Some((now, mouse));
+            return false;
+        }
+        self.last_click = None;
+
+        if self.focus.is_some() {
+            return false;
+        }
+        let Some(index) = self.slot_at(self.screen_to_world(mouse)) else {
+            return false;
+        };
+
+        self.focus_slot(index);
+        log::info!("🔍 Focused on slot {index}, Escape returns to the grid");
+        true
+    }
+
+    /// Show one slot alone, keeping the grid camera saved by an earlier focus
+    pub fn focus_slot(&mut self, index: usize) {
+        let (saved_target, saved_zoom) = match &self.focus {
+            Some(focus) => (focus.saved_target, focus.saved_zoom),
+            None => (self.camera.target, self.camera.zoom),
+        };
+        self.focus = Some(FocusMode {
+            slot: index,
+            saved_target,
+            saved_zoom,
+        });
+        // The single-image layout fits the slot to the view at this zoom
+        self.camera.target = Vec2::ZERO;
+        self.camera.zoom = Vec2::ONE;
+        self.layout_needs_update = true;
+    }
+
+    pub fn exit_focus(&mut self) {
+        let Some(focus) = self.focus.take() else {
+            return;
+        };
+        self.camera.target = focus.saved_target;
+        self.camera.zoom = focus.saved_zoom;
+        self.layout_needs_update = true;
+        log::info!("🔍 Back to the grid");
+    }
+
+    /// Hide every slot but the focused one; called by the layout after the filter
+    pub fn apply_focus_mode(&mut self) {
+        let Some(focus) = &self.focus else {
+            return;
+        };
+        for (index, slot) in self.image_slots.iter_mut().enumerate() {
+            slot.hidden |= index != focus.slot;
+        }
+    }
+}
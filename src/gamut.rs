@@ -0,0 +1,134 @@
+use macroquad::prelude::*;
+
+use crate::color_profile::{invert3, multiply3};
+use crate::types::GTexViewerApp;
+
+/// D65 white point chromaticity
+const D65_WHITE: [f32; 2] = [0.3127, 0.3290];
+
+/// RGB gamuts that can be used as overlay source or target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gamut {
+    Rec709,
+    DciP3,
+    Rec2020,
+}
+
+impl Gamut {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gamut::Rec709 => "Rec.709",
+            Gamut::DciP3 => "DCI-P3",
+            Gamut::Rec2020 => "Rec.2020",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Gamut::Rec709 => Gamut::DciP3,
+            Gamut::DciP3 => Gamut::Rec2020,
+            Gamut::Rec2020 => Gamut::Rec709,
+        }
+    }
+
+    /// Red, green and blue primaries as CIE xy chromaticities
+    /// DCI-P3 uses the D65 white point (Display P3) like the other two
+    fn primaries(&self) -> [[f32; 2]; 3] {
+        match self {
+            Gamut::Rec709 => [[0.640, 0.330], [0.300, 0.600], [0.150, 0.060]],
+            Gamut::DciP3 => [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]],
+            Gamut::Rec2020 => [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]],
+        }
+    }
+
+    /// Linear RGB to XYZ matrix derived from the primaries and white point
+    fn rgb_to_xyz(&self) -> [[f32; 3]; 3] {
+        let to_xyz = |[x, y]: [f32; 2]| [x / y, 1.0, (1.0 - x - y) / y];
+
+        let [r, g, b] = self.primaries().map(to_xyz);
+        let primaries = [[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]];
+        let white = to_xyz(D65_WHITE);
+
+        // Scale each primary so that RGB(1, 1, 1) maps to the white point
+        let inverse = invert3(&primaries).unwrap_or(primaries);
+        let scale: [f32; 3] =
+            std::array::from_fn(|row| (0..3).map(|k| inverse[row][k] * white[k]).sum());
+
+        std::array::from_fn(|row| std::array::from_fn(|col| primaries[row][col] * scale[col]))
+    }
+}
+
+/// Highlights pixels whose chromaticity falls outside the target gamut
+/// Pixel values are interpreted as linear RGB in the source gamut
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GamutOverlay {
+    pub target: Option<Gamut>,
+    pub source: Gamut,
+}
+
+impl Default for GamutOverlay {
+    fn default() -> Self {
+        Self {
+            target: None,
+            source: Gamut::Rec2020,
+        }
+    }
+}
+
+impl GamutOverlay {
+    pub fn is_active(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Source to target linear RGB conversion as a `mat4` uniform (column-major)
+    /// Any negative component after conversion lies outside the target triangle
+    pub fn shader_matrix(&self) -> Mat4 {
+        let Some(target) = self.target else {
+            return Mat4::IDENTITY;
+        };
+
+        let xyz_to_target = invert3(&target.rgb_to_xyz()).unwrap_or([[0.0; 3]; 3]);
+        let m = multiply3(&xyz_to_target, &self.source.rgb_to_xyz());
+        Mat4::from_cols(
+            vec4(m[0][0], m[1][0], m[2][0], 0.0),
+            vec4(m[0][1], m[1][1], m[2][1], 0.0),
+            vec4(m[0][2], m[1][2], m[2][2], 0.0),
+            vec4(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+
+    pub fn label(&self) -> Option<String> {
+        self.target
+            .map(|target| format!("{} in {}", self.source.name(), target.name()))
+    }
+}
+
+impl GTexViewerApp {
+    pub fn handle_gamut_input(&mut self) {
+        // Ctrl+G collapses source groups
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if !is_key_pressed(KeyCode::G) || ctrl_down {
+            return;
+        }
+
+        if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+            // Shift+G cycles the gamut the texture data is interpreted in
+            self.gamut_overlay.source = self.gamut_overlay.source.next();
+        } else {
+            // G cycles the target gamut: off -> Rec.709 -> DCI-P3 -> Rec.2020 -> off
+            self.gamut_overlay.target = match self.gamut_overlay.target {
+                None => Some(Gamut::Rec709),
+                Some(Gamut::Rec2020) => None,
+                Some(gamut) => Some(gamut.next()),
+            };
+        }
+
+        match self.gamut_overlay.label() {
+            Some(label) => log::info!("🌈 Gamut overlay: {label}"),
+            None => log::info!("🌈 Gamut overlay off"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+
+/// Grid divisions Shift+Z steps through
+const GRID_DIVISIONS: [u32; 6] = [2, 4, 8, 16, 32, 64];
+
+const THIRDS_COLOR: Color = Color::new(1.0, 0.85, 0.2, 0.8);
+const CENTER_COLOR: Color = Color::new(1.0, 0.3, 0.8, 0.9);
+const GRID_COLOR: Color = Color::new(0.3, 0.9, 1.0, 0.5);
+
+/// Alignment guide drawn over the image under the cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuideMode {
+    Off,
+    Thirds,      // Rule-of-thirds lines
+    CenterCross, // Horizontal and vertical centre lines
+    Grid,        // Even N×N grid
+}
+
+impl GuideMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            GuideMode::Off => "Off",
+            GuideMode::Thirds => "Rule of thirds",
+            GuideMode::CenterCross => "Center cross",
+            GuideMode::Grid => "Grid",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            GuideMode::Off => GuideMode::Thirds,
+            GuideMode::Thirds => GuideMode::CenterCross,
+            GuideMode::CenterCross => GuideMode::Grid,
+            GuideMode::Grid => GuideMode::Off,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuideOverlay {
+    pub mode: GuideMode,
+    pub divisions: u32, // Cells per side in grid mode
+}
+
+impl Default for GuideOverlay {
+    fn default() -> Self {
+        Self {
+            mode: GuideMode::Off,
+            divisions: 8,
+        }
+    }
+}
+
+impl GuideOverlay {
+    /// Line positions as fractions of the image extent, with their colour
+    fn lines(&self) -> (Vec<f32>, Color) {
+        match self.mode {
+            GuideMode::Off => (Vec::new(), GRID_COLOR),
+            GuideMode::Thirds => (vec![1.0 / 3.0, 2.0 / 3.0], THIRDS_COLOR),
+            GuideMode::CenterCross => (vec![0.5], CENTER_COLOR),
+            GuideMode::Grid => (
+                (1..self.divisions)
+                    .map(|i| i as f32 / self.divisions as f32)
+                    .collect(),
+                GRID_COLOR,
+            ),
+        }
+    }
+}
+
+impl GTexViewerApp {
+    /// Z cycles the guide overlay, Shift+Z steps the grid divisions
+    pub fn handle_guides_input(&mut self) {
+        let modifier_down = [
+            KeyCode::LeftControl,
+            KeyCode::RightControl,
+            KeyCode::LeftAlt,
+            KeyCode::RightAlt,
+            KeyCode::LeftSuper,
+            KeyCode::RightSuper,
+        ]
+        .into_iter()
+        .any(is_key_down);
+        if modifier_down || !is_key_pressed(KeyCode::Z) {
+            return;
+        }
+
+        if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+            let current = GRID_DIVISIONS
+                .iter()
+                .position(|&divisions| divisions == self.guides.divisions);
+            self.guides.divisions =
+                GRID_DIVISIONS[current.map_or(0, |index| (index + 1) % GRID_DIVISIONS.len())];
+            self.guides.mode = GuideMode::Grid;
+            log::info!("📐 Grid: {0}×{0}", self.guides.divisions);
+        } else {
+            self.guides.mode = self.guides.mode.next();
+            log::info!("📐 Guides: {}", self.guides.mode.name());
+        }
+    }
+
+    /// Guides over the hovered image, in screen space so lines stay one pixel wide
+    pub fn draw_guides(&self) {
+        if self.guides.mode == GuideMode::Off {
+            return;
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let Some(slot) = self
+            .slot_at(self.screen_to_world(vec2(mouse_x, mouse_y)))
+            .map(|index| &self.image_slots[index])
+        else {
+            return;
+        };
+
+        let top_left = self.world_to_screen(slot.position);
+        let size = self.world_to_screen(slot.position + slot.size) - top_left;
+        let (fractions, color) = self.guides.lines();
+
+        for fraction in fractions {
+            let x = (top_left.x + size.x * fraction).round() + 0.5;
+            let y = (top_left.y + size.y * fraction).round() + 0.5;
+            draw_line(x, top_left.y, x, top_left.y + size.y, 1.0, color);
+            draw_line(top_left.x, y, top_left.x + size.x, y, 1.0, color);
+        }
+        draw_rectangle_lines(top_left.x, top_left.y, size.x, size.y, 1.0, color);
+    }
+}
@@ -0,0 +1,119 @@
+use macroquad::prelude::*;
+
+use crate::analysis::Histogram;
+use crate::analysis::histogram::HISTOGRAM_BINS;
+use crate::types::{GTexViewerApp, ImageState};
+use crate::workspace::Panel;
+
+pub const HISTOGRAM_HEIGHT: f32 = 150.0;
+
+/// Curve colours for R, G, B and A
+const CHANNEL_COLORS: [Color; 4] = [
+    Color::new(1.0, 0.3, 0.3, 0.9),
+    Color::new(0.3, 1.0, 0.3, 0.9),
+    Color::new(0.35, 0.55, 1.0, 0.9),
+    Color::new(0.85, 0.85, 0.85, 0.7),
+];
+
+impl GTexViewerApp {
+    /// I toggles the histogram of the image under the cursor
+    pub fn handle_histogram_input(&mut self) {
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::I) && !alt_down && !shift_down {
+            self.toggle_panel(Panel::Histogram);
+        }
+
+        if !self.panel_shown(Panel::Histogram) {
+            return;
+        }
+
+        // Follow the hovered image; keep the last one when over empty space
+        let mouse_screen = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+        if let Some(index) = self.slot_at(mouse_world)
+            && let ImageState::Loaded { image } = &self.image_slots[index].state
+        {
+            self.histogram_target = Some(image.metadata.entry_key());
+        }
+    }
+
+    fn histogram_source(&self) -> Option<(&str, &Histogram)> {
+        let target = self.histogram_target.as_ref()?;
+        self.image_slots.iter().find_map(|slot| match &slot.state {
+            ImageState::Loaded { image } if image.metadata.entry_key() == *target => image
+                .analysis
+                .histogram
+                .as_ref()
+                .map(|histogram| (image.metadata.name.as_str(), histogram)),
+            _ => None,
+        })
+    }
+
+    pub fn draw_histogram(&self) {
+        if !self.panel_shown(Panel::Histogram) {
+            return;
+        }
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 14,
+            color: WHITE,
+            ..Default::default()
+        };
+
+        let panel = self.panel_rect(Panel::Histogram);
+        draw_rectangle(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            Color::new(0.1, 0.1, 0.1, 0.9),
+        );
+
+        let Some((name, histogram)) = self.histogram_source() else {
+            draw_text_ex(
+                "Histogram: hover an image",
+                panel.x + 10.0,
+                panel.y + 20.0,
+                text_params,
+            );
+            return;
+        };
+        draw_text_ex(name, panel.x + 10.0, panel.y + 20.0, text_params);
+
+        let graph = Rect::new(
+            panel.x + 10.0,
+            panel.y + 30.0,
+            panel.w - 20.0,
+            panel.h - 40.0,
+        );
+        draw_rectangle(
+            graph.x,
+            graph.y,
+            graph.w,
+            graph.h,
+            Color::new(0.05, 0.05, 0.05, 1.0),
+        );
+
+        // Square-root scale keeps sparse tones visible next to a dominant one
+        let peak = (histogram.peak().max(1) as f32).sqrt();
+        let channels = if histogram.has_alpha { 4 } else { 3 };
+        for (bins, color) in histogram.channels[..channels]
+            .iter()
+            .zip(CHANNEL_COLORS)
+            .rev()
+        {
+            let point = |bin: usize| {
+                vec2(
+                    graph.x + bin as f32 / (HISTOGRAM_BINS - 1) as f32 * graph.w,
+                    graph.y + graph.h - (bins[bin] as f32).sqrt() / peak * graph.h,
+                )
+            };
+            for bin in 1..HISTOGRAM_BINS {
+                let (from, to) = (point(bin - 1), point(bin));
+                draw_line(from.x, from.y, to.x, to.y, 1.0, color);
+            }
+        }
+    }
+}
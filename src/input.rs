@@ -1,6 +1,7 @@
+use macroquad::math::Rect as MacroRect;
 use macroquad::prelude::*;
 
-use crate::types::{ChannelMode, GTexViewerApp};
+use crate::types::{ChannelMode, GTexViewerApp, ImageSlot};
 
 impl GTexViewerApp {
     pub fn handle_camera_input(&mut self) {
@@ -84,10 +85,18 @@ impl GTexViewerApp {
             self.channel_mode = ChannelMode::SwapRB;
         } else if is_key_pressed(KeyCode::Key8) {
             self.channel_mode = ChannelMode::SwapGB;
+        } else if is_key_pressed(KeyCode::Key9) {
+            self.channel_mode = ChannelMode::Luminance;
+        } else if is_key_pressed(KeyCode::Key0) {
+            self.channel_mode = ChannelMode::Invert;
+        } else if is_key_pressed(KeyCode::J) {
+            self.channel_mode = ChannelMode::Quadrants;
         }
 
-        // Or use C key to cycle through modes
-        if is_key_pressed(KeyCode::C) {
+        // Or use C key to cycle through modes (Alt+C is colour vision, Shift+C false colour)
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::C) && !alt_down && !shift_down {
             self.channel_mode = match self.channel_mode {
                 ChannelMode::Normal => ChannelMode::Red,
                 ChannelMode::Red => ChannelMode::Green,
@@ -96,20 +105,94 @@ impl GTexViewerApp {
                 ChannelMode::Alpha => ChannelMode::SwapRG,
                 ChannelMode::SwapRG => ChannelMode::SwapRB,
                 ChannelMode::SwapRB => ChannelMode::SwapGB,
-                ChannelMode::SwapGB => ChannelMode::Normal,
+                ChannelMode::SwapGB => ChannelMode::Luminance,
+                ChannelMode::Luminance => ChannelMode::Invert,
+                ChannelMode::Invert => ChannelMode::Quadrants,
+                ChannelMode::Quadrants => ChannelMode::Normal,
             };
         }
 
         // Redraw will be automatically triggered by key_down events
     }
 
+    pub fn handle_filter_input(&mut self) {
+        // F cycles Auto (zoom-based) / Nearest / Linear filtering for every image
+        let modifier_down = [
+            KeyCode::LeftControl,
+            KeyCode::RightControl,
+            KeyCode::LeftAlt,
+            KeyCode::RightAlt,
+            KeyCode::LeftSuper,
+            KeyCode::RightSuper,
+        ]
+        .into_iter()
+        .any(is_key_down);
+        if is_key_pressed(KeyCode::F) && !modifier_down {
+            self.filter_override = self.filter_override.next();
+            log::info!("🔍 Texture filtering: {}", self.filter_override.name());
+        }
+    }
+
     pub fn handle_layout_input(&mut self) {
-        if is_key_pressed(KeyCode::R) {
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::R) && !shift_down {
             log::info!("🔄 Recalculating layout to fit viewport at current zoom level");
             self.layout_needs_update = true;
         }
     }
 
+    pub fn handle_alpha_cutoff_input(&mut self) {
+        // Bracket keys nudge the alpha-test cutoff used for mip coverage analysis
+        if is_key_pressed(KeyCode::LeftBracket) {
+            self.alpha_cutoff = (self.alpha_cutoff - 0.05).max(0.05);
+            log::info!("🍃 Alpha cutoff: {:.2}", self.alpha_cutoff);
+        } else if is_key_pressed(KeyCode::RightBracket) {
+            self.alpha_cutoff = (self.alpha_cutoff + 0.05).min(0.95);
+            log::info!("🍃 Alpha cutoff: {:.2}", self.alpha_cutoff);
+        }
+    }
+
+    pub fn handle_unpremultiply_input(&mut self) {
+        // A cycles which premultiplied images are divided by alpha for display; detected
+        // ones need opting in, the heuristic also matches dark straight-alpha content
+        let modifier_down = [
+            KeyCode::LeftControl,
+            KeyCode::RightControl,
+            KeyCode::LeftAlt,
+            KeyCode::RightAlt,
+            KeyCode::LeftSuper,
+            KeyCode::RightSuper,
+        ]
+        .into_iter()
+        .any(is_key_down);
+        if is_key_pressed(KeyCode::A) && !modifier_down {
+            self.unpremultiply = self.unpremultiply.next();
+            log::info!("🫧 Premultiplied alpha {}", self.unpremultiply.name());
+        }
+    }
+
+    /// Index of the image slot containing a world-space point
+    pub fn slot_at(&self, world_pos: Vec2) -> Option<usize> {
+        self.image_slots.iter().position(|slot| {
+            !slot.hidden
+                && world_pos.x >= slot.position.x
+                && world_pos.x <= slot.position.x + slot.size.x
+                && world_pos.y >= slot.position.y
+                && world_pos.y <= slot.position.y + slot.size.y
+        })
+    }
+
+    /// Whether any part of a shown slot lies within a world-space rect
+    pub fn slot_overlaps(slot: &ImageSlot, rect: &MacroRect) -> bool {
+        !slot.hidden
+            && rect.overlaps(&MacroRect::new(
+                slot.position.x,
+                slot.position.y,
+                slot.size.x,
+                slot.size.y,
+            ))
+    }
+
     pub fn screen_to_world(&self, screen_pos: Vec2) -> Vec2 {
         // Convert screen coordinates to world coordinates using camera transform
         let screen_width = screen_width();
@@ -129,4 +212,30 @@ impl GTexViewerApp {
 
         vec2(world_x, world_y)
     }
+
+    /// World-space rect currently on screen
+    pub fn visible_world_rect(&self) -> MacroRect {
+        let top_left = self.screen_to_world(vec2(0.0, 0.0));
+        let bottom_right = self.screen_to_world(vec2(screen_width(), screen_height()));
+        MacroRect::new(
+            top_left.x,
+            top_left.y,
+            bottom_right.x - top_left.x,
+            bottom_right.y - top_left.y,
+        )
+    }
+
+    /// Inverse of `screen_to_world`, for drawing UI over world-space content
+    pub fn world_to_screen(&self, world_pos: Vec2) -> Vec2 {
+        let aspect_ratio = screen_width() / screen_height();
+        let effective_zoom_y = self.camera.zoom.y * aspect_ratio;
+
+        let normalized_x = (world_pos.x - self.camera.target.x) * self.camera.zoom.x;
+        let normalized_y = (world_pos.y - self.camera.target.y) * effective_zoom_y;
+
+        vec2(
+            (normalized_x + 1.0) / 2.0 * screen_width(),
+            (normalized_y + 1.0) / 2.0 * screen_height(),
+        )
+    }
 }
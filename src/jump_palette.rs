@@ -0,0 +1,235 @@
+use macroquad::prelude::*;
+use std::time::{Duration, Instant};
+
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Entries listed below the query line
+const MAX_RESULTS: usize = 12;
+
+/// How long the jumped-to slot is outlined
+const JUMP_HIGHLIGHT_DURATION: Duration = Duration::from_millis(1500);
+
+/// Ctrl+P "jump to texture" overlay
+#[derive(Default)]
+pub struct JumpPalette {
+    pub query: String,
+    pub selected: usize, // Index into the current matches
+}
+
+/// Fuzzy subsequence score of a query against a name, None when it doesn't match
+/// Consecutive characters, word starts and short names rank higher
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let name: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (i, c) in name.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if !c.to_lowercase().eq(query[query_index].to_lowercase()) {
+            continue;
+        }
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == i) {
+            score += 5;
+        }
+        let word_start = i == 0
+            || matches!(name[i - 1], '/' | '\\' | '_' | '-' | '.' | ' ')
+            || (name[i - 1].is_lowercase() && c.is_uppercase());
+        if word_start {
+            score += 3;
+        }
+
+        previous_match = Some(i);
+        query_index += 1;
+    }
+
+    (query_index == query.len()).then(|| score * 100 - name.len() as i32)
+}
+
+impl GTexViewerApp {
+    /// Names of all slots, including placeholders and failed entries
+    fn slot_name(&self, index: usize) -> Option<&str> {
+        let metadata = match &self.image_slots.get(index)?.state {
+            ImageState::Loaded { image } => &image.metadata,
+            ImageState::Placeholder {
+                original_metadata, ..
+            } => original_metadata,
+            ImageState::Failed { metadata, .. } => metadata.as_ref()?,
+        };
+        Some(&metadata.name)
+    }
+
+    /// Slot indices matching the palette query, best match first
+    pub fn jump_palette_matches(&self) -> Vec<usize> {
+        let Some(ref palette) = self.jump_palette else {
+            return Vec::new();
+        };
+
+        // Slots hidden by the filter box can't be jumped to
+        let mut matches: Vec<(usize, i32)> = (0..self.image_slots.len())
+            .filter(|&index| !self.image_slots[index].hidden)
+            .filter_map(|index| {
+                let score = fuzzy_score(&palette.query, self.slot_name(index)?)?;
+                Some((index, score))
+            })
+            .collect();
+        // Stable sort keeps layout order among equal scores
+        matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        matches.into_iter().map(|(index, _)| index).collect()
+    }
+
+    /// Handle the jump palette: Ctrl+P opens it, arrows pick, Enter jumps, Escape closes
+    /// Returns true while the palette is open so other keyboard shortcuts stay inactive
+    pub fn handle_jump_palette_input(&mut self) -> bool {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+
+        if self.jump_palette.is_none() {
+            if !(ctrl_down && is_key_pressed(KeyCode::P)) || self.image_slots.is_empty() {
+                return false;
+            }
+            // Drop the "p" typed along with the shortcut
+            while get_char_pressed().is_some() {}
+            self.jump_palette = Some(JumpPalette::default());
+            macroquad::miniquad::window::schedule_update();
+            return true;
+        }
+
+        if let Some(palette) = self.jump_palette.as_mut() {
+            while let Some(c) = get_char_pressed() {
+                if !c.is_control() && !ctrl_down {
+                    palette.query.push(c);
+                    palette.selected = 0;
+                }
+            }
+
+            if is_key_pressed(KeyCode::Backspace) {
+                palette.query.pop();
+                palette.selected = 0;
+            }
+        }
+
+        let match_count = self.jump_palette_matches().len().min(MAX_RESULTS);
+        if let Some(palette) = self.jump_palette.as_mut() {
+            if is_key_pressed(KeyCode::Down) && match_count > 0 {
+                palette.selected = (palette.selected + 1) % match_count;
+            } else if is_key_pressed(KeyCode::Up) && match_count > 0 {
+                palette.selected = (palette.selected + match_count - 1) % match_count;
+            }
+        }
+
+        if is_key_pressed(KeyCode::Escape) || (ctrl_down && is_key_pressed(KeyCode::P)) {
+            self.jump_palette = None;
+        } else if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            let selected = self
+                .jump_palette
+                .as_ref()
+                .map_or(0, |palette| palette.selected);
+            let target = self.jump_palette_matches().get(selected).copied();
+            self.jump_palette = None;
+
+            if let Some(index) = target {
+                log::info!(
+                    "🎯 Jumping to {}",
+                    self.slot_name(index).unwrap_or("texture")
+                );
+                self.focus_on_slots(&[index]);
+                self.highlighted_slots = vec![index];
+                self.highlight_until = Some(Instant::now() + JUMP_HIGHLIGHT_DURATION);
+                self.start_burst_rendering(JUMP_HIGHLIGHT_DURATION);
+            }
+        }
+
+        macroquad::miniquad::window::schedule_update();
+        true
+    }
+
+    pub fn draw_jump_palette(&self) {
+        let Some(ref palette) = self.jump_palette else {
+            return;
+        };
+
+        let matches = self.jump_palette_matches();
+        let text_size = 18.0;
+        let line_height = 26.0;
+        let width = (screen_width() * 0.5).clamp(360.0, 720.0);
+        let rows = matches.len().clamp(1, MAX_RESULTS);
+        let height = line_height * (rows as f32 + 1.0) + 24.0;
+        let x = ((screen_width() - width) / 2.0).round();
+        let y = (screen_height() * 0.15).round();
+
+        draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.9));
+        draw_rectangle_lines(x, y, width, height, 1.0, GRAY);
+
+        let text_params = |color: Color| TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: text_size as u16,
+            color,
+            ..Default::default()
+        };
+        let baseline = |row: usize| y + 12.0 + line_height * (row as f32 + 0.75);
+
+        draw_text_ex(
+            &format!("Jump to: {}_", palette.query),
+            x + 16.0,
+            baseline(0),
+            text_params(WHITE),
+        );
+
+        if matches.is_empty() {
+            draw_text_ex(
+                "No matching textures",
+                x + 16.0,
+                baseline(1),
+                text_params(LIGHTGRAY),
+            );
+            return;
+        }
+
+        for (row, &index) in matches.iter().take(MAX_RESULTS).enumerate() {
+            let selected = row == palette.selected;
+            if selected {
+                draw_rectangle(
+                    x + 4.0,
+                    y + 12.0 + line_height * (row as f32 + 1.0),
+                    width - 8.0,
+                    line_height,
+                    Color::new(0.3, 0.5, 0.9, 0.5),
+                );
+            }
+            draw_text_ex(
+                self.slot_name(index).unwrap_or("?"),
+                x + 16.0,
+                baseline(row + 1),
+                text_params(if selected { WHITE } else { LIGHTGRAY }),
+            );
+        }
+
+        if matches.len() > MAX_RESULTS {
+            let more = format!("+{} more", matches.len() - MAX_RESULTS);
+            let more_width = measure_text(&more, self.ui_font.as_ref(), 14, 1.0).width;
+            draw_text_ex(
+                &more,
+                x + width - more_width - 12.0,
+                baseline(0),
+                TextParams {
+                    font: self.ui_font.as_ref(),
+                    font_size: 14,
+                    color: GRAY,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+}
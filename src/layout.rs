@@ -2,6 +2,8 @@ use macroquad::math::Rect as MacroRect;
 use macroquad::prelude::*;
 use taffy::prelude::*;
 
+use crate::captions::CAPTION_HEIGHT;
+use crate::slot_groups::{GROUP_HEADER_HEIGHT, GROUP_STACK_SIZE, SlotGroup};
 use crate::texture_pipeline::EmbeddedMetadata;
 use crate::types::{GTexViewerApp, ImageContext, ImageState};
 
@@ -71,7 +73,10 @@ impl GTexViewerApp {
         let original_height = metadata.height as f32;
         let max_size = 100.0; // This will be interpreted as pixels by the layout system
 
-        let (layout_width, layout_height) = if original_width >= original_height {
+        let (layout_width, layout_height) = if original_width == 0.0 || original_height == 0.0 {
+            // Size not probed yet (lazily listed entry): square until it loads
+            (max_size, max_size)
+        } else if original_width >= original_height {
             // Width-constrained: width = 100, height = proportional
             (max_size, max_size * original_height / original_width)
         } else {
@@ -96,11 +101,21 @@ impl GTexViewerApp {
             self.image_slots.len()
         );
 
-        let slot_count = self.image_slots.len();
+        // Slots the filter or focus mode hides take no space in the layout
+        self.apply_slot_filter();
+        self.apply_focus_mode();
+        let shown: Vec<usize> = (0..self.image_slots.len())
+            .filter(|&index| !self.image_slots[index].hidden)
+            .collect();
+        for slot in self.image_slots.iter_mut().filter(|slot| slot.hidden) {
+            slot.position = Vec2::ZERO;
+            slot.size = Vec2::ZERO;
+        }
 
         // Special case for single image - use direct screen coordinates
-        if slot_count == 1 {
-            let slot = &mut self.image_slots[0];
+        if let [index] = shown[..] {
+            self.slot_groups.clear();
+            let slot = &mut self.image_slots[index];
 
             // Get the actual image size
             let image_size = match &slot.state {
@@ -137,7 +152,7 @@ impl GTexViewerApp {
             slot.size = display_size;
         } else {
             // Use Taffy Flexbox for multi-image layout
-            self.setup_taffy_flexbox_layout(available_size);
+            self.setup_taffy_flexbox_layout(available_size, &shown);
         }
 
         // Calculate actual content bounds based on all image positions
@@ -146,10 +161,13 @@ impl GTexViewerApp {
         self.layout_needs_update = false;
     }
 
-    pub fn setup_taffy_flexbox_layout(&mut self, _available_size: Vec2) {
+    pub fn setup_taffy_flexbox_layout(&mut self, _available_size: Vec2, shown: &[usize]) {
         // Clear existing tree
         self.taffy_tree = TaffyTree::new();
 
+        // Flexbox places children in order, so the sort mode decides where each slot lands
+        let shown = self.sorted_slots(shown);
+
         // Calculate visible viewport space considering current zoom level
         // When zoomed out, we have more visible space and can fit more columns
         let base_viewport_width = screen_width();
@@ -159,6 +177,12 @@ impl GTexViewerApp {
 
         // Create flexbox container style that wraps items and centers them
         let gap_size = 20.0; // Gap in pixels
+        // Rows leave room below for file name captions while they're on
+        let caption_band = if self.show_captions {
+            CAPTION_HEIGHT
+        } else {
+            0.0
+        };
         let flex_style = Style {
             display: Display::Flex,
             flex_direction: FlexDirection::Row,
@@ -172,56 +196,111 @@ impl GTexViewerApp {
             },
             gap: Size {
                 width: length(gap_size),
-                height: length(gap_size),
+                height: length(gap_size + caption_band),
             },
             ..Default::default()
         };
 
+        // Slots from more than one source get a header row and a wrapping row per source;
+        // a single source stays one flat wall
+        let groups = self.group_slots(&shown);
+        let grouped = groups.len() > 1;
+
         // Create nodes for each image slot using measure functions for aspect ratios
-        let mut child_nodes = Vec::with_capacity(self.image_slots.len());
+        let mut slot_nodes = Vec::with_capacity(shown.len());
+        let mut group_nodes = Vec::new(); // Header and stacked card nodes, one entry per group
+        let mut root_children = Vec::new();
 
-        for slot in self.image_slots.iter() {
-            // Get the actual image size for the measure function
-            let image_size = match &slot.state {
-                ImageState::Loaded { image } => {
-                    vec2(image.info.width as f32, image.info.height as f32)
-                }
-                ImageState::Placeholder {
-                    layout_metadata, ..
-                } => vec2(layout_metadata.width as f32, layout_metadata.height as f32),
-                ImageState::Failed { .. } => vec2(100.0, 100.0),
+        if grouped {
+            let header_style = Style {
+                size: Size {
+                    width: length(viewport_width),
+                    height: length(GROUP_HEADER_HEIGHT),
+                },
+                flex_shrink: 0.0,
+                ..Default::default()
             };
-
-            // Create image context for measure function
-            let image_context = ImageContext {
-                width: image_size.x,
-                height: image_size.y,
+            let group_style = Style {
+                size: Size {
+                    width: length(viewport_width),
+                    height: auto(),
+                },
+                flex_shrink: 0.0,
+                ..flex_style.clone()
             };
 
-            // Create child style that lets measure function and Taffy flexbox work together
-            let child_style = Style {
-                // Let measure function determine dimensions
+            // Collapsed groups show one stacked card in place of their thumbnails
+            let stack_style = Style {
                 size: Size {
-                    width: auto(),
-                    height: auto(),
+                    width: length(GROUP_STACK_SIZE),
+                    height: length(GROUP_STACK_SIZE),
                 },
-                // No max_size constraints - let Taffy's flexbox algorithm handle space allocation
-                flex_shrink: 1.0, // Allow shrinking if needed
-                flex_grow: 0.0,   // Don't grow beyond measure function result
+                flex_shrink: 0.0,
                 ..Default::default()
             };
 
-            // Create leaf node with context for measure function
-            if let Ok(node) = self
-                .taffy_tree
-                .new_leaf_with_context(child_style, image_context)
-            {
-                child_nodes.push(node);
+            for group in &groups {
+                let mut items = Vec::with_capacity(group.slots.len());
+                let mut stack = None;
+                if self.collapsed_groups.contains(&group.key) {
+                    for &index in &group.slots {
+                        let slot = &mut self.image_slots[index];
+                        slot.hidden = true;
+                        slot.position = Vec2::ZERO;
+                        slot.size = Vec2::ZERO;
+                    }
+                    stack = self.taffy_tree.new_leaf(stack_style.clone()).ok();
+                    items.extend(stack);
+                } else {
+                    for &index in &group.slots {
+                        if let Some(node) = self.image_leaf(index) {
+                            slot_nodes.push((index, node));
+                            items.push(node);
+                        }
+                    }
+                }
+
+                let header = self.taffy_tree.new_leaf(header_style.clone()).ok();
+                let row = self
+                    .taffy_tree
+                    .new_with_children(group_style.clone(), &items)
+                    .ok();
+                group_nodes.push(header.zip(row).map(|(header, row)| {
+                    root_children.extend([header, row]);
+                    (header, stack)
+                }));
+            }
+        } else {
+            for &index in &shown {
+                if let Some(node) = self.image_leaf(index) {
+                    slot_nodes.push((index, node));
+                    root_children.push(node);
+                }
             }
         }
 
+        // Groups stack vertically, centered like the flat wall
+        let root_style = if grouped {
+            Style {
+                flex_direction: FlexDirection::Column,
+                flex_wrap: FlexWrap::NoWrap,
+                justify_content: Some(JustifyContent::Center),
+                align_items: Some(AlignItems::Center),
+                gap: Size {
+                    width: length(gap_size),
+                    height: length(gap_size / 2.0 + caption_band),
+                },
+                ..flex_style
+            }
+        } else {
+            flex_style
+        };
+
         // Create the flexbox container with all child nodes
-        if let Ok(root_node) = self.taffy_tree.new_with_children(flex_style, &child_nodes) {
+        if let Ok(root_node) = self
+            .taffy_tree
+            .new_with_children(root_style, &root_children)
+        {
             // Compute layout with measure function
             // The container size should be the adjusted viewport size considering zoom!
             let container_size = Size {
@@ -241,40 +320,114 @@ impl GTexViewerApp {
             );
 
             if layout_result.is_ok() {
-                // Apply computed layout to image slots, converting pixel coordinates to world coordinates
-                for (index, slot) in self.image_slots.iter_mut().enumerate() {
-                    if let Some(&child_node) = child_nodes.get(index)
-                        && let Ok(layout) = self.taffy_tree.layout(child_node)
-                    {
-                        // Taffy gives us positions in visible space - convert to world coordinates
-                        // The layout was computed using visible space dimensions, so we need to convert back
-                        let pixels_per_world_unit =
-                            base_viewport_width.max(base_viewport_height) / 2.0;
-                        let world_scale = 1.0 / pixels_per_world_unit;
-
-                        // Convert layout coordinates to world coordinates
-                        let world_x = (layout.location.x - viewport_width / 2.0) * world_scale;
-                        let world_y = (layout.location.y - viewport_height / 2.0) * world_scale;
-                        let world_w = layout.size.width * world_scale;
-                        let world_h = layout.size.height * world_scale;
-
-                        slot.position = vec2(world_x, world_y);
-                        slot.size = vec2(world_w, world_h);
-
-                        // Debug logging for layout positions
-                        log::debug!(
-                            "Layout slot {index}: pos=({world_x:.1}, {world_y:.1}), size=({world_w:.1}, {world_h:.1})"
-                        );
+                // Taffy gives us positions in visible space - convert to world coordinates
+                // The layout was computed using visible space dimensions, so we need to convert back
+                let pixels_per_world_unit = base_viewport_width.max(base_viewport_height) / 2.0;
+                let world_scale = 1.0 / pixels_per_world_unit;
+                let to_world = |node: NodeId| {
+                    let layout = self.taffy_tree.layout(node).ok()?;
+                    // Locations are relative to the parent, so add up the group row's offset
+                    let mut location = layout.location;
+                    let mut current = node;
+                    while let Some(parent) = self.taffy_tree.parent(current) {
+                        let parent_location = self.taffy_tree.layout(parent).ok()?.location;
+                        location.x += parent_location.x;
+                        location.y += parent_location.y;
+                        current = parent;
                     }
+                    Some(MacroRect::new(
+                        (location.x - viewport_width / 2.0) * world_scale,
+                        (location.y - viewport_height / 2.0) * world_scale,
+                        layout.size.width * world_scale,
+                        layout.size.height * world_scale,
+                    ))
+                };
+
+                // Apply computed layout to image slots, converting pixel coordinates to world coordinates
+                let slot_rects: Vec<(usize, MacroRect)> = slot_nodes
+                    .iter()
+                    .filter_map(|&(index, node)| Some((index, to_world(node)?)))
+                    .collect();
+                let group_rects: Vec<Option<(MacroRect, Option<MacroRect>)>> = group_nodes
+                    .iter()
+                    .map(|nodes| {
+                        let (header, stack) = (*nodes)?;
+                        Some((to_world(header)?, stack.and_then(to_world)))
+                    })
+                    .collect();
+
+                for (index, rect) in slot_rects {
+                    let slot = &mut self.image_slots[index];
+                    slot.position = vec2(rect.x, rect.y);
+                    slot.size = vec2(rect.w, rect.h);
+
+                    // Debug logging for layout positions
+                    log::debug!(
+                        "Layout slot {index}: pos=({:.1}, {:.1}), size=({:.1}, {:.1})",
+                        rect.x,
+                        rect.y,
+                        rect.w,
+                        rect.h
+                    );
                 }
+
+                // Group nodes only exist when grouped, so a flat wall leaves no groups
+                self.slot_groups = groups
+                    .into_iter()
+                    .zip(group_rects)
+                    .filter_map(|(group, rects)| {
+                        let (header, stack) = rects?;
+                        Some(SlotGroup {
+                            header,
+                            stack,
+                            ..group
+                        })
+                    })
+                    .collect();
             } else {
                 log::error!("❌ Taffy layout computation failed: {layout_result:?}");
             }
         }
     }
 
+    /// Leaf node sized by the measure function from a slot's image aspect ratio
+    fn image_leaf(&mut self, index: usize) -> Option<NodeId> {
+        // Get the actual image size for the measure function
+        let image_size = match &self.image_slots[index].state {
+            ImageState::Loaded { image } => vec2(image.info.width as f32, image.info.height as f32),
+            ImageState::Placeholder {
+                layout_metadata, ..
+            } => vec2(layout_metadata.width as f32, layout_metadata.height as f32),
+            ImageState::Failed { .. } => vec2(100.0, 100.0),
+        };
+
+        // Create image context for measure function
+        let image_context = ImageContext {
+            width: image_size.x,
+            height: image_size.y,
+        };
+
+        // Create child style that lets measure function and Taffy flexbox work together
+        let child_style = Style {
+            // Let measure function determine dimensions
+            size: Size {
+                width: auto(),
+                height: auto(),
+            },
+            // No max_size constraints - let Taffy's flexbox algorithm handle space allocation
+            flex_shrink: 1.0, // Allow shrinking if needed
+            flex_grow: 0.0,   // Don't grow beyond measure function result
+            ..Default::default()
+        };
+
+        // Create leaf node with context for measure function
+        self.taffy_tree
+            .new_leaf_with_context(child_style, image_context)
+            .ok()
+    }
+
     pub fn calculate_content_bounds(&mut self) {
-        if self.image_slots.is_empty() {
+        if self.image_slots.iter().all(|slot| slot.hidden) {
             self.content_bounds = MacroRect::new(0.0, 0.0, 0.0, 0.0);
             return;
         }
@@ -284,7 +437,7 @@ impl GTexViewerApp {
         let mut max_x = f32::NEG_INFINITY;
         let mut max_y = f32::NEG_INFINITY;
 
-        for slot in &self.image_slots {
+        for slot in self.image_slots.iter().filter(|slot| !slot.hidden) {
             min_x = min_x.min(slot.position.x);
             min_y = min_y.min(slot.position.y);
             max_x = max_x.max(slot.position.x + slot.size.x);
@@ -0,0 +1,285 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+use crate::workspace::Panel;
+
+/// Display-only levels curve: input black/white points, midtone gamma and contrast
+/// Evaluated in the display shader; texture data is never modified
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelsCurve {
+    pub black_point: f32,
+    pub white_point: f32,
+    pub gamma: f32,
+    pub contrast: f32,
+}
+
+impl Default for LevelsCurve {
+    fn default() -> Self {
+        Self {
+            black_point: 0.0,
+            white_point: 1.0,
+            gamma: 1.0,
+            contrast: 1.0,
+        }
+    }
+}
+
+impl LevelsCurve {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// CPU mirror of the shader curve, used to plot the widget
+    pub fn apply(&self, value: f32) -> f32 {
+        let range = (self.white_point - self.black_point).max(1e-4);
+        let normalized = ((value - self.black_point) / range).clamp(0.0, 1.0);
+        let curved = normalized.powf(1.0 / self.gamma);
+        ((curved - 0.5) * self.contrast + 0.5).clamp(0.0, 1.0)
+    }
+
+    /// Input value whose output is mid-gray - where the midtone handle sits
+    fn midtone_position(&self) -> f32 {
+        self.black_point + (self.white_point - self.black_point) * 0.5f32.powf(self.gamma)
+    }
+}
+
+/// Draggable handles on the levels widget
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LevelsHandle {
+    Black,
+    Midtone,
+    White,
+    Contrast,
+}
+
+/// Size of the levels widget in any dock
+pub const LEVELS_WIDGET_SIZE: Vec2 = Vec2::new(230.0, 200.0);
+const GRAPH_HEIGHT: f32 = 110.0;
+const CONTRAST_RANGE: (f32, f32) = (0.25, 2.0);
+
+impl GTexViewerApp {
+    fn levels_widget_rect(&self) -> MacroRect {
+        self.panel_rect(Panel::Levels)
+    }
+
+    fn levels_graph_rect(&self) -> MacroRect {
+        let panel = self.levels_widget_rect();
+        MacroRect::new(panel.x + 15.0, panel.y + 25.0, panel.w - 30.0, GRAPH_HEIGHT)
+    }
+
+    /// Y coordinate of the black/midtone/white handle track
+    fn levels_track_y(&self) -> f32 {
+        let graph = self.levels_graph_rect();
+        graph.y + graph.h + 12.0
+    }
+
+    /// Y coordinate of the contrast slider
+    fn levels_contrast_y(&self) -> f32 {
+        self.levels_track_y() + 30.0
+    }
+
+    /// Handle L (toggle) / Shift+L (reset) and handle dragging
+    /// Returns true when the widget owns the mouse so the camera doesn't pan
+    pub fn handle_levels_input(&mut self) -> bool {
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        if is_key_pressed(KeyCode::L) && !alt_down {
+            if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+                self.levels = LevelsCurve::default();
+                log::info!("📈 Levels reset");
+            } else {
+                self.toggle_panel(Panel::Levels);
+            }
+        }
+
+        if !self.panel_shown(Panel::Levels) {
+            self.levels_drag = None;
+            return false;
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let panel = self.levels_widget_rect();
+        let graph = self.levels_graph_rect();
+        let mouse_in_panel = panel.contains(vec2(mouse_x, mouse_y));
+
+        if is_mouse_button_pressed(MouseButton::Left) && mouse_in_panel {
+            let to_x = |value: f32| graph.x + value * graph.w;
+            let track_y = self.levels_track_y();
+            let contrast_y = self.levels_contrast_y();
+
+            self.levels_drag = if (mouse_y - track_y).abs() < 10.0 {
+                // Pick the closest of the three track handles
+                [
+                    (LevelsHandle::Black, to_x(self.levels.black_point)),
+                    (LevelsHandle::Midtone, to_x(self.levels.midtone_position())),
+                    (LevelsHandle::White, to_x(self.levels.white_point)),
+                ]
+                .into_iter()
+                .map(|(handle, x)| (handle, (x - mouse_x).abs()))
+                .filter(|(_, distance)| *distance < 12.0)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(handle, _)| handle)
+            } else if (mouse_y - contrast_y).abs() < 10.0 {
+                Some(LevelsHandle::Contrast)
+            } else {
+                None
+            };
+        }
+
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.levels_drag = None;
+        }
+
+        if let Some(handle) = self.levels_drag {
+            let t = ((mouse_x - graph.x) / graph.w).clamp(0.0, 1.0);
+            let curve = &mut self.levels;
+
+            match handle {
+                LevelsHandle::Black => curve.black_point = t.min(curve.white_point - 0.02),
+                LevelsHandle::White => curve.white_point = t.max(curve.black_point + 0.02),
+                LevelsHandle::Midtone => {
+                    let range = curve.white_point - curve.black_point;
+                    let relative = ((t - curve.black_point) / range).clamp(0.01, 0.99);
+                    curve.gamma = (relative.ln() / 0.5f32.ln()).clamp(0.1, 10.0);
+                }
+                LevelsHandle::Contrast => {
+                    curve.contrast = CONTRAST_RANGE.0 + t * (CONTRAST_RANGE.1 - CONTRAST_RANGE.0);
+                }
+            }
+        }
+
+        // Keep the panel from leaking clicks into camera panning
+        self.levels_drag.is_some() || (mouse_in_panel && is_mouse_button_down(MouseButton::Left))
+    }
+
+    pub fn draw_levels_widget(&self) {
+        if !self.panel_shown(Panel::Levels) {
+            return;
+        }
+
+        let panel = self.levels_widget_rect();
+        let graph = self.levels_graph_rect();
+        let curve = &self.levels;
+
+        draw_rectangle(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            Color::new(0.1, 0.1, 0.1, 0.95),
+        );
+        draw_rectangle_lines(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            2.0,
+            Color::new(0.3, 0.3, 0.3, 0.9),
+        );
+
+        let title_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 14,
+            color: WHITE,
+            ..Default::default()
+        };
+        draw_text_ex(
+            &format!(
+                "Levels  B {:.2}  W {:.2}  Gamma {:.2}",
+                curve.black_point, curve.white_point, curve.gamma
+            ),
+            panel.x + 10.0,
+            panel.y + 17.0,
+            title_params,
+        );
+
+        // Graph background with identity diagonal for reference
+        draw_rectangle(
+            graph.x,
+            graph.y,
+            graph.w,
+            graph.h,
+            Color::new(0.05, 0.05, 0.05, 1.0),
+        );
+        draw_line(
+            graph.x,
+            graph.y + graph.h,
+            graph.x + graph.w,
+            graph.y,
+            1.0,
+            Color::new(0.3, 0.3, 0.3, 1.0),
+        );
+
+        // Plot the curve as a polyline
+        let steps = 64;
+        let point = |i: usize| {
+            let input = i as f32 / steps as f32;
+            vec2(
+                graph.x + input * graph.w,
+                graph.y + graph.h - curve.apply(input) * graph.h,
+            )
+        };
+        for i in 0..steps {
+            let (a, b) = (point(i), point(i + 1));
+            draw_line(a.x, a.y, b.x, b.y, 2.0, WHITE);
+        }
+
+        // Handle track: black, midtone and white triangles
+        let track_y = self.levels_track_y();
+        draw_line(
+            graph.x,
+            track_y,
+            graph.x + graph.w,
+            track_y,
+            1.0,
+            Color::new(0.4, 0.4, 0.4, 1.0),
+        );
+        for (value, color) in [
+            (curve.black_point, BLACK),
+            (curve.midtone_position(), GRAY),
+            (curve.white_point, WHITE),
+        ] {
+            let x = graph.x + value * graph.w;
+            draw_triangle(
+                vec2(x, track_y - 6.0),
+                vec2(x - 6.0, track_y + 6.0),
+                vec2(x + 6.0, track_y + 6.0),
+                color,
+            );
+            draw_triangle_lines(
+                vec2(x, track_y - 6.0),
+                vec2(x - 6.0, track_y + 6.0),
+                vec2(x + 6.0, track_y + 6.0),
+                1.0,
+                LIGHTGRAY,
+            );
+        }
+
+        // Contrast slider
+        let contrast_y = self.levels_contrast_y();
+        let contrast_t =
+            (curve.contrast - CONTRAST_RANGE.0) / (CONTRAST_RANGE.1 - CONTRAST_RANGE.0);
+        draw_line(
+            graph.x,
+            contrast_y,
+            graph.x + graph.w,
+            contrast_y,
+            2.0,
+            Color::new(0.4, 0.4, 0.4, 1.0),
+        );
+        draw_circle(graph.x + contrast_t * graph.w, contrast_y, 6.0, SKYBLUE);
+
+        let label_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 12,
+            color: GRAY,
+            ..Default::default()
+        };
+        draw_text_ex(
+            &format!("Contrast {:.2}   (Shift+L to reset)", curve.contrast),
+            graph.x,
+            contrast_y + 20.0,
+            label_params,
+        );
+    }
+}
@@ -1,11 +1,58 @@
+pub mod analysis;
 pub mod app;
+pub mod array_layers;
+pub mod background;
+pub mod breadcrumb;
+pub mod captions;
+pub mod color_profile;
+pub mod color_vision;
+pub mod compressed_upload;
+pub mod credentials;
+pub mod cubemap_view;
+pub mod details;
+pub mod display_transform;
+pub mod export;
+pub mod exposure;
+pub mod eyedropper;
+pub mod false_color;
+pub mod focus_mode;
+pub mod gamut;
+pub mod guides;
+pub mod histogram;
 pub mod input;
+pub mod jump_palette;
 pub mod layout;
+pub mod levels;
 pub mod loading;
+pub mod lut;
+pub mod minimap;
+pub mod offset_map;
+pub mod pixel_diff;
+pub mod pixel_inspector;
+pub mod portable;
+pub mod render_test;
 pub mod renderer;
+pub mod resolution;
+pub mod sampling;
+pub mod selection;
+pub mod slideshow;
+pub mod slot_filter;
+pub mod slot_groups;
+pub mod slot_sort;
+pub mod srgb;
+pub mod status_bar;
+pub mod surface_preview;
+pub mod sync_compare;
+pub mod tabs;
+pub mod texture_cache;
 pub mod texture_pipeline;
+pub mod tiling;
+pub mod timeline;
+pub mod transcode;
 pub mod types;
 pub mod ui;
 pub mod utils;
+pub mod validation;
+pub mod workspace;
 
 pub use types::GTexViewerApp;
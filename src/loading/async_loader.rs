@@ -2,27 +2,209 @@ use macroquad::prelude::*;
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::texture_pipeline::{EmbeddedMetadata, ImageInfo, Pipeline};
+use crate::analysis::TextureAnalysis;
+use crate::array_layers::LayerStack;
+use crate::compressed_upload::{compressed_upload_enabled, upload_compressed};
+use crate::cubemap_view::CubemapView;
+use crate::loading::WorkerTracker;
+use crate::loading::workers::WorkerGuard;
+use crate::sampling::{OwnedTexture, upload_texture};
+use crate::srgb::{stores_color, upload_srgb};
+use crate::texture_pipeline::animation::{DecodedFrame, decode_animation};
+use crate::texture_pipeline::cubemap::{CubemapImages, decode_cubemap};
+use crate::texture_pipeline::parsers::{CompressedFormat, GpuPayload, TranscodeTarget};
+use crate::texture_pipeline::progressive::decode_preview;
+use crate::texture_pipeline::{
+    EmbeddedMetadata, ImageInfo, LoadedImageData, Pipeline, RetryLater, RetryPolicy,
+};
+use crate::tiling::SamplerPreview;
+use crate::timeline::Animation;
+use crate::transcode::TranscodeSettings;
 
 #[derive(Clone)]
 pub struct LoadedImage {
     pub texture: Texture2D,
     pub info: ImageInfo,
     pub path: std::path::PathBuf,
+    pub analysis: TextureAnalysis,
+    pub metadata: EmbeddedMetadata, // Metadata the image was loaded from
+    pub animation: Option<Animation>, // Frames and playback state for animated images
+    pub expanded_frame: Option<usize>, // Frame shown when the animation is expanded into one slot per frame
+    pub cubemap: Option<CubemapView>,  // Unfolded layouts for KTX2/DDS cubemaps
+    pub layers: Option<LayerStack>,    // Every layer of a KTX2/DDS texture array
+    pub gpu_compressed: bool, // Texture holds block-compressed data, so it can't be read back
+    pub gpu_upload: Option<Arc<OwnedTexture>>, // Frees a compressed or sRGB upload with its last holder
+    pub sampler_preview: SamplerPreview,       // Wrap modes for the tiling preview
+    pub tier: ResolutionTier, // Whether `texture` is the proxy or the native resolution
+    pub proxy: Option<Texture2D>, // Downscaled texture kept for zoomed-out viewing, None when not needed
 }
 
+impl LoadedImage {
+    /// Native pixel size of the texture being shown, even while the proxy stands in for it
+    pub fn pixel_size(&self) -> Vec2 {
+        match self.tier {
+            ResolutionTier::Proxy => vec2(self.info.width as f32, self.info.height as f32),
+            ResolutionTier::Full => self.texture.size(),
+        }
+    }
+}
+
+/// Longest edge of the proxy texture uploaded in place of large images
+pub const PROXY_SIZE: u32 = 256;
+
+/// Resolution an image is uploaded at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolutionTier {
+    Proxy, // Downscaled to PROXY_SIZE, enough while the slot is small on screen
+    Full,  // Native resolution
+}
+
+/// Upper bound on decoded texture array layers per image to keep memory in check
+const MAX_ARRAY_LAYERS: usize = 256;
+
+// By entry key and tier, so a proxy reload and an upgrade of one entry don't collide
+type CompletedImages =
+    Arc<Mutex<HashMap<(String, ResolutionTier), Result<LoadedImageResult, String>>>>;
+
 pub struct AsyncImageLoader {
-    completed_images: Arc<Mutex<HashMap<String, Result<LoadedImageResult, String>>>>,
+    completed_images: CompletedImages,
+    previews: Arc<Mutex<HashMap<String, Image>>>, // Coarse previews published ahead of the full decode
     max_updates_per_frame: usize,
-    cancel_flag: Arc<AtomicBool>, // Atomic flag for cancellation
+    cancel_flag: Arc<AtomicBool>,     // Atomic flag for cancellation
+    pub transcode: TranscodeSettings, // Basis transcode targets applied to new batches
 }
 
 struct LoadedImageResult {
     parsed_image: Image,
     info: ImageInfo,
     source_path: std::path::PathBuf,
+    analysis: TextureAnalysis,
+    metadata: EmbeddedMetadata,
+    frames: Option<Vec<DecodedFrame>>,
+    cubemap: Option<CubemapImages>,
+    layers: Option<Vec<Image>>,
+    gpu_payload: Option<GpuPayload>,
+    tier: ResolutionTier,
+}
+
+/// Why a load attempt produced no image
+enum LoadFailure {
+    Failed(String),
+    RetryLater(EmbeddedMetadata, RetryLater, String), // Transient read failure, handed back to re-queue
+}
+
+impl From<String> for LoadFailure {
+    fn from(error: String) -> Self {
+        Self::Failed(error)
+    }
+}
+
+/// Where a batch's loads report to, shared with the retries they re-queue
+#[derive(Clone)]
+struct LoadJob {
+    completed_images: CompletedImages,
+    previews: Arc<Mutex<HashMap<String, Image>>>,
+    cancel_flag: Arc<AtomicBool>,
+    transcode: Arc<TranscodeSettings>,
+    tier: ResolutionTier,
+    _worker: Arc<WorkerGuard>, // Held until the batch and its retries are done
+}
+
+impl LoadJob {
+    fn run(&self, metadata: EmbeddedMetadata, attempt: u32) {
+        // Check for cancellation before processing each image
+        if self.cancel_flag.load(Ordering::Relaxed) {
+            log::debug!("🚫 Cancellation requested, skipping image load");
+            return;
+        }
+
+        let key = metadata.entry_key();
+        let transcode_target = self.transcode.target_for(&key);
+        let result = match AsyncImageLoader::load_single_image_with_hint(
+            metadata,
+            transcode_target,
+            self.tier,
+            &self.previews,
+        ) {
+            Ok(result) => Ok(result),
+            Err(LoadFailure::Failed(error)) => Err(error),
+            Err(LoadFailure::RetryLater(metadata, retry, error)) => {
+                if attempt < retry.max_attempts {
+                    let delay = RetryPolicy::default().delay(attempt);
+                    log::warn!(
+                        "🔁 Load of {key} failed (attempt {attempt}/{}), retrying in {delay:?}: {error}",
+                        retry.max_attempts
+                    );
+                    let job = self.clone();
+                    run_after(delay, move || {
+                        rayon::spawn(move || job.run(metadata, attempt + 1));
+                    });
+                    return;
+                }
+                Err(format!(
+                    "Failed to load image data using hint: still failing after {attempt} attempts: {error}"
+                ))
+            }
+        };
+
+        // Check for cancellation before storing result
+        if self.cancel_flag.load(Ordering::Relaxed) {
+            log::debug!("🚫 Cancellation requested, not storing result for {key}");
+            return;
+        }
+
+        if let Ok(mut completed) = self.completed_images.lock() {
+            match &result {
+                Ok(_) => log::info!("✅ Rayon completed successfully: {key}"),
+                Err(e) => log::warn!("⚠️ Rayon skipping file: {key}: {e}"),
+            }
+            completed.insert((key, self.tier), result);
+        } else {
+            log::error!("🔒 Failed to acquire lock for completed_images: {key}");
+        }
+    }
+}
+
+/// Run `job` after `delay` on a timer thread, so waits between retries never hold a
+/// rayon thread
+fn run_after(delay: Duration, job: impl FnOnce() + Send + 'static) {
+    type Delayed = (Instant, Box<dyn FnOnce() + Send>);
+    static TIMER: OnceLock<mpsc::Sender<Delayed>> = OnceLock::new();
+
+    let timer = TIMER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Delayed>();
+        thread::spawn(move || {
+            let mut pending: Vec<Delayed> = Vec::new();
+            loop {
+                let received = match pending.iter().map(|(due, _)| *due).min() {
+                    Some(due) => {
+                        receiver.recv_timeout(due.saturating_duration_since(Instant::now()))
+                    }
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match received {
+                    Ok(delayed) => pending.push(delayed),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+
+                let now = Instant::now();
+                let (due, waiting): (Vec<_>, Vec<_>) =
+                    pending.into_iter().partition(|(due, _)| *due <= now);
+                pending = waiting;
+                for (_, job) in due {
+                    job();
+                }
+            }
+        });
+        sender
+    });
+    let _ = timer.send((Instant::now() + delay, Box::new(job)));
 }
 
 impl Default for AsyncImageLoader {
@@ -35,52 +217,54 @@ impl AsyncImageLoader {
     pub fn new() -> Self {
         Self {
             completed_images: Arc::new(Mutex::new(HashMap::new())),
+            previews: Arc::new(Mutex::new(HashMap::new())),
             max_updates_per_frame: 1, // Only process 1 texture per frame to keep UI responsive
             cancel_flag: Arc::new(AtomicBool::new(false)),
+            transcode: TranscodeSettings::from_env(),
         }
     }
 
-    pub fn start_loading_batch(&mut self, metadata_list: Vec<EmbeddedMetadata>) {
+    /// Decode a batch on the Rayon pool; the batch counts as one worker in `workers`
+    /// Large single images come back as proxies, upgraded later with `ResolutionTier::Full`
+    pub fn start_loading_batch(
+        &mut self,
+        metadata_list: Vec<EmbeddedMetadata>,
+        workers: &WorkerTracker,
+    ) {
+        self.start_loading_batch_at(metadata_list, ResolutionTier::Proxy, workers);
+    }
+
+    /// Decode a batch at the given tier; `Full` only re-decodes the native texture of
+    /// images already loaded as proxies
+    pub fn start_loading_batch_at(
+        &mut self,
+        metadata_list: Vec<EmbeddedMetadata>,
+        tier: ResolutionTier,
+        workers: &WorkerTracker,
+    ) {
         log::info!(
-            "🚀 Starting batch loading for {} images",
+            "🚀 Starting batch loading for {} images ({tier:?})",
             metadata_list.len()
         );
 
         // Reset cancellation flag for new batch
         self.cancel_flag.store(false, Ordering::Relaxed);
 
-        let completed_images = self.completed_images.clone();
-        let cancel_flag = self.cancel_flag.clone();
+        let job = LoadJob {
+            completed_images: self.completed_images.clone(),
+            previews: self.previews.clone(),
+            cancel_flag: self.cancel_flag.clone(),
+            transcode: Arc::new(self.transcode.clone()),
+            tier,
+            _worker: Arc::new(workers.start()),
+        };
 
         rayon::spawn(move || {
-            metadata_list.into_par_iter().for_each(|metadata| {
-                // Check for cancellation before processing each image
-                if cancel_flag.load(Ordering::Relaxed) {
-                    log::debug!("🚫 Cancellation requested, skipping image load");
-                    return;
-                }
-
-                let key = format!("{}:{}", metadata.source_path.display(), metadata.name);
-                let result = Self::load_single_image_with_hint(metadata);
+            metadata_list
+                .into_par_iter()
+                .for_each(|metadata| job.run(metadata, 1));
 
-                // Check for cancellation before storing result
-                if cancel_flag.load(Ordering::Relaxed) {
-                    log::debug!("🚫 Cancellation requested, not storing result for {key}");
-                    return;
-                }
-
-                if let Ok(mut completed) = completed_images.lock() {
-                    match &result {
-                        Ok(_) => log::info!("✅ Rayon completed successfully: {key}"),
-                        Err(e) => log::warn!("⚠️ Rayon skipping file: {key}: {e}"),
-                    }
-                    completed.insert(key, result);
-                } else {
-                    log::error!("🔒 Failed to acquire lock for completed_images: {key}");
-                }
-            });
-
-            if cancel_flag.load(Ordering::Relaxed) {
+            if job.cancel_flag.load(Ordering::Relaxed) {
                 log::info!("🚫 Rayon batch loading was cancelled");
             }
         });
@@ -89,18 +273,44 @@ impl AsyncImageLoader {
     /// NEW: Direct hint-based loading - NO RE-PARSING of containers!
     /// This follows the refactoring plan exactly
     fn load_single_image_with_hint(
-        metadata: EmbeddedMetadata,
-    ) -> Result<LoadedImageResult, String> {
-        let key = format!("{}:{}", metadata.source_path.display(), metadata.name);
+        mut metadata: EmbeddedMetadata,
+        transcode_target: TranscodeTarget,
+        tier: ResolutionTier,
+        previews: &Mutex<HashMap<String, Image>>,
+    ) -> Result<LoadedImageResult, LoadFailure> {
+        let key = metadata.entry_key();
 
         let pipeline = Pipeline::new();
 
         // Use the hint system for direct access - NO container re-parsing!
-        let loaded_data = pipeline.metadata_to_loaded_data(&metadata).map_err(|e| {
-            let error_msg = format!("Failed to load image data using hint: {e}");
-            log::error!("Failed to load {key}: {e}");
-            error_msg
-        })?;
+        let mut loaded_data = match pipeline.metadata_to_loaded_data(&metadata) {
+            Ok(loaded_data) => loaded_data,
+            Err(e) => {
+                if let Some(&retry) = e.downcast_ref::<RetryLater>() {
+                    return Err(LoadFailure::RetryLater(metadata, retry, format!("{e:#}")));
+                }
+                log::error!("Failed to load {key}: {e}");
+                return Err(format!("Failed to load image data using hint: {e}").into());
+            }
+        };
+        loaded_data.transcode_target = transcode_target;
+
+        // Lazily listed entries only learn their format and size once read
+        if metadata.embedded_hint.deferred_probe() {
+            metadata.format = loaded_data.format;
+            metadata.width = loaded_data.width;
+            metadata.height = loaded_data.height;
+        }
+
+        // Large JPEGs show a coarse preview while the full decode runs; upgrades
+        // already have their proxy on screen
+        if tier == ResolutionTier::Proxy
+            && let Some(preview) = decode_preview(&loaded_data)
+            && let Ok(mut previews) = previews.lock()
+        {
+            log::debug!("🌫️ Preview ready for {key}");
+            previews.insert(key.clone(), downscale_to_proxy(&preview).unwrap_or(preview));
+        }
 
         // Parse the loaded data to macroquad format
         let (macroquad_image, info) = pipeline.parse_image_data(&loaded_data).map_err(|e| {
@@ -109,42 +319,192 @@ impl AsyncImageLoader {
             error_msg
         })?;
 
+        // Upgrades of proxies only need the native texture; the rest was kept from the proxy load
+        if tier == ResolutionTier::Full {
+            return Ok(LoadedImageResult {
+                parsed_image: macroquad_image,
+                info,
+                source_path: metadata.source_path.clone(),
+                analysis: TextureAnalysis::default(),
+                metadata,
+                frames: None,
+                cubemap: None,
+                layers: None,
+                gpu_payload: Self::gpu_payload(&loaded_data),
+                tier,
+            });
+        }
+
+        // Analyse while the decoded pixels are still on the CPU
+        let analysis = TextureAnalysis::analyze(&loaded_data, &macroquad_image);
+        if let Some(ref coverage) = analysis.alpha_coverage
+            && coverage.is_thinning(crate::analysis::DEFAULT_ALPHA_CUTOFF)
+        {
+            log::warn!(
+                "🍃 Alpha coverage thins out down the mip chain for {key}: {}",
+                coverage.summary(crate::analysis::DEFAULT_ALPHA_CUTOFF)
+            );
+        }
+
+        // Animated GIF/APNG/WebP keep all frames for the timeline scrubber
+        let frames = decode_animation(&loaded_data);
+
+        // KTX2/DDS cubemaps also get cross and equirectangular layouts
+        let cubemap = decode_cubemap(&loaded_data);
+
+        // Texture arrays keep every layer for browsing
+        let layers = Self::decode_layers(&pipeline, &loaded_data, &macroquad_image);
+
+        // Large single images start out as a proxy; multi-texture images stay native
+        let proxy = (frames.is_none() && cubemap.is_none() && layers.is_none())
+            .then(|| downscale_to_proxy(&macroquad_image))
+            .flatten();
+        let (parsed_image, gpu_payload, tier) = match proxy {
+            Some(proxy) => (proxy, None, ResolutionTier::Proxy),
+            None => (
+                macroquad_image,
+                Self::gpu_payload(&loaded_data),
+                ResolutionTier::Full,
+            ),
+        };
+
         Ok(LoadedImageResult {
-            parsed_image: macroquad_image,
+            parsed_image,
             info,
             source_path: metadata.source_path.clone(),
+            analysis,
+            metadata,
+            frames,
+            cubemap,
+            layers,
+            gpu_payload,
+            tier,
         })
     }
 
-    pub fn update(&mut self) -> Vec<(String, Result<LoadedImage, String>)> {
+    /// Compressed formats keep their block data for a direct GPU upload; the CPU
+    /// decode is still needed for analysis and as the fallback
+    fn gpu_payload(loaded_data: &LoadedImageData) -> Option<GpuPayload> {
+        compressed_upload_enabled()
+            .then(|| CompressedFormat::gpu_payload(loaded_data))
+            .flatten()
+    }
+
+    /// Decode layers 1.. of a texture array after the already parsed layer 0
+    /// None for single-layer images or when any layer fails to decode
+    fn decode_layers(
+        pipeline: &Pipeline,
+        loaded_data: &LoadedImageData,
+        first_layer: &Image,
+    ) -> Option<Vec<Image>> {
+        let layer_count = pipeline.layer_count(loaded_data).min(MAX_ARRAY_LAYERS);
+        if layer_count < 2 {
+            return None;
+        }
+
+        let mut layers = vec![first_layer.clone()];
+        for layer in 1..layer_count {
+            match pipeline.parse_image_layer(loaded_data, layer) {
+                Ok((image, _)) => layers.push(image),
+                Err(e) => {
+                    log::warn!(
+                        "⚠️ Failed to decode layer {layer} of {}: {e}",
+                        loaded_data.name
+                    );
+                    return None;
+                }
+            }
+        }
+
+        log::info!(
+            "🗂️ Decoded {layer_count} array layers for {}",
+            loaded_data.name
+        );
+        Some(layers)
+    }
+
+    /// Upload finished decodes, returned with the tier they were requested at
+    pub fn update(&mut self) -> Vec<(String, ResolutionTier, Result<LoadedImage, String>)> {
         let mut completed = Vec::new();
         let mut processed_count = 0;
 
         if let Ok(mut completed_images) = self.completed_images.try_lock() {
             let keys_to_process: Vec<_> = completed_images.keys().cloned().collect();
 
-            for key in keys_to_process {
+            for entry in keys_to_process {
                 if processed_count >= self.max_updates_per_frame {
                     break;
                 }
 
-                if let Some(result) = completed_images.remove(&key) {
+                if let Some(result) = completed_images.remove(&entry) {
+                    let (key, requested) = entry;
                     let final_result = match result {
-                        Ok(loaded_result) => {
-                            let texture = Texture2D::from_image(&loaded_result.parsed_image);
+                        Ok(mut loaded_result) => {
+                            let tier = loaded_result.tier;
+                            // Block-compressed data goes to the GPU as-is when it can sample it
+                            let (gpu_texture, gpu_upload) = loaded_result
+                                .gpu_payload
+                                .as_ref()
+                                .and_then(upload_compressed)
+                                .unzip();
+                            let gpu_compressed = gpu_texture.is_some();
+                            if gpu_compressed {
+                                loaded_result.info.color_space.push_str(" · GPU");
+                            }
+                            // Colour is stored as sRGB so filtering averages linear light
+                            // Proxies and images that swap in frames, faces or layers stay
+                            // RGBA8 like the textures they trade places with
+                            let srgb_upload = (!gpu_compressed
+                                && tier == ResolutionTier::Full
+                                && loaded_result.frames.is_none()
+                                && loaded_result.cubemap.is_none()
+                                && loaded_result.layers.is_none()
+                                && stores_color(&loaded_result.metadata.name, &loaded_result.info))
+                            .then(|| upload_srgb(&loaded_result.parsed_image))
+                            .flatten();
+                            let (texture, gpu_upload) = match (gpu_texture, srgb_upload) {
+                                (Some(texture), _) => (texture, gpu_upload),
+                                (None, Some((texture, owner))) => (texture, Some(owner)),
+                                (None, None) => (upload_texture(&loaded_result.parsed_image), None),
+                            };
                             // Start with linear filtering as default, will be changed at render time
                             texture.set_filter(FilterMode::Linear);
+                            let sampler_preview = SamplerPreview::for_hint(
+                                loaded_result.metadata.embedded_hint.as_ref(),
+                            );
+                            let cubemap = loaded_result.cubemap.map(|images| {
+                                CubemapView::new(
+                                    &texture,
+                                    loaded_result.info.width,
+                                    loaded_result.info.height,
+                                    images,
+                                )
+                            });
 
                             Ok(LoadedImage {
                                 texture,
                                 info: loaded_result.info,
                                 path: loaded_result.source_path,
+                                analysis: loaded_result.analysis,
+                                metadata: loaded_result.metadata,
+                                animation: loaded_result.frames.map(Animation::from_frames),
+                                expanded_frame: None,
+                                cubemap,
+                                layers: loaded_result
+                                    .layers
+                                    .as_deref()
+                                    .map(LayerStack::from_images),
+                                gpu_compressed,
+                                gpu_upload,
+                                sampler_preview,
+                                tier,
+                                proxy: (tier == ResolutionTier::Proxy).then(|| texture.clone()),
                             })
                         }
                         Err(error) => Err(error),
                     };
 
-                    completed.push((key, final_result));
+                    completed.push((key, requested, final_result));
                     processed_count += 1;
                 }
             }
@@ -153,6 +513,23 @@ impl AsyncImageLoader {
         completed
     }
 
+    /// Upload coarse previews published since the last call
+    /// Previews are drawn with the default material, so they skip the sRGB upload
+    pub fn take_previews(&mut self) -> Vec<(String, Texture2D)> {
+        let Ok(mut previews) = self.previews.try_lock() else {
+            return Vec::new();
+        };
+
+        previews
+            .drain()
+            .map(|(key, image)| {
+                let texture = Texture2D::from_image(&image);
+                texture.set_filter(FilterMode::Linear);
+                (key, texture)
+            })
+            .collect()
+    }
+
     /// Cancel all ongoing loading operations and clear completed results
     pub fn cancel_all(&mut self) {
         log::info!("🚫 Cancelling all async loading operations");
@@ -168,6 +545,9 @@ impl AsyncImageLoader {
                 log::info!("🧹 Cleared {cleared_count} completed loading results");
             }
         }
+        if let Ok(mut previews) = self.previews.lock() {
+            previews.clear();
+        }
     }
 
     /// Check if cancellation was requested
@@ -175,3 +555,28 @@ impl AsyncImageLoader {
         self.cancel_flag.load(Ordering::Relaxed)
     }
 }
+
+/// Downscale an image so its longest edge is `PROXY_SIZE`; None when it's already that small
+fn downscale_to_proxy(image: &Image) -> Option<Image> {
+    let (width, height) = (image.width as u32, image.height as u32);
+    if width.max(height) <= PROXY_SIZE {
+        return None;
+    }
+
+    let scale = PROXY_SIZE as f32 / width.max(height) as f32;
+    let proxy_width = ((width as f32 * scale).round() as u32).max(1);
+    let proxy_height = ((height as f32 * scale).round() as u32).max(1);
+    let rgba = image::RgbaImage::from_raw(width, height, image.bytes.clone())?;
+    let proxy = image::imageops::resize(
+        &rgba,
+        proxy_width,
+        proxy_height,
+        image::imageops::FilterType::Triangle,
+    );
+
+    Some(Image {
+        width: proxy_width as u16,
+        height: proxy_height as u16,
+        bytes: proxy.into_raw(),
+    })
+}
@@ -0,0 +1,399 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::loading::WorkerTracker;
+use crate::texture_pipeline::{EmbeddedMetadata, FileHint, Pipeline};
+use crate::types::{GTexViewerApp, ImageSlot, ImageState};
+use macroquad::prelude::Vec2;
+
+/// How often watched files are checked; a change must hold for one interval before re-scanning
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long slots touched by a re-scan stay highlighted
+const CHANGE_HIGHLIGHT_DURATION: Duration = Duration::from_secs(3);
+
+/// Modification time and length, the cheap "did it change" check
+type FileStamp = (SystemTime, u64);
+
+/// What identifies an entry's content between scans
+/// Entries are only read and hashed when the container records neither a byte range
+/// nor a CRC for them (compressed streams, solid archives)
+#[derive(Debug, Clone, PartialEq)]
+struct EntryStamp {
+    byte_range: Option<(u64, u64)>, // Offset and size inside the container, when known
+    file_size: u64,
+    crc: Option<u32>,  // CRC-32 recorded by the container, when it has one
+    hash: Option<u64>, // Hash of the entry's bytes when nothing cheaper tells them apart
+}
+
+/// Entries added, removed or modified in a container since its previous scan
+pub struct ContainerChange {
+    pub container: PathBuf,
+    pub added: Vec<EmbeddedMetadata>,
+    pub modified: Vec<EmbeddedMetadata>,
+    pub removed: Vec<String>, // Entry names
+}
+
+struct WatchedFile {
+    stamp: Option<FileStamp>,
+    pending_stamp: Option<FileStamp>, // Seen once; re-scanned if unchanged next interval
+    entries: HashMap<String, EntryStamp>,
+}
+
+/// Polls loaded containers on a worker thread and re-scans the ones edited externally
+/// Dropping the watcher stops the worker
+pub struct ContainerWatcher {
+    watch_sender: mpsc::Sender<Vec<PathBuf>>,
+    receiver: mpsc::Receiver<ContainerChange>,
+    cancel_flag: Arc<AtomicBool>,
+}
+
+impl ContainerWatcher {
+    pub fn start(workers: &WorkerTracker) -> Self {
+        let (watch_sender, watch_receiver) = mpsc::channel();
+        let (sender, receiver) = mpsc::channel();
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let worker_cancel_flag = cancel_flag.clone();
+        let worker = workers.start();
+
+        thread::spawn(move || {
+            let _worker = worker;
+            Self::run(&watch_receiver, &sender, &worker_cancel_flag);
+        });
+
+        Self {
+            watch_sender,
+            receiver,
+            cancel_flag,
+        }
+    }
+
+    /// Start watching files; already-watched paths are ignored
+    pub fn watch(&self, paths: Vec<PathBuf>) {
+        let _ = self.watch_sender.send(paths);
+    }
+
+    fn run(
+        watch_receiver: &mpsc::Receiver<Vec<PathBuf>>,
+        sender: &mpsc::Sender<ContainerChange>,
+        cancel_flag: &AtomicBool,
+    ) {
+        let pipeline = Pipeline::new();
+        let mut watched: HashMap<PathBuf, WatchedFile> = HashMap::new();
+
+        loop {
+            let mut new_paths = match watch_receiver.recv_timeout(WATCH_INTERVAL) {
+                Ok(paths) => paths,
+                Err(mpsc::RecvTimeoutError::Timeout) => Vec::new(),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            };
+            while let Ok(paths) = watch_receiver.try_recv() {
+                new_paths.extend(paths);
+            }
+            if cancel_flag.load(Ordering::Relaxed) {
+                log::debug!("🚫 Container watch stopped");
+                return;
+            }
+
+            // Baseline scans so later changes have something to compare against
+            for path in new_paths {
+                if watched.contains_key(&path) {
+                    continue;
+                }
+                let stamp = file_stamp(&path);
+                let Some(scanned) = scan_entries(&pipeline, &path, cancel_flag) else {
+                    return;
+                };
+                let entries = scanned
+                    .into_iter()
+                    .map(|(metadata, entry_stamp)| (metadata.name, entry_stamp))
+                    .collect();
+                watched.insert(
+                    path,
+                    WatchedFile {
+                        stamp,
+                        pending_stamp: None,
+                        entries,
+                    },
+                );
+            }
+
+            for (path, file) in &mut watched {
+                if cancel_flag.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let stamp = file_stamp(path);
+                if stamp.is_none() || stamp == file.stamp {
+                    file.pending_stamp = None;
+                    continue;
+                }
+                // Wait for the writer to finish: the stamp must hold for one interval
+                if file.pending_stamp != stamp {
+                    file.pending_stamp = stamp;
+                    continue;
+                }
+
+                let Some(scanned) = scan_entries(&pipeline, path, cancel_flag) else {
+                    return;
+                };
+                if scanned.is_empty() && !file.entries.is_empty() {
+                    // Unreadable (likely still being written); retry on the next change
+                    log::debug!("Re-scan of {} found nothing, waiting", path.display());
+                    file.pending_stamp = None;
+                    continue;
+                }
+                file.stamp = stamp;
+                file.pending_stamp = None;
+
+                let change = diff_entries(path, &file.entries, &scanned);
+                file.entries = scanned
+                    .into_iter()
+                    .map(|(metadata, entry_stamp)| (metadata.name, entry_stamp))
+                    .collect();
+
+                if change.added.is_empty()
+                    && change.modified.is_empty()
+                    && change.removed.is_empty()
+                {
+                    log::debug!("{} touched but no entry changed", path.display());
+                    continue;
+                }
+                if sender.send(change).is_err() {
+                    return; // Receiver dropped
+                }
+                macroquad::miniquad::window::schedule_update();
+            }
+        }
+    }
+
+    /// Drain detected changes without blocking
+    fn poll(&self) -> Vec<ContainerChange> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for ContainerWatcher {
+    fn drop(&mut self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Every entry of a file (recursing into nested containers) with its content stamp
+/// None when cancelled part way
+fn scan_entries(
+    pipeline: &Pipeline,
+    path: &Path,
+    cancel_flag: &AtomicBool,
+) -> Option<Vec<(EmbeddedMetadata, EntryStamp)>> {
+    let entries = pipeline.extract_all_metadata_recursive(vec![path.to_path_buf()]);
+    let mut scanned = Vec::with_capacity(entries.len());
+
+    for metadata in entries {
+        if cancel_flag.load(Ordering::Relaxed) {
+            log::debug!("🚫 Container scan of {} cancelled", path.display());
+            return None;
+        }
+
+        let byte_range = metadata.embedded_hint.byte_range();
+        let crc = metadata.embedded_hint.content_crc();
+        let hash = (byte_range.is_none() && crc.is_none())
+            .then(|| pipeline.load_bytes(&metadata).ok())
+            .flatten()
+            .map(|data| {
+                let mut hasher = DefaultHasher::new();
+                hasher.write(&data);
+                hasher.finish()
+            });
+        let stamp = EntryStamp {
+            byte_range,
+            file_size: metadata.file_size,
+            crc,
+            hash,
+        };
+        scanned.push((metadata, stamp));
+    }
+
+    Some(scanned)
+}
+
+fn diff_entries(
+    container: &Path,
+    previous: &HashMap<String, EntryStamp>,
+    scanned: &[(EmbeddedMetadata, EntryStamp)],
+) -> ContainerChange {
+    let mut change = ContainerChange {
+        container: container.to_path_buf(),
+        added: Vec::new(),
+        modified: Vec::new(),
+        removed: Vec::new(),
+    };
+
+    for (metadata, stamp) in scanned {
+        match previous.get(&metadata.name) {
+            None => change.added.push(metadata.clone()),
+            Some(old) if old != stamp => {
+                log::debug!(
+                    "{} changed: range {:?} -> {:?}, size {} -> {}, crc {:?} -> {:?}, hash {:?} -> {:?}",
+                    metadata.name,
+                    old.byte_range,
+                    stamp.byte_range,
+                    old.file_size,
+                    stamp.file_size,
+                    old.crc,
+                    stamp.crc,
+                    old.hash,
+                    stamp.hash
+                );
+                change.modified.push(metadata.clone());
+            }
+            Some(_) => {}
+        }
+    }
+
+    let scanned_names: HashSet<&str> = scanned
+        .iter()
+        .map(|(metadata, _)| metadata.name.as_str())
+        .collect();
+    change.removed = previous
+        .keys()
+        .filter(|name| !scanned_names.contains(name.as_str()))
+        .cloned()
+        .collect();
+
+    change
+}
+
+fn slot_metadata(slot: &ImageSlot) -> Option<&EmbeddedMetadata> {
+    match &slot.state {
+        ImageState::Loaded { image } => Some(&image.metadata),
+        ImageState::Placeholder {
+            original_metadata, ..
+        } => Some(original_metadata),
+        ImageState::Failed { metadata, .. } => metadata.as_ref(),
+    }
+}
+
+impl GTexViewerApp {
+    /// Watch the containers behind newly created slots for external edits
+    /// Loose image files are left alone so large folders aren't hashed twice
+    pub fn watch_loaded_containers(&mut self, metadata_list: &[EmbeddedMetadata]) {
+        let mut paths: Vec<PathBuf> = metadata_list
+            .iter()
+            .filter(|metadata| {
+                metadata
+                    .embedded_hint
+                    .as_any()
+                    .downcast_ref::<FileHint>()
+                    .is_none()
+            })
+            .map(|metadata| metadata.source_path.clone())
+            .filter(|path| path.is_file())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        if paths.is_empty() {
+            return;
+        }
+
+        self.container_watcher
+            .get_or_insert_with(|| ContainerWatcher::start(&self.workers))
+            .watch(paths);
+    }
+
+    /// Apply re-scan results: drop removed entries, reload modified and added ones
+    /// in place, and highlight every slot that changed
+    pub fn update_container_watch(&mut self) {
+        let Some(ref watcher) = self.container_watcher else {
+            return;
+        };
+
+        for change in watcher.poll() {
+            self.apply_container_change(change);
+        }
+    }
+
+    fn apply_container_change(&mut self, change: ContainerChange) {
+        let file_name = change
+            .container
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        log::info!(
+            "🔁 {file_name} changed on disk: {} added, {} removed, {} modified",
+            change.added.len(),
+            change.removed.len(),
+            change.modified.len()
+        );
+
+        let in_container = |metadata: &EmbeddedMetadata, name: &str| {
+            metadata.source_path == change.container && metadata.name == name
+        };
+
+        if !change.removed.is_empty() {
+            self.image_slots.retain(|slot| {
+                !slot_metadata(slot).is_some_and(|metadata| {
+                    change
+                        .removed
+                        .iter()
+                        .any(|name| in_container(metadata, name))
+                })
+            });
+            // Slot indices held elsewhere may now point at other images
+            self.reset_slot_indices();
+        }
+
+        let mut changed_slots = Vec::new();
+        for metadata in change.modified.iter().chain(&change.added) {
+            let placeholder = ImageState::Placeholder {
+                original_metadata: metadata.clone(),
+                layout_metadata: Self::adjust_metadata_for_layout(metadata),
+            };
+            let existing = self.image_slots.iter().position(|slot| {
+                slot_metadata(slot).is_some_and(|old| in_container(old, &metadata.name))
+            });
+
+            match existing {
+                Some(index) => {
+                    let slot = &mut self.image_slots[index];
+                    slot.state = placeholder;
+                    slot.violations.clear();
+                    changed_slots.push(index);
+                }
+                None => {
+                    self.image_slots.push(ImageSlot {
+                        state: placeholder,
+                        position: Vec2::ZERO, // Layout will calculate these
+                        size: Vec2::ZERO,
+                        violations: Vec::new(),
+                        hidden: false,
+                    });
+                    changed_slots.push(self.image_slots.len() - 1);
+                }
+            }
+        }
+
+        let reload: Vec<EmbeddedMetadata> =
+            change.modified.into_iter().chain(change.added).collect();
+        if !reload.is_empty() {
+            self.async_loader.start_loading_batch(reload, &self.workers);
+        }
+
+        self.layout_needs_update = true;
+        self.highlighted_slots = changed_slots;
+        self.highlight_until = Some(Instant::now() + CHANGE_HIGHLIGHT_DURATION);
+        self.start_burst_rendering(CHANGE_HIGHLIGHT_DURATION);
+    }
+}
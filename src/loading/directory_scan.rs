@@ -0,0 +1,231 @@
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::loading::{DroppedRoot, WorkerTracker};
+use crate::status_bar::STATUS_BAR_HEIGHT;
+use crate::texture_pipeline::{
+    DirectoryEvent, DirectorySource, Pipeline, is_remote_url, is_stdin_path,
+};
+use crate::types::GTexViewerApp;
+use macroquad::prelude::*;
+
+/// Discovered paths are handed to the pipeline in chunks of this size...
+const DISCOVERY_CHUNK_SIZE: usize = 32;
+/// ...or at least this often, so slow network shares still show results early
+const DISCOVERY_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+pub enum ScanMessage {
+    Discovered(Vec<PathBuf>),
+    Progress {
+        visited_dirs: usize,
+        current_dir: PathBuf,
+    },
+    Finished,
+}
+
+/// Directory traversal running on a worker thread
+/// Dropping the scan cancels the worker
+pub struct DirectoryScan {
+    receiver: mpsc::Receiver<ScanMessage>,
+    cancel_flag: Arc<AtomicBool>,
+    pub found_files: usize,
+    pub visited_dirs: usize,
+    pub current_dir: Option<PathBuf>,
+}
+
+impl DirectoryScan {
+    pub fn start(roots: Vec<PathBuf>, workers: &WorkerTracker) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let worker_cancel_flag = cancel_flag.clone();
+        let worker = workers.start();
+
+        thread::spawn(move || {
+            let _worker = worker;
+            Self::scan(roots, &sender, &worker_cancel_flag);
+            let _ = sender.send(ScanMessage::Finished);
+        });
+
+        Self {
+            receiver,
+            cancel_flag,
+            found_files: 0,
+            visited_dirs: 0,
+            current_dir: None,
+        }
+    }
+
+    fn scan(roots: Vec<PathBuf>, sender: &mpsc::Sender<ScanMessage>, cancel_flag: &AtomicBool) {
+        let pipeline = Pipeline::new();
+        let mut pending = Vec::new();
+        let mut last_flush = Instant::now();
+        let mut visited_dirs = 0;
+
+        DirectorySource::walk(&roots, pipeline.source_registry(), |event| {
+            if cancel_flag.load(Ordering::Relaxed) {
+                log::debug!("🚫 Directory scan cancelled");
+                return ControlFlow::Break(());
+            }
+
+            match event {
+                DirectoryEvent::Found(path) => pending.push(path),
+                DirectoryEvent::Entered(path) => {
+                    visited_dirs += 1;
+                    let _ = sender.send(ScanMessage::Progress {
+                        visited_dirs,
+                        current_dir: path,
+                    });
+                }
+            }
+
+            if pending.len() >= DISCOVERY_CHUNK_SIZE
+                || (!pending.is_empty() && last_flush.elapsed() >= DISCOVERY_FLUSH_INTERVAL)
+            {
+                if sender
+                    .send(ScanMessage::Discovered(std::mem::take(&mut pending)))
+                    .is_err()
+                {
+                    return ControlFlow::Break(()); // Receiver dropped
+                }
+                last_flush = Instant::now();
+            }
+            ControlFlow::Continue(())
+        });
+
+        if !pending.is_empty() && !cancel_flag.load(Ordering::Relaxed) {
+            let _ = sender.send(ScanMessage::Discovered(pending));
+        }
+    }
+
+    /// Drain pending messages without blocking
+    fn poll(&mut self) -> (Vec<PathBuf>, bool) {
+        let mut discovered = Vec::new();
+        let mut finished = false;
+
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                ScanMessage::Discovered(paths) => {
+                    self.found_files += paths.len();
+                    discovered.extend(paths);
+                }
+                ScanMessage::Progress {
+                    visited_dirs,
+                    current_dir,
+                } => {
+                    self.visited_dirs = visited_dirs;
+                    self.current_dir = Some(current_dir);
+                }
+                ScanMessage::Finished => finished = true,
+            }
+        }
+
+        (discovered, finished)
+    }
+}
+
+impl Drop for DirectoryScan {
+    fn drop(&mut self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+    }
+}
+
+impl GTexViewerApp {
+    /// Start walking dropped files/folders on a worker, feeding paths into the pipeline as found
+    /// URLs and stdin (`-`) skip the walk and go straight to metadata extraction
+    pub fn start_directory_scan(&mut self, roots: Vec<PathBuf>) {
+        let (streams, roots): (Vec<PathBuf>, Vec<PathBuf>) = roots
+            .into_iter()
+            .partition(|root| is_remote_url(&root.to_string_lossy()) || is_stdin_path(root));
+        if !streams.is_empty() {
+            log::info!("🌐 Opening {} URL(s) or piped input", streams.len());
+            self.load_images(streams);
+        }
+        if roots.is_empty() {
+            return;
+        }
+
+        log::info!("📂 Scanning {} dropped path(s)", roots.len());
+        self.is_loading = true;
+        self.loading_completed_once = false;
+        self.dropped_roots
+            .extend(roots.iter().map(|root| DroppedRoot::new(root)));
+        self.directory_scan = Some(DirectoryScan::start(roots, &self.workers));
+    }
+
+    pub fn update_directory_scan(&mut self) {
+        let Some(ref mut scan) = self.directory_scan else {
+            return;
+        };
+
+        let (discovered, finished) = scan.poll();
+
+        if finished {
+            log::info!(
+                "📂 Directory scan finished: {} files in {} folders",
+                scan.found_files,
+                scan.visited_dirs
+            );
+            self.directory_scan = None;
+        }
+
+        if !discovered.is_empty() {
+            self.load_images(discovered);
+        }
+
+        if finished && self.metadata_receivers.is_empty() && self.image_slots.is_empty() {
+            // Nothing supported was found
+            self.is_loading = false;
+        }
+
+        // Keep polling (and the progress text current) while the worker runs
+        if self.directory_scan.is_some() {
+            macroquad::miniquad::window::schedule_update();
+        }
+    }
+
+    /// File-discovery progress shown while a scan is running
+    pub fn draw_scan_progress(&self) {
+        let Some(ref scan) = self.directory_scan else {
+            return;
+        };
+
+        let current = scan
+            .current_dir
+            .as_ref()
+            .and_then(|dir| dir.file_name())
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        let text = format!(
+            "Scanning folders... {} files found in {} folders {}",
+            scan.found_files, scan.visited_dirs, current
+        );
+        let text_size = 16.0;
+        let text_dims = measure_text(&text, self.ui_font.as_ref(), text_size as u16, 1.0);
+        let x = ((screen_width() - text_dims.width) / 2.0).round();
+        let y = (screen_height() - STATUS_BAR_HEIGHT - 20.0).round();
+
+        draw_rectangle(
+            x - 8.0,
+            y - text_dims.height - 6.0,
+            text_dims.width + 16.0,
+            text_dims.height + 12.0,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+        draw_text_ex(
+            &text,
+            x,
+            y,
+            TextParams {
+                font: self.ui_font.as_ref(),
+                font_size: text_size as u16,
+                color: WHITE,
+                ..Default::default()
+            },
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use macroquad::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Bytes hashed from each end of a file for its fingerprint
+const FINGERPRINT_SAMPLE_SIZE: u64 = 64 * 1024;
+
+/// How long re-dropped slots stay highlighted
+const HIGHLIGHT_DURATION: Duration = Duration::from_millis(1500);
+
+/// A path dropped into the current session
+#[derive(Debug, Clone)]
+pub struct DroppedRoot {
+    pub path: PathBuf, // Canonicalized
+    pub fingerprint: Option<u64>,
+}
+
+impl DroppedRoot {
+    pub fn new(path: &Path) -> Self {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let fingerprint = path.is_file().then(|| file_fingerprint(&path)).flatten();
+        Self { path, fingerprint }
+    }
+}
+
+/// Cheap content fingerprint: file size plus the first and last 64 KB
+/// Enough to recognise the same container dropped from a different location
+pub fn file_fingerprint(path: &Path) -> Option<u64> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let size = file.metadata().ok()?.len();
+
+    let mut hasher = DefaultHasher::new();
+    hasher.write_u64(size);
+
+    let mut buffer = vec![0u8; FINGERPRINT_SAMPLE_SIZE.min(size) as usize];
+    file.read_exact(&mut buffer).ok()?;
+    hasher.write(&buffer);
+
+    if size > FINGERPRINT_SAMPLE_SIZE {
+        let tail_start = size
+            .saturating_sub(FINGERPRINT_SAMPLE_SIZE)
+            .max(FINGERPRINT_SAMPLE_SIZE);
+        file.seek(SeekFrom::Start(tail_start)).ok()?;
+        buffer.clear();
+        file.read_to_end(&mut buffer).ok()?;
+        hasher.write(&buffer);
+    }
+
+    Some(hasher.finish())
+}
+
+impl GTexViewerApp {
+    /// The already-loaded root matching a dropped path, by location or by content
+    fn find_loaded_root(&self, dropped: &DroppedRoot) -> Option<PathBuf> {
+        self.dropped_roots
+            .iter()
+            .find(|root| {
+                dropped.path.starts_with(&root.path)
+                    || (dropped.fingerprint.is_some() && dropped.fingerprint == root.fingerprint)
+            })
+            .map(|root| {
+                if dropped.path.starts_with(&root.path) {
+                    dropped.path.clone()
+                } else {
+                    root.path.clone()
+                }
+            })
+    }
+
+    /// If every dropped path is already loaded, highlight and center the existing slots
+    /// instead of reprocessing them. Returns true when the drop was handled this way
+    pub fn handle_duplicate_drop(&mut self, dropped_paths: &[PathBuf]) -> bool {
+        if self.dropped_roots.is_empty() {
+            return false;
+        }
+
+        let mut existing_roots = Vec::new();
+        for path in dropped_paths {
+            match self.find_loaded_root(&DroppedRoot::new(path)) {
+                Some(root) => existing_roots.push(root),
+                None => return false,
+            }
+        }
+
+        let slot_indices: Vec<usize> = self
+            .image_slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| {
+                let source_path = match &slot.state {
+                    ImageState::Loaded { image } => Some(&image.metadata.source_path),
+                    ImageState::Placeholder {
+                        original_metadata, ..
+                    } => Some(&original_metadata.source_path),
+                    ImageState::Failed { metadata, .. } => {
+                        metadata.as_ref().map(|metadata| &metadata.source_path)
+                    }
+                };
+                source_path
+                    .and_then(|path| path.canonicalize().ok())
+                    .is_some_and(|path| existing_roots.iter().any(|root| path.starts_with(root)))
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        log::info!(
+            "♻️ Dropped path(s) already loaded, highlighting {} existing slot(s)",
+            slot_indices.len()
+        );
+
+        self.focus_on_slots(&slot_indices);
+        self.highlighted_slots = slot_indices;
+        self.highlight_until = Some(Instant::now() + HIGHLIGHT_DURATION);
+        self.start_burst_rendering(HIGHLIGHT_DURATION);
+        true
+    }
+
+    /// Center the camera on the given slots and zoom so they fit the viewport
+    pub fn focus_on_slots(&mut self, slot_indices: &[usize]) {
+        let Some(bounds) = slot_indices
+            .iter()
+            .filter_map(|&index| self.image_slots.get(index))
+            .map(|slot| Rect::new(slot.position.x, slot.position.y, slot.size.x, slot.size.y))
+            .reduce(|a, b| a.combine_with(b))
+        else {
+            return;
+        };
+
+        let aspect_ratio = screen_width() / screen_height();
+        let fit_zoom = (1.8 / bounds.w).min(1.8 / (bounds.h * aspect_ratio));
+        let (min_zoom, max_zoom) = self.calculate_dynamic_zoom_limits();
+        let zoom = fit_zoom.clamp(min_zoom, max_zoom);
+
+        self.camera.target = bounds.center();
+        self.camera.zoom = vec2(zoom, zoom);
+    }
+
+    /// Pulsing outline around highlighted slots (drawn in world space)
+    pub fn draw_slot_highlights(&mut self) {
+        let Some(until) = self.highlight_until else {
+            return;
+        };
+
+        let now = Instant::now();
+        if now >= until {
+            self.highlight_until = None;
+            self.highlighted_slots.clear();
+            return;
+        }
+
+        let remaining = (until - now).as_secs_f32();
+        let pulse = 0.5 + 0.5 * (remaining * 10.0).sin();
+        let color = Color::new(1.0, 0.85, 0.1, 0.4 + 0.6 * pulse);
+        let thickness = 3.0 / self.camera.zoom.x.max(0.0001) / screen_width() * 2.0;
+
+        for &index in &self.highlighted_slots {
+            if let Some(slot) = self.image_slots.get(index) {
+                draw_rectangle_lines(
+                    slot.position.x,
+                    slot.position.y,
+                    slot.size.x,
+                    slot.size.y,
+                    thickness,
+                    color,
+                );
+            }
+        }
+    }
+}
@@ -1,41 +1,21 @@
-use std::fs;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
-use crate::texture_pipeline::Pipeline;
+use crate::cubemap_view::CubemapLayout;
+use crate::loading::ResolutionTier;
+use crate::slot_sort::slot_metadata;
+use crate::texture_pipeline::{EmbeddedMetadata, Pipeline, is_remote_url, is_stdin_path, vfs};
 use crate::types::{GTexViewerApp, ImageSlot, ImageState};
 use macroquad::prelude::Vec2;
 
-impl GTexViewerApp {
-    fn collect_image_files_recursively(path: &PathBuf) -> Vec<PathBuf> {
-        let mut image_files = Vec::new();
-
-        if let Ok(metadata) = fs::metadata(path) {
-            if metadata.is_file() {
-                // Check if this individual file is supported using lightweight format detection
-                let pipeline = Pipeline::new();
-                if let Some(source) = pipeline.source_registry().find_source(path)
-                    && source.can_load_path(path).unwrap_or(false)
-                {
-                    image_files.push(path.clone());
-                }
-            } else if metadata.is_dir() {
-                // Recursively traverse directory
-                if let Ok(entries) = fs::read_dir(path) {
-                    for entry in entries.flatten() {
-                        let entry_path = entry.path();
-                        // Recursively collect from subdirectories and files
-                        image_files.extend(Self::collect_image_files_recursively(&entry_path));
-                    }
-                }
-            }
-        }
-
-        image_files
-    }
+/// How long closing the window waits for background workers to notice cancellation
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
 
+impl GTexViewerApp {
     /// Cancel all ongoing operations and clean state for fresh start
     pub fn cancel_all_loading(&mut self) {
         log::info!("🚫 Cancelling all loading operations");
@@ -44,8 +24,25 @@ impl GTexViewerApp {
         self.metadata_cancel_flag.store(true, Ordering::Relaxed);
         self.async_loader.cancel_all();
 
-        // Clear all state
+        // Clear all state (dropping the scan stops its worker)
+        self.directory_scan = None;
+        self.container_watcher = None;
+        self.dropped_roots.clear();
+        self.highlighted_slots.clear();
+        self.selected_slots.clear();
+        self.focus = None;
+        self.sync_compare = None;
+        self.pixel_diff = None;
+        self.pixel_diff_pending = None;
+        self.animation_focus = None;
         self.image_slots.clear();
+        self.texture_cache.clear();
+        self.full_resolution_pending.clear();
+        self.placeholder_previews.clear();
+        self.pixel_probe = Default::default();
+        self.slot_filter = Default::default();
+        self.slot_groups.clear();
+        self.collapsed_groups.clear();
         self.metadata_receivers.clear();
         self.pending_metadata.clear();
 
@@ -53,9 +50,52 @@ impl GTexViewerApp {
         self.is_loading = false;
         self.loading_completed_once = false;
         self.layout_needs_update = true;
+        self.release_unused_mounts();
 
         log::info!("🧹 All loading operations cancelled and state cleared");
     }
+
+    /// Unmount downloads and piped data that no slot of any tab still comes from,
+    /// freeing their memory
+    pub fn release_unused_mounts(&self) {
+        let in_use: HashSet<&Path> = self
+            .image_slots
+            .iter()
+            .chain(self.parked_slots())
+            .filter_map(slot_metadata)
+            .map(|metadata| metadata.source_path.as_path())
+            .collect();
+
+        for point in vfs::mount_points() {
+            let transient = is_remote_url(&point.to_string_lossy()) || is_stdin_path(&point);
+            if transient && !in_use.contains(point.as_path()) {
+                vfs::unmount(&point);
+            }
+        }
+    }
+
+    /// Stop background work before the window closes
+    /// Workers still busy after `SHUTDOWN_TIMEOUT` (e.g. inside a single decode) are abandoned
+    pub fn shutdown(&mut self) {
+        log::info!("👋 Window closing, stopping background work");
+
+        self.metadata_cancel_flag.store(true, Ordering::Relaxed);
+        self.async_loader.cancel_all();
+        self.directory_scan = None;
+        self.container_watcher = None;
+        // Parked tabs hold their own container watchers
+        self.tabs.clear();
+        self.metadata_receivers.clear();
+
+        let remaining = self.workers.wait_idle(SHUTDOWN_TIMEOUT);
+        if remaining == 0 {
+            log::info!("👋 Clean shutdown, all workers stopped");
+        } else {
+            log::warn!(
+                "⏱️ Abandoning {remaining} worker(s) still running after {SHUTDOWN_TIMEOUT:?}"
+            );
+        }
+    }
     pub fn handle_file_drops(&mut self) {
         // Get dropped files from macroquad
         use macroquad::prelude::*;
@@ -67,20 +107,19 @@ impl GTexViewerApp {
                 .filter_map(|file| file.path.clone())
                 .collect();
 
-            // Recursively collect all image files from dropped paths (files and directories)
-            let mut all_paths = Vec::new();
-            for path in dropped_paths {
-                all_paths.extend(Self::collect_image_files_recursively(&path));
-            }
+            // .cube files become the show LUT instead of replacing the images
+            let dropped_paths = self.take_dropped_luts(dropped_paths);
 
-            if !all_paths.is_empty() {
+            // Re-dropping what's already loaded just highlights the existing slots
+            if !dropped_paths.is_empty() && !self.handle_duplicate_drop(&dropped_paths) {
                 // Cancel all ongoing operations first
                 self.cancel_all_loading();
 
                 // Reset camera view position to show new images
                 self.camera = macroquad::prelude::Camera2D::default();
 
-                self.load_images(all_paths);
+                // Walk dropped files and directories on a worker; paths are fed in as found
+                self.start_directory_scan(dropped_paths);
 
                 // Start burst rendering to ensure file drop UI updates are fully drawn
                 self.start_burst_rendering(std::time::Duration::from_secs(1));
@@ -88,9 +127,37 @@ impl GTexViewerApp {
         }
     }
 
+    /// Ctrl+V opens http(s) URLs from the clipboard, replacing the current images like a drop
+    pub fn handle_paste_input(&mut self) {
+        use macroquad::prelude::*;
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if !(ctrl_down && is_key_pressed(KeyCode::V)) {
+            return;
+        }
+
+        let urls: Vec<PathBuf> = macroquad::miniquad::window::clipboard_get()
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter(|text| is_remote_url(text))
+            .map(PathBuf::from)
+            .collect();
+        if urls.is_empty() {
+            log::info!("📋 Clipboard has no http(s) URL to open");
+            return;
+        }
+
+        self.cancel_all_loading();
+        self.camera = macroquad::prelude::Camera2D::default();
+        self.start_directory_scan(urls);
+        self.start_burst_rendering(std::time::Duration::from_secs(1));
+    }
+
     pub fn load_initial_file_if_needed(&mut self) {
         if let Some(path) = self.initial_file_path.take() {
-            self.load_images(vec![path]);
+            self.start_directory_scan(vec![path]);
             // Trigger redraw when initial file starts loading
             macroquad::miniquad::window::schedule_update();
         }
@@ -107,7 +174,7 @@ impl GTexViewerApp {
         self.is_loading = true;
         self.loading_completed_once = false; // Reset completion flag for new loading session
 
-        // Paths are already filtered by collect_image_files_recursively
+        // Paths are already filtered by the directory scan
         let supported_paths = paths;
 
         // Skip initial placeholder creation - wait for proper metadata with hints
@@ -138,7 +205,9 @@ impl GTexViewerApp {
 
             let paths_batch = paths_batch.to_vec();
             let cancel_flag = self.metadata_cancel_flag.clone();
+            let worker = self.workers.start();
             thread::spawn(move || {
+                let _worker = worker;
                 log::debug!(
                     "Batch {} thread started with {} paths",
                     batch_index,
@@ -195,8 +264,29 @@ impl GTexViewerApp {
         // Check for completed images from Rayon
         let completed = self.async_loader.update();
         let mut failed_keys = Vec::new();
+        let mut any_loaded = false;
+
+        // Coarse previews stand in for placeholders until their texture arrives
+        for (key, preview) in self.async_loader.take_previews() {
+            if self.find_slot_by_key(&key).is_some() {
+                self.placeholder_previews.insert(key, preview);
+                macroquad::miniquad::window::schedule_update();
+            }
+        }
+
+        for (key, tier, result) in completed {
+            // Full-resolution textures only ever replace the proxy of a loaded slot
+            if tier == ResolutionTier::Full {
+                self.apply_full_resolution(&key, result);
+                continue;
+            }
+            self.placeholder_previews.remove(&key);
+
+            let violations = match &result {
+                Ok(loaded_image) => self.validate_loaded_image(loaded_image),
+                Err(_) => Vec::new(),
+            };
 
-        for (key, result) in completed {
             // Find the corresponding slot and update it
             if let Some(slot) = self.find_slot_by_key(&key) {
                 match result {
@@ -205,6 +295,8 @@ impl GTexViewerApp {
                         slot.state = ImageState::Loaded {
                             image: loaded_image,
                         };
+                        slot.violations = violations;
+                        any_loaded = true;
                         // Don't trigger layout recalculation - just replace placeholder with loaded image
 
                         // For single images, trigger auto-centering
@@ -225,29 +317,28 @@ impl GTexViewerApp {
             }
         }
 
+        // Animated images loaded while frames are expanded get their own frame slots
+        if any_loaded && self.expand_frames {
+            self.expand_animation_frames();
+        }
+
+        // Cubemaps follow the chosen layout as soon as they load
+        if any_loaded && self.cubemap_layout != CubemapLayout::Face {
+            self.apply_cubemap_layout();
+        }
+
         // Remove slots for failed/skipped images
         if !failed_keys.is_empty() {
+            let slot_count = self.image_slots.len();
             self.image_slots.retain(|slot| {
                 let slot_key = match &slot.state {
                     ImageState::Placeholder {
                         original_metadata, ..
-                    } => Some(format!(
-                        "{}:{}",
-                        original_metadata.source_path.display(),
-                        original_metadata.name
-                    )),
-                    ImageState::Loaded { image } => Some(format!(
-                        "{}:{}",
-                        image.path.display(),
-                        image
-                            .path
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("unknown")
-                    )),
-                    ImageState::Failed { metadata, .. } => metadata
-                        .as_ref()
-                        .map(|m| format!("{}:{}", m.source_path.display(), m.name)),
+                    } => Some(original_metadata.entry_key()),
+                    ImageState::Loaded { image } => Some(image.metadata.entry_key()),
+                    ImageState::Failed { metadata, .. } => {
+                        metadata.as_ref().map(EmbeddedMetadata::entry_key)
+                    }
                 };
 
                 // Keep slots that don't match any failed key
@@ -256,14 +347,20 @@ impl GTexViewerApp {
                     .any(|failed_key| slot_key.as_deref() == Some(failed_key))
             });
 
-            if !failed_keys.is_empty() {
-                self.layout_needs_update = true;
-                macroquad::miniquad::window::schedule_update();
+            // Focus, selection and compare indices would point at other images
+            if self.image_slots.len() != slot_count {
+                self.reset_slot_indices();
             }
+            self.layout_needs_update = true;
+            macroquad::miniquad::window::schedule_update();
         }
 
         // Check if all loading is complete
-        if self.is_loading && !self.loading_completed_once && self.metadata_receivers.is_empty() {
+        if self.is_loading
+            && !self.loading_completed_once
+            && self.metadata_receivers.is_empty()
+            && self.directory_scan.is_none()
+        {
             // Check if we have any placeholder states left
             let still_loading = self
                 .image_slots
@@ -296,11 +393,7 @@ impl GTexViewerApp {
             let slot_key = match &slot.state {
                 ImageState::Placeholder {
                     original_metadata, ..
-                } => Some(format!(
-                    "{}:{}",
-                    original_metadata.source_path.display(),
-                    original_metadata.name
-                )),
+                } => Some(original_metadata.entry_key()),
                 _ => None,
             };
 
@@ -352,6 +445,8 @@ impl GTexViewerApp {
                             },
                             position: Vec2::ZERO,
                             size: Vec2::ZERO,
+                            violations: Vec::new(),
+                            hidden: false,
                         };
                         self.image_slots.push(slot);
                         self.layout_needs_update = true;
@@ -376,10 +471,8 @@ impl GTexViewerApp {
         let all_metadata_complete = remaining_receivers == 0;
 
         if all_metadata_complete && !self.pending_metadata.is_empty() {
-            // Clear any existing placeholder slots and create new ones with adjusted dimensions
-            self.image_slots.clear();
-
-            // Create placeholder slots with both original and adjusted dimensions
+            // Append placeholder slots with both original and adjusted dimensions
+            // Scans feed paths in incrementally, so earlier (possibly loaded) slots are kept
             for metadata in &self.pending_metadata {
                 let adjusted_metadata = Self::adjust_metadata_for_layout(metadata);
 
@@ -390,6 +483,8 @@ impl GTexViewerApp {
                     },
                     position: Vec2::ZERO, // Layout will calculate these
                     size: Vec2::ZERO,     // Layout will calculate these
+                    violations: Vec::new(),
+                    hidden: false,
                 };
                 self.image_slots.push(slot);
             }
@@ -402,7 +497,9 @@ impl GTexViewerApp {
 
             // Start async loading with original metadata (not adjusted)
             let original_metadata = self.pending_metadata.clone();
-            self.async_loader.start_loading_batch(original_metadata);
+            self.watch_loaded_containers(&original_metadata);
+            self.async_loader
+                .start_loading_batch(original_metadata, &self.workers);
 
             // Clear pending metadata since we've processed it
             self.pending_metadata.clear();
@@ -1,4 +1,12 @@
 pub mod async_loader;
+pub mod container_watch;
+pub mod directory_scan;
+pub mod duplicate_detection;
 pub mod file_handler;
+pub mod workers;
 
-pub use async_loader::{AsyncImageLoader, LoadedImage};
+pub use async_loader::{AsyncImageLoader, LoadedImage, PROXY_SIZE, ResolutionTier};
+pub use container_watch::ContainerWatcher;
+pub use directory_scan::DirectoryScan;
+pub use duplicate_detection::DroppedRoot;
+pub use workers::WorkerTracker;
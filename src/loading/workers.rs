@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often `wait_idle` re-checks the worker count
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Count of background workers still running
+/// Each worker holds a guard from `start` for as long as it runs
+#[derive(Clone, Default)]
+pub struct WorkerTracker {
+    active: Arc<AtomicUsize>,
+}
+
+/// Marks one worker as running until dropped, including when the worker panics
+pub struct WorkerGuard {
+    active: Arc<AtomicUsize>,
+}
+
+impl WorkerTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&self) -> WorkerGuard {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        WorkerGuard {
+            active: self.active.clone(),
+        }
+    }
+
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Block until every worker has finished or `timeout` passes; returns how many are left
+    pub fn wait_idle(&self, timeout: Duration) -> usize {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let active = self.active();
+            if active == 0 || Instant::now() >= deadline {
+                return active;
+            }
+            std::thread::sleep(IDLE_POLL_INTERVAL);
+        }
+    }
+}
+
+impl Drop for WorkerGuard {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,235 @@
+use anyhow::{Context, Result, bail};
+use macroquad::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::types::GTexViewerApp;
+
+/// Environment variable naming a .cube LUT to apply at startup
+pub const LUT_ENV: &str = "GTEXVIEWER_LUT";
+
+/// Largest LUT edge accepted; 65 is the biggest size grading tools export
+const MAX_LUT_SIZE: usize = 65;
+
+/// Parsed Adobe/Resolve .cube 3D LUT
+#[derive(Debug, Clone)]
+pub struct CubeLut {
+    pub title: Option<String>,
+    pub size: usize,
+    pub domain_min: Vec3,
+    pub domain_max: Vec3,
+    pub table: Vec<Vec3>, // size³ entries, red varying fastest
+}
+
+impl CubeLut {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut lut = CubeLut {
+            title: None,
+            size: 0,
+            domain_min: Vec3::ZERO,
+            domain_max: Vec3::ONE,
+            table: Vec::new(),
+        };
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let line_number = number + 1;
+            match words[0] {
+                "TITLE" => {
+                    lut.title = Some(line["TITLE".len()..].trim().trim_matches('"').to_string());
+                }
+                "LUT_1D_SIZE" => bail!("1D LUTs are not supported"),
+                "LUT_3D_SIZE" => {
+                    lut.size = words
+                        .get(1)
+                        .and_then(|size| size.parse().ok())
+                        .filter(|size| (2..=MAX_LUT_SIZE).contains(size))
+                        .with_context(|| format!("Line {line_number}: invalid LUT_3D_SIZE"))?;
+                }
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let values = parse_floats(&words[1..], line_number)?;
+                    let [r, g, b] = values[..] else {
+                        bail!("Line {line_number}: {} needs three values", words[0]);
+                    };
+                    if words[0] == "DOMAIN_MIN" {
+                        lut.domain_min = vec3(r, g, b);
+                    } else {
+                        lut.domain_max = vec3(r, g, b);
+                    }
+                }
+                // Resolve's single-range form of the domain
+                "LUT_3D_INPUT_RANGE" => {
+                    let values = parse_floats(&words[1..], line_number)?;
+                    let [min, max] = values[..] else {
+                        bail!("Line {line_number}: LUT_3D_INPUT_RANGE needs two values");
+                    };
+                    lut.domain_min = Vec3::splat(min);
+                    lut.domain_max = Vec3::splat(max);
+                }
+                keyword if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    log::debug!("Ignoring .cube keyword {keyword}");
+                }
+                _ => {
+                    let values = parse_floats(&words, line_number)?;
+                    let [r, g, b] = values[..] else {
+                        bail!("Line {line_number}: table entries need three values");
+                    };
+                    lut.table.push(vec3(r, g, b));
+                }
+            }
+        }
+
+        if lut.size == 0 {
+            bail!("Missing LUT_3D_SIZE");
+        }
+        let expected = lut.size.pow(3);
+        if lut.table.len() != expected {
+            bail!(
+                "Expected {expected} table entries, found {}",
+                lut.table.len()
+            );
+        }
+        if lut.domain_max.cmple(lut.domain_min).any() {
+            bail!("DOMAIN_MAX must be above DOMAIN_MIN");
+        }
+        Ok(lut)
+    }
+
+    /// The cube as `size` slices of size×size laid side by side, blue selecting the slice,
+    /// since GLSL 100 has no 3D textures
+    fn to_image(&self) -> Image {
+        let size = self.size;
+        let width = size * size;
+        let mut bytes = vec![255u8; width * size * 4];
+        for (index, entry) in self.table.iter().enumerate() {
+            let (r, g, b) = (index % size, index / size % size, index / (size * size));
+            let offset = (g * width + b * size + r) * 4;
+            let rgb = (entry.clamp(Vec3::ZERO, Vec3::ONE) * 255.0).round();
+            bytes[offset..offset + 3].copy_from_slice(&[rgb.x as u8, rgb.y as u8, rgb.z as u8]);
+        }
+        Image {
+            width: width as u16,
+            height: size as u16,
+            bytes,
+        }
+    }
+}
+
+fn parse_floats(words: &[&str], line_number: usize) -> Result<Vec<f32>> {
+    words
+        .iter()
+        .map(|word| word.parse::<f32>())
+        .collect::<Result<_, _>>()
+        .with_context(|| format!("Line {line_number}: invalid number"))
+}
+
+/// A .cube LUT uploaded for the display shader
+#[derive(Debug)]
+pub struct ColorLut {
+    pub name: String, // TITLE, else the file name
+    pub lut: CubeLut,
+    pub texture: Texture2D,
+    pub enabled: bool,
+}
+
+impl ColorLut {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read LUT: {}", path.display()))?;
+        let lut =
+            CubeLut::parse(&text).with_context(|| format!("Invalid LUT: {}", path.display()))?;
+
+        let texture = Texture2D::from_image(&lut.to_image());
+        texture.set_filter(FilterMode::Linear);
+
+        let name = lut.title.clone().unwrap_or_else(|| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        Ok(Self {
+            name,
+            lut,
+            texture,
+            enabled: true,
+        })
+    }
+
+    /// The LUT named by `$GTEXVIEWER_LUT`, if set and valid
+    pub fn load_default() -> Option<Self> {
+        let path =
+            crate::portable::resolve_config_path(PathBuf::from(std::env::var(LUT_ENV).ok()?));
+        match Self::load(&path) {
+            Ok(lut) => {
+                log::info!("🎬 LUT loaded from {}", path.display());
+                Some(lut)
+            }
+            Err(e) => {
+                log::warn!("⚠️ Failed to load LUT {}: {e:#}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn is_cube_file(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("cube"))
+    }
+}
+
+impl GTexViewerApp {
+    /// Load the last dropped .cube file as the active LUT and pass the rest on as images
+    pub fn take_dropped_luts(&mut self, paths: Vec<PathBuf>) -> Vec<PathBuf> {
+        let (luts, images): (Vec<PathBuf>, Vec<PathBuf>) = paths
+            .into_iter()
+            .partition(|path| ColorLut::is_cube_file(path));
+
+        if let Some(path) = luts.last() {
+            match ColorLut::load(path) {
+                Ok(lut) => {
+                    log::info!("🎬 LUT applied: {}", lut.name);
+                    self.color_lut = Some(lut);
+                }
+                Err(e) => log::warn!("⚠️ {e:#}"),
+            }
+        }
+        images
+    }
+
+    /// Y toggles the loaded LUT
+    pub fn handle_lut_input(&mut self) {
+        let modifier_down = [
+            KeyCode::LeftControl,
+            KeyCode::RightControl,
+            KeyCode::LeftAlt,
+            KeyCode::RightAlt,
+            KeyCode::LeftSuper,
+            KeyCode::RightSuper,
+        ]
+        .into_iter()
+        .any(is_key_down);
+        if modifier_down || !is_key_pressed(KeyCode::Y) {
+            return;
+        }
+        match self.color_lut.as_mut() {
+            Some(lut) => {
+                lut.enabled = !lut.enabled;
+                log::info!(
+                    "🎬 LUT {} {}",
+                    lut.name,
+                    if lut.enabled { "on" } else { "off" }
+                );
+            }
+            None => log::info!("🎬 No LUT loaded; drop a .cube file or set {LUT_ENV}"),
+        }
+    }
+
+    /// The LUT to apply, if one is loaded and enabled
+    pub fn active_lut(&self) -> Option<&ColorLut> {
+        self.color_lut.as_ref().filter(|lut| lut.enabled)
+    }
+}
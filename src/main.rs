@@ -59,16 +59,63 @@ fn window_conf() -> macroquad::conf::Conf {
     }
 }
 
-#[macroquad::main(window_conf)]
-async fn main() {
+/// Window for render tests: frames must keep coming without input events
+fn render_test_conf() -> macroquad::conf::Conf {
+    let mut conf = window_conf();
+    conf.miniquad_conf.window_title = "gTexViewer render test".to_owned();
+    conf.miniquad_conf.platform.blocking_event_loop = false;
+    conf.update_on = None;
+    conf
+}
+
+fn main() {
     env_logger::init();
 
+    let mut args: Vec<String> = env::args().collect();
+
+    // Portable mode (`--portable` or a marker file) keeps settings next to the executable
+    gtexviewer::portable::init(&mut args);
+
+    // Third-party sources from the plugins folder (`--features plugins`)
+    gtexviewer::texture_pipeline::plugins::init();
+
+    // Headless validation for CI: `gtexviewer validate --rules rules.toml <paths>`
+    if args.get(1).map(String::as_str) == Some("validate") {
+        std::process::exit(gtexviewer::validation::cli::run(&args[2..]));
+    }
+
+    // Headless indexing for external tools: `gtexviewer manifest --out manifest.json <paths>`
+    if args.get(1).map(String::as_str) == Some("manifest") {
+        std::process::exit(gtexviewer::export::manifest::run(&args[2..]));
+    }
+
+    // Golden-image render tests for packagers: `gtexviewer --render-test render-tests.toml`
+    if args.get(1).map(String::as_str) == Some(gtexviewer::render_test::RENDER_TEST_FLAG) {
+        let test_args = args[2..].to_vec();
+        macroquad::Window::from_config(render_test_conf(), async move {
+            std::process::exit(gtexviewer::render_test::run(&test_args).await);
+        });
+        return;
+    }
+
     // Check if a file was passed as command line argument (for file association)
-    let initial_file = env::args().nth(1);
+    let initial_file = args.get(1).cloned();
 
+    macroquad::Window::from_config(window_conf(), run_viewer(initial_file));
+}
+
+async fn run_viewer(initial_file: Option<String>) {
     let mut app = GTexViewerApp::new(initial_file).await;
 
+    // Handle the close button ourselves so background workers are stopped before exit
+    prevent_quit();
+
     loop {
+        if is_quit_requested() {
+            app.shutdown();
+            break;
+        }
+
         app.update().await;
         app.draw().await;
 
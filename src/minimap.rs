@@ -0,0 +1,96 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Longest edge of the minimap, in screen pixels
+const MINIMAP_SIZE: f32 = 180.0;
+const MINIMAP_MARGIN: f32 = 5.0;
+
+impl GTexViewerApp {
+    /// Screen rect of the minimap and its scale from world units, while the view doesn't
+    /// show all the content
+    fn minimap_frame(&self) -> Option<(MacroRect, f32)> {
+        let bounds = self.content_bounds;
+        if bounds.w <= 0.0 || bounds.h <= 0.0 {
+            return None;
+        }
+        let view = self.visible_world_rect();
+        let all_visible = view.x <= bounds.x
+            && view.y <= bounds.y
+            && view.right() >= bounds.right()
+            && view.bottom() >= bounds.bottom();
+        if all_visible && !self.minimap_dragging {
+            return None;
+        }
+
+        let scale = (MINIMAP_SIZE / bounds.w).min(MINIMAP_SIZE / bounds.h);
+        let size = bounds.size() * scale;
+        let frame = MacroRect::new(
+            screen_width() - MINIMAP_MARGIN - size.x,
+            MINIMAP_MARGIN,
+            size.x,
+            size.y,
+        );
+        Some((frame, scale))
+    }
+
+    /// Clicking or dragging on the minimap centres the view there.
+    /// Returns true while it has the mouse so it doesn't also pan
+    pub fn handle_minimap_input(&mut self) -> bool {
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.minimap_dragging = false;
+            return false;
+        }
+        let Some((frame, scale)) = self.minimap_frame() else {
+            return false;
+        };
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse = vec2(mouse_x, mouse_y);
+        if is_mouse_button_pressed(MouseButton::Left) && frame.contains(mouse) {
+            self.minimap_dragging = true;
+        }
+        if !self.minimap_dragging {
+            return false;
+        }
+
+        let local = (mouse - frame.point()).clamp(Vec2::ZERO, frame.size());
+        self.camera.target = self.content_bounds.point() + local / scale;
+        true
+    }
+
+    /// Slots as small blocks with the current view outlined, top right
+    pub fn draw_minimap(&self) {
+        let Some((frame, scale)) = self.minimap_frame() else {
+            return;
+        };
+        let to_map = |world: Vec2| frame.point() + (world - self.content_bounds.point()) * scale;
+
+        draw_rectangle(
+            frame.x - 3.0,
+            frame.y - 3.0,
+            frame.w + 6.0,
+            frame.h + 6.0,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+        for slot in self.image_slots.iter().filter(|slot| !slot.hidden) {
+            let corner = to_map(slot.position);
+            let size = (slot.size * scale).max(Vec2::ONE);
+            let color = match slot.state {
+                ImageState::Loaded { .. } => Color::new(0.6, 0.6, 0.6, 0.9),
+                ImageState::Placeholder { .. } => Color::new(0.35, 0.35, 0.35, 0.9),
+                ImageState::Failed { .. } => Color::new(0.6, 0.2, 0.2, 0.9),
+            };
+            draw_rectangle(corner.x, corner.y, size.x, size.y, color);
+        }
+
+        // The view, clipped to the minimap
+        let view = self.visible_world_rect();
+        let top_left = to_map(view.point()).clamp(frame.point(), frame.point() + frame.size());
+        let bottom_right =
+            to_map(view.point() + view.size()).clamp(frame.point(), frame.point() + frame.size());
+        let size = bottom_right - top_left;
+        draw_rectangle_lines(top_left.x, top_left.y, size.x, size.y, 1.5, YELLOW);
+    }
+}
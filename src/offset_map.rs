@@ -0,0 +1,206 @@
+use macroquad::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::types::{GTexViewerApp, ImageState};
+use crate::workspace::Panel;
+
+/// Where one entry's bytes sit inside its container file
+#[derive(Debug, Clone)]
+pub struct ByteSpan {
+    pub name: String,
+    pub offset: u64,
+    pub length: u64,
+}
+
+impl ByteSpan {
+    /// Largest power-of-two alignment of the span's start (capped at 4 KB)
+    pub fn alignment(&self) -> u64 {
+        if self.offset == 0 {
+            4096
+        } else {
+            (1u64 << self.offset.trailing_zeros()).min(4096)
+        }
+    }
+}
+
+/// Byte layout of a container: which ranges hold texture data and which don't
+#[derive(Debug, Clone)]
+pub struct ContainerLayout {
+    pub path: PathBuf,
+    pub file_size: u64,
+    pub spans: Vec<ByteSpan>, // Sorted by offset
+}
+
+impl ContainerLayout {
+    pub fn texture_bytes(&self) -> u64 {
+        self.spans.iter().map(|span| span.length).sum()
+    }
+}
+
+pub const OFFSET_MAP_HEIGHT: f32 = 56.0;
+
+impl GTexViewerApp {
+    /// Layout of a container from the byte ranges of its entries' hints
+    /// None when no entry of the container is read straight from the file
+    pub fn container_layout(&self, container: &Path) -> Option<ContainerLayout> {
+        let mut spans: Vec<ByteSpan> = self
+            .image_slots
+            .iter()
+            .filter_map(|slot| match &slot.state {
+                ImageState::Loaded { image } => Some(&image.metadata),
+                ImageState::Placeholder {
+                    original_metadata, ..
+                } => Some(original_metadata),
+                ImageState::Failed { metadata, .. } => metadata.as_ref(),
+            })
+            .filter(|metadata| metadata.source_path == container)
+            .filter_map(|metadata| {
+                let (offset, length) = metadata.embedded_hint.byte_range()?;
+                Some(ByteSpan {
+                    name: metadata.name.clone(),
+                    offset,
+                    length,
+                })
+            })
+            .collect();
+
+        if spans.is_empty() {
+            return None;
+        }
+        spans.sort_by_key(|span| span.offset);
+
+        let file_size = std::fs::metadata(container).ok()?.len();
+        Some(ContainerLayout {
+            path: container.to_path_buf(),
+            file_size,
+            spans,
+        })
+    }
+
+    /// M toggles the offset map for the container under the cursor
+    pub fn handle_offset_map_input(&mut self) {
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        if is_key_pressed(KeyCode::M) && !alt_down {
+            self.toggle_panel(Panel::OffsetMap);
+        }
+
+        if !self.panel_shown(Panel::OffsetMap) {
+            return;
+        }
+
+        // Follow the hovered entry's container; keep the last one when over empty space
+        let mouse_screen = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+        if let Some(index) = self.slot_at(mouse_world) {
+            let source_path = match &self.image_slots[index].state {
+                ImageState::Loaded { image } => Some(&image.metadata.source_path),
+                ImageState::Placeholder {
+                    original_metadata, ..
+                } => Some(&original_metadata.source_path),
+                ImageState::Failed { metadata, .. } => {
+                    metadata.as_ref().map(|metadata| &metadata.source_path)
+                }
+            };
+            if let Some(source_path) = source_path {
+                self.offset_map_container = Some(source_path.clone());
+            }
+        }
+    }
+
+    pub fn draw_offset_map(&self) {
+        if !self.panel_shown(Panel::OffsetMap) {
+            return;
+        }
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 14,
+            color: WHITE,
+            ..Default::default()
+        };
+
+        let panel = self.panel_rect(Panel::OffsetMap);
+        draw_rectangle(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            Color::new(0.1, 0.1, 0.1, 0.9),
+        );
+
+        let Some(layout) = self
+            .offset_map_container
+            .as_ref()
+            .and_then(|container| self.container_layout(container))
+        else {
+            draw_text_ex(
+                "Offset map: hover a GLB/ZIP/USDZ entry",
+                panel.x + 10.0,
+                panel.y + 20.0,
+                text_params,
+            );
+            return;
+        };
+
+        let texture_bytes = layout.texture_bytes();
+        let file_name = layout
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("container");
+        let mut title = format!(
+            "{file_name}: {} | textures {} ({:.0}%) | other {}",
+            format_size(layout.file_size),
+            format_size(texture_bytes),
+            texture_bytes as f64 / layout.file_size.max(1) as f64 * 100.0,
+            format_size(layout.file_size.saturating_sub(texture_bytes))
+        );
+
+        // Bar: file background, one segment per entry, hovered entry highlighted
+        let bar = Rect::new(panel.x + 10.0, panel.y + 30.0, panel.w - 20.0, 16.0);
+        draw_rectangle(
+            bar.x,
+            bar.y,
+            bar.w,
+            bar.h,
+            Color::new(0.25, 0.25, 0.25, 1.0),
+        );
+
+        let scale = bar.w as f64 / layout.file_size.max(1) as f64;
+        let (mouse_x, mouse_y) = mouse_position();
+        for (i, span) in layout.spans.iter().enumerate() {
+            let x = bar.x + (span.offset as f64 * scale) as f32;
+            let w = ((span.length as f64 * scale) as f32).max(1.0);
+            let hovered = bar.contains(vec2(mouse_x, mouse_y)) && mouse_x >= x && mouse_x <= x + w;
+            let color = if hovered {
+                YELLOW
+            } else if i % 2 == 0 {
+                Color::new(0.3, 0.6, 1.0, 1.0)
+            } else {
+                Color::new(0.2, 0.45, 0.8, 1.0)
+            };
+            draw_rectangle(x, bar.y, w, bar.h, color);
+
+            if hovered {
+                title = format!(
+                    "{}: @{} +{} (aligned to {} B)",
+                    span.name,
+                    span.offset,
+                    format_size(span.length),
+                    span.alignment()
+                );
+            }
+        }
+
+        draw_text_ex(&title, panel.x + 10.0, panel.y + 20.0, text_params);
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    if mb >= 1.0 {
+        format!("{mb:.1} MB")
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
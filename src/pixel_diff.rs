@@ -0,0 +1,261 @@
+use macroquad::prelude::*;
+use std::sync::mpsc;
+
+use crate::export::decode_pixels;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Gain the diff view opens with; Up/Down double or halve it
+const DEFAULT_GAIN: f32 = 8.0;
+const MAX_GAIN: f32 = 256.0;
+
+/// |A−B| of two decoded images, computed on a worker
+pub struct DiffResult {
+    pub names: (String, String),
+    pub width: u32,
+    pub height: u32,
+    pub diff: Vec<u8>, // Per-channel absolute difference, RGBA
+    pub max_error: u8,
+    pub mean_error: f64, // Mean absolute error over every RGBA channel, in 0–255 steps
+    pub differing_pixels: usize, // Texels where any channel differs
+}
+
+impl DiffResult {
+    fn compute(names: (String, String), a: &Image, b: &Image) -> Self {
+        let diff: Vec<u8> = a
+            .bytes
+            .iter()
+            .zip(&b.bytes)
+            .map(|(&a, &b)| a.abs_diff(b))
+            .collect();
+        let max_error = diff.iter().copied().max().unwrap_or(0);
+        let mean_error =
+            diff.iter().map(|&d| d as u64).sum::<u64>() as f64 / diff.len().max(1) as f64;
+        let differing_pixels = diff
+            .chunks_exact(4)
+            .filter(|texel| texel.iter().any(|&d| d > 0))
+            .count();
+
+        Self {
+            names,
+            width: a.width as u32,
+            height: a.height as u32,
+            diff,
+            max_error,
+            mean_error,
+            differing_pixels,
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let texels = (self.width as usize * self.height as usize).max(1);
+        if self.max_error == 0 {
+            return "identical".to_string();
+        }
+        format!(
+            "max {} | mean {:.3} | {:.2}% of texels differ",
+            self.max_error,
+            self.mean_error,
+            self.differing_pixels as f64 * 100.0 / texels as f64
+        )
+    }
+}
+
+/// Full-window abs(A−B) view of two selected images
+pub struct PixelDiff {
+    pub result: DiffResult,
+    pub gain: f32,
+    texture: Texture2D,
+}
+
+impl PixelDiff {
+    fn new(result: DiffResult) -> Self {
+        let texture = Self::render(&result, DEFAULT_GAIN);
+        Self {
+            result,
+            gain: DEFAULT_GAIN,
+            texture,
+        }
+    }
+
+    /// RGB difference scaled by gain, opaque so alpha-only changes still read as black
+    fn render(result: &DiffResult, gain: f32) -> Texture2D {
+        let bytes = result
+            .diff
+            .chunks_exact(4)
+            .flat_map(|texel| {
+                let scale = |d: u8| (d as f32 * gain).min(255.0) as u8;
+                [scale(texel[0]), scale(texel[1]), scale(texel[2]), 255]
+            })
+            .collect();
+        let texture = Texture2D::from_image(&Image {
+            width: result.width as u16,
+            height: result.height as u16,
+            bytes,
+        });
+        texture.set_filter(FilterMode::Nearest);
+        texture
+    }
+
+    fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.clamp(1.0, MAX_GAIN);
+        self.texture = Self::render(&self.result, self.gain);
+    }
+}
+
+impl GTexViewerApp {
+    /// Shift+D opens the diff of the two selected images; while open, Up/Down change the
+    /// gain and Escape or Shift+D close it. Returns true while open so other input stays inactive
+    pub fn handle_pixel_diff_input(&mut self) -> bool {
+        if let Some(receiver) = &self.pixel_diff_pending {
+            match receiver.try_recv() {
+                Ok(Ok(result)) => {
+                    log::info!(
+                        "🆚 {} vs {}: {}",
+                        result.names.0,
+                        result.names.1,
+                        result.summary()
+                    );
+                    self.pixel_diff = Some(PixelDiff::new(result));
+                    self.pixel_diff_pending = None;
+                }
+                Ok(Err(e)) => {
+                    log::warn!("⚠️ Pixel diff failed: {e}");
+                    self.pixel_diff_pending = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.pixel_diff_pending = None,
+            }
+        }
+
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        let toggle = shift_down && is_key_pressed(KeyCode::D);
+
+        let Some(diff) = self.pixel_diff.as_mut() else {
+            if toggle {
+                self.start_pixel_diff();
+            }
+            return false;
+        };
+
+        if toggle || is_key_pressed(KeyCode::Escape) {
+            self.pixel_diff = None;
+        } else if is_key_pressed(KeyCode::Up) {
+            diff.set_gain(diff.gain * 2.0);
+        } else if is_key_pressed(KeyCode::Down) {
+            diff.set_gain(diff.gain / 2.0);
+        }
+        macroquad::miniquad::window::schedule_update();
+        true
+    }
+
+    fn start_pixel_diff(&mut self) {
+        let images: Vec<_> = self
+            .selected_slots
+            .iter()
+            .filter_map(|&index| match &self.image_slots.get(index)?.state {
+                ImageState::Loaded { image } => Some(image),
+                _ => None,
+            })
+            .collect();
+        let [a, b] = images[..] else {
+            log::info!("🆚 Ctrl+click two loaded images to compare, then press Shift+D");
+            return;
+        };
+        if (a.info.width, a.info.height) != (b.info.width, b.info.height) {
+            log::warn!(
+                "⚠️ Can't diff {} ({}×{}) against {} ({}×{})",
+                a.metadata.name,
+                a.info.width,
+                a.info.height,
+                b.metadata.name,
+                b.info.width,
+                b.info.height
+            );
+            return;
+        }
+
+        // Compare the stored pixels, not the possibly proxied or compressed uploads
+        let (sender, receiver) = mpsc::channel();
+        let metadata = (a.metadata.clone(), b.metadata.clone());
+        let worker = self.workers.start();
+        std::thread::spawn(move || {
+            let _worker = worker;
+            let result = decode_pixels(&metadata.0)
+                .and_then(|a| Ok((a, decode_pixels(&metadata.1)?)))
+                .map_err(|e| format!("{e:#}"))
+                .and_then(|(a, b)| {
+                    if (a.width, a.height) != (b.width, b.height) {
+                        return Err("decoded sizes differ".to_string());
+                    }
+                    let names = (metadata.0.display_path(), metadata.1.display_path());
+                    Ok(DiffResult::compute(names, &a, &b))
+                });
+            let _ = sender.send(result);
+        });
+        self.pixel_diff_pending = Some(receiver);
+        log::info!(
+            "🆚 Comparing {} and {}...",
+            a.metadata.name,
+            b.metadata.name
+        );
+    }
+
+    /// The diff fitted to the window with its error stats
+    pub fn draw_pixel_diff(&self) {
+        let Some(diff) = &self.pixel_diff else {
+            return;
+        };
+
+        draw_rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::new(0.0, 0.0, 0.0, 0.95),
+        );
+
+        let margin = 50.0;
+        let size = vec2(diff.result.width as f32, diff.result.height as f32);
+        let scale = ((screen_width() - margin * 2.0) / size.x)
+            .min((screen_height() - margin * 2.0) / size.y);
+        let shown = size * scale;
+        let position = (vec2(screen_width(), screen_height()) - shown) / 2.0;
+        draw_texture_ex(
+            &diff.texture,
+            position.x.round(),
+            position.y.round(),
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(shown.round()),
+                ..Default::default()
+            },
+        );
+        draw_rectangle_lines(position.x, position.y, shown.x, shown.y, 1.0, DARKGRAY);
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 16,
+            color: WHITE,
+            ..Default::default()
+        };
+        draw_text_ex(
+            &format!(
+                "|A−B|  A: {}  B: {}",
+                diff.result.names.0, diff.result.names.1
+            ),
+            margin,
+            22.0,
+            text_params.clone(),
+        );
+        draw_text_ex(
+            &format!(
+                "{} | gain ×{} (↑/↓) | Esc closes",
+                diff.result.summary(),
+                diff.gain
+            ),
+            margin,
+            42.0,
+            text_params,
+        );
+    }
+}
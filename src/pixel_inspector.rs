@@ -0,0 +1,133 @@
+use macroquad::miniquad::TextureId;
+use macroquad::prelude::*;
+use std::sync::mpsc;
+
+use crate::export::{decode_pixels, world_to_pixel};
+use crate::loading::ResolutionTier;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// CPU copy of the hovered image's pixels, for exact value readout
+/// Only one image is kept; hovering another replaces it
+#[derive(Default)]
+pub struct PixelProbe {
+    source: Option<(String, TextureId)>, // Entry key and displayed texture the pixels belong to
+    pixels: Option<Image>,               // Tightly packed RGBA8, as stored
+    pending: Option<(String, mpsc::Receiver<Result<Image, String>>)>, // Decode of GPU-only pixels, by entry key
+    awaiting_decode: bool, // Hovered image needs a decode once the one in flight finishes
+}
+
+/// One texel under the cursor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelValue {
+    pub x: u32,
+    pub y: u32,
+    pub rgba: [u8; 4],
+}
+
+impl PixelValue {
+    /// e.g. "(12, 40) RGBA 255 128 0 255 · 1.000 0.502 0.000 1.000"
+    pub fn summary(&self) -> String {
+        let [r, g, b, a] = self.rgba;
+        let normalized = self.rgba.map(|value| value as f32 / 255.0);
+        format!(
+            "({}, {}) RGBA {r} {g} {b} {a} · {:.3} {:.3} {:.3} {:.3}",
+            self.x, self.y, normalized[0], normalized[1], normalized[2], normalized[3]
+        )
+    }
+}
+
+impl GTexViewerApp {
+    /// Keep the pixels of the hovered image on the CPU: read back from the GPU when the
+    /// texture holds them as stored, decoded again on the loader pool for compressed uploads
+    /// and proxies. Only one decode runs at a time; results for an image no longer hovered
+    /// are dropped
+    pub fn update_pixel_probe(&mut self) {
+        if let Some((key, receiver)) = &self.pixel_probe.pending {
+            let result = match receiver.try_recv() {
+                Ok(result) => Some(result),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    Some(Err("decode worker stopped".to_string()))
+                }
+            };
+            if let Some(result) = result {
+                let current = self
+                    .pixel_probe
+                    .source
+                    .as_ref()
+                    .map(|(source_key, _)| source_key);
+                if current == Some(key) {
+                    match result {
+                        Ok(pixels) => {
+                            self.pixel_probe.pixels = Some(pixels);
+                            macroquad::miniquad::window::schedule_update();
+                        }
+                        Err(e) => log::warn!("⚠️ Pixel inspector could not decode the image: {e}"),
+                    }
+                }
+                self.pixel_probe.pending = None;
+            }
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let Some(index) = self.slot_at(self.screen_to_world(vec2(mouse_x, mouse_y))) else {
+            return;
+        };
+        let ImageState::Loaded { image } = &self.image_slots[index].state else {
+            return;
+        };
+
+        let source = (image.metadata.entry_key(), image.texture.raw_miniquad_id());
+        if self.pixel_probe.source.as_ref() != Some(&source) {
+            let needs_decode = image.gpu_compressed || image.tier == ResolutionTier::Proxy;
+            // A decode of this entry may still be in flight from an earlier hover
+            let in_flight = self
+                .pixel_probe
+                .pending
+                .as_ref()
+                .is_some_and(|(key, _)| *key == source.0);
+            self.pixel_probe.source = Some(source);
+            self.pixel_probe.awaiting_decode = needs_decode && !in_flight;
+            self.pixel_probe.pixels = if needs_decode {
+                None
+            } else {
+                // Also covers the current animation frame, array layer or cubemap layout
+                Some(image.texture.get_texture_data())
+            };
+        }
+
+        if self.pixel_probe.awaiting_decode && self.pixel_probe.pending.is_none() {
+            let (sender, receiver) = mpsc::channel();
+            let metadata = image.metadata.clone();
+            let key = metadata.entry_key();
+            let worker = self.workers.start();
+            rayon::spawn(move || {
+                let _worker = worker;
+                let _ = sender.send(decode_pixels(&metadata).map_err(|e| format!("{e:#}")));
+            });
+            self.pixel_probe.pending = Some((key, receiver));
+            self.pixel_probe.awaiting_decode = false;
+        }
+    }
+
+    /// Exact value of the texel under a world-space point of a slot, once its pixels are
+    /// on the CPU; None while they're still being decoded
+    pub fn probe_pixel(&self, index: usize, world: Vec2) -> Option<PixelValue> {
+        let slot = self.image_slots.get(index)?;
+        let ImageState::Loaded { image } = &slot.state else {
+            return None;
+        };
+        let source = (image.metadata.entry_key(), image.texture.raw_miniquad_id());
+        if self.pixel_probe.source.as_ref() != Some(&source) {
+            return None;
+        }
+        let pixels = self.pixel_probe.pixels.as_ref()?;
+
+        let pixel = world_to_pixel(slot, image, world);
+        let x = (pixel.x as u32).min(pixels.width as u32 - 1);
+        let y = (pixel.y as u32).min(pixels.height as u32 - 1);
+        let offset = (y as usize * pixels.width as usize + x as usize) * 4;
+        let rgba = pixels.bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(PixelValue { x, y, rgba })
+    }
+}
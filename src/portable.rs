@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Command line flag turning on portable mode
+pub const PORTABLE_FLAG: &str = "--portable";
+
+/// Marker file next to the executable that turns on portable mode
+pub const PORTABLE_MARKER_FILE: &str = "gtexviewer.portable";
+
+/// Folder next to the executable holding settings in portable mode
+const PORTABLE_DATA_DIR: &str = "gtexviewer-data";
+
+/// Executable folder when running portable, set once at startup
+static PORTABLE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Turn on portable mode when `--portable` is passed or the marker file sits next to
+/// the executable. Removes the flag from `args`; call before any config is read
+pub fn init(args: &mut Vec<String>) {
+    let flag = args.iter().any(|arg| arg == PORTABLE_FLAG);
+    args.retain(|arg| arg != PORTABLE_FLAG);
+
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let root = exe_dir.filter(|dir| flag || dir.join(PORTABLE_MARKER_FILE).is_file());
+
+    if let Some(ref root) = root {
+        log::info!(
+            "🧳 Portable mode: settings stored in {}",
+            root.join(PORTABLE_DATA_DIR).display()
+        );
+    }
+    let _ = PORTABLE_ROOT.set(root);
+}
+
+/// Executable folder when running portable
+pub fn portable_root() -> Option<&'static Path> {
+    PORTABLE_ROOT.get_or_init(|| None).as_deref()
+}
+
+pub fn is_portable() -> bool {
+    portable_root().is_some()
+}
+
+/// Where gTexViewer keeps its own settings: `gtexviewer-data` next to the executable
+/// in portable mode, else the per-user config folder
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join(PORTABLE_DATA_DIR));
+    }
+
+    #[cfg(target_os = "macos")]
+    let config_dir = std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join("Library/Application Support"));
+
+    #[cfg(target_os = "windows")]
+    let config_dir = std::env::var("APPDATA").ok().map(PathBuf::from);
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var("HOME")
+                .ok()
+                .map(|home| PathBuf::from(home).join(".config"))
+        });
+
+    #[cfg(not(any(unix, target_os = "windows")))]
+    let config_dir: Option<PathBuf> = None;
+
+    config_dir.map(|dir| dir.join("gtexviewer"))
+}
+
+/// Resolve a config file path given by an environment variable or a default name
+/// Relative paths are looked up in the working directory first; in portable mode,
+/// ones not found there resolve against the executable's folder
+pub fn resolve_config_path(path: PathBuf) -> PathBuf {
+    if path.is_absolute() || path.exists() {
+        return path;
+    }
+
+    match portable_root() {
+        Some(root) => root.join(path),
+        None => path,
+    }
+}
@@ -0,0 +1,381 @@
+use anyhow::{Context, Result};
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::types::{ChannelMode, FilterOverride, GTexViewerApp, ImageState};
+
+/// Command line flag running golden-image render tests instead of the viewer
+pub const RENDER_TEST_FLAG: &str = "--render-test";
+
+const USAGE: &str = "Usage: gtexviewer --render-test <cases.toml> [--update] [--out <dir>]";
+
+/// How long a case may take to load before it fails
+const LOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Output width of whole-layout renders without an explicit size
+const DEFAULT_LAYOUT_WIDTH: u32 = 1024;
+
+/// One golden-image comparison from the cases file
+#[derive(Debug, Deserialize)]
+pub struct RenderCase {
+    pub name: String,
+    pub inputs: Vec<PathBuf>, // Files, folders or containers, relative to the cases file
+    pub slot: Option<usize>,  // Render only this slot; None renders the whole layout
+    pub size: Option<(u32, u32)>, // Output size; defaults to the slot's native size
+    pub channel: Option<String>, // normal, red, green, blue, alpha, swap-rg, swap-rb, swap-gb, luminance, invert, quadrants
+    pub filter: Option<String>, // auto, nearest or linear (default nearest)
+    #[serde(default)]
+    pub frame: usize, // Frame shown for animated images
+    #[serde(default)]
+    pub tolerance: u8, // Largest per-channel difference still counted as a match
+    pub golden: Option<PathBuf>, // Defaults to `golden/<name>.png` next to the cases file
+}
+
+/// `render-tests.toml`: a list of `[[case]]` tables
+#[derive(Debug, Deserialize)]
+pub struct RenderTestSuite {
+    #[serde(rename = "case", default)]
+    pub cases: Vec<RenderCase>,
+}
+
+impl RenderTestSuite {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read render test cases: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid render test cases: {}", path.display()))
+    }
+}
+
+/// Pixels of a render that differ from its golden image beyond the tolerance
+pub struct ImageDiff {
+    pub mismatched: usize,
+    pub max_delta: u8,
+    pub diff: Image, // Mismatches in red over a dimmed copy of the render
+}
+
+/// Compare two RGBA8 images of the same size; None when the sizes differ
+pub fn compare_images(actual: &Image, golden: &Image, tolerance: u8) -> Option<ImageDiff> {
+    if actual.width != golden.width || actual.height != golden.height {
+        return None;
+    }
+
+    let mut mismatched = 0;
+    let mut max_delta = 0;
+    let mut diff_bytes = Vec::with_capacity(actual.bytes.len());
+    for (actual_pixel, golden_pixel) in actual.bytes.chunks(4).zip(golden.bytes.chunks(4)) {
+        let delta = actual_pixel
+            .iter()
+            .zip(golden_pixel)
+            .map(|(a, g)| a.abs_diff(*g))
+            .max()
+            .unwrap_or(0);
+        max_delta = max_delta.max(delta);
+
+        if delta > tolerance {
+            mismatched += 1;
+            diff_bytes.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let gray = ((actual_pixel[0] as u16 + actual_pixel[1] as u16 + actual_pixel[2] as u16)
+                / 12) as u8;
+            diff_bytes.extend_from_slice(&[gray, gray, gray, 255]);
+        }
+    }
+
+    Some(ImageDiff {
+        mismatched,
+        max_delta,
+        diff: Image {
+            width: actual.width,
+            height: actual.height,
+            bytes: diff_bytes,
+        },
+    })
+}
+
+fn parse_channel(name: &str) -> Option<ChannelMode> {
+    match name.to_lowercase().as_str() {
+        "normal" | "rgba" => Some(ChannelMode::Normal),
+        "red" => Some(ChannelMode::Red),
+        "green" => Some(ChannelMode::Green),
+        "blue" => Some(ChannelMode::Blue),
+        "alpha" => Some(ChannelMode::Alpha),
+        "swap-rg" => Some(ChannelMode::SwapRG),
+        "swap-rb" => Some(ChannelMode::SwapRB),
+        "swap-gb" => Some(ChannelMode::SwapGB),
+        "luminance" => Some(ChannelMode::Luminance),
+        "invert" => Some(ChannelMode::Invert),
+        "quadrants" => Some(ChannelMode::Quadrants),
+        _ => None,
+    }
+}
+
+fn parse_filter(name: &str) -> Option<FilterOverride> {
+    match name.to_lowercase().as_str() {
+        "auto" => Some(FilterOverride::Auto),
+        "nearest" => Some(FilterOverride::Nearest),
+        "linear" => Some(FilterOverride::Linear),
+        _ => None,
+    }
+}
+
+fn load_golden(path: &Path) -> Result<Image> {
+    let golden = image::open(path)
+        .with_context(|| format!("Failed to read golden image: {}", path.display()))?
+        .to_rgba8();
+    Ok(Image {
+        width: golden.width() as u16,
+        height: golden.height() as u16,
+        bytes: golden.into_raw(),
+    })
+}
+
+/// How a case ended
+enum CaseOutcome {
+    Passed,
+    Updated,
+    Failed(String),
+}
+
+/// `--render-test` mode: render every case offscreen, compare against its golden image
+/// and return the process exit code (0 all passed, 1 mismatches, 2 usage errors)
+/// Must run inside the macroquad window since rendering needs a GL context
+pub async fn run(args: &[String]) -> i32 {
+    let mut cases_path = None;
+    let mut output_dir = None;
+    let mut update = false;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--update" => update = true,
+            "--out" => output_dir = args_iter.next().map(PathBuf::from),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return 0;
+            }
+            _ => cases_path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    let Some(cases_path) = cases_path else {
+        eprintln!("{USAGE}");
+        return 2;
+    };
+    let suite = match RenderTestSuite::load(&cases_path) {
+        Ok(suite) => suite,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            return 2;
+        }
+    };
+
+    let base_dir = cases_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let output_dir = output_dir.unwrap_or_else(|| base_dir.join("render-test-output"));
+
+    let mut failed = 0;
+    for case in &suite.cases {
+        let outcome = run_case(case, &base_dir, &output_dir, update)
+            .await
+            .unwrap_or_else(|e| CaseOutcome::Failed(format!("{e:#}")));
+        match outcome {
+            CaseOutcome::Passed => println!("ok      {}", case.name),
+            CaseOutcome::Updated => println!("updated {}", case.name),
+            CaseOutcome::Failed(reason) => {
+                println!("FAILED  {}: {reason}", case.name);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("{} of {} render tests failed", failed, suite.cases.len());
+    if failed > 0 { 1 } else { 0 }
+}
+
+async fn run_case(
+    case: &RenderCase,
+    base_dir: &Path,
+    output_dir: &Path,
+    update: bool,
+) -> Result<CaseOutcome> {
+    let channel = match case.channel.as_deref() {
+        Some(name) => {
+            parse_channel(name).with_context(|| format!("Unknown channel mode: {name}"))?
+        }
+        None => ChannelMode::Normal,
+    };
+    let filter = match case.filter.as_deref() {
+        Some(name) => parse_filter(name).with_context(|| format!("Unknown filter: {name}"))?,
+        None => FilterOverride::Nearest,
+    };
+
+    let mut app = GTexViewerApp::new(None).await;
+    // Only what the case asks for; machine-specific display settings stay off
+    app.monitor_profile_enabled = false;
+    app.display_view_index = 0;
+    app.channel_mode = channel;
+    app.filter_override = filter;
+
+    let inputs = case
+        .inputs
+        .iter()
+        .map(|input| base_dir.join(input))
+        .collect();
+    app.start_directory_scan(inputs);
+    app.wait_until_loaded(LOAD_TIMEOUT).await?;
+    app.hold_animations(case.frame);
+
+    let actual = match case.slot {
+        Some(index) => {
+            let slot = app
+                .image_slots
+                .get(index)
+                .with_context(|| format!("Slot {index} doesn't exist"))?;
+            let ImageState::Loaded { image } = &slot.state else {
+                anyhow::bail!("Slot {index} failed to load");
+            };
+            let region = MacroRect::new(slot.position.x, slot.position.y, slot.size.x, slot.size.y);
+            let (width, height) = case.size.unwrap_or((image.info.width, image.info.height));
+            app.render_offscreen(region, width, height)
+        }
+        None => {
+            let region = app.content_bounds;
+            let (width, height) = case.size.unwrap_or_else(|| {
+                let aspect = region.h / region.w.max(1e-6);
+                (
+                    DEFAULT_LAYOUT_WIDTH,
+                    (DEFAULT_LAYOUT_WIDTH as f32 * aspect).round().max(1.0) as u32,
+                )
+            });
+            app.render_offscreen(region, width, height)
+        }
+    };
+    app.shutdown();
+
+    let golden_path = base_dir.join(
+        case.golden
+            .clone()
+            .unwrap_or_else(|| Path::new("golden").join(format!("{}.png", case.name))),
+    );
+
+    if update || !golden_path.exists() {
+        if let Some(parent) = golden_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        actual.export_png(&golden_path.to_string_lossy());
+        return Ok(CaseOutcome::Updated);
+    }
+
+    let golden = load_golden(&golden_path)?;
+    let failure = match compare_images(&actual, &golden, case.tolerance) {
+        None => Some((
+            format!(
+                "rendered {}x{}, golden is {}x{}",
+                actual.width, actual.height, golden.width, golden.height
+            ),
+            None,
+        )),
+        Some(diff) if diff.mismatched > 0 => Some((
+            format!(
+                "{} pixels differ (max delta {}, tolerance {})",
+                diff.mismatched, diff.max_delta, case.tolerance
+            ),
+            Some(diff.diff),
+        )),
+        Some(_) => None,
+    };
+
+    let Some((reason, diff_image)) = failure else {
+        return Ok(CaseOutcome::Passed);
+    };
+
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+    let actual_path = output_dir.join(format!("{}.actual.png", case.name));
+    actual.export_png(&actual_path.to_string_lossy());
+    if let Some(diff_image) = diff_image {
+        diff_image.export_png(
+            &output_dir
+                .join(format!("{}.diff.png", case.name))
+                .to_string_lossy(),
+        );
+    }
+    Ok(CaseOutcome::Failed(format!(
+        "{reason}; render saved to {}",
+        actual_path.display()
+    )))
+}
+
+impl GTexViewerApp {
+    /// Run frames until every slot has loaded or failed and the layout is settled
+    pub async fn wait_until_loaded(&mut self, timeout: Duration) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            self.update().await;
+            self.draw().await;
+            next_frame().await;
+
+            let settled = self.metadata_receivers.is_empty()
+                && self.directory_scan.is_none()
+                && !self.layout_needs_update
+                && !self
+                    .image_slots
+                    .iter()
+                    .any(|slot| matches!(slot.state, ImageState::Placeholder { .. }));
+            if settled && !self.image_slots.is_empty() {
+                return Ok(());
+            }
+            if started.elapsed() > timeout {
+                anyhow::bail!("Loading didn't finish within {}s", timeout.as_secs());
+            }
+        }
+    }
+
+    /// Stop animated images on a fixed frame so renders don't depend on timing
+    pub fn hold_animations(&mut self, frame: usize) {
+        for slot in &mut self.image_slots {
+            if let ImageState::Loaded { image } = &mut slot.state
+                && let Some(animation) = &mut image.animation
+            {
+                animation.playing = false;
+                animation.seek(frame);
+                image.texture = animation.current_texture().clone();
+            }
+        }
+    }
+
+    /// Draw a world-space region of the layout into an offscreen target and read it back
+    /// The camera is set so zoom-dependent choices (pixel-perfect filtering, texel
+    /// footprint) see the target's pixel density instead of the window's
+    pub fn render_offscreen(&mut self, region: MacroRect, width: u32, height: u32) -> Image {
+        let target = render_target(width, height);
+        target.texture.set_filter(FilterMode::Nearest);
+
+        // Inverse of `slot_screen_size` so world units map to target pixels
+        let window_camera = (self.camera.target, self.camera.zoom);
+        self.camera.target = region.center();
+        let aspect_ratio = screen_width() / screen_height();
+        let world_to_pixels = vec2(screen_width() / 2.0, screen_height() / (2.0 * aspect_ratio));
+        self.camera.zoom =
+            vec2(width as f32 / region.w, height as f32 / region.h) / world_to_pixels;
+
+        // Render targets aren't flipped like the screen, so rows read back top first
+        set_camera(&Camera2D {
+            target: region.center(),
+            zoom: vec2(2.0 / region.w, 2.0 / region.h),
+            render_target: Some(target.clone()),
+            ..Default::default()
+        });
+        clear_background(BLACK);
+        self.draw_images_within(Some(region));
+        set_default_camera();
+
+        self.ui_text_queue.clear();
+        (self.camera.target, self.camera.zoom) = window_camera;
+        target.texture.get_texture_data()
+    }
+}
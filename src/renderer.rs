@@ -1,8 +1,14 @@
 use macroquad::math::Rect as MacroRect;
 use macroquad::prelude::*;
 
-use crate::texture_pipeline::EmbeddedMetadata;
-use crate::types::{ChannelMode, GTexViewerApp, ImageSlot, ImageState, UiText};
+use crate::color_vision::ColorVision;
+use crate::display_transform::DisplayTransform;
+use crate::sampling::set_texture_filter;
+use crate::srgb::{is_srgb_texture, set_srgb_decode};
+use crate::texture_pipeline::{EmbeddedMetadata, WrapMode};
+use crate::tiling::set_texture_wrap;
+use crate::timeline::FrameCompare;
+use crate::types::{ChannelMode, FilterOverride, GTexViewerApp, ImageSlot, ImageState, UiText};
 
 impl GTexViewerApp {
     pub fn init_channel_shader(&mut self) {
@@ -28,10 +34,253 @@ varying lowp vec4 color;
 varying lowp vec2 uv;
 
 uniform sampler2D Texture;
+uniform sampler2D PreviousFrame;
+uniform sampler2D LutTexture;
+uniform lowp int frame_compare;
 uniform lowp int channel_mode;
+uniform mediump float levels_black;
+uniform mediump float levels_white;
+uniform mediump float levels_gamma;
+uniform mediump float levels_contrast;
+uniform lowp int display_transform;
+uniform mediump float hdr_headroom;
+uniform lowp int icc_enabled;
+uniform mediump mat4 icc_matrix;
+uniform mediump vec3 icc_gamma;
+uniform lowp int gamut_enabled;
+uniform mediump mat4 gamut_matrix;
+uniform lowp int sampling_enabled;
+uniform mediump float lod_bias;
+uniform mediump float anisotropy;
+uniform mediump vec2 texel_footprint;
+uniform mediump vec2 texture_size;
+uniform lowp int tiling_enabled;
+uniform mediump float tiling_repeats;
+uniform mediump vec2 wrap_border;
+uniform lowp vec4 border_color;
+uniform lowp int texture_srgb;
+uniform lowp int color_managed;
+uniform lowp int unpremultiply;
+uniform lowp int surface_mode;
+uniform mediump vec3 light_dir;
+uniform lowp int normal_flip_green;
+uniform mediump float relief_scale;
+uniform lowp int color_vision_enabled;
+uniform mediump mat4 color_vision_matrix;
+uniform mediump float exposure;
+uniform mediump float display_gamma;
+uniform lowp int false_color;
+uniform lowp int lut_enabled;
+uniform mediump float lut_size;
+uniform mediump vec3 lut_domain_min;
+uniform mediump vec3 lut_domain_max;
+
+mediump vec4 sample_texture(sampler2D tex, mediump vec2 coord) {
+    if (sampling_enabled == 0) {
+        return texture2D(tex, coord);
+    }
+
+    // Emulated anisotropic filtering: probes spread along the footprint's major axis,
+    // each biased down to the finer mip the minor axis calls for
+    mediump float major = max(texel_footprint.x, texel_footprint.y);
+    mediump float minor = max(min(texel_footprint.x, texel_footprint.y), 0.0001);
+    mediump float probes = clamp(ceil(major / minor), 1.0, anisotropy);
+    if (probes <= 1.0) {
+        return texture2D(tex, coord, lod_bias);
+    }
+
+    mediump float bias = lod_bias - log2(probes);
+    mediump vec2 axis = texel_footprint.x >= texel_footprint.y
+        ? vec2(major / texture_size.x, 0.0)
+        : vec2(0.0, major / texture_size.y);
+    mediump vec4 sum = vec4(0.0);
+    for (int i = 0; i < 16; i++) {
+        if (float(i) >= probes) {
+            break;
+        }
+        sum += texture2D(tex, coord + axis * ((float(i) + 0.5) / probes - 0.5), bias);
+    }
+    return sum / probes;
+}
+
+mediump vec4 sample_wrapped(sampler2D tex, mediump vec2 coord) {
+    mediump vec4 texel = sample_texture(tex, coord);
+
+    // Clamp-to-border: the hardware clamps, then texels outside 0..1 fade to the border
+    // colour over half a texel, as bilinear filtering against the border would
+    mediump vec2 inside = clamp(coord * texture_size + 0.5, 0.0, 1.0)
+        * clamp((1.0 - coord) * texture_size + 0.5, 0.0, 1.0);
+    inside = mix(vec2(1.0), inside, wrap_border);
+    return mix(border_color, texel, inside.x * inside.y);
+}
+
+mediump vec3 srgb_to_linear(mediump vec3 c) {
+    return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), step(vec3(0.04045), c));
+}
+
+mediump vec3 linear_to_srgb(mediump vec3 c) {
+    c = clamp(c, 0.0, 1.0);
+    return mix(c * 12.92, 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, step(vec3(0.0031308), c));
+}
+
+mediump vec4 to_working_space(mediump vec4 texel) {
+    // sRGB textures arrive linear; the working space is linear when colour-managed,
+    // the stored values otherwise
+    if (color_managed == 1 && texture_srgb == 0) {
+        texel.rgb = srgb_to_linear(texel.rgb);
+    } else if (color_managed == 0 && texture_srgb == 1) {
+        texel.rgb = linear_to_srgb(texel.rgb);
+    }
+    // Straight colour so the blend doesn't multiply by alpha a second time
+    if (unpremultiply == 1 && texel.a > 0.0) {
+        texel.rgb = texel.rgb / texel.a;
+    }
+    return texel;
+}
+
+mediump vec3 stored_values(mediump vec3 rgb) {
+    // Data maps are authored as raw values, so undo the hardware sRGB decode
+    return texture_srgb == 1 ? linear_to_srgb(rgb) : rgb;
+}
+
+mediump vec3 shade_normal_map(mediump vec3 stored) {
+    // Tangent-space normal lighting a flat grey surface; Z is rebuilt from X and Y so
+    // two-channel (BC5-style) maps shade the same as full ones
+    mediump vec2 xy = stored.rg * 2.0 - 1.0;
+    if (normal_flip_green == 1) {
+        xy.y = -xy.y;
+    }
+    mediump vec3 n = normalize(vec3(xy, sqrt(max(1.0 - dot(xy, xy), 0.0))));
+    mediump float diffuse = max(dot(n, light_dir), 0.0);
+    mediump vec3 half_dir = normalize(light_dir + vec3(0.0, 0.0, 1.0));
+    mediump float specular = pow(max(dot(n, half_dir), 0.0), 32.0) * 0.25;
+    return vec3(0.03 + 0.7 * diffuse + specular);
+}
+
+mediump vec3 shade_heightmap(sampler2D tex, mediump vec2 coord) {
+    // Hillshade from central differences of the red channel; the slope is in height
+    // per texel, scaled so the full 0..1 range rises relief_scale of the width
+    mediump vec2 texel = 1.0 / texture_size;
+    mediump float left = stored_values(texture2D(tex, coord - vec2(texel.x, 0.0)).rgb).r;
+    mediump float right = stored_values(texture2D(tex, coord + vec2(texel.x, 0.0)).rgb).r;
+    mediump float up = stored_values(texture2D(tex, coord - vec2(0.0, texel.y)).rgb).r;
+    mediump float down = stored_values(texture2D(tex, coord + vec2(0.0, texel.y)).rgb).r;
+    mediump float rise = relief_scale * texture_size.x * 0.5;
+    // Texture V runs down the screen while the light's Y points up
+    mediump vec3 n = normalize(vec3((left - right) * rise, (down - up) * rise, 1.0));
+    mediump float diffuse = max(dot(n, light_dir), 0.0);
+    return vec3(0.05 + 0.9 * diffuse);
+}
+
+mediump vec3 viridis(mediump float t) {
+    // Polynomial fit of matplotlib's viridis
+    const mediump vec3 c0 = vec3(0.2777273, 0.0054073, 0.3340998);
+    const mediump vec3 c1 = vec3(0.1050930, 1.4046135, 1.3845902);
+    const mediump vec3 c2 = vec3(-0.3308618, 0.2148476, 0.0950952);
+    const mediump vec3 c3 = vec3(-4.6342305, -5.7991010, -19.3324410);
+    const mediump vec3 c4 = vec3(6.2282699, 14.1799334, 56.6905526);
+    const mediump vec3 c5 = vec3(4.7763850, -13.7451454, -65.3530326);
+    const mediump vec3 c6 = vec3(-5.4354559, 4.6458526, 26.3124352);
+    return c0 + t * (c1 + t * (c2 + t * (c3 + t * (c4 + t * (c5 + t * c6)))));
+}
+
+mediump vec3 turbo(mediump float t) {
+    // Polynomial approximation of Google's Turbo
+    const mediump vec4 red4 = vec4(0.13572138, 4.61539260, -42.66032258, 132.13108234);
+    const mediump vec4 green4 = vec4(0.09140261, 2.19418839, 4.84296658, -14.18503333);
+    const mediump vec4 blue4 = vec4(0.10667330, 12.64194608, -60.58204836, 110.36276771);
+    const mediump vec2 red2 = vec2(-152.94239396, 59.28637943);
+    const mediump vec2 green2 = vec2(4.27729857, 2.82956604);
+    const mediump vec2 blue2 = vec2(-89.90310912, 27.34824973);
+    mediump vec4 v4 = vec4(1.0, t, t * t, t * t * t);
+    mediump vec2 v2 = v4.zw * v4.z;
+    return vec3(
+        dot(v4, red4) + dot(v2, red2),
+        dot(v4, green4) + dot(v2, green2),
+        dot(v4, blue4) + dot(v2, blue2)
+    );
+}
+
+mediump vec3 hable(mediump vec3 x) {
+    return ((x * (0.15 * x + 0.05) + 0.004) / (x * (0.15 * x + 0.5) + 0.06)) - 0.0667;
+}
+
+mediump vec3 apply_display_transform(mediump vec3 rgb) {
+    // Restore scene-linear values, then map them to the display
+    mediump vec3 linear = srgb_to_linear(rgb) * hdr_headroom;
+    if (display_transform == 1) {
+        // ACES filmic fit (Narkowicz)
+        linear = (linear * (2.51 * linear + 0.03)) / (linear * (2.43 * linear + 0.59) + 0.14);
+    } else if (display_transform == 2) {
+        // Hable filmic, white point 11.2, exposure bias 2.0
+        linear = hable(linear * 2.0) / hable(vec3(11.2));
+    }
+    return linear_to_srgb(linear);
+}
+
+mediump vec3 apply_lut(mediump vec3 rgb) {
+    // The cube is stored as lut_size slices side by side; the hardware filters red and
+    // green within a slice, blue is blended between the two nearest slices
+    mediump vec3 cell = clamp((rgb - lut_domain_min) / (lut_domain_max - lut_domain_min), 0.0, 1.0)
+        * (lut_size - 1.0);
+    mediump float slice = floor(cell.b);
+    mediump float next_slice = min(slice + 1.0, lut_size - 1.0);
+    mediump vec2 scale = vec2(1.0 / (lut_size * lut_size), 1.0 / lut_size);
+    mediump vec2 within = cell.rg + 0.5;
+    mediump vec3 lower = texture2D(LutTexture, vec2(slice * lut_size + within.x, within.y) * scale).rgb;
+    mediump vec3 upper = texture2D(LutTexture, vec2(next_slice * lut_size + within.x, within.y) * scale).rgb;
+    return mix(lower, upper, cell.b - slice);
+}
+
+mediump vec3 apply_levels(mediump vec3 rgb) {
+    mediump float range = max(levels_white - levels_black, 0.0001);
+    mediump vec3 normalized = clamp((rgb - vec3(levels_black)) / range, 0.0, 1.0);
+    mediump vec3 curved = pow(normalized, vec3(1.0 / levels_gamma));
+    return clamp((curved - 0.5) * levels_contrast + 0.5, 0.0, 1.0);
+}
+
+mediump vec3 apply_monitor_profile(mediump vec3 rgb) {
+    // sRGB display-referred values re-encoded for the monitor's primaries and response
+    mediump vec3 display_linear = (icc_matrix * vec4(srgb_to_linear(rgb), 1.0)).rgb;
+    return pow(clamp(display_linear, 0.0, 1.0), 1.0 / icc_gamma);
+}
+
+bool outside_gamut(mediump vec3 rgb) {
+    // Negative components in the target space mean the chromaticity is outside its triangle
+    mediump vec3 linear = srgb_to_linear(rgb) * hdr_headroom;
+    mediump vec3 target = (gamut_matrix * vec4(linear, 1.0)).rgb;
+    return min(target.r, min(target.g, target.b)) < -0.001;
+}
 
 void main() {
-    lowp vec4 tex_color = texture2D(Texture, uv);
+    // Quadrant view draws the whole image in each quarter of the quad
+    mediump vec2 image_uv = channel_mode == 10 ? fract(uv * 2.0) : uv;
+    // Tiling preview spreads UVs around the original 0..1 tile so the wrap mode shows on
+    // every side, e.g. -1..2 for 3x3
+    mediump vec2 coord = tiling_enabled == 1
+        ? image_uv * tiling_repeats - (tiling_repeats - 1.0) / 2.0
+        : image_uv;
+    mediump vec4 sampled = sample_wrapped(Texture, coord);
+    mediump vec4 tex_color = to_working_space(sampled);
+
+    if (surface_mode != 0) {
+        // Shading is linear light; raw mode shows it already encoded
+        mediump vec3 shaded = surface_mode == 1
+            ? shade_normal_map(stored_values(sampled.rgb))
+            : shade_heightmap(Texture, coord);
+        tex_color = vec4(color_managed == 1 ? shaded : linear_to_srgb(shaded), sampled.a);
+    }
+
+    if (frame_compare == 1) {
+        // Onion skin: previous frame as a red-tinted ghost
+        mediump vec4 previous = to_working_space(sample_wrapped(PreviousFrame, coord));
+        tex_color = mix(tex_color, vec4(previous.rgb * vec3(1.0, 0.35, 0.35), previous.a), 0.35);
+    } else if (frame_compare == 2) {
+        // Frame difference, amplified; unchanged pixels are black
+        mediump vec4 previous = to_working_space(sample_wrapped(PreviousFrame, coord));
+        mediump vec4 diff = abs(tex_color - previous);
+        tex_color = vec4(clamp((diff.rgb + vec3(diff.a)) * 4.0, 0.0, 1.0), 1.0);
+    }
     
     if (channel_mode == 0) {
         // Normal RGBA
@@ -57,10 +306,90 @@ void main() {
     } else if (channel_mode == 7) {
         // Swap green and blue
         gl_FragColor = vec4(tex_color.r, tex_color.b, tex_color.g, tex_color.a) * color;
+    } else if (channel_mode == 8) {
+        // Rec.709 luminance (luma of the stored values when not colour-managed)
+        mediump float luminance = dot(tex_color.rgb, vec3(0.2126, 0.7152, 0.0722));
+        gl_FragColor = vec4(vec3(luminance), tex_color.a) * color;
+    } else if (channel_mode == 9) {
+        // Inverted colour; flipped in encoded values so mid-grey stays mid-grey
+        mediump vec3 inverted = color_managed == 1
+            ? srgb_to_linear(1.0 - linear_to_srgb(tex_color.rgb))
+            : 1.0 - clamp(tex_color.rgb, 0.0, 1.0);
+        gl_FragColor = vec4(inverted, tex_color.a) * color;
+    } else if (channel_mode == 10) {
+        // One opaque channel per quadrant: R top-left, G top-right, B bottom-left, A bottom-right
+        mediump vec2 quadrant = step(vec2(0.5), uv);
+        mediump vec4 pick = quadrant.y < 0.5
+            ? (quadrant.x < 0.5 ? vec4(1.0, 0.0, 0.0, 0.0) : vec4(0.0, 1.0, 0.0, 0.0))
+            : (quadrant.x < 0.5 ? vec4(0.0, 0.0, 1.0, 0.0) : vec4(0.0, 0.0, 0.0, 1.0));
+        mediump float value = dot(tex_color, pick);
+        gl_FragColor = vec4(value, value, value, 1.0) * color;
     } else {
         // Fallback to normal
         gl_FragColor = tex_color * color;
     }
+
+    // False colour for single-channel views, indexed by the stored value
+    if (false_color != 0) {
+        mediump float value = clamp(color_managed == 1
+            ? linear_to_srgb(gl_FragColor.rgb).r
+            : gl_FragColor.r, 0.0, 1.0);
+        mediump vec3 mapped = clamp(false_color == 1 ? viridis(value) : turbo(value), 0.0, 1.0);
+        gl_FragColor.rgb = color_managed == 1 ? srgb_to_linear(mapped) : mapped;
+    }
+
+    // Colour vision deficiency simulation works on linear light
+    if (color_vision_enabled == 1) {
+        mediump vec3 linear = color_managed == 1
+            ? gl_FragColor.rgb
+            : srgb_to_linear(gl_FragColor.rgb);
+        linear = max((color_vision_matrix * vec4(linear, 1.0)).rgb, 0.0);
+        gl_FragColor.rgb = color_managed == 1 ? linear : linear_to_srgb(linear);
+    }
+
+    // Exposure in stops, on linear light
+    if (exposure != 0.0) {
+        mediump vec3 linear = color_managed == 1
+            ? gl_FragColor.rgb
+            : srgb_to_linear(gl_FragColor.rgb);
+        linear *= exp2(exposure);
+        gl_FragColor.rgb = color_managed == 1 ? linear : linear_to_srgb(linear);
+    }
+
+    // Back to sRGB-encoded values for the display stages below
+    if (color_managed == 1) {
+        gl_FragColor.rgb = linear_to_srgb(gl_FragColor.rgb);
+    }
+
+    // Out-of-gamut pixels get diagonal magenta stripes
+    bool out_of_gamut = gamut_enabled == 1 && outside_gamut(gl_FragColor.rgb);
+
+    // Display/view transform for scene-linear and tone-mapped viewing
+    if (display_transform != 0 || hdr_headroom > 1.0) {
+        gl_FragColor.rgb = apply_display_transform(gl_FragColor.rgb);
+    }
+
+    // Show LUT grades the display-referred image
+    if (lut_enabled == 1) {
+        gl_FragColor.rgb = apply_lut(gl_FragColor.rgb);
+    }
+
+    // Display gamma on the encoded output
+    gl_FragColor.rgb = pow(max(gl_FragColor.rgb, 0.0), vec3(1.0 / display_gamma));
+
+    // Display-only levels curve
+    gl_FragColor.rgb = apply_levels(gl_FragColor.rgb);
+
+    // Monitor ICC profile is always the final display transform
+    if (icc_enabled == 1) {
+        gl_FragColor.rgb = apply_monitor_profile(gl_FragColor.rgb);
+    }
+
+    if (out_of_gamut) {
+        mediump float stripe = step(0.5, fract((gl_FragCoord.x + gl_FragCoord.y) / 8.0));
+        gl_FragColor.rgb = mix(vec3(1.0, 0.0, 1.0), gl_FragColor.rgb, stripe * 0.5);
+        gl_FragColor.a = 1.0;
+    }
 }";
 
         let material = load_material(
@@ -69,7 +398,47 @@ void main() {
                 fragment: FRAGMENT_SHADER,
             },
             MaterialParams {
-                uniforms: vec![UniformDesc::new("channel_mode", UniformType::Int1)],
+                uniforms: vec![
+                    UniformDesc::new("frame_compare", UniformType::Int1),
+                    UniformDesc::new("channel_mode", UniformType::Int1),
+                    UniformDesc::new("levels_black", UniformType::Float1),
+                    UniformDesc::new("levels_white", UniformType::Float1),
+                    UniformDesc::new("levels_gamma", UniformType::Float1),
+                    UniformDesc::new("levels_contrast", UniformType::Float1),
+                    UniformDesc::new("display_transform", UniformType::Int1),
+                    UniformDesc::new("hdr_headroom", UniformType::Float1),
+                    UniformDesc::new("icc_enabled", UniformType::Int1),
+                    UniformDesc::new("icc_matrix", UniformType::Mat4),
+                    UniformDesc::new("icc_gamma", UniformType::Float3),
+                    UniformDesc::new("gamut_enabled", UniformType::Int1),
+                    UniformDesc::new("gamut_matrix", UniformType::Mat4),
+                    UniformDesc::new("sampling_enabled", UniformType::Int1),
+                    UniformDesc::new("lod_bias", UniformType::Float1),
+                    UniformDesc::new("anisotropy", UniformType::Float1),
+                    UniformDesc::new("texel_footprint", UniformType::Float2),
+                    UniformDesc::new("texture_size", UniformType::Float2),
+                    UniformDesc::new("tiling_enabled", UniformType::Int1),
+                    UniformDesc::new("tiling_repeats", UniformType::Float1),
+                    UniformDesc::new("wrap_border", UniformType::Float2),
+                    UniformDesc::new("border_color", UniformType::Float4),
+                    UniformDesc::new("texture_srgb", UniformType::Int1),
+                    UniformDesc::new("color_managed", UniformType::Int1),
+                    UniformDesc::new("unpremultiply", UniformType::Int1),
+                    UniformDesc::new("surface_mode", UniformType::Int1),
+                    UniformDesc::new("light_dir", UniformType::Float3),
+                    UniformDesc::new("normal_flip_green", UniformType::Int1),
+                    UniformDesc::new("relief_scale", UniformType::Float1),
+                    UniformDesc::new("color_vision_enabled", UniformType::Int1),
+                    UniformDesc::new("color_vision_matrix", UniformType::Mat4),
+                    UniformDesc::new("exposure", UniformType::Float1),
+                    UniformDesc::new("display_gamma", UniformType::Float1),
+                    UniformDesc::new("false_color", UniformType::Int1),
+                    UniformDesc::new("lut_enabled", UniformType::Int1),
+                    UniformDesc::new("lut_size", UniformType::Float1),
+                    UniformDesc::new("lut_domain_min", UniformType::Float3),
+                    UniformDesc::new("lut_domain_max", UniformType::Float3),
+                ],
+                textures: vec!["PreviousFrame".to_string(), "LutTexture".to_string()],
                 ..Default::default()
             },
         );
@@ -85,6 +454,17 @@ void main() {
     }
 
     pub fn draw_images(&mut self) {
+        self.draw_images_within(None);
+    }
+
+    /// Draw the slots overlapping `region`, or those in the window's view when None;
+    /// offscreen slots skip drawing and filter setup entirely
+    pub fn draw_images_within(&mut self, region: Option<MacroRect>) {
+        self.draw_slots_within(region, None);
+    }
+
+    /// Like `draw_images_within`, limited to one slot and without overlays when `only` is set
+    pub fn draw_slots_within(&mut self, region: Option<MacroRect>, only: Option<usize>) {
         // Setup layout if needed
         let available_size = vec2(screen_width(), screen_height());
         self.setup_layout(available_size);
@@ -104,8 +484,17 @@ void main() {
         // Collect UI texts to avoid borrowing conflicts
         let mut ui_texts = Vec::new();
 
-        // Draw all image slots at their calculated positions
-        for slot in self.image_slots.iter() {
+        // Draw the image slots on screen at their calculated positions
+        let view = region.unwrap_or_else(|| self.visible_world_rect());
+        for slot in self
+            .image_slots
+            .iter()
+            .enumerate()
+            .filter(|&(index, slot)| {
+                only.is_none_or(|only| only == index) && Self::slot_overlaps(slot, &view)
+            })
+            .map(|(_, slot)| slot)
+        {
             match &slot.state {
                 ImageState::Placeholder {
                     original_metadata, ..
@@ -115,20 +504,60 @@ void main() {
                 }
 
                 ImageState::Loaded { image } => {
-                    // Determine filtering mode based on zoom level and set it on the texture
-                    let use_pixel_perfect = self.should_use_pixel_perfect_for_slot(slot);
-                    let filter_mode = if use_pixel_perfect {
-                        FilterMode::Nearest
-                    } else {
-                        FilterMode::Linear
+                    // Determine filtering mode from the override or the zoom level
+                    let filter_mode = match self.filter_override {
+                        FilterOverride::Nearest => FilterMode::Nearest,
+                        FilterOverride::Linear => FilterMode::Linear,
+                        FilterOverride::Auto if self.should_use_pixel_perfect_for_slot(slot) => {
+                            FilterMode::Nearest
+                        }
+                        FilterOverride::Auto => FilterMode::Linear,
                     };
 
-                    // Apply filtering mode to the texture at render time
-                    image.texture.set_filter(filter_mode);
-
-                    // Use custom shader if available and channel mode is not normal
+                    // Apply filtering mode to the texture at render time, through the
+                    // mip chain while previewing engine sampling
+                    let sampling_preview = self.sampling_preview_active();
+                    set_texture_filter(&image.texture, filter_mode, sampling_preview);
+
+                    // Wrap modes only differ from the viewer's clamp while tiling
+                    let preview = &image.sampler_preview;
+                    let wrap_sampler = preview.tiling.then_some(preview.sampler);
+                    set_texture_wrap(&image.texture, wrap_sampler);
+
+                    // Use custom shader if available and any display adjustment is active
+                    // HDR images always need it to restore their headroom
+                    // Animated images in a frame-compare mode need it to sample the previous frame
+                    let previous_frame = image
+                        .animation
+                        .as_ref()
+                        .filter(|_| self.frame_compare != FrameCompare::Off)
+                        .map(|animation| animation.previous_texture());
+                    let texture_srgb = is_srgb_texture(&image.texture);
+                    // Linear-light filtering only shows while colour is minified
+                    let linear_filtering = texture_srgb
+                        && self.color_managed
+                        && filter_mode == FilterMode::Linear
+                        && self.slot_screen_size(slot).max_element()
+                            < image.texture.size().max_element();
+                    let unpremultiply = image
+                        .analysis
+                        .premultiplied
+                        .is_some_and(|verdict| self.unpremultiply.applies_to(verdict));
+                    let mut use_display_shader = self.display_shader_active()
+                        || linear_filtering
+                        || unpremultiply
+                        || image.info.hdr_headroom > 1.0
+                        || previous_frame.is_some()
+                        || preview.tiling;
+                    if texture_srgb {
+                        // Without the shader, sRGB decoding is skipped so the default
+                        // material draws the stored values; drivers that can't skip it
+                        // always go through the shader to encode for display
+                        let decode = use_display_shader && self.channel_switch_material.is_some();
+                        use_display_shader |= set_srgb_decode(&image.texture, decode);
+                    }
                     if let Some(ref material) = self.channel_switch_material
-                        && self.channel_mode != ChannelMode::Normal
+                        && use_display_shader
                     {
                         // Set the channel mode uniform
                         let mode_value = match self.channel_mode {
@@ -140,9 +569,100 @@ void main() {
                             ChannelMode::SwapRG => 5,
                             ChannelMode::SwapRB => 6,
                             ChannelMode::SwapGB => 7,
+                            ChannelMode::Luminance => 8,
+                            ChannelMode::Invert => 9,
+                            ChannelMode::Quadrants => 10,
                         };
 
+                        match previous_frame {
+                            Some(previous) => {
+                                set_texture_filter(previous, filter_mode, sampling_preview);
+                                set_texture_wrap(previous, wrap_sampler);
+                                material.set_texture("PreviousFrame", previous.clone());
+                                material.set_uniform(
+                                    "frame_compare",
+                                    self.frame_compare.shader_index(),
+                                );
+                            }
+                            None => {
+                                material.set_texture("PreviousFrame", image.texture.clone());
+                                material.set_uniform("frame_compare", 0i32);
+                            }
+                        }
                         material.set_uniform("channel_mode", mode_value);
+                        material
+                            .set_uniform("false_color", self.active_false_color().shader_index());
+                        material.set_uniform("levels_black", self.levels.black_point);
+                        material.set_uniform("levels_white", self.levels.white_point);
+                        material.set_uniform("levels_gamma", self.levels.gamma);
+                        material.set_uniform("levels_contrast", self.levels.contrast);
+                        material.set_uniform("exposure", self.exposure_gamma.exposure);
+                        material.set_uniform("display_gamma", self.exposure_gamma.gamma);
+                        match self.active_lut() {
+                            Some(lut) => {
+                                material.set_texture("LutTexture", lut.texture.clone());
+                                material.set_uniform("lut_enabled", 1i32);
+                                material.set_uniform("lut_size", lut.lut.size as f32);
+                                material.set_uniform("lut_domain_min", lut.lut.domain_min);
+                                material.set_uniform("lut_domain_max", lut.lut.domain_max);
+                            }
+                            None => {
+                                material.set_texture("LutTexture", image.texture.clone());
+                                material.set_uniform("lut_enabled", 0i32);
+                            }
+                        }
+                        material.set_uniform(
+                            "display_transform",
+                            self.display_transform().shader_index(),
+                        );
+                        material.set_uniform("hdr_headroom", image.info.hdr_headroom);
+                        match self.active_monitor_profile() {
+                            Some(profile) => {
+                                material.set_uniform("icc_enabled", 1i32);
+                                material.set_uniform("icc_matrix", profile.shader_matrix());
+                                material.set_uniform("icc_gamma", Vec3::from(profile.gamma));
+                            }
+                            None => material.set_uniform("icc_enabled", 0i32),
+                        }
+                        material
+                            .set_uniform("gamut_enabled", self.gamut_overlay.is_active() as i32);
+                        material.set_uniform("gamut_matrix", self.gamut_overlay.shader_matrix());
+                        let texture_size = vec2(image.info.width as f32, image.info.height as f32);
+                        material.set_uniform("sampling_enabled", sampling_preview as i32);
+                        material.set_uniform("lod_bias", self.sampling.lod_bias);
+                        material.set_uniform("anisotropy", self.sampling.anisotropy as f32);
+                        material.set_uniform(
+                            "texel_footprint",
+                            texture_size / self.slot_screen_size(slot).max(Vec2::splat(1e-3)),
+                        );
+                        material.set_uniform("texture_size", texture_size);
+                        let border_axes = wrap_sampler.map_or(Vec2::ZERO, |sampler| {
+                            vec2(
+                                (sampler.wrap_s == WrapMode::ClampToBorder) as i32 as f32,
+                                (sampler.wrap_t == WrapMode::ClampToBorder) as i32 as f32,
+                            )
+                        });
+                        material.set_uniform("tiling_enabled", preview.tiling as i32);
+                        material.set_uniform("tiling_repeats", preview.repeats as f32);
+                        material.set_uniform("wrap_border", border_axes);
+                        material.set_uniform("border_color", preview.border.rgba());
+                        material.set_uniform("texture_srgb", texture_srgb as i32);
+                        material.set_uniform("color_managed", self.color_managed as i32);
+                        material.set_uniform("unpremultiply", unpremultiply as i32);
+                        material
+                            .set_uniform("surface_mode", self.surface_preview.mode.shader_index());
+                        material.set_uniform("light_dir", self.surface_preview.light);
+                        material.set_uniform(
+                            "normal_flip_green",
+                            self.surface_preview.flip_green as i32,
+                        );
+                        material.set_uniform("relief_scale", self.surface_preview.relief_scale);
+                        material.set_uniform(
+                            "color_vision_enabled",
+                            (self.color_vision != ColorVision::Normal) as i32,
+                        );
+                        material
+                            .set_uniform("color_vision_matrix", self.color_vision.shader_matrix());
                         gl_use_material(material);
                     }
 
@@ -158,11 +678,32 @@ void main() {
                     );
 
                     // Reset to default material if we used custom shader
-                    if self.channel_switch_material.is_some()
-                        && self.channel_mode != ChannelMode::Normal
-                    {
+                    if self.channel_switch_material.is_some() && use_display_shader {
                         gl_use_default_material();
                     }
+
+                    // Separate the quadrant view's channels
+                    if self.channel_mode == ChannelMode::Quadrants {
+                        let center = slot.position + slot.size / 2.0;
+                        let end = slot.position + slot.size;
+                        let color = Color::new(0.5, 0.5, 0.5, 0.8);
+                        draw_line(center.x, slot.position.y, center.x, end.y, 0.004, color);
+                        draw_line(slot.position.x, center.y, end.x, center.y, 0.004, color);
+                    }
+
+                    // Outline the original 0..1 tile in the middle of the tiling preview
+                    if preview.tiling {
+                        let tile = slot.size / preview.repeats as f32;
+                        let origin = slot.position + tile * preview.center_offset();
+                        draw_rectangle_lines(
+                            origin.x,
+                            origin.y,
+                            tile.x,
+                            tile.y,
+                            0.004,
+                            Color::new(1.0, 0.85, 0.2, 0.8),
+                        );
+                    }
                 }
                 ImageState::Failed {
                     metadata: _,
@@ -197,10 +738,32 @@ void main() {
             }
         }
 
+        if only.is_none() {
+            // Outline Ctrl+clicked slots
+            self.draw_selection();
+
+            // Flash slots of a duplicate drop
+            self.draw_slot_highlights();
+        }
+
         // Add collected UI texts to queue
         self.ui_text_queue.extend(ui_texts);
     }
 
+    /// Whether any display adjustment requires the custom shader
+    pub fn display_shader_active(&self) -> bool {
+        self.channel_mode != ChannelMode::Normal
+            || !self.levels.is_identity()
+            || !self.exposure_gamma.is_identity()
+            || self.active_lut().is_some()
+            || self.display_transform() != DisplayTransform::Standard
+            || self.active_monitor_profile().is_some()
+            || self.gamut_overlay.is_active()
+            || self.sampling_preview_active()
+            || self.surface_preview.is_active()
+            || self.color_vision != ColorVision::Normal
+    }
+
     pub fn draw_placeholder(&self, slot: &ImageSlot, metadata: &EmbeddedMetadata) -> Vec<UiText> {
         // Use the original simple loading placeholder - just like the original working system
         let rect = MacroRect::new(slot.position.x, slot.position.y, slot.size.x, slot.size.y);
@@ -221,6 +784,22 @@ void main() {
             return vec![];
         }
 
+        // Coarse preview of a large image still decoding, refined in place once loaded
+        if !self.placeholder_previews.is_empty()
+            && let Some(preview) = self.placeholder_previews.get(&metadata.entry_key())
+        {
+            draw_texture_ex(
+                preview,
+                rect.x,
+                rect.y,
+                WHITE,
+                DrawTextureParams {
+                    dest_size: Some(vec2(rect.w, rect.h)),
+                    ..Default::default()
+                },
+            );
+        }
+
         // Draw placeholder border using individual lines for better control
         let line_thickness = 0.004; // Slightly thicker for visibility
         let color = Color::new(0.8, 0.8, 0.8, 0.9); // Light gray, slightly transparent
@@ -357,28 +936,26 @@ void main() {
         }
     }
 
+    /// On-screen size of a slot in pixels at the current zoom
+    pub fn slot_screen_size(&self, slot: &ImageSlot) -> Vec2 {
+        // thumbnail_size_in_world_units * zoom * pixels_per_world_unit = screen pixels
+        let aspect_ratio = screen_width() / screen_height();
+        let world_to_pixels_x = screen_width() / 2.0; // World spans -1 to +1 = 2 units
+        let world_to_pixels_y = screen_height() / (2.0 * aspect_ratio);
+
+        vec2(
+            slot.size.x * world_to_pixels_x * self.camera.zoom.x,
+            slot.size.y * world_to_pixels_y * self.camera.zoom.y,
+        )
+    }
+
     pub fn should_use_pixel_perfect_for_slot(&self, slot: &ImageSlot) -> bool {
         match &slot.state {
             ImageState::Loaded { image } => {
-                // Calculate the actual zoom level needed for 1:1 pixel mapping
-                // thumbnail_size_in_world_units * zoom * pixels_per_world_unit = original_pixels
-
-                let thumbnail_width_world = slot.size.x;
-                let thumbnail_height_world = slot.size.y;
-
-                // Convert world units to screen pixels to find current scale
-                let aspect_ratio = screen_width() / screen_height();
-                let world_to_pixels_x = screen_width() / 2.0; // World spans -1 to +1 = 2 units
-                let world_to_pixels_y = screen_height() / (2.0 * aspect_ratio);
-
-                let thumbnail_width_pixels =
-                    thumbnail_width_world * world_to_pixels_x * self.camera.zoom.x;
-                let thumbnail_height_pixels =
-                    thumbnail_height_world * world_to_pixels_y * self.camera.zoom.y;
-
                 // Check if we're at or above 1:1 pixel mapping (pixel-perfect threshold)
-                let scale_x = thumbnail_width_pixels / image.info.width as f32;
-                let scale_y = thumbnail_height_pixels / image.info.height as f32;
+                let thumbnail_pixels = self.slot_screen_size(slot);
+                let scale_x = thumbnail_pixels.x / image.info.width as f32;
+                let scale_y = thumbnail_pixels.y / image.info.height as f32;
                 let effective_scale = scale_x.max(scale_y);
 
                 // Use pixel-perfect when at 0.5x or higher scale (easier to trigger for large images)
@@ -0,0 +1,99 @@
+use crate::loading::{LoadedImage, ResolutionTier};
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Zoomed-out full-resolution slots swap back to the proxy below this fraction of the
+/// proxy size, so zooming around the threshold doesn't keep reloading
+const DOWNGRADE_RATIO: f32 = 0.75;
+
+impl GTexViewerApp {
+    /// Request native textures for visible proxies drawn larger than the proxy itself,
+    /// and swap native textures back to their proxy once zoomed out
+    pub fn update_resolution_tiers(&mut self) {
+        let view = self.visible_world_rect();
+        let mut upgrade = Vec::new();
+
+        for index in 0..self.image_slots.len() {
+            let slot = &self.image_slots[index];
+            if !Self::slot_overlaps(slot, &view) {
+                continue;
+            }
+            let screen_size = self.slot_screen_size(slot).max_element();
+
+            let ImageState::Loaded { image } = &mut self.image_slots[index].state else {
+                continue;
+            };
+            let Some(proxy) = image.proxy.clone() else {
+                continue;
+            };
+            let proxy_size = proxy.size().max_element();
+
+            match image.tier {
+                ResolutionTier::Proxy if screen_size > proxy_size => {
+                    let key = image.metadata.entry_key();
+                    if self.full_resolution_pending.insert(key) {
+                        upgrade.push(image.metadata.clone());
+                    }
+                }
+                ResolutionTier::Full if screen_size < proxy_size * DOWNGRADE_RATIO => {
+                    image.texture = proxy;
+                    image.tier = ResolutionTier::Proxy;
+                    // Dropping the owner frees a compressed or sRGB native texture's VRAM
+                    image.gpu_upload = None;
+                    if image.gpu_compressed {
+                        image.gpu_compressed = false;
+                        if let Some(color_space) = image.info.color_space.strip_suffix(" · GPU") {
+                            image.info.color_space = color_space.to_string();
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !upgrade.is_empty() {
+            log::info!("🔎 Loading {} textures at full resolution", upgrade.len());
+            self.async_loader
+                .start_loading_batch_at(upgrade, ResolutionTier::Full, &self.workers);
+        }
+    }
+
+    /// Swap a finished full-resolution decode into its proxied slot
+    /// Upgrades never load a slot outright: the result is dropped when the slot was
+    /// evicted or reloaded since it was requested
+    pub fn apply_full_resolution(&mut self, key: &str, result: Result<LoadedImage, String>) {
+        if !self.full_resolution_pending.remove(key) {
+            log::debug!("Dropping stale full-resolution texture of {key}");
+            return;
+        }
+
+        let loaded = match result {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                log::warn!("⚠️ Full-resolution load of {key} failed, keeping the proxy: {e}");
+                return;
+            }
+        };
+
+        let slot = self
+            .image_slots
+            .iter_mut()
+            .find_map(|slot| match &mut slot.state {
+                ImageState::Loaded { image }
+                    if image.proxy.is_some() && image.metadata.entry_key() == key =>
+                {
+                    Some(image)
+                }
+                _ => None,
+            });
+        let Some(image) = slot else {
+            log::debug!("Dropping full-resolution texture of {key}, its proxy is gone");
+            return;
+        };
+        image.texture = loaded.texture;
+        image.info.color_space = loaded.info.color_space;
+        image.gpu_compressed = loaded.gpu_compressed;
+        image.gpu_upload = loaded.gpu_upload;
+        image.tier = ResolutionTier::Full;
+        macroquad::miniquad::window::schedule_update();
+    }
+}
@@ -0,0 +1,257 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::miniquad::{MipmapFilterMode, TextureId};
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+use crate::workspace::Panel;
+
+/// Engine-style sampling preview: mipmapped trilinear filtering with anisotropy and LOD bias
+/// Applies while the sampling panel is shown; anisotropy is emulated in the display shader
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplingSettings {
+    pub anisotropy: u32, // Max probes along the footprint's major axis (1 = isotropic)
+    pub lod_bias: f32,   // Added to the hardware-selected mip level
+}
+
+impl Default for SamplingSettings {
+    fn default() -> Self {
+        Self {
+            anisotropy: 1,
+            lod_bias: 0.0,
+        }
+    }
+}
+
+/// Anisotropy levels offered by the panel, matching common engine settings
+pub const ANISOTROPY_LEVELS: [u32; 5] = [1, 2, 4, 8, 16];
+
+/// Size of the sampling widget in any dock
+pub const SAMPLING_WIDGET_SIZE: Vec2 = Vec2::new(230.0, 110.0);
+const LOD_BIAS_RANGE: (f32, f32) = (-4.0, 4.0);
+
+/// Owns a texture created through miniquad, which macroquad doesn't manage; deleted
+/// once the last image holding it is dropped
+#[derive(Debug)]
+pub struct OwnedTexture(pub TextureId);
+
+impl Drop for OwnedTexture {
+    fn drop(&mut self) {
+        crate::srgb::forget_texture(self.0);
+        unsafe { get_internal_gl() }
+            .quad_context
+            .delete_texture(self.0);
+    }
+}
+
+/// Create a texture with a full mip chain so the sampling preview can switch on instantly
+/// Values are stored as-is; colour that should filter in linear light uses `upload_srgb`
+pub fn upload_texture(image: &Image) -> Texture2D {
+    let texture = Texture2D::from_image(image);
+    let gl = unsafe { get_internal_gl() };
+    gl.quad_context
+        .texture_generate_mipmaps(texture.raw_miniquad_id());
+    texture
+}
+
+/// Set the texture filter, sampling the mip chain only when `mipmapped`
+pub fn set_texture_filter(texture: &Texture2D, filter: FilterMode, mipmapped: bool) {
+    if !mipmapped {
+        texture.set_filter(filter);
+        return;
+    }
+
+    let gl = unsafe { get_internal_gl() };
+    gl.quad_context
+        .texture_set_filter(texture.raw_miniquad_id(), filter, MipmapFilterMode::Linear);
+}
+
+impl GTexViewerApp {
+    /// Whether textures are drawn with the engine sampling settings instead of the viewer's
+    pub fn sampling_preview_active(&self) -> bool {
+        self.panel_shown(Panel::Sampling)
+    }
+
+    fn sampling_widget_rect(&self) -> MacroRect {
+        self.panel_rect(Panel::Sampling)
+    }
+
+    /// Rect of each anisotropy button, in `ANISOTROPY_LEVELS` order
+    fn anisotropy_button_rects(&self) -> impl Iterator<Item = (u32, MacroRect)> {
+        let panel = self.sampling_widget_rect();
+        let width = (panel.w - 30.0) / ANISOTROPY_LEVELS.len() as f32;
+        ANISOTROPY_LEVELS
+            .into_iter()
+            .enumerate()
+            .map(move |(i, level)| {
+                (
+                    level,
+                    MacroRect::new(
+                        panel.x + 15.0 + i as f32 * width,
+                        panel.y + 28.0,
+                        width - 4.0,
+                        22.0,
+                    ),
+                )
+            })
+    }
+
+    /// X range and Y coordinate of the LOD bias slider
+    fn lod_bias_track(&self) -> (f32, f32, f32) {
+        let panel = self.sampling_widget_rect();
+        (panel.x + 15.0, panel.w - 30.0, panel.y + 68.0)
+    }
+
+    /// Handle S (toggle) / Shift+S (reset), anisotropy clicks and bias dragging
+    /// Returns true when the widget owns the mouse so the camera doesn't pan
+    pub fn handle_sampling_input(&mut self) -> bool {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        if is_key_pressed(KeyCode::S) && !ctrl_down && !alt_down {
+            if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+                self.sampling = SamplingSettings::default();
+                log::info!("🧮 Sampling reset");
+            } else {
+                self.toggle_panel(Panel::Sampling);
+            }
+        }
+
+        if !self.panel_shown(Panel::Sampling) {
+            self.sampling_drag = false;
+            return false;
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse = vec2(mouse_x, mouse_y);
+        let mouse_in_panel = self.sampling_widget_rect().contains(mouse);
+        let (track_x, track_w, track_y) = self.lod_bias_track();
+
+        if is_mouse_button_pressed(MouseButton::Left) && mouse_in_panel {
+            let clicked = self
+                .anisotropy_button_rects()
+                .find(|(_, rect)| rect.contains(mouse))
+                .map(|(level, _)| level);
+            if let Some(level) = clicked {
+                self.sampling.anisotropy = level;
+                log::info!("🧮 Anisotropy {level}x");
+            }
+            self.sampling_drag = (mouse_y - track_y).abs() < 10.0;
+        }
+
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.sampling_drag = false;
+        }
+
+        if self.sampling_drag {
+            let t = ((mouse_x - track_x) / track_w).clamp(0.0, 1.0);
+            let bias = LOD_BIAS_RANGE.0 + t * (LOD_BIAS_RANGE.1 - LOD_BIAS_RANGE.0);
+            // Snap to quarter mips like engine settings usually are
+            self.sampling.lod_bias = (bias * 4.0).round() / 4.0;
+        }
+
+        // Keep the panel from leaking clicks into camera panning
+        self.sampling_drag || (mouse_in_panel && is_mouse_button_down(MouseButton::Left))
+    }
+
+    pub fn draw_sampling_widget(&self) {
+        if !self.panel_shown(Panel::Sampling) {
+            return;
+        }
+
+        let panel = self.sampling_widget_rect();
+        let settings = &self.sampling;
+
+        draw_rectangle(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            Color::new(0.1, 0.1, 0.1, 0.95),
+        );
+        draw_rectangle_lines(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            2.0,
+            Color::new(0.3, 0.3, 0.3, 0.9),
+        );
+
+        let title_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 14,
+            color: WHITE,
+            ..Default::default()
+        };
+        draw_text_ex(
+            &format!(
+                "Sampling  Aniso {}x  Bias {:+.2}",
+                settings.anisotropy, settings.lod_bias
+            ),
+            panel.x + 10.0,
+            panel.y + 17.0,
+            title_params,
+        );
+
+        let button_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 12,
+            color: WHITE,
+            ..Default::default()
+        };
+        for (level, rect) in self.anisotropy_button_rects() {
+            let fill = if level == settings.anisotropy {
+                Color::new(0.25, 0.45, 0.7, 1.0)
+            } else {
+                Color::new(0.2, 0.2, 0.2, 1.0)
+            };
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, fill);
+            let label = format!("{level}x");
+            let dims = measure_text(&label, self.ui_font.as_ref(), 12, 1.0);
+            draw_text_ex(
+                &label,
+                rect.x + (rect.w - dims.width) / 2.0,
+                rect.y + 15.0,
+                button_params.clone(),
+            );
+        }
+
+        // LOD bias slider with a tick at zero
+        let (track_x, track_w, track_y) = self.lod_bias_track();
+        let to_x = |bias: f32| {
+            track_x + (bias - LOD_BIAS_RANGE.0) / (LOD_BIAS_RANGE.1 - LOD_BIAS_RANGE.0) * track_w
+        };
+        draw_line(
+            track_x,
+            track_y,
+            track_x + track_w,
+            track_y,
+            2.0,
+            Color::new(0.4, 0.4, 0.4, 1.0),
+        );
+        draw_line(
+            to_x(0.0),
+            track_y - 5.0,
+            to_x(0.0),
+            track_y + 5.0,
+            1.0,
+            GRAY,
+        );
+        draw_circle(to_x(settings.lod_bias), track_y, 6.0, SKYBLUE);
+
+        let label_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 12,
+            color: GRAY,
+            ..Default::default()
+        };
+        draw_text_ex(
+            "Mip LOD bias   (Shift+S to reset)",
+            track_x,
+            track_y + 22.0,
+            label_params,
+        );
+    }
+}
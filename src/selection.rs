@@ -0,0 +1,53 @@
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+
+const SELECTION_COLOR: Color = Color::new(0.2, 0.6, 1.0, 1.0);
+
+impl GTexViewerApp {
+    /// Ctrl+click adds or removes the image under the cursor, Ctrl+click on empty space
+    /// clears the selection. Returns true when a click was used so it doesn't also pan
+    pub fn handle_selection_input(&mut self) -> bool {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if !(ctrl_down && is_mouse_button_pressed(MouseButton::Left)) {
+            return false;
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        match self.slot_at(self.screen_to_world(vec2(mouse_x, mouse_y))) {
+            Some(index) => {
+                if let Some(position) = self.selected_slots.iter().position(|&i| i == index) {
+                    self.selected_slots.remove(position);
+                } else {
+                    self.selected_slots.push(index);
+                }
+            }
+            None => self.selected_slots.clear(),
+        }
+        log::info!("☑️ {} selected", self.selected_slots.len());
+        true
+    }
+
+    /// Outline around selected slots (drawn in world space)
+    pub fn draw_selection(&self) {
+        let thickness = 2.0 / self.camera.zoom.x.max(0.0001) / screen_width() * 2.0;
+        for slot in self
+            .selected_slots
+            .iter()
+            .filter_map(|&index| self.image_slots.get(index))
+            .filter(|slot| !slot.hidden)
+        {
+            draw_rectangle_lines(
+                slot.position.x,
+                slot.position.y,
+                slot.size.x,
+                slot.size.y,
+                thickness,
+                SELECTION_COLOR,
+            );
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use macroquad::prelude::*;
+use std::time::{Duration, Instant};
+
+use crate::types::GTexViewerApp;
+
+/// Auto-advance interval the slideshow starts with; Up/Down change it a second at a time
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fullscreen presentation of one image at a time, built on focus mode
+pub struct Slideshow {
+    pub interval: Duration,
+    pub next_at: Option<Instant>, // When auto-advance moves on, None while paused
+    was_focused: bool,            // Whether focus mode was already on, so leaving keeps it
+}
+
+impl GTexViewerApp {
+    /// F11 starts the slideshow from the hovered or focused image; while running, Left/Right
+    /// step, Space toggles auto-advance, Up/Down change its interval and Escape or F11 leave.
+    /// Only F11 is bound, since F already cycles texture filtering.
+    /// Channel, false colour and exposure keys still apply. Returns true while running so
+    /// other input stays inactive
+    pub fn handle_slideshow_input(&mut self) -> bool {
+        let toggle = is_key_pressed(KeyCode::F11);
+
+        if self.slideshow.is_none() {
+            if toggle {
+                self.start_slideshow();
+            }
+            return self.slideshow.is_some();
+        }
+        if toggle || is_key_pressed(KeyCode::Escape) {
+            self.stop_slideshow();
+            return true;
+        }
+
+        self.handle_channel_input();
+        self.handle_false_color_input();
+        self.handle_exposure_input();
+
+        // Ctrl+arrows stay with exposure and gamma
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let arrow = |key| !ctrl_down && is_key_pressed(key);
+
+        let Some(slideshow) = self.slideshow.as_mut() else {
+            return true;
+        };
+        if is_key_pressed(KeyCode::Space) {
+            slideshow.next_at = match slideshow.next_at {
+                Some(_) => None,
+                None => Some(Instant::now() + slideshow.interval),
+            };
+            log::info!(
+                "🖼️ Auto-advance {}",
+                if slideshow.next_at.is_some() {
+                    "on"
+                } else {
+                    "off"
+                }
+            );
+        }
+        let step = Duration::from_secs(1);
+        if arrow(KeyCode::Up) {
+            slideshow.interval = (slideshow.interval + step).min(MAX_INTERVAL);
+        }
+        if arrow(KeyCode::Down) {
+            slideshow.interval = slideshow.interval.saturating_sub(step).max(step);
+        }
+
+        let due = slideshow
+            .next_at
+            .is_some_and(|next_at| Instant::now() >= next_at);
+        if due {
+            slideshow.next_at = Some(Instant::now() + slideshow.interval);
+        }
+        if arrow(KeyCode::Right) || due {
+            self.step_slideshow(1);
+        } else if arrow(KeyCode::Left) {
+            self.step_slideshow(-1);
+        }
+
+        // Keep redrawing so the timer fires without input
+        if self.slideshow.as_ref().is_some_and(|s| s.next_at.is_some()) {
+            macroquad::miniquad::window::schedule_update();
+        }
+        true
+    }
+
+    /// Slots the slideshow visits: those the filter shows, in grid order
+    fn slideshow_order(&mut self) -> Vec<usize> {
+        // Focus mode hides everything else, so ask the filter alone
+        self.apply_slot_filter();
+        let shown: Vec<usize> = (0..self.image_slots.len())
+            .filter(|&index| !self.image_slots[index].hidden)
+            .collect();
+        self.apply_focus_mode();
+        self.sorted_slots(&shown)
+    }
+
+    fn start_slideshow(&mut self) {
+        let order = self.slideshow_order();
+        let (mouse_x, mouse_y) = mouse_position();
+        let start = self
+            .focus
+            .as_ref()
+            .map(|focus| focus.slot)
+            .or_else(|| self.slot_at(self.screen_to_world(vec2(mouse_x, mouse_y))))
+            .or_else(|| order.first().copied());
+        let Some(start) = start else {
+            return;
+        };
+
+        self.slideshow = Some(Slideshow {
+            interval: DEFAULT_INTERVAL,
+            next_at: None,
+            was_focused: self.focus.is_some(),
+        });
+        set_fullscreen(true);
+        self.focus_slot(start);
+        log::info!(
+            "🖼️ Slideshow of {} images, F11 or Escape leaves",
+            order.len()
+        );
+    }
+
+    fn stop_slideshow(&mut self) {
+        let Some(slideshow) = self.slideshow.take() else {
+            return;
+        };
+        set_fullscreen(false);
+        if !slideshow.was_focused {
+            self.exit_focus();
+        }
+        self.layout_needs_update = true;
+    }
+
+    /// Move `delta` images along the grid order, wrapping at either end
+    fn step_slideshow(&mut self, delta: isize) {
+        let order = self.slideshow_order();
+        if order.is_empty() {
+            return;
+        }
+        let current = self
+            .focus
+            .as_ref()
+            .and_then(|focus| order.iter().position(|&index| index == focus.slot))
+            .unwrap_or(0);
+        let next = (current as isize + delta).rem_euclid(order.len() as isize) as usize;
+        self.focus_slot(order[next]);
+    }
+
+    /// Position, name and auto-advance state along the bottom edge
+    pub fn draw_slideshow(&self) {
+        let (Some(slideshow), Some(focus)) = (&self.slideshow, &self.focus) else {
+            return;
+        };
+        let Some(slot) = self.image_slots.get(focus.slot) else {
+            return;
+        };
+
+        let name = crate::slot_sort::slot_metadata(slot)
+            .map_or_else(|| "Failed".to_string(), |metadata| metadata.display_path());
+        let timer = match slideshow.next_at {
+            Some(_) => format!("auto {}s", slideshow.interval.as_secs()),
+            None => "paused".to_string(),
+        };
+        let text = format!("{name} · {} · {timer}", self.channel_mode.name());
+
+        let font_size = 16;
+        let width = measure_text(&text, self.ui_font.as_ref(), font_size, 1.0).width;
+        let x = ((screen_width() - width) / 2.0).round();
+        let y = screen_height() - 16.0;
+        draw_rectangle(
+            x - 8.0,
+            y - 19.0,
+            width + 16.0,
+            26.0,
+            Color::new(0.0, 0.0, 0.0, 0.6),
+        );
+        draw_text_ex(
+            &text,
+            x,
+            y,
+            TextParams {
+                font: self.ui_font.as_ref(),
+                font_size,
+                color: WHITE,
+                ..Default::default()
+            },
+        );
+    }
+}
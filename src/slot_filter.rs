@@ -0,0 +1,151 @@
+use macroquad::prelude::*;
+use regex::Regex;
+
+use crate::slot_sort::slot_metadata;
+use crate::texture_pipeline::EmbeddedMetadata;
+use crate::types::GTexViewerApp;
+
+/// `/` filter box narrowing the grid to matching entries
+#[derive(Debug, Default)]
+pub struct SlotFilter {
+    pub query: String,
+    pub editing: bool, // Typing goes into the query
+}
+
+/// Compiled query: globs when it has `*` or `?`, else a case-insensitive substring
+enum NameMatcher {
+    Substring(String),
+    Glob(Regex),
+}
+
+impl NameMatcher {
+    fn new(query: &str) -> Option<Self> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        if !query.contains(['*', '?']) {
+            return Some(NameMatcher::Substring(query.to_lowercase()));
+        }
+
+        let pattern: String = query
+            .chars()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                c => regex::escape(&c.to_string()),
+            })
+            .collect();
+        Regex::new(&format!("(?i)^{pattern}$"))
+            .ok()
+            .map(NameMatcher::Glob)
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            NameMatcher::Substring(query) => text.to_lowercase().contains(query),
+            NameMatcher::Glob(regex) => regex.is_match(text),
+        }
+    }
+
+    /// Entry name, the containers it sits in, or the whole "pack.zip / model.glb / BaseColor" path
+    fn matches_entry(&self, metadata: &EmbeddedMetadata) -> bool {
+        self.matches(&metadata.name)
+            || metadata
+                .parents
+                .iter()
+                .any(|segment| self.matches(&segment.name))
+            || self.matches(&metadata.display_path())
+    }
+}
+
+impl GTexViewerApp {
+    /// `/` opens the filter box; Enter keeps the filter, Escape clears it
+    /// Returns true while typing so other keyboard shortcuts stay inactive
+    pub fn handle_slot_filter_input(&mut self) -> bool {
+        if !self.slot_filter.editing {
+            if !is_key_pressed(KeyCode::Slash) || self.image_slots.is_empty() {
+                return false;
+            }
+            // Drop the "/" typed along with the shortcut
+            while get_char_pressed().is_some() {}
+            self.slot_filter.editing = true;
+            macroquad::miniquad::window::schedule_update();
+            return true;
+        }
+
+        let previous = self.slot_filter.query.clone();
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() {
+                self.slot_filter.query.push(c);
+            }
+        }
+        if is_key_pressed(KeyCode::Backspace) {
+            self.slot_filter.query.pop();
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.slot_filter.query.clear();
+            self.slot_filter.editing = false;
+        } else if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::KpEnter) {
+            self.slot_filter.editing = false;
+            log::info!(
+                "🔍 Filter \"{}\": {} of {} textures",
+                self.slot_filter.query,
+                self.image_slots.iter().filter(|slot| !slot.hidden).count(),
+                self.image_slots.len()
+            );
+        }
+
+        if self.slot_filter.query != previous {
+            self.layout_needs_update = true;
+            self.camera.target = Vec2::ZERO;
+        }
+        macroquad::miniquad::window::schedule_update();
+        true
+    }
+
+    /// Hide slots that don't match the filter; run before laying out
+    pub fn apply_slot_filter(&mut self) {
+        let matcher = NameMatcher::new(&self.slot_filter.query);
+        for slot in &mut self.image_slots {
+            let hidden = match (&matcher, slot_metadata(slot)) {
+                (None, _) => false,
+                (Some(matcher), Some(metadata)) => !matcher.matches_entry(metadata),
+                (Some(_), None) => true,
+            };
+            slot.hidden = hidden;
+        }
+    }
+
+    pub fn draw_slot_filter(&self) {
+        if !self.slot_filter.editing && self.slot_filter.query.is_empty() {
+            return;
+        }
+
+        let shown = self.image_slots.iter().filter(|slot| !slot.hidden).count();
+        let cursor = if self.slot_filter.editing { "_" } else { "" };
+        let text = format!(
+            "Filter: {}{cursor}   {shown}/{} shown",
+            self.slot_filter.query,
+            self.image_slots.len()
+        );
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 16,
+            color: if shown == 0 { ORANGE } else { WHITE },
+            ..Default::default()
+        };
+        let width = measure_text(&text, self.ui_font.as_ref(), 16, 1.0).width + 20.0;
+        let x = ((screen_width() - width) / 2.0).round();
+        // Below the tab strip when it's shown
+        let y = if self.tabs.len() > 1 { 70.0 } else { 40.0 };
+
+        draw_rectangle(x, y, width, 28.0, Color::new(0.0, 0.0, 0.0, 0.85));
+        if self.slot_filter.editing {
+            draw_rectangle_lines(x, y, width, 28.0, 1.0, GRAY);
+        }
+        draw_text_ex(&text, x + 10.0, y + 19.0, text_params);
+    }
+}
@@ -0,0 +1,266 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+use crate::slot_sort::slot_metadata;
+use crate::types::{GTexViewerApp, ImageSlot};
+
+/// Height of a group's header row, in layout pixels
+pub const GROUP_HEADER_HEIGHT: f32 = 24.0;
+/// Edge of the stacked-card placeholder a collapsed group shows, in layout pixels
+pub const GROUP_STACK_SIZE: f32 = 100.0;
+
+/// Cursor travel, in screen pixels, past which a press on a header pans instead of toggling
+const CLICK_SLOP: f32 = 4.0;
+
+/// Slots sharing one source: a container chain, or the folder of loose files
+#[derive(Debug, Clone)]
+pub struct SlotGroup {
+    pub key: String,              // Identifies the source across relayouts
+    pub label: String,            // "pack.zip / model.glb", or "textures/" for a folder
+    pub slots: Vec<usize>,        // Slot indices in grid order
+    pub header: MacroRect,        // World-space header row, set by the layout
+    pub stack: Option<MacroRect>, // World-space stacked card, when collapsed
+}
+
+/// Group key and header label for a slot
+fn slot_source(slot: &ImageSlot) -> (String, String) {
+    let Some(metadata) = slot_metadata(slot) else {
+        return (String::new(), "Failed".to_string());
+    };
+
+    let file_name = metadata
+        .source_path
+        .file_name()
+        .unwrap_or(metadata.source_path.as_os_str())
+        .to_string_lossy()
+        .into_owned();
+
+    // Loose files group by the folder they sit in
+    if metadata.parents.is_empty() && file_name == metadata.name {
+        let folder = metadata
+            .source_path
+            .parent()
+            .unwrap_or(&metadata.source_path);
+        let folder_name = folder.file_name().map_or_else(
+            || folder.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        return (format!("{}/", folder.display()), format!("{folder_name}/"));
+    }
+
+    let mut key = format!("{}:", metadata.source_path.display());
+    let mut label = file_name;
+    for segment in &metadata.parents {
+        key.push_str(&segment.name);
+        key.push('/');
+        label.push_str(" / ");
+        label.push_str(&segment.name);
+    }
+    (key, label)
+}
+
+impl GTexViewerApp {
+    /// Split slots (in grid order) by source, groups ordered by their first slot
+    pub fn group_slots(&self, order: &[usize]) -> Vec<SlotGroup> {
+        let mut groups: Vec<SlotGroup> = Vec::new();
+        let mut by_key: HashMap<String, usize> = HashMap::new();
+
+        for &index in order {
+            let (key, label) = slot_source(&self.image_slots[index]);
+            let group = *by_key.entry(key.clone()).or_insert_with(|| {
+                groups.push(SlotGroup {
+                    key,
+                    label,
+                    slots: Vec::new(),
+                    header: MacroRect::new(0.0, 0.0, 0.0, 0.0),
+                    stack: None,
+                });
+                groups.len() - 1
+            });
+            groups[group].slots.push(index);
+        }
+        groups
+    }
+
+    /// Group whose header or stacked card is at a world-space point
+    fn group_header_at(&self, world: Vec2) -> Option<&SlotGroup> {
+        self.slot_groups.iter().find(|group| {
+            group.header.contains(world) || group.stack.is_some_and(|stack| stack.contains(world))
+        })
+    }
+
+    fn toggle_group(&mut self, key: &str) {
+        let collapsed = !self.collapsed_groups.remove(key);
+        if collapsed {
+            self.collapsed_groups.insert(key.to_string());
+        }
+        if let Some(group) = self.slot_groups.iter().find(|group| group.key == key) {
+            log::info!(
+                "🗂️ {} {}",
+                group.label,
+                if collapsed { "collapsed" } else { "expanded" }
+            );
+        }
+        self.layout_needs_update = true;
+    }
+
+    /// Clicking a header or stacked card toggles its group; Ctrl+G toggles the group under
+    /// the cursor, Ctrl+Shift+G collapses every group (or expands them all when all are collapsed)
+    pub fn handle_group_input(&mut self) {
+        if self.slot_groups.is_empty() {
+            self.group_press = None;
+            return;
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse_screen = vec2(mouse_x, mouse_y);
+        let mouse_world = self.screen_to_world(mouse_screen);
+
+        // Toggle on release so a drag starting on a header still pans
+        if is_mouse_button_pressed(MouseButton::Left) {
+            self.group_press = self
+                .group_header_at(mouse_world)
+                .map(|group| (group.key.clone(), mouse_screen));
+        }
+        if is_mouse_button_released(MouseButton::Left)
+            && let Some((key, pressed_at)) = self.group_press.take()
+            && pressed_at.distance(mouse_screen) <= CLICK_SLOP
+        {
+            self.toggle_group(&key);
+        }
+
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if !(ctrl_down && is_key_pressed(KeyCode::G)) {
+            return;
+        }
+
+        if is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift) {
+            let keys: Vec<String> = self
+                .slot_groups
+                .iter()
+                .map(|group| group.key.clone())
+                .collect();
+            if keys.iter().all(|key| self.collapsed_groups.contains(key)) {
+                self.collapsed_groups.clear();
+                log::info!("🗂️ All groups expanded");
+            } else {
+                self.collapsed_groups.extend(keys);
+                log::info!("🗂️ All groups collapsed");
+            }
+            self.layout_needs_update = true;
+            return;
+        }
+
+        let hovered = self
+            .group_header_at(mouse_world)
+            .or_else(|| {
+                let index = self.slot_at(mouse_world)?;
+                self.slot_groups
+                    .iter()
+                    .find(|group| group.slots.contains(&index))
+            })
+            .map(|group| group.key.clone());
+        if let Some(key) = hovered {
+            self.toggle_group(&key);
+        }
+    }
+
+    /// Source name and count centered on each group's header row, and the stacked card of
+    /// collapsed groups
+    pub fn draw_group_headers(&self) {
+        let text_size = 16;
+        for group in &self.slot_groups {
+            if let Some(stack) = group.stack {
+                self.draw_group_stack(stack, group.slots.len());
+            }
+
+            let top_left = self.world_to_screen(vec2(group.header.x, group.header.y));
+            let bottom_right = self.world_to_screen(vec2(
+                group.header.x + group.header.w,
+                group.header.y + group.header.h,
+            ));
+            let height = bottom_right.y - top_left.y;
+            if bottom_right.y < 0.0 || top_left.y > screen_height() || height < 10.0 {
+                continue;
+            }
+
+            let collapsed = group.stack.is_some();
+            let text = format!(
+                "[{}] {} · {}",
+                if collapsed { "+" } else { "-" },
+                group.label,
+                group.slots.len()
+            );
+            let text_width = measure_text(&text, self.ui_font.as_ref(), text_size, 1.0).width;
+            let center_x = (top_left.x + bottom_right.x) / 2.0;
+            let line_y = bottom_right.y.round() - 0.5;
+
+            draw_line(
+                top_left.x,
+                line_y,
+                bottom_right.x,
+                line_y,
+                1.0,
+                Color::new(1.0, 1.0, 1.0, 0.2),
+            );
+            draw_rectangle(
+                center_x - text_width / 2.0 - 8.0,
+                line_y - height.min(22.0),
+                text_width + 16.0,
+                height.min(22.0),
+                Color::new(0.0, 0.0, 0.0, 0.6),
+            );
+            draw_text_ex(
+                &text,
+                center_x - text_width / 2.0,
+                line_y - 6.0,
+                TextParams {
+                    font: self.ui_font.as_ref(),
+                    font_size: text_size,
+                    color: LIGHTGRAY,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    /// Three offset cards with the texture count, standing in for a collapsed group
+    fn draw_group_stack(&self, stack: MacroRect, count: usize) {
+        let top_left = self.world_to_screen(vec2(stack.x, stack.y));
+        let size = self.world_to_screen(vec2(stack.x + stack.w, stack.y + stack.h)) - top_left;
+        let offset = (size.x * 0.06).max(2.0);
+        let card = size - Vec2::splat(offset * 2.0);
+
+        for layer in (0..3).rev() {
+            let corner = top_left + Vec2::splat(offset * layer as f32);
+            let shade = 0.18 + 0.06 * (2 - layer) as f32;
+            draw_rectangle(
+                corner.x,
+                corner.y,
+                card.x,
+                card.y,
+                Color::new(shade, shade, shade, 1.0),
+            );
+            draw_rectangle_lines(corner.x, corner.y, card.x, card.y, 1.0, GRAY);
+        }
+
+        let text = format!("{count} textures");
+        let font_size = (card.y * 0.16).clamp(8.0, 24.0) as u16;
+        let text_width = measure_text(&text, self.ui_font.as_ref(), font_size, 1.0).width;
+        draw_text_ex(
+            &text,
+            top_left.x + (card.x - text_width) / 2.0,
+            top_left.y + card.y / 2.0,
+            TextParams {
+                font: self.ui_font.as_ref(),
+                font_size,
+                color: WHITE,
+                ..Default::default()
+            },
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use macroquad::prelude::*;
+use std::cmp::Reverse;
+
+use crate::texture_pipeline::EmbeddedMetadata;
+use crate::types::{GTexViewerApp, ImageSlot, ImageState};
+
+/// Order of slots in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    #[default]
+    LoadOrder, // As discovered
+    Name,       // Full "pack.zip / model.glb / BaseColor" path, case-insensitive
+    FileSize,   // Largest first
+    Dimensions, // Most pixels first
+    Format,     // Grouped by format, then by name
+}
+
+impl SortMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            SortMode::LoadOrder => "Load order",
+            SortMode::Name => "Name",
+            SortMode::FileSize => "File size",
+            SortMode::Dimensions => "Dimensions",
+            SortMode::Format => "Format",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::LoadOrder => SortMode::Name,
+            SortMode::Name => SortMode::FileSize,
+            SortMode::FileSize => SortMode::Dimensions,
+            SortMode::Dimensions => SortMode::Format,
+            SortMode::Format => SortMode::LoadOrder,
+        }
+    }
+}
+
+/// Metadata the slot was created from; failed slots may have none
+pub(crate) fn slot_metadata(slot: &ImageSlot) -> Option<&EmbeddedMetadata> {
+    match &slot.state {
+        ImageState::Loaded { image } => Some(&image.metadata),
+        ImageState::Placeholder {
+            original_metadata, ..
+        } => Some(original_metadata),
+        ImageState::Failed { metadata, .. } => metadata.as_ref(),
+    }
+}
+
+/// Pixel count, from the decoded image once loaded
+fn slot_pixels(slot: &ImageSlot) -> u64 {
+    match &slot.state {
+        ImageState::Loaded { image } => image.info.width as u64 * image.info.height as u64,
+        _ => {
+            slot_metadata(slot).map_or(0, |metadata| metadata.width as u64 * metadata.height as u64)
+        }
+    }
+}
+
+impl GTexViewerApp {
+    /// Shift+R cycles the grid sort order
+    pub fn handle_sort_input(&mut self) {
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if !(shift_down && is_key_pressed(KeyCode::R)) {
+            return;
+        }
+
+        self.sort_mode = self.sort_mode.next();
+        self.layout_needs_update = true;
+        log::info!("🔀 Sort: {}", self.sort_mode.name());
+    }
+
+    /// Slot indices in grid order; ties and slots without metadata keep load order,
+    /// the latter placed last
+    pub fn sorted_slots(&self, indices: &[usize]) -> Vec<usize> {
+        let mut sorted = indices.to_vec();
+        let slot = |index: usize| &self.image_slots[index];
+        let missing = |index: usize| slot_metadata(slot(index)).is_none();
+        let name = |index: usize| {
+            slot_metadata(slot(index)).map(|metadata| metadata.display_path().to_lowercase())
+        };
+
+        match self.sort_mode {
+            SortMode::LoadOrder => return sorted,
+            SortMode::Name => sorted.sort_by_cached_key(|&index| (missing(index), name(index))),
+            SortMode::FileSize => sorted.sort_by_cached_key(|&index| {
+                (
+                    missing(index),
+                    Reverse(slot_metadata(slot(index)).map_or(0, |metadata| metadata.file_size)),
+                )
+            }),
+            SortMode::Dimensions => sorted
+                .sort_by_cached_key(|&index| (missing(index), Reverse(slot_pixels(slot(index))))),
+            SortMode::Format => sorted.sort_by_cached_key(|&index| {
+                (
+                    missing(index),
+                    slot_metadata(slot(index)).map(|metadata| format!("{:?}", metadata.format)),
+                    name(index),
+                )
+            }),
+        }
+        sorted
+    }
+}
@@ -0,0 +1,253 @@
+use macroquad::miniquad::{
+    RawId, TextureAccess, TextureFormat, TextureId, TextureParams, TextureSource, gl,
+};
+use macroquad::prelude::*;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::sampling::OwnedTexture;
+use crate::texture_pipeline::ImageInfo;
+use crate::types::GTexViewerApp;
+
+const GL_SRGB8_ALPHA8: u32 = 0x8C43;
+const GL_TEXTURE_BINDING_2D: u32 = 0x8069;
+// EXT_texture_sRGB_decode
+const GL_TEXTURE_SRGB_DECODE_EXT: u32 = 0x8A48;
+const GL_DECODE_EXT: u32 = 0x8A49;
+const GL_SKIP_DECODE_EXT: u32 = 0x8A4A;
+
+/// File name suffixes of maps holding data rather than colour (normals, heights, masks)
+const DATA_MAP_SUFFIXES: &[&str] = &[
+    "n",
+    "nrm",
+    "nor",
+    "norm",
+    "normal",
+    "normals",
+    "height",
+    "disp",
+    "displacement",
+    "bump",
+    "rough",
+    "roughness",
+    "metal",
+    "metallic",
+    "metalness",
+    "gloss",
+    "glossiness",
+    "ao",
+    "occlusion",
+    "orm",
+    "arm",
+    "rma",
+    "mask",
+];
+
+/// Whether the driver accepts sRGB textures, settled by the first upload
+static SRGB_SUPPORTED: OnceLock<bool> = OnceLock::new();
+/// Whether sRGB textures can skip decoding, settled by the first switch
+static SRGB_DECODE_CONTROL: OnceLock<bool> = OnceLock::new();
+
+/// GL names of the textures stored as sRGB, so draws know to encode them for display
+fn srgb_textures() -> &'static Mutex<HashSet<u32>> {
+    static TEXTURES: OnceLock<Mutex<HashSet<u32>>> = OnceLock::new();
+    TEXTURES.get_or_init(Default::default)
+}
+
+fn raw_texture_id(id: TextureId) -> Option<u32> {
+    let context = unsafe { get_internal_gl() }.quad_context;
+    let RawId::OpenGl(raw_id) = (unsafe { context.texture_raw_id(id) }) else {
+        return None;
+    };
+    Some(raw_id)
+}
+
+/// Clear errors left by earlier GL calls, so the next `glGetError` reports only what follows
+fn drain_gl_errors() {
+    // Bounded, since a lost context keeps reporting an error
+    for _ in 0..16 {
+        if unsafe { gl::glGetError() } == 0 {
+            break;
+        }
+    }
+}
+
+/// Whether an image holds colour, and so is worth storing as sRGB
+/// HDR images and data maps (normals, heights, masks), named by the usual suffixes,
+/// keep their stored values
+pub fn stores_color(name: &str, info: &ImageInfo) -> bool {
+    if info.hdr_headroom > 1.0 {
+        return false;
+    }
+    // One- and two-channel block formats hold masks and normals
+    if ["BC4", "BC5", "EAC"]
+        .iter()
+        .any(|format| info.color_space.starts_with(format))
+    {
+        return false;
+    }
+
+    let stem = Path::new(name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(name)
+        .to_ascii_lowercase();
+    let suffix = stem.rsplit(['_', '-', '.', ' ']).next().unwrap_or(&stem);
+    !DATA_MAP_SUFFIXES.contains(&suffix)
+}
+
+/// Upload colour as an SRGB8_ALPHA8 texture with a full mip chain, so the GPU decodes
+/// it to linear light before filtering and mip generation
+/// None on drivers without sRGB textures, where callers upload RGBA8 instead
+/// The returned owner must be kept alongside the texture; dropping it frees the VRAM
+pub fn upload_srgb(image: &Image) -> Option<(Texture2D, Arc<OwnedTexture>)> {
+    if SRGB_SUPPORTED.get() == Some(&false) {
+        return None;
+    }
+
+    // Allocated empty so the pixels are only sent once, as sRGB
+    let context = unsafe { get_internal_gl() }.quad_context;
+    let id = context.new_texture(
+        TextureAccess::Static,
+        TextureSource::Empty,
+        TextureParams {
+            width: image.width as u32,
+            height: image.height as u32,
+            format: TextureFormat::RGBA8,
+            ..Default::default()
+        },
+    );
+    let RawId::OpenGl(raw_id) = (unsafe { context.texture_raw_id(id) }) else {
+        context.delete_texture(id);
+        return None;
+    };
+
+    // Bind around miniquad's state cache and restore the previous binding afterwards
+    let error = unsafe {
+        drain_gl_errors();
+        let mut previous = 0;
+        gl::glGetIntegerv(GL_TEXTURE_BINDING_2D, &mut previous);
+        gl::glBindTexture(gl::GL_TEXTURE_2D, raw_id);
+        gl::glTexImage2D(
+            gl::GL_TEXTURE_2D,
+            0,
+            GL_SRGB8_ALPHA8 as i32,
+            image.width as i32,
+            image.height as i32,
+            0,
+            gl::GL_RGBA,
+            gl::GL_UNSIGNED_BYTE,
+            image.bytes.as_ptr() as *const _,
+        );
+        let error = gl::glGetError();
+        gl::glBindTexture(gl::GL_TEXTURE_2D, previous as u32);
+        error
+    };
+
+    let supported = *SRGB_SUPPORTED.get_or_init(|| {
+        let supported = error == 0;
+        if supported {
+            log::info!("🎨 sRGB textures supported, filtering colour in linear light");
+        } else {
+            log::warn!("⚠️ sRGB textures unsupported (GL error {error:#x}), storing RGBA8");
+        }
+        supported
+    });
+    if error != 0 {
+        if supported {
+            log::warn!("⚠️ sRGB upload failed (GL error {error:#x}), storing RGBA8");
+        }
+        context.delete_texture(id);
+        return None;
+    }
+
+    context.texture_generate_mipmaps(id);
+    if let Ok(mut textures) = srgb_textures().lock() {
+        textures.insert(raw_id);
+    }
+    Some((
+        Texture2D::from_miniquad_texture(id),
+        Arc::new(OwnedTexture(id)),
+    ))
+}
+
+/// Whether a texture was stored as sRGB by `upload_srgb`
+pub fn is_srgb_texture(texture: &Texture2D) -> bool {
+    raw_texture_id(texture.raw_miniquad_id()).is_some_and(|raw_id| {
+        srgb_textures()
+            .lock()
+            .is_ok_and(|textures| textures.contains(&raw_id))
+    })
+}
+
+/// Drop a deleted texture from the sRGB set, so a texture reusing its name isn't
+/// mistaken for sRGB
+pub fn forget_texture(id: TextureId) {
+    if let Some(raw_id) = raw_texture_id(id)
+        && let Ok(mut textures) = srgb_textures().lock()
+    {
+        textures.remove(&raw_id);
+    }
+}
+
+/// Switch whether sampling an sRGB texture decodes it to linear light; skipping lets the
+/// default material draw the stored values
+/// Returns whether the texture decodes afterwards, which it always does on drivers
+/// without EXT_texture_sRGB_decode
+pub fn set_srgb_decode(texture: &Texture2D, decode: bool) -> bool {
+    if SRGB_DECODE_CONTROL.get() == Some(&false) {
+        return true;
+    }
+    let Some(raw_id) = raw_texture_id(texture.raw_miniquad_id()) else {
+        return true;
+    };
+
+    let probing = SRGB_DECODE_CONTROL.get().is_none();
+    let mode = if decode {
+        GL_DECODE_EXT
+    } else {
+        GL_SKIP_DECODE_EXT
+    };
+    let error = unsafe {
+        if probing {
+            drain_gl_errors();
+        }
+        let mut previous = 0;
+        gl::glGetIntegerv(GL_TEXTURE_BINDING_2D, &mut previous);
+        gl::glBindTexture(gl::GL_TEXTURE_2D, raw_id);
+        gl::glTexParameteri(gl::GL_TEXTURE_2D, GL_TEXTURE_SRGB_DECODE_EXT, mode as i32);
+        let error = if probing { gl::glGetError() } else { 0 };
+        gl::glBindTexture(gl::GL_TEXTURE_2D, previous as u32);
+        error
+    };
+
+    let supported = *SRGB_DECODE_CONTROL.get_or_init(|| {
+        let supported = error == 0;
+        if !supported {
+            log::info!("🎨 sRGB decoding can't be skipped, sRGB textures use the display shader");
+        }
+        supported
+    });
+    decode || !supported
+}
+
+impl GTexViewerApp {
+    /// Alt+V switches between colour-managed (linear-light) and raw stored values
+    pub fn handle_color_management_input(&mut self) {
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        if !alt_down || !is_key_pressed(KeyCode::V) {
+            return;
+        }
+
+        self.color_managed = !self.color_managed;
+        log::info!(
+            "🎨 {}",
+            if self.color_managed {
+                "Colour-managed output"
+            } else {
+                "Raw output"
+            }
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use macroquad::prelude::*;
+
+use crate::analysis::streaming::format_bytes;
+use crate::color_vision::ColorVision;
+use crate::export::world_to_pixel;
+use crate::false_color::FalseColor;
+use crate::slot_sort::SortMode;
+use crate::texture_cache::image_vram_bytes;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Height of the bar along the bottom edge; bottom-docked panels stack above it
+pub const STATUS_BAR_HEIGHT: f32 = 24.0;
+
+const SEGMENT_PADDING: f32 = 10.0;
+const STATUS_FONT_SIZE: u16 = 14;
+
+impl GTexViewerApp {
+    /// Display state: image count, channel and view, exposure, filtering and sort
+    fn display_segments(&self) -> Vec<String> {
+        let loaded_count = self
+            .image_slots
+            .iter()
+            .filter(|slot| matches!(slot.state, ImageState::Loaded { .. }))
+            .count();
+
+        let mut mode = self.channel_mode.name().to_string();
+        match self.active_false_color() {
+            FalseColor::Off => {}
+            map => mode.push_str(&format!(" · {}", map.name())),
+        }
+        if self.surface_preview.is_active() {
+            mode.push_str(&format!(" · {}", self.surface_preview.mode.name()));
+        }
+
+        let mut view = self
+            .current_display_view()
+            .map(|view| view.name.clone())
+            .unwrap_or_else(|| "sRGB".to_string());
+        if !self.color_managed {
+            view.push_str(" (raw)");
+        }
+        match self.color_vision {
+            ColorVision::Normal => {}
+            vision => view.push_str(&format!(" · {}", vision.name())),
+        }
+        if let Some(lut) = self.active_lut() {
+            view.push_str(&format!(" · LUT {}", lut.name));
+        }
+
+        let mut segments = vec![
+            format!("Images: {loaded_count}/{}", self.image_slots.len()),
+            format!("Mode: {mode}"),
+            format!("View: {view}"),
+        ];
+        if !self.exposure_gamma.is_identity() {
+            segments.push(format!("Exposure: {}", self.exposure_gamma.label()));
+        }
+        segments.push(format!("Filter: {}", self.filter_override.name()));
+        if self.sort_mode != SortMode::LoadOrder {
+            segments.push(format!("Sort: {}", self.sort_mode.name()));
+        }
+        segments
+    }
+
+    /// Cursor position in world and texel space, zoom, selection and texture memory
+    fn cursor_segments(&self) -> Vec<String> {
+        let (mouse_x, mouse_y) = mouse_position();
+        let world = self.screen_to_world(vec2(mouse_x, mouse_y));
+
+        let mut segments = vec![format!("World: {:.3}, {:.3}", world.x, world.y)];
+        if let Some(index) = self.slot_at(world)
+            && let ImageState::Loaded { image } = &self.image_slots[index].state
+        {
+            let pixel = world_to_pixel(&self.image_slots[index], image, world).floor();
+            segments.push(format!("Pixel: {}, {}", pixel.x as u32, pixel.y as u32));
+        }
+        segments.push(format!("Zoom: {:.0}%", self.camera.zoom.x * 100.0));
+        if !self.selected_slots.is_empty() {
+            segments.push(format!("Selected: {}", self.selected_slots.len()));
+        }
+
+        // Expanded frames share their animation's textures, so count each image once
+        let used: u64 = self
+            .image_slots
+            .iter()
+            .filter_map(|slot| match &slot.state {
+                ImageState::Loaded { image } if image.expanded_frame.is_none() => {
+                    Some(image_vram_bytes(image))
+                }
+                _ => None,
+            })
+            .sum();
+        segments.push(format!(
+            "VRAM: {} / {}",
+            format_bytes(used),
+            format_bytes(self.texture_cache.budget_bytes)
+        ));
+        segments
+    }
+
+    /// Full-width bar along the bottom: display state on the left, cursor readouts on the right
+    pub fn draw_status_bar(&self) {
+        if self.image_slots.is_empty() {
+            return;
+        }
+
+        let top = screen_height() - STATUS_BAR_HEIGHT;
+        draw_rectangle(
+            0.0,
+            top,
+            screen_width(),
+            STATUS_BAR_HEIGHT,
+            Color::new(0.0, 0.0, 0.0, 0.8),
+        );
+        draw_line(
+            0.0,
+            top,
+            screen_width(),
+            top,
+            1.0,
+            Color::new(1.0, 1.0, 1.0, 0.15),
+        );
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: STATUS_FONT_SIZE,
+            color: WHITE,
+            ..Default::default()
+        };
+        let baseline = (top + 17.0).round();
+        let width =
+            |text: &str| measure_text(text, self.ui_font.as_ref(), STATUS_FONT_SIZE, 1.0).width;
+        let divider = |x: f32| {
+            draw_line(
+                x,
+                top + 5.0,
+                x,
+                top + STATUS_BAR_HEIGHT - 5.0,
+                1.0,
+                DARKGRAY,
+            )
+        };
+
+        let mut x = SEGMENT_PADDING;
+        for (i, segment) in self.display_segments().iter().enumerate() {
+            if i > 0 {
+                divider(x - SEGMENT_PADDING);
+            }
+            draw_text_ex(segment, x, baseline, text_params.clone());
+            x += width(segment) + SEGMENT_PADDING * 2.0;
+        }
+
+        // Right-aligned, so walk from the edge inward
+        let mut right = screen_width() - SEGMENT_PADDING;
+        for (i, segment) in self.cursor_segments().iter().rev().enumerate() {
+            if i > 0 {
+                divider(right + SEGMENT_PADDING);
+            }
+            let segment_width = width(segment);
+            draw_text_ex(
+                segment,
+                (right - segment_width).round(),
+                baseline,
+                TextParams {
+                    color: LIGHTGRAY,
+                    ..text_params.clone()
+                },
+            );
+            right -= segment_width + SEGMENT_PADDING * 2.0;
+        }
+    }
+}
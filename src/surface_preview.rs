@@ -0,0 +1,131 @@
+use macroquad::prelude::*;
+
+use crate::types::GTexViewerApp;
+
+/// How the texture is interpreted as a lit surface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceMode {
+    Off,
+    NormalMap, // Tangent-space normals shading a flat surface
+    Heightmap, // Red channel as height, hillshaded from its slope
+}
+
+impl SurfaceMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            SurfaceMode::Off => "Off",
+            SurfaceMode::NormalMap => "Normal map",
+            SurfaceMode::Heightmap => "Heightmap relief",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            SurfaceMode::Off => SurfaceMode::NormalMap,
+            SurfaceMode::NormalMap => SurfaceMode::Heightmap,
+            SurfaceMode::Heightmap => SurfaceMode::Off,
+        }
+    }
+
+    pub fn shader_index(self) -> i32 {
+        match self {
+            SurfaceMode::Off => 0,
+            SurfaceMode::NormalMap => 1,
+            SurfaceMode::Heightmap => 2,
+        }
+    }
+}
+
+/// Lit-surface interpretation of the texture for validating material maps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfacePreview {
+    pub mode: SurfaceMode,
+    pub flip_green: bool, // DirectX (Y-down) convention instead of OpenGL (Y-up)
+    pub light: Vec3,      // Unit direction towards the light, +Y up the screen, +Z out of it
+    pub relief_scale: f32, // Full height range as a fraction of the texture width
+}
+
+impl Default for SurfacePreview {
+    fn default() -> Self {
+        Self {
+            mode: SurfaceMode::Off,
+            flip_green: false,
+            light: vec3(-0.5, 0.5, 0.707).normalize(),
+            relief_scale: 0.05,
+        }
+    }
+}
+
+impl SurfacePreview {
+    pub fn is_active(&self) -> bool {
+        self.mode != SurfaceMode::Off
+    }
+
+    /// Light from a screen position: the window centre lights head-on, the edges graze
+    pub fn light_from_screen(position: Vec2) -> Vec3 {
+        let center = vec2(screen_width(), screen_height()) / 2.0;
+        let radius = center.min_element().max(1.0);
+        let offset = (position - center) / radius;
+        let xy = vec2(offset.x, -offset.y).clamp_length_max(0.97);
+        vec3(xy.x, xy.y, (1.0 - xy.length_squared()).sqrt()).normalize()
+    }
+}
+
+impl GTexViewerApp {
+    /// N cycles off / normal map / heightmap relief, Shift+N flips the green channel
+    /// convention, right-drag moves the light while a mode is on, and Up/Down
+    /// exaggerate or flatten the relief
+    pub fn handle_surface_preview_input(&mut self) {
+        let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        // Alt+N toggles captions
+        let alt = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+
+        if is_key_pressed(KeyCode::N) && !alt {
+            if shift {
+                self.surface_preview.flip_green = !self.surface_preview.flip_green;
+                log::info!(
+                    "💡 Normal map green channel: {}",
+                    if self.surface_preview.flip_green {
+                        "DirectX (Y-)"
+                    } else {
+                        "OpenGL (Y+)"
+                    }
+                );
+            } else {
+                self.surface_preview.mode = self.surface_preview.mode.next();
+                log::info!("💡 Surface preview: {}", self.surface_preview.mode.name());
+            }
+        }
+
+        if !self.surface_preview.is_active() {
+            return;
+        }
+
+        if is_mouse_button_down(MouseButton::Right) {
+            let (x, y) = mouse_position();
+            self.surface_preview.light = SurfacePreview::light_from_screen(vec2(x, y));
+            macroquad::miniquad::window::schedule_update();
+        }
+
+        // Ctrl+Up/Down adjust exposure instead
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if self.surface_preview.mode == SurfaceMode::Heightmap && !ctrl_down {
+            let factor = if is_key_pressed(KeyCode::Up) {
+                1.25
+            } else if is_key_pressed(KeyCode::Down) {
+                0.8
+            } else {
+                return;
+            };
+            self.surface_preview.relief_scale =
+                (self.surface_preview.relief_scale * factor).clamp(0.001, 1.0);
+            log::info!(
+                "💡 Relief height: {:.1}% of the width",
+                self.surface_preview.relief_scale * 100.0
+            );
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Most images shown side by side
+const MAX_PANES: usize = 4;
+
+/// Side-by-side view of selected images sharing one zoom and pan, in image-relative
+/// coordinates so variants at different resolutions line up texel for texel
+pub struct SyncCompare {
+    pub slots: Vec<usize>,
+    pub center: Vec2,        // UV at the middle of every pane
+    pub zoom: f32,           // Image width as a multiple of the pane width
+    drag_from: Option<Vec2>, // Cursor position at the last frame of a pan
+}
+
+impl SyncCompare {
+    /// Screen rect of each pane, left to right
+    fn panes(&self) -> Vec<MacroRect> {
+        let width = screen_width() / self.slots.len() as f32;
+        (0..self.slots.len())
+            .map(|i| MacroRect::new(i as f32 * width, 0.0, width, screen_height()))
+            .collect()
+    }
+
+    /// Screen pixels per UV unit across and down, for an image of the given height/width ratio
+    fn uv_scale(&self, pane: MacroRect, aspect: f32) -> Vec2 {
+        let across = self.zoom * pane.w;
+        vec2(across, across * aspect)
+    }
+}
+
+impl GTexViewerApp {
+    /// Height/width ratio of a slot as laid out
+    fn slot_aspect(&self, index: usize) -> f32 {
+        self.image_slots
+            .get(index)
+            .map_or(1.0, |slot| slot.size.y / slot.size.x.max(f32::EPSILON))
+    }
+
+    /// Ctrl+D opens the synchronized compare of the selected images; while open, scrolling
+    /// zooms and dragging pans every pane, Escape or Ctrl+D close it, and channel, false
+    /// colour and exposure keys still apply. Returns true while open so other input stays inactive
+    pub fn handle_sync_compare_input(&mut self) -> bool {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let toggle = ctrl_down && is_key_pressed(KeyCode::D);
+
+        if self.sync_compare.is_none() {
+            if toggle {
+                self.start_sync_compare();
+            }
+            return false;
+        }
+        if toggle || is_key_pressed(KeyCode::Escape) {
+            self.sync_compare = None;
+            return true;
+        }
+
+        self.handle_channel_input();
+        self.handle_false_color_input();
+        self.handle_exposure_input();
+
+        let aspects: Vec<f32> = self
+            .sync_compare
+            .as_ref()
+            .map(|compare| compare.slots.iter().map(|&i| self.slot_aspect(i)).collect())
+            .unwrap_or_default();
+        let Some(compare) = self.sync_compare.as_mut() else {
+            return true;
+        };
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse = vec2(mouse_x, mouse_y);
+        let panes = compare.panes();
+        let Some(hovered) = panes.iter().position(|pane| pane.contains(mouse)) else {
+            return true;
+        };
+        let pane = panes[hovered];
+
+        // Zoom around the cursor: the UV under it stays put in every pane
+        let wheel = mouse_wheel().1;
+        if wheel != 0.0 {
+            let offset = mouse - pane.center();
+            let uv = compare.center + offset / compare.uv_scale(pane, aspects[hovered]);
+            compare.zoom = (compare.zoom * 1.015_f32.powf(wheel)).clamp(0.05, 10_000.0);
+            compare.center = uv - offset / compare.uv_scale(pane, aspects[hovered]);
+        }
+
+        if is_mouse_button_down(MouseButton::Left) {
+            if let Some(from) = compare.drag_from {
+                compare.center -= (mouse - from) / compare.uv_scale(pane, aspects[hovered]);
+            }
+            compare.drag_from = Some(mouse);
+        } else {
+            compare.drag_from = None;
+        }
+
+        macroquad::miniquad::window::schedule_update();
+        true
+    }
+
+    fn start_sync_compare(&mut self) {
+        let slots: Vec<usize> = self
+            .selected_slots
+            .iter()
+            .copied()
+            .filter(|&index| {
+                self.image_slots.get(index).is_some_and(|slot| {
+                    !slot.hidden && matches!(slot.state, ImageState::Loaded { .. })
+                })
+            })
+            .take(MAX_PANES)
+            .collect();
+        if slots.len() < 2 {
+            log::info!("🪞 Ctrl+click two or more loaded images to compare, then press Ctrl+D");
+            return;
+        }
+
+        // Fit the tallest image in its pane
+        let pane = vec2(screen_width() / slots.len() as f32, screen_height());
+        let tallest = slots
+            .iter()
+            .map(|&index| self.slot_aspect(index))
+            .fold(0.0, f32::max);
+        let zoom = 0.9_f32.min(0.9 * pane.y / (pane.x * tallest));
+
+        log::info!("🪞 Comparing {} images side by side", slots.len());
+        self.sync_compare = Some(SyncCompare {
+            slots,
+            center: vec2(0.5, 0.5),
+            zoom,
+            drag_from: None,
+        });
+    }
+
+    /// Each compared image in its own viewport, through the regular display pipeline
+    pub fn draw_sync_compare(&mut self) {
+        let Some(compare) = self.sync_compare.as_ref() else {
+            return;
+        };
+        let slots = compare.slots.clone();
+        let panes = compare.panes();
+        let (center, zoom) = (compare.center, compare.zoom);
+        let dpi = macroquad::miniquad::window::dpi_scale();
+        let window_camera = (self.camera.target, self.camera.zoom);
+
+        for (&index, pane) in slots.iter().zip(&panes) {
+            let Some(slot) = self.image_slots.get(index) else {
+                continue;
+            };
+            let pixels_per_world = zoom * pane.w / slot.size.x.max(f32::EPSILON);
+            let target = slot.position + center * slot.size;
+            let region = MacroRect::new(
+                target.x - pane.w / 2.0 / pixels_per_world,
+                target.y - pane.h / 2.0 / pixels_per_world,
+                pane.w / pixels_per_world,
+                pane.h / pixels_per_world,
+            );
+
+            // The app camera drives per-slot filtering, so match it to this pane
+            self.camera.target = target;
+            self.camera.zoom = Vec2::splat(pixels_per_world * 2.0 / screen_width());
+            set_camera(&Camera2D {
+                target,
+                zoom: vec2(
+                    pixels_per_world * 2.0 / pane.w,
+                    pixels_per_world * 2.0 / pane.h,
+                ),
+                viewport: Some((
+                    (pane.x * dpi) as i32,
+                    0,
+                    (pane.w * dpi) as i32,
+                    (pane.h * dpi) as i32,
+                )),
+                ..Default::default()
+            });
+            self.draw_slots_within(Some(region), Some(index));
+        }
+
+        set_default_camera();
+        (self.camera.target, self.camera.zoom) = window_camera;
+        self.ui_text_queue.clear();
+
+        let text_params = TextParams {
+            font: self.ui_font.as_ref(),
+            font_size: 16,
+            color: WHITE,
+            ..Default::default()
+        };
+        for (&index, pane) in slots.iter().zip(&panes) {
+            if pane.x > 0.0 {
+                draw_line(pane.x, 0.0, pane.x, pane.h, 2.0, DARKGRAY);
+            }
+            let name = match self.image_slots.get(index).map(|slot| &slot.state) {
+                Some(ImageState::Loaded { image }) => image.metadata.display_path(),
+                _ => continue,
+            };
+            let width = measure_text(&name, self.ui_font.as_ref(), 16, 1.0).width;
+            draw_rectangle(
+                pane.x + 8.0,
+                8.0,
+                width + 16.0,
+                26.0,
+                Color::new(0.0, 0.0, 0.0, 0.7),
+            );
+            draw_text_ex(&name, pane.x + 16.0, 27.0, text_params.clone());
+        }
+
+        let hint = format!(
+            "Synced compare · {:.0}% · scroll zooms, drag pans · Esc closes",
+            zoom * 100.0
+        );
+        let width = measure_text(&hint, self.ui_font.as_ref(), 16, 1.0).width;
+        let x = ((screen_width() - width) / 2.0).round();
+        draw_rectangle(
+            x - 8.0,
+            screen_height() - 34.0,
+            width + 16.0,
+            26.0,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+        draw_text_ex(&hint, x, screen_height() - 15.0, text_params);
+    }
+}
@@ -0,0 +1,250 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use crate::loading::{ContainerWatcher, DroppedRoot};
+use crate::slot_filter::SlotFilter;
+use crate::slot_sort::SortMode;
+use crate::texture_cache::TextureCache;
+use crate::types::{ChannelMode, GTexViewerApp, ImageSlot, ImageState};
+
+const TAB_HEIGHT: f32 = 24.0;
+const TAB_MAX_WIDTH: f32 = 180.0;
+/// Top of the tab strip
+const TAB_STRIP_Y: f32 = 5.0;
+
+/// Slots and view of one tab; the active tab's live in `GTexViewerApp`, the others here
+#[derive(Default)]
+pub struct Session {
+    image_slots: Vec<ImageSlot>,
+    camera: Camera2D,
+    channel_mode: ChannelMode,
+    dropped_roots: Vec<DroppedRoot>,
+    container_watcher: Option<ContainerWatcher>,
+    content_bounds: MacroRect,
+    loading_completed_once: bool,
+    texture_cache: Option<TextureCache>, // Created on first use so every tab reads the budget alike
+    full_resolution_pending: HashSet<String>,
+    placeholder_previews: HashMap<String, Texture2D>,
+    slot_filter: SlotFilter,
+    sort_mode: SortMode,
+    collapsed_groups: HashSet<String>,
+    selected_slots: Vec<usize>,
+}
+
+/// Tab label: the first dropped path's file name and the image count
+fn session_title(dropped_roots: &[DroppedRoot], slot_count: usize) -> String {
+    let name = dropped_roots
+        .first()
+        .and_then(|root| root.path.file_name())
+        .map_or_else(
+            || "Empty".to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+    match dropped_roots.len() {
+        0 | 1 => format!("{name} ({slot_count})"),
+        more => format!("{name} +{} ({slot_count})", more - 1),
+    }
+}
+
+impl GTexViewerApp {
+    /// Exchange the app's per-tab state with `session`
+    fn swap_session(&mut self, session: &mut Session) {
+        let cache = session
+            .texture_cache
+            .get_or_insert_with(TextureCache::from_env);
+        mem::swap(&mut self.texture_cache, cache);
+        mem::swap(&mut self.image_slots, &mut session.image_slots);
+        mem::swap(&mut self.camera, &mut session.camera);
+        mem::swap(&mut self.channel_mode, &mut session.channel_mode);
+        mem::swap(&mut self.dropped_roots, &mut session.dropped_roots);
+        mem::swap(&mut self.container_watcher, &mut session.container_watcher);
+        mem::swap(&mut self.content_bounds, &mut session.content_bounds);
+        mem::swap(
+            &mut self.loading_completed_once,
+            &mut session.loading_completed_once,
+        );
+        mem::swap(
+            &mut self.full_resolution_pending,
+            &mut session.full_resolution_pending,
+        );
+        mem::swap(
+            &mut self.placeholder_previews,
+            &mut session.placeholder_previews,
+        );
+        mem::swap(&mut self.slot_filter, &mut session.slot_filter);
+        mem::swap(&mut self.sort_mode, &mut session.sort_mode);
+        mem::swap(&mut self.collapsed_groups, &mut session.collapsed_groups);
+        mem::swap(&mut self.selected_slots, &mut session.selected_slots);
+    }
+
+    /// Slots of the tabs in the background
+    pub fn parked_slots(&self) -> impl Iterator<Item = &ImageSlot> {
+        self.tabs.iter().flat_map(|session| &session.image_slots)
+    }
+
+    /// Loader results are matched to slots by entry key, so a tab can't be left mid-load
+    /// Evicted placeholders only load once scrolled back into view, so they don't count
+    fn session_busy(&self) -> bool {
+        self.is_loading
+            || self.directory_scan.is_some()
+            || !self.metadata_receivers.is_empty()
+            || !self.full_resolution_pending.is_empty()
+            || self.image_slots.iter().any(|slot| match &slot.state {
+                ImageState::Placeholder {
+                    original_metadata, ..
+                } => !self
+                    .texture_cache
+                    .is_evicted(&original_metadata.entry_key()),
+                _ => false,
+            })
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        if self.session_busy() {
+            log::info!("🗂️ Wait for this tab to finish loading before switching");
+            return;
+        }
+
+        // Park the active tab's state in its entry, then take the target's
+        let mut tabs = mem::take(&mut self.tabs);
+        self.swap_session(&mut tabs[self.active_tab]);
+        self.swap_session(&mut tabs[index]);
+        self.tabs = tabs;
+        self.active_tab = index;
+
+        // Slot indices and overlays of the old tab don't carry over
+        self.highlighted_slots.clear();
+        self.animation_focus = None;
+        self.focus = None;
+        self.sync_compare = None;
+        self.pixel_diff = None;
+        self.pixel_diff_pending = None;
+        self.pixel_probe = Default::default();
+        self.slot_groups.clear();
+        self.layout_needs_update = true;
+        log::info!("🗂️ Tab {} of {}", index + 1, self.tabs.len());
+    }
+
+    fn open_tab(&mut self) {
+        if self.session_busy() {
+            log::info!("🗂️ Wait for this tab to finish loading before opening another");
+            return;
+        }
+        self.tabs.push(Session::default());
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    fn close_tab(&mut self) {
+        if self.tabs.len() < 2 {
+            return;
+        }
+        let closing = self.active_tab;
+        self.cancel_all_loading();
+        self.switch_tab(if closing == 0 { 1 } else { closing - 1 });
+        self.tabs.remove(closing);
+        if self.active_tab > closing {
+            self.active_tab -= 1;
+        }
+        self.release_unused_mounts();
+    }
+
+    /// Screen rect of each tab in the strip, empty while there's only one tab
+    fn tab_rects(&self) -> Vec<MacroRect> {
+        if self.tabs.len() < 2 {
+            return Vec::new();
+        }
+        let width = ((screen_width() - 10.0) / self.tabs.len() as f32).min(TAB_MAX_WIDTH);
+        (0..self.tabs.len())
+            .map(|i| MacroRect::new(5.0 + i as f32 * width, TAB_STRIP_Y, width - 2.0, TAB_HEIGHT))
+            .collect()
+    }
+
+    /// Ctrl+T opens a tab, Ctrl+W closes the active one, Ctrl+PageDown/PageUp or a click on
+    /// the strip switch tabs. Returns true when a click was used so it doesn't also pan
+    pub fn handle_tab_input(&mut self) -> bool {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if ctrl_down {
+            let count = self.tabs.len();
+            if is_key_pressed(KeyCode::T) {
+                self.open_tab();
+            } else if is_key_pressed(KeyCode::W) {
+                self.close_tab();
+            } else if is_key_pressed(KeyCode::PageDown) {
+                self.switch_tab((self.active_tab + 1) % count);
+            } else if is_key_pressed(KeyCode::PageUp) {
+                self.switch_tab((self.active_tab + count - 1) % count);
+            }
+        }
+
+        if !is_mouse_button_pressed(MouseButton::Left) {
+            return false;
+        }
+        let (mouse_x, mouse_y) = mouse_position();
+        let Some(index) = self
+            .tab_rects()
+            .iter()
+            .position(|rect| rect.contains(vec2(mouse_x, mouse_y)))
+        else {
+            return false;
+        };
+        self.switch_tab(index);
+        true
+    }
+
+    /// One tab per session along the top, shown once a second tab is open
+    pub fn draw_tabs(&self) {
+        let text_size = 14;
+        for (index, rect) in self.tab_rects().into_iter().enumerate() {
+            let active = index == self.active_tab;
+            // The active tab's state lives in the app, not its entry
+            let title = if active {
+                session_title(&self.dropped_roots, self.image_slots.len())
+            } else {
+                let session = &self.tabs[index];
+                session_title(&session.dropped_roots, session.image_slots.len())
+            };
+
+            draw_rectangle(
+                rect.x,
+                rect.y,
+                rect.w,
+                rect.h,
+                if active {
+                    Color::new(0.25, 0.25, 0.25, 0.9)
+                } else {
+                    Color::new(0.0, 0.0, 0.0, 0.7)
+                },
+            );
+            if active {
+                draw_line(
+                    rect.x,
+                    rect.y + rect.h,
+                    rect.x + rect.w,
+                    rect.y + rect.h,
+                    2.0,
+                    WHITE,
+                );
+            }
+            let title = self.fit_text(&title, rect.w - 12.0);
+            draw_text_ex(
+                &title,
+                rect.x + 6.0,
+                rect.y + 17.0,
+                TextParams {
+                    font: self.ui_font.as_ref(),
+                    font_size: text_size,
+                    color: if active { WHITE } else { LIGHTGRAY },
+                    ..Default::default()
+                },
+            );
+        }
+    }
+}
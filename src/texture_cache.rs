@@ -0,0 +1,192 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use crate::loading::{LoadedImage, ResolutionTier};
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Environment variable overriding the texture memory budget, in megabytes
+pub const VRAM_BUDGET_ENV: &str = "GTEXVIEWER_VRAM_BUDGET";
+
+/// Budget when `$GTEXVIEWER_VRAM_BUDGET` is unset, sized for integrated GPUs
+const DEFAULT_VRAM_BUDGET_MB: u64 = 1024;
+
+/// The view is grown by this fraction of a screen on each side, so slots just
+/// past the edge neither get evicted nor wait for a reload while panning
+const VISIBLE_MARGIN: f32 = 0.25;
+
+/// Least-recently-visible eviction of uploaded textures once they exceed the budget
+/// Evicted slots go back to placeholders and reload when they scroll into view
+#[derive(Debug)]
+pub struct TextureCache {
+    pub budget_bytes: u64,
+    last_visible: HashMap<String, Instant>, // When each loaded image was last on screen
+    evicted: HashSet<String>,               // Placeholders to reload once visible again
+}
+
+impl TextureCache {
+    /// Budget from `$GTEXVIEWER_VRAM_BUDGET` (MB), else the default
+    pub fn from_env() -> Self {
+        let budget_mb = std::env::var(VRAM_BUDGET_ENV)
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|&mb| mb > 0)
+            .unwrap_or(DEFAULT_VRAM_BUDGET_MB);
+
+        Self {
+            budget_bytes: budget_mb * 1024 * 1024,
+            last_visible: HashMap::new(),
+            evicted: HashSet::new(),
+        }
+    }
+
+    /// Whether the entry's slot was evicted and waits, unloaded, to come back into view
+    pub fn is_evicted(&self, key: &str) -> bool {
+        self.evicted.contains(key)
+    }
+
+    /// Forget everything, e.g. when a new set of files replaces the slots
+    pub fn clear(&mut self) {
+        self.last_visible.clear();
+        self.evicted.clear();
+    }
+}
+
+/// Estimated GPU memory of a texture with its full mip chain
+fn texture_bytes(texture: &Texture2D) -> u64 {
+    let texels = texture.width() as u64 * texture.height() as u64;
+    texels * 4 * 4 / 3
+}
+
+/// Estimated GPU memory held by a loaded image, including frames, layers and cubemap layouts
+/// Block-compressed uploads are counted at one byte per texel without mips
+pub fn image_vram_bytes(image: &LoadedImage) -> u64 {
+    let main = if image.gpu_compressed {
+        image.texture.width() as u64 * image.texture.height() as u64
+    } else {
+        texture_bytes(&image.texture)
+    };
+
+    let frames = image.animation.as_ref().map_or(0, |animation| {
+        animation.frames.iter().map(texture_bytes).sum()
+    });
+    let layers = image
+        .layers
+        .as_ref()
+        .map_or(0, |layers| layers.layers.iter().map(texture_bytes).sum());
+    let cubemap = image.cubemap.as_ref().map_or(0, |cubemap| {
+        cubemap
+            .unfolded_textures()
+            .into_iter()
+            .map(texture_bytes)
+            .sum()
+    });
+
+    // The proxy stays resident while the native texture is shown
+    let proxy = image
+        .proxy
+        .as_ref()
+        .filter(|_| image.tier == ResolutionTier::Full)
+        .map_or(0, texture_bytes);
+
+    main + proxy + frames + layers + cubemap
+}
+
+impl GTexViewerApp {
+    /// Track visibility, evict the least recently visible textures over budget and
+    /// reload evicted images that came back into view
+    pub fn update_texture_cache(&mut self) {
+        let view = self.visible_world_rect();
+        let margin = vec2(view.w, view.h) * VISIBLE_MARGIN;
+        let view = MacroRect::new(
+            view.x - margin.x,
+            view.y - margin.y,
+            view.w + margin.x * 2.0,
+            view.h + margin.y * 2.0,
+        );
+        let now = Instant::now();
+
+        let mut used = 0;
+        let mut candidates = Vec::new();
+        let mut reload = Vec::new();
+
+        for (index, slot) in self.image_slots.iter().enumerate() {
+            let visible = Self::slot_overlaps(slot, &view);
+
+            match &slot.state {
+                ImageState::Loaded { image } => {
+                    // Expanded frames share one animation, so only the whole image can go
+                    if image.expanded_frame.is_some() {
+                        continue;
+                    }
+
+                    let key = image.metadata.entry_key();
+                    let bytes = image_vram_bytes(image);
+                    used += bytes;
+                    if visible {
+                        self.texture_cache.last_visible.insert(key, now);
+                    } else {
+                        let seen = self.texture_cache.last_visible.get(&key).copied();
+                        candidates.push((seen, index, bytes));
+                    }
+                }
+                ImageState::Placeholder {
+                    original_metadata, ..
+                } if visible => {
+                    if self
+                        .texture_cache
+                        .evicted
+                        .remove(&original_metadata.entry_key())
+                    {
+                        reload.push(original_metadata.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if used > self.texture_cache.budget_bytes {
+            // Never-seen images first, then the longest unseen
+            candidates.sort_by_key(|&(seen, ..)| seen);
+
+            let mut evicted = 0;
+            for (_, index, bytes) in candidates {
+                if used <= self.texture_cache.budget_bytes {
+                    break;
+                }
+
+                let slot = &mut self.image_slots[index];
+                let ImageState::Loaded { image } = &slot.state else {
+                    continue;
+                };
+                let metadata = image.metadata.clone();
+                let key = metadata.entry_key();
+                slot.state = ImageState::Placeholder {
+                    layout_metadata: Self::adjust_metadata_for_layout(&metadata),
+                    original_metadata: metadata,
+                };
+                self.texture_cache.last_visible.remove(&key);
+                // A full-resolution decode still in flight is dropped when it arrives
+                self.full_resolution_pending.remove(&key);
+                self.texture_cache.evicted.insert(key);
+                used -= bytes;
+                evicted += 1;
+            }
+
+            if evicted > 0 {
+                log::info!(
+                    "🧮 Evicted {evicted} offscreen textures, {} MB of {} MB in use",
+                    used / (1024 * 1024),
+                    self.texture_cache.budget_bytes / (1024 * 1024)
+                );
+            }
+        }
+
+        if !reload.is_empty() {
+            log::info!("🧮 Reloading {} textures back in view", reload.len());
+            self.async_loader.start_loading_batch(reload, &self.workers);
+            self.start_burst_rendering(std::time::Duration::from_millis(500));
+        }
+    }
+}
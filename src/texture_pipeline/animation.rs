@@ -0,0 +1,98 @@
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, Frames};
+use imagesize::ImageType;
+use macroquad::prelude::Image;
+use std::io::Cursor;
+use std::time::Duration;
+
+use crate::texture_pipeline::LoadedImageData;
+
+/// Upper bound on decoded frames per image to keep memory in check
+const MAX_ANIMATION_FRAMES: usize = 1024;
+/// Upper bound on the RGBA bytes of all decoded frames, so large canvases stop well
+/// before `MAX_ANIMATION_FRAMES`
+const MAX_ANIMATION_BYTES: usize = 512 << 20;
+
+/// Delay used when a frame declares none (matches browser behaviour)
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// A fully composited animation frame
+pub struct DecodedFrame {
+    pub image: Image,
+    pub delay: Duration,
+}
+
+/// Decode every frame of an animated GIF, APNG or WebP
+/// Returns None for still images (a single frame) and other formats
+pub fn decode_animation(data: &LoadedImageData) -> Option<Vec<DecodedFrame>> {
+    let frames = match data.format {
+        ImageType::Gif => GifDecoder::new(Cursor::new(&data.data)).ok()?.into_frames(),
+        ImageType::Png => {
+            let decoder = PngDecoder::new(Cursor::new(&data.data)).ok()?;
+            if !decoder.is_apng().ok()? {
+                return None;
+            }
+            decoder.apng().ok()?.into_frames()
+        }
+        ImageType::Webp => {
+            let decoder = WebPDecoder::new(Cursor::new(&data.data)).ok()?;
+            if !decoder.has_animation() {
+                return None;
+            }
+            decoder.into_frames()
+        }
+        _ => return None,
+    };
+
+    let decoded = collect_frames(frames);
+    if decoded.len() < 2 {
+        return None;
+    }
+
+    log::info!(
+        "🎞️ Decoded {} animation frames for {}",
+        decoded.len(),
+        data.name
+    );
+    Some(decoded)
+}
+
+/// Frames up to `MAX_ANIMATION_FRAMES` or `MAX_ANIMATION_BYTES`, whichever comes first
+fn collect_frames(frames: Frames<'_>) -> Vec<DecodedFrame> {
+    let mut decoded = Vec::new();
+    let mut total_bytes = 0usize;
+
+    for frame in frames.take(MAX_ANIMATION_FRAMES).map_while(Result::ok) {
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        let delay_ms = numerator as f64 / denominator.max(1) as f64;
+        let delay = if delay_ms > 0.0 {
+            Duration::from_secs_f64(delay_ms / 1000.0)
+        } else {
+            DEFAULT_FRAME_DELAY
+        };
+
+        let buffer = frame.into_buffer();
+        total_bytes = total_bytes.saturating_add(buffer.as_raw().len());
+        if total_bytes > MAX_ANIMATION_BYTES {
+            log::warn!(
+                "⚠️ Animation exceeds {} MiB decoded, keeping the first {} frames",
+                MAX_ANIMATION_BYTES >> 20,
+                decoded.len()
+            );
+            break;
+        }
+
+        decoded.push(DecodedFrame {
+            image: Image {
+                width: buffer.width() as u16,
+                height: buffer.height() as u16,
+                bytes: buffer.into_raw(),
+            },
+            delay,
+        });
+    }
+
+    decoded
+}
@@ -0,0 +1,171 @@
+use imagesize::{DdsCompression, ImageType};
+use macroquad::prelude::Image;
+use std::f32::consts::PI;
+
+use crate::texture_pipeline::parsers::{CompressedFormat, DdsLayout};
+use crate::texture_pipeline::{ImageDataParser, LoadedImageData};
+
+/// Faces in KTX2/DDS order: +X, -X, +Y, -Y, +Z, -Z
+pub const CUBE_FACES: usize = 6;
+
+/// Widest equirectangular projection built from a cubemap, to bound decode time and memory
+const MAX_EQUIRECT_WIDTH: usize = 2048;
+
+/// Unfolded views of a cubemap, built off the main thread
+pub struct CubemapImages {
+    pub cross: Image,    // Horizontal cross, 4×3 faces
+    pub equirect: Image, // Equirectangular projection, 2:1
+}
+
+/// Decode all six faces of a KTX2/DDS cubemap and lay them out
+/// Returns None for 2D textures, arrays and other formats
+pub fn decode_cubemap(data: &LoadedImageData) -> Option<CubemapImages> {
+    let faces = match data.format {
+        ImageType::Ktx2 => ktx2_faces(data)?,
+        ImageType::Dds(compression) => dds_faces(data, compression)?,
+        _ => return None,
+    };
+
+    let size = faces[0].width as usize;
+    if faces
+        .iter()
+        .any(|face| face.width as usize != size || face.height as usize != size)
+    {
+        log::debug!("Cubemap faces of {} aren't square and equal", data.name);
+        return None;
+    }
+
+    log::info!("🧊 Decoded {CUBE_FACES} cubemap faces for {}", data.name);
+    Some(CubemapImages {
+        cross: compose_cross(&faces, size),
+        equirect: compose_equirect(&faces, size),
+    })
+}
+
+fn ktx2_faces(data: &LoadedImageData) -> Option<Vec<Image>> {
+    let mut ktx2 = ktx2_rw::Ktx2Texture::from_memory(&data.data).ok()?;
+    if ktx2.faces() as usize != CUBE_FACES {
+        return None;
+    }
+    if ktx2.needs_transcoding() {
+        ktx2.transcode_basis(ktx2_rw::TranscodeFormat::Rgba32)
+            .ok()?;
+    }
+
+    let (width, height) = (ktx2.width() as u16, ktx2.height() as u16);
+    (0..CUBE_FACES as u32)
+        .map(|face| {
+            let bytes = ktx2.get_image_data(0, 0, face).ok()?.to_vec();
+            Some(Image {
+                width,
+                height,
+                bytes,
+            })
+        })
+        .collect()
+}
+
+/// Slice each face's top mip out of a DDS cubemap and decode it like a plain DDS payload
+fn dds_faces(data: &LoadedImageData, compression: DdsCompression) -> Option<Vec<Image>> {
+    let layout = DdsLayout::parse(data, compression).filter(|layout| layout.is_cubemap)?;
+
+    (0..CUBE_FACES)
+        .map(|face| {
+            let face_data = LoadedImageData {
+                data: layout.surface(&data.data, face)?.to_vec(),
+                ..data.clone()
+            };
+            CompressedFormat
+                .parse(&face_data)
+                .ok()
+                .map(|(image, _)| image)
+        })
+        .collect()
+}
+
+/// Copy a face into a larger RGBA image at a face-sized grid cell
+fn blit_face(target: &mut Image, face: &Image, size: usize, column: usize, row: usize) {
+    let target_width = target.width as usize;
+    for y in 0..size {
+        let src = y * size * 4;
+        let dst = ((row * size + y) * target_width + column * size) * 4;
+        target.bytes[dst..dst + size * 4].copy_from_slice(&face.bytes[src..src + size * 4]);
+    }
+}
+
+/// Horizontal cross: +Y above, -X +Z +X -Z across the middle, -Y below
+fn compose_cross(faces: &[Image], size: usize) -> Image {
+    let mut cross = Image {
+        width: (size * 4) as u16,
+        height: (size * 3) as u16,
+        bytes: vec![0; size * 4 * size * 3 * 4],
+    };
+
+    for (face, column, row) in [
+        (2, 1, 0),
+        (1, 0, 1),
+        (4, 1, 1),
+        (0, 2, 1),
+        (5, 3, 1),
+        (3, 1, 2),
+    ] {
+        blit_face(&mut cross, &faces[face], size, column, row);
+    }
+    cross
+}
+
+/// Equirectangular projection, sampling the nearest texel of the face each direction hits
+fn compose_equirect(faces: &[Image], size: usize) -> Image {
+    let width = (size * 4).min(MAX_EQUIRECT_WIDTH);
+    let height = width / 2;
+    let mut bytes = vec![0; width * height * 4];
+
+    for y in 0..height {
+        let latitude = PI / 2.0 - (y as f32 + 0.5) / height as f32 * PI;
+        for x in 0..width {
+            let longitude = (x as f32 + 0.5) / width as f32 * 2.0 * PI - PI;
+            let direction = [
+                latitude.cos() * longitude.sin(),
+                latitude.sin(),
+                latitude.cos() * longitude.cos(),
+            ];
+
+            let (face, s, t) = cube_face_coords(direction);
+            let texel_x = ((s * size as f32) as usize).min(size - 1);
+            let texel_y = ((t * size as f32) as usize).min(size - 1);
+            let src = (texel_y * size + texel_x) * 4;
+            let dst = (y * width + x) * 4;
+            bytes[dst..dst + 4].copy_from_slice(&faces[face].bytes[src..src + 4]);
+        }
+    }
+
+    Image {
+        width: width as u16,
+        height: height as u16,
+        bytes,
+    }
+}
+
+/// Face index and 0..1 face coordinates for a direction, following the GL cube map rules
+fn cube_face_coords([x, y, z]: [f32; 3]) -> (usize, f32, f32) {
+    let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+    let (face, sc, tc, major) = if ax >= ay && ax >= az {
+        if x > 0.0 {
+            (0, -z, -y, ax)
+        } else {
+            (1, z, -y, ax)
+        }
+    } else if ay >= az {
+        if y > 0.0 {
+            (2, x, z, ay)
+        } else {
+            (3, x, -z, ay)
+        }
+    } else if z > 0.0 {
+        (4, x, -y, az)
+    } else {
+        (5, -x, -y, az)
+    };
+
+    (face, (sc / major + 1.0) / 2.0, (tc / major + 1.0) / 2.0)
+}
@@ -1,9 +1,12 @@
+use serde::Serialize;
 use std::any::{Any, TypeId};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Trait for embedded hints as specified in the refactoring plan
 /// Extended with Any for downcasting capabilities
-pub trait EmbeddedHint: Any + Send + Sync + 'static {
+/// Serializable so manifests can record where each entry lives; hints are tagged with a
+/// `kind` field and skip captured data blobs
+pub trait EmbeddedHint: Any + Send + Sync + erased_serde::Serialize + 'static {
     fn debug_info(&self) -> String;
 
     /// Get the type ID for downcasting
@@ -23,6 +26,47 @@ pub trait EmbeddedHint: Any + Send + Sync + 'static {
     fn header_bytes(&self) -> Option<&[u8]> {
         None // Default implementation - no header data
     }
+
+    /// Where the entry's stored bytes live in the container file: (offset, length)
+    /// Only for entries read straight from the file on disk
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        None
+    }
+
+    /// File holding the entry's bytes as-is and contiguous: (path, offset, length)
+    /// Lets any part of the entry be read by range without loading the rest
+    fn file_slice(&self) -> Option<(&Path, u64, u64)> {
+        None
+    }
+
+    /// CRC-32 of the entry's bytes as recorded by the container, for telling whether an
+    /// entry changed without reading it
+    fn content_crc(&self) -> Option<u32> {
+        None
+    }
+
+    /// Wrap modes the referencing asset declares for the texture, e.g. a glTF sampler
+    fn sampler(&self) -> Option<TextureSampler> {
+        None
+    }
+
+    /// Boxed copy of the hint, used by `EmbeddedMetadata::clone`
+    /// Required so hint types defined by plugin sources can be cloned too
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint>;
+
+    /// Entry was listed without reading its header; format and size are probed
+    /// from its bytes when it's loaded
+    fn deferred_probe(&self) -> bool {
+        false
+    }
+}
+
+erased_serde::serialize_trait_object!(EmbeddedHint);
+
+/// A nested container an entry was found in, e.g. a GLB inside a ZIP
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct ContainerSegment {
+    pub name: String, // Entry name of the container within its own parent
 }
 
 /// Metadata for images (both direct files and embedded content)
@@ -33,36 +77,53 @@ pub struct EmbeddedMetadata {
     pub height: usize,
     pub file_size: u64,
     pub embedded_hint: Box<dyn EmbeddedHint>,
-    pub source_path: PathBuf,
+    pub source_path: PathBuf,           // Outermost file on disk (or URL)
+    pub parents: Vec<ContainerSegment>, // Nested containers below source_path, outermost first
+}
+
+impl EmbeddedMetadata {
+    /// "pack.zip / model.glb / BaseColor": the file, nested containers, then the entry
+    pub fn display_path(&self) -> String {
+        let file_name = self
+            .source_path
+            .file_name()
+            .unwrap_or(self.source_path.as_os_str())
+            .to_string_lossy()
+            .into_owned();
+
+        let mut parts = vec![file_name];
+        parts.extend(self.parents.iter().map(|segment| segment.name.clone()));
+        // Loose files are named after themselves
+        if parts.last() != Some(&self.name) {
+            parts.push(self.name.clone());
+        }
+        parts.join(" / ")
+    }
+
+    /// Key identifying the entry across loads; distinct for same-named entries of
+    /// different nested containers
+    pub fn entry_key(&self) -> String {
+        let mut key = format!("{}:", self.source_path.display());
+        for segment in &self.parents {
+            key.push_str(&segment.name);
+            key.push('/');
+        }
+        key.push_str(&self.name);
+        key
+    }
 }
 
 impl Clone for EmbeddedMetadata {
     fn clone(&self) -> Self {
-        // Create a new hint by downcasting and reconstructing
-        let new_hint: Box<dyn EmbeddedHint> =
-            if let Some(file_hint) = self.embedded_hint.as_any().downcast_ref::<FileHint>() {
-                Box::new(file_hint.clone())
-            } else if let Some(glb_hint) = self.embedded_hint.as_any().downcast_ref::<GlbHint>() {
-                Box::new(glb_hint.clone())
-            } else if let Some(fbx_hint) = self.embedded_hint.as_any().downcast_ref::<FbxHint>() {
-                Box::new(fbx_hint.clone())
-            } else if let Some(zip_hint) = self.embedded_hint.as_any().downcast_ref::<ZipHint>() {
-                Box::new(zip_hint.clone())
-            } else {
-                panic!(
-                    "Unknown hint type cannot be cloned: {}",
-                    self.embedded_hint.debug_info()
-                )
-            };
-
         EmbeddedMetadata {
             name: self.name.clone(),
             format: self.format,
             width: self.width,
             height: self.height,
             file_size: self.file_size,
-            embedded_hint: new_hint,
+            embedded_hint: self.embedded_hint.clone_hint(),
             source_path: self.source_path.clone(),
+            parents: self.parents.clone(),
         }
     }
 }
@@ -77,14 +138,55 @@ impl std::fmt::Debug for EmbeddedMetadata {
             .field("file_size", &self.file_size)
             .field("embedded_hint", &self.embedded_hint.debug_info())
             .field("source_path", &self.source_path)
+            .field("parents", &self.parents)
             .finish()
     }
 }
 
+/// Texture coordinate wrapping outside 0..1
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WrapMode {
+    Repeat,
+    MirroredRepeat,
+    ClampToEdge,
+    ClampToBorder, // Not expressible in glTF; engine-only
+}
+
+impl WrapMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            WrapMode::Repeat => "Repeat",
+            WrapMode::MirroredRepeat => "Mirror",
+            WrapMode::ClampToEdge => "Clamp",
+            WrapMode::ClampToBorder => "Border",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            WrapMode::Repeat => WrapMode::MirroredRepeat,
+            WrapMode::MirroredRepeat => WrapMode::ClampToEdge,
+            WrapMode::ClampToEdge => WrapMode::ClampToBorder,
+            WrapMode::ClampToBorder => WrapMode::Repeat,
+        }
+    }
+}
+
+/// Per-axis wrap modes declared for a texture
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct TextureSampler {
+    pub wrap_s: WrapMode, // Horizontal (U)
+    pub wrap_t: WrapMode, // Vertical (V)
+}
+
 /// Hint for direct file loading
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "file")]
 pub struct FileHint {
     pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampler: Option<TextureSampler>, // Set when a model references the file (glTF)
 }
 
 impl EmbeddedHint for FileHint {
@@ -95,19 +197,65 @@ impl EmbeddedHint for FileHint {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn sampler(&self) -> Option<TextureSampler> {
+        self.sampler
+    }
+}
+
+/// Hint for a file fetched over HTTP(S)
+/// Only the header is requested during metadata extraction; the body is downloaded on load
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "url")]
+pub struct UrlHint {
+    pub url: String,
+    pub size: Option<u64>, // Total size reported by the server, if any
+    #[serde(skip)]
+    pub header_bytes: Option<Vec<u8>>, // First bytes for format detection
+}
+
+impl EmbeddedHint for UrlHint {
+    fn debug_info(&self) -> String {
+        let header_info = if self.header_bytes.is_some() {
+            "+header"
+        } else {
+            ""
+        };
+        format!("URL[{}]{}", self.url, header_info)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn header_bytes(&self) -> Option<&[u8]> {
+        self.header_bytes.as_deref()
+    }
 }
 
 /// Hint for GLB embedded textures
 /// CRITICAL: This now contains ABSOLUTE file offset for direct access
 /// For nested containers (ZIP→GLB), can store texture data directly
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "glb")]
 pub struct GlbHint {
     pub container_path: PathBuf,
-    pub buffer_index: usize,           // For cache lookup (if needed)
-    pub absolute_file_offset: u64,     // NEW: Direct file offset
-    pub length: usize,                 // Length in bytes
+    pub buffer_path: Option<PathBuf>, // External .bin of a .gltf; None for the GLB itself
+    pub buffer_index: usize,          // For cache lookup (if needed)
+    pub absolute_file_offset: u64,    // NEW: Direct file offset
+    pub length: usize,                // Length in bytes
     pub relative_buffer_offset: usize, // OLD: Buffer-relative offset (for fallback)
+    #[serde(skip)]
     pub texture_data: Option<Vec<u8>>, // NEW: Direct texture data for nested containers
+    pub sampler: TextureSampler,      // glTF sampler wrap modes
 }
 
 impl EmbeddedHint for GlbHint {
@@ -117,24 +265,55 @@ impl EmbeddedHint for GlbHint {
         } else {
             ""
         };
+        let file_info = match &self.buffer_path {
+            Some(bin_path) => format!("{}", bin_path.display()),
+            None => "file".to_string(),
+        };
         format!(
-            "GLB[buf:{}]@file:{}+{}{}",
-            self.buffer_index, self.absolute_file_offset, self.length, data_info
+            "GLB[buf:{}]@{}:{}+{}{}",
+            self.buffer_index, file_info, self.absolute_file_offset, self.length, data_info
         )
     }
 
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        // .gltf buffers live in separate .bin files; only GLB blobs are in the container
+        let is_glb = self
+            .container_path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("glb"));
+        (is_glb && self.texture_data.is_none())
+            .then_some((self.absolute_file_offset, self.length as u64))
+    }
+
+    fn file_slice(&self) -> Option<(&Path, u64, u64)> {
+        let path = self.buffer_path.as_deref().unwrap_or(&self.container_path);
+        self.texture_data
+            .is_none()
+            .then_some((path, self.absolute_file_offset, self.length as u64))
+    }
+
+    fn sampler(&self) -> Option<TextureSampler> {
+        Some(self.sampler)
+    }
 }
 
 /// Hint for FBX embedded textures
 /// According to refact_pipeline.md: Should contain direct access data
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "fbx")]
 pub struct FbxHint {
     pub container_path: PathBuf,
     pub texture_name: String,
     pub texture_index: usize,
+    #[serde(skip)]
     pub texture_data: Vec<u8>, // Direct data - no re-parsing needed!
 }
 
@@ -146,25 +325,37 @@ impl EmbeddedHint for FbxHint {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
 }
 
 /// Hint for ZIP embedded entries
 /// Contains the entry name and index for direct access
 /// Now includes optional header bytes for fast format detection
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "zip")]
 pub struct ZipHint {
     pub container_path: PathBuf,
     pub entry_name: String,
     pub entry_index: usize,
     pub compressed_size: u64,
     pub uncompressed_size: u64,
+    pub data_offset: u64, // Start of the entry's (compressed) data in the archive
+    pub crc32: u32,       // CRC-32 of the uncompressed data, from the central directory
+    #[serde(skip)]
     pub header_bytes: Option<Vec<u8>>, // First 64 bytes for format detection
+    #[serde(skip)]
+    pub deferred: bool, // Listed lazily from a huge archive, header not read yet
 }
 
 impl EmbeddedHint for ZipHint {
     fn debug_info(&self) -> String {
         let header_info = if self.header_bytes.is_some() {
             "+header"
+        } else if self.deferred {
+            "+lazy"
         } else {
             ""
         };
@@ -178,7 +369,687 @@ impl EmbeddedHint for ZipHint {
         self
     }
 
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn content_crc(&self) -> Option<u32> {
+        Some(self.crc32)
+    }
+
+    fn header_bytes(&self) -> Option<&[u8]> {
+        self.header_bytes.as_deref()
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        Some((self.data_offset, self.compressed_size))
+    }
+
+    fn deferred_probe(&self) -> bool {
+        self.deferred
+    }
+}
+
+/// Hint for GIMP XCF documents
+/// The composite is flattened on demand; nested XCF files carry it directly
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "xcf")]
+pub struct XcfHint {
+    pub container_path: PathBuf,
+    #[serde(skip)]
+    pub flattened_data: Option<Vec<u8>>, // Pre-flattened QOI data for nested containers
+}
+
+impl EmbeddedHint for XcfHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.flattened_data.is_some() {
+            "+data"
+        } else {
+            ""
+        };
+        format!("XCF[{}]{}", self.container_path.display(), data_info)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+}
+
+/// Compression layer wrapped around a TAR stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TarCompression {
+    None,
+    Gzip,
+    Xz,
+}
+
+/// Hint for TAR embedded entries
+/// Uncompressed archives are read directly at `data_offset`; compressed ones are
+/// re-streamed up to `entry_index`
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "tar")]
+pub struct TarHint {
+    pub container_path: PathBuf,
+    pub entry_path: String,
+    pub entry_index: usize,
+    pub data_offset: u64, // Offset of entry data within the (decompressed) TAR stream
+    pub size: u64,
+    pub compression: TarCompression,
+    #[serde(skip)]
+    pub header_bytes: Option<Vec<u8>>, // First bytes for format detection
+    #[serde(skip)]
+    pub entry_data: Option<Vec<u8>>, // Direct entry data for nested containers
+}
+
+impl EmbeddedHint for TarHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.entry_data.is_some() {
+            "+data"
+        } else if self.header_bytes.is_some() {
+            "+header"
+        } else {
+            ""
+        };
+        format!(
+            "TAR[{}]:{}@{}+{}{}",
+            self.entry_index, self.entry_path, self.data_offset, self.size, data_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn header_bytes(&self) -> Option<&[u8]> {
+        self.header_bytes.as_deref()
+    }
+
+    fn file_slice(&self) -> Option<(&Path, u64, u64)> {
+        // Offsets in compressed archives are into the decompressed stream
+        (self.compression == TarCompression::None && self.entry_data.is_none()).then_some((
+            self.container_path.as_path(),
+            self.data_offset,
+            self.size,
+        ))
+    }
+}
+
+/// Hint for 7-Zip embedded entries
+/// Entries are matched by name during selective extraction
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "7z")]
+pub struct SevenZipHint {
+    pub container_path: PathBuf,
+    pub entry_name: String,
+    pub entry_index: usize,
+    pub size: u64,
+    #[serde(skip)]
+    pub header_bytes: Option<Vec<u8>>, // First bytes for format detection
+    #[serde(skip)]
+    pub entry_data: Option<Vec<u8>>, // Direct entry data for nested containers
+}
+
+impl EmbeddedHint for SevenZipHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.entry_data.is_some() {
+            "+data"
+        } else if self.header_bytes.is_some() {
+            "+header"
+        } else {
+            ""
+        };
+        format!("7Z[{}]:{}{}", self.entry_index, self.entry_name, data_info)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn header_bytes(&self) -> Option<&[u8]> {
+        self.header_bytes.as_deref()
+    }
+}
+
+/// Hint for files inside ISO9660/UDF disc images
+/// Files are stored uncompressed, possibly split across several extents
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "iso")]
+pub struct IsoHint {
+    pub container_path: PathBuf,
+    pub entry_path: String,
+    pub extents: Vec<(u64, u64)>, // (byte offset, length) runs within the image, in file order
+    pub size: u64,
+    #[serde(skip)]
+    pub header_bytes: Option<Vec<u8>>, // First bytes for format detection
+    #[serde(skip)]
+    pub entry_data: Option<Vec<u8>>, // Direct entry data for nested images and UDF in-entry files
+}
+
+impl EmbeddedHint for IsoHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.entry_data.is_some() {
+            "+data"
+        } else if self.header_bytes.is_some() {
+            "+header"
+        } else {
+            ""
+        };
+        format!(
+            "ISO[{} extents]:{}+{}{}",
+            self.extents.len(),
+            self.entry_path,
+            self.size,
+            data_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn header_bytes(&self) -> Option<&[u8]> {
+        self.header_bytes.as_deref()
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        // Offsets are only meaningful for unfragmented files read from the image on disk
+        match self.extents.as_slice() {
+            [(offset, _)] if self.entry_data.is_none() => Some((*offset, self.size)),
+            _ => None,
+        }
+    }
+
+    fn file_slice(&self) -> Option<(&Path, u64, u64)> {
+        let (offset, size) = self.byte_range()?;
+        Some((&self.container_path, offset, size))
+    }
+}
+
+/// Single-stream compression wrapping a whole file (e.g. `texture.png.zst`)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamWrapper {
+    Zstd,
+    Gzip,
+}
+
+/// Hint for a plain image stored inside a single-stream compression wrapper
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "wrapped")]
+pub struct WrappedHint {
+    pub container_path: PathBuf,
+    pub entry_name: String,
+    pub wrapper: StreamWrapper,
+    pub inner_size: u64,
+    #[serde(skip)]
+    pub inner_data: Option<Vec<u8>>, // Decoded payload for nested wrappers
+}
+
+impl EmbeddedHint for WrappedHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.inner_data.is_some() {
+            "+data"
+        } else {
+            ""
+        };
+        format!(
+            "Wrapped[{:?}]:{}({} bytes){}",
+            self.wrapper, self.entry_name, self.inner_size, data_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+}
+
+/// Hint for textures inside a USDZ package
+/// USDZ entries are stored uncompressed, so they can be read directly at `data_offset`
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "usdz")]
+pub struct UsdzHint {
+    pub container_path: PathBuf,
+    pub entry_name: String,
+    pub data_offset: u64,
+    pub crc32: u32, // CRC-32 of the uncompressed data, from the central directory
+    pub size: u64,
+    pub stored: bool, // False for non-conforming packages with compressed entries
+    #[serde(skip)]
+    pub entry_data: Option<Vec<u8>>, // Direct entry data for nested packages
+}
+
+impl EmbeddedHint for UsdzHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.entry_data.is_some() {
+            "+data"
+        } else {
+            ""
+        };
+        format!(
+            "USDZ[@{}]:{}({} bytes){}",
+            self.data_offset, self.entry_name, self.size, data_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn content_crc(&self) -> Option<u32> {
+        Some(self.crc32)
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        (self.stored && self.entry_data.is_none()).then_some((self.data_offset, self.size))
+    }
+
+    fn file_slice(&self) -> Option<(&Path, u64, u64)> {
+        let (offset, size) = self.byte_range()?;
+        Some((&self.container_path, offset, size))
+    }
+}
+
+/// Hint for a texture inside an Android APK
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "apk")]
+pub struct ApkHint {
+    pub container_path: PathBuf,
+    pub entry_name: String, // Path stored in the APK, possibly obfuscated
+    pub entry_index: usize,
+    pub compressed_size: u64,
+    pub data_offset: u64,
+    pub crc32: u32, // CRC-32 of the uncompressed data, from the central directory
+    pub nine_patch_border: bool, // Source nine-patch whose 1px guide border is cropped on load
+}
+
+impl EmbeddedHint for ApkHint {
+    fn debug_info(&self) -> String {
+        let nine_patch_info = if self.nine_patch_border {
+            "+9patch"
+        } else {
+            ""
+        };
+        format!(
+            "APK[{}]:{}{}",
+            self.entry_index, self.entry_name, nine_patch_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn content_crc(&self) -> Option<u32> {
+        Some(self.crc32)
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        Some((self.data_offset, self.compressed_size))
+    }
+}
+
+/// How an idTech lump is turned into pixels
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WadLump {
+    Image,                                   // PNG/JPEG/TGA stored as-is
+    DoomPicture,                             // Column-based patch or sprite
+    DoomFlat, // Raw floor/ceiling indices, size given by the lump length
+    MipTexture { embedded_palette: bool }, // Quake/Half-Life miptex, top mip only
+    QuakePicture { embedded_palette: bool }, // qpic with index 255 transparent
+}
+
+impl WadLump {
+    fn name(self) -> &'static str {
+        match self {
+            WadLump::Image => "image",
+            WadLump::DoomPicture => "picture",
+            WadLump::DoomFlat => "flat",
+            WadLump::MipTexture { .. } => "miptex",
+            WadLump::QuakePicture { .. } => "qpic",
+        }
+    }
+}
+
+/// Hint for a lump inside an idTech WAD (IWAD/PWAD/WAD2/WAD3)
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "wad")]
+pub struct WadHint {
+    pub container_path: PathBuf,
+    pub lump_name: String,
+    pub lump_index: usize,
+    pub data_offset: u64,
+    pub size: u64,
+    pub lump: WadLump,
+}
+
+impl EmbeddedHint for WadHint {
+    fn debug_info(&self) -> String {
+        format!(
+            "WAD[{}]:{}({}, {} bytes)",
+            self.lump_index,
+            self.lump_name,
+            self.lump.name(),
+            self.size
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        Some((self.data_offset, self.size))
+    }
+}
+
+/// Hint for a lump or image inside a PK3/PK4 (ZIP) archive
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "pk3")]
+pub struct Pk3Hint {
+    pub container_path: PathBuf,
+    pub entry_name: String,
+    pub entry_index: usize,
+    pub compressed_size: u64,
+    pub data_offset: u64,
+    pub crc32: u32, // CRC-32 of the uncompressed data, from the central directory
+    pub lump: WadLump,
+}
+
+impl EmbeddedHint for Pk3Hint {
+    fn debug_info(&self) -> String {
+        format!(
+            "PK3[{}]:{}({})",
+            self.entry_index,
+            self.entry_name,
+            self.lump.name()
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn content_crc(&self) -> Option<u32> {
+        Some(self.crc32)
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        Some((self.data_offset, self.compressed_size))
+    }
+}
+
+/// Hint for an image XObject inside a PDF document
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "pdf")]
+pub struct PdfHint {
+    pub container_path: PathBuf,
+    pub object_id: (u32, u16),
+    pub size: u64, // Encoded stream size
+    #[serde(skip)]
+    pub image_data: Option<Vec<u8>>, // Standalone JPEG/PNG for nested documents
+}
+
+impl EmbeddedHint for PdfHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.image_data.is_some() {
+            "+data"
+        } else {
+            ""
+        };
+        format!(
+            "PDF[{} {}]:{} bytes{}",
+            self.object_id.0, self.object_id.1, self.size, data_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+}
+
+/// How a sprite was rotated when packed into its atlas
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AtlasRotation {
+    None,
+    Clockwise,        // TexturePacker JSON/XML/.plist: stored rotated 90° clockwise
+    CounterClockwise, // libGDX/Spine: stored rotated 90° counter-clockwise
+}
+
+/// Hint for one sprite region of a packed atlas image
+/// The rectangle is in atlas pixels and describes the sprite before rotation
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "atlas")]
+pub struct AtlasHint {
+    pub container_path: PathBuf, // Atlas descriptor
+    pub image_path: PathBuf,
+    pub sprite_name: String,
+    pub rect: [u32; 4], // x, y, width, height
+    pub rotation: AtlasRotation,
+}
+
+impl EmbeddedHint for AtlasHint {
+    fn debug_info(&self) -> String {
+        let [x, y, width, height] = self.rect;
+        let rotation_info = if self.rotation == AtlasRotation::None {
+            ""
+        } else {
+            " rotated"
+        };
+        format!(
+            "Atlas[{}]:{} @{},{} {}x{}{}",
+            self.image_path.display(),
+            self.sprite_name,
+            x,
+            y,
+            width,
+            height,
+            rotation_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+}
+
+/// Compression method of an Unreal Engine .pak entry
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PakCompression {
+    None,
+    Zlib,
+    Gzip,
+    Unsupported(String), // Oodle and custom methods, by name
+}
+
+/// Hint for entries inside an Unreal Engine .pak archive
+/// Stored entries are read directly at `data_offset`; compressed ones block by block
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "pak")]
+pub struct PakHint {
+    pub container_path: PathBuf,
+    pub entry_name: String,
+    pub data_offset: u64, // Start of the entry's data, past its record header
+    pub size: u64,        // Stored (compressed) size
+    pub uncompressed_size: u64,
+    pub compression: PakCompression,
+    pub blocks: Vec<(u64, u64)>, // Absolute start/end of each compressed block
+    #[serde(skip)]
+    pub header_bytes: Option<Vec<u8>>, // First bytes for format detection
+    #[serde(skip)]
+    pub entry_data: Option<Vec<u8>>, // Direct entry data for nested containers
+}
+
+impl EmbeddedHint for PakHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.entry_data.is_some() {
+            "+data"
+        } else if self.header_bytes.is_some() {
+            "+header"
+        } else {
+            ""
+        };
+        format!(
+            "PAK[{:?}]:{}@{}+{}{}",
+            self.compression, self.entry_name, self.data_offset, self.size, data_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
     fn header_bytes(&self) -> Option<&[u8]> {
         self.header_bytes.as_deref()
     }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        self.entry_data
+            .is_none()
+            .then_some((self.data_offset, self.size))
+    }
+}
+
+/// Package files of a cooked Unreal asset stored inside a .pak
+#[derive(Clone, Debug, Serialize)]
+pub struct UassetPakParts {
+    pub uasset: PakHint,
+    pub uexp: Option<PakHint>,  // Export data of split packages
+    pub ubulk: Option<PakHint>, // Streamed mip payloads
+}
+
+/// Hint for a cooked Unreal Engine UTexture2D
+/// Loose assets read their .uexp/.ubulk next to `container_path`; pak assets carry them
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "uasset")]
+pub struct UassetHint {
+    pub container_path: PathBuf, // The .uasset on disk, or the .pak holding it
+    pub asset_name: String,
+    pub pixel_format: String, // e.g. PF_DXT5
+    pub pak_parts: Option<UassetPakParts>,
+    #[serde(skip)]
+    pub pixel_data: Option<Vec<u8>>, // Decoded top mip for nested containers
+}
+
+impl EmbeddedHint for UassetHint {
+    fn debug_info(&self) -> String {
+        let data_info = if self.pixel_data.is_some() {
+            "+data"
+        } else if self.pak_parts.is_some() {
+            "+pak"
+        } else {
+            ""
+        };
+        format!(
+            "UASSET[{}]:{}{}",
+            self.pixel_format, self.asset_name, data_info
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+}
+
+/// Hint for a file inside a Source engine VPK
+/// Entry data may start with preload bytes stored in the directory tree, followed by the
+/// rest in a numbered chunk file (or the directory file itself)
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename = "vpk")]
+pub struct VpkHint {
+    pub container_path: PathBuf, // The _dir.vpk
+    pub entry_path: String,
+    pub archive_path: PathBuf, // Chunk holding the data, e.g. pak01_003.vpk
+    pub data_offset: u64,      // Absolute offset in archive_path
+    pub length: u64,
+    pub preload_offset: u64, // Absolute offset in container_path
+    pub preload_size: u64,
+    pub vtf: bool, // Valve texture decoded to its top mip on load
+}
+
+impl EmbeddedHint for VpkHint {
+    fn debug_info(&self) -> String {
+        let chunk_name = self
+            .archive_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        format!(
+            "VPK[{}@{}+{}]:{}",
+            chunk_name, self.data_offset, self.length, self.entry_path
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_hint(&self) -> Box<dyn EmbeddedHint> {
+        Box::new(self.clone())
+    }
+
+    fn byte_range(&self) -> Option<(u64, u64)> {
+        // Only entries stored in one piece inside the directory file itself
+        if self.length == 0 {
+            Some((self.preload_offset, self.preload_size))
+        } else {
+            (self.preload_size == 0 && self.archive_path == self.container_path)
+                .then_some((self.data_offset, self.length))
+        }
+    }
 }
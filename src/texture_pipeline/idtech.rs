@@ -0,0 +1,504 @@
+use anyhow::{Context, Result};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::texture_pipeline::{WadLump, vfs};
+
+/// WAD2/WAD3 directory lump types
+pub const QUAKE_LUMP_PALETTE: u8 = 0x40;
+pub const QUAKE_LUMP_QPIC: u8 = 0x42;
+pub const QUAKE_LUMP_MIPTEX_WAD3: u8 = 0x43;
+pub const QUAKE_LUMP_MIPTEX: u8 = 0x44;
+
+const MAX_PICTURE_SIZE: u32 = 4096;
+const MAX_LUMPS: usize = 1 << 20;
+
+/// Doom lumps that are never graphics even though some parse as patches
+const DOOM_DATA_LUMPS: &[&str] = &[
+    "PLAYPAL", "COLORMAP", "ENDOOM", "PNAMES", "TEXTURE1", "TEXTURE2", "GENMIDI", "DMXGUS",
+    "DMXGUSC", "THINGS", "LINEDEFS", "SIDEDEFS", "VERTEXES", "SEGS", "SSECTORS", "NODES",
+    "SECTORS", "REJECT", "BLOCKMAP", "BEHAVIOR", "SCRIPTS", "TEXTMAP", "ZNODES", "ENDMAP",
+];
+
+/// Where a Doom lump sits, from WAD markers (F_START, S_START, ...) or PK3 folders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoomNamespace {
+    Global,
+    Flats,
+    Sprites,
+    Patches,
+    Textures,
+}
+
+impl DoomNamespace {
+    /// Namespace opened or closed by a marker lump; None for ordinary lumps
+    pub fn from_marker(name: &str) -> Option<(Self, bool)> {
+        let (prefix, start) = if let Some(prefix) = name.strip_suffix("_START") {
+            (prefix, true)
+        } else {
+            (name.strip_suffix("_END")?, false)
+        };
+        let namespace = match prefix {
+            "F" | "FF" | "F1" | "F2" | "F3" => Self::Flats,
+            "S" | "SS" => Self::Sprites,
+            "P" | "PP" | "P1" | "P2" | "P3" => Self::Patches,
+            "TX" => Self::Textures,
+            _ => return None,
+        };
+        Some((namespace, start))
+    }
+
+    /// Namespace of a PK3 entry from its top-level folder
+    pub fn from_folder(entry_name: &str) -> Option<Self> {
+        let folder = entry_name.split('/').next()?.to_lowercase();
+        match folder.as_str() {
+            "flats" => Some(Self::Flats),
+            "sprites" => Some(Self::Sprites),
+            "patches" => Some(Self::Patches),
+            "textures" => Some(Self::Textures),
+            "graphics" => Some(Self::Global),
+            _ => None,
+        }
+    }
+}
+
+/// 256-color palette (PLAYPAL, Quake palette.lmp or a WAD3 lump's own)
+#[derive(Clone)]
+pub struct Palette([[u8; 3]; 256]);
+
+impl Palette {
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let data = data.get(..768)?;
+        let mut colors = [[0u8; 3]; 256];
+        for (color, rgb) in colors.iter_mut().zip(data.chunks_exact(3)) {
+            color.copy_from_slice(rgb);
+        }
+        Some(Self(colors))
+    }
+
+    /// Used when no palette can be found, so shapes stay recognizable
+    pub fn grayscale() -> Self {
+        let mut colors = [[0u8; 3]; 256];
+        for (i, color) in colors.iter_mut().enumerate() {
+            *color = [i as u8; 3];
+        }
+        Self(colors)
+    }
+
+    fn rgba(&self, index: u8) -> Rgba<u8> {
+        let [r, g, b] = self.0[index as usize];
+        Rgba([r, g, b, 255])
+    }
+}
+
+/// One lump in a WAD directory
+#[derive(Debug, Clone)]
+pub struct WadEntry {
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+    pub lump_type: u8, // WAD2/WAD3 only
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WadKind {
+    Doom,     // IWAD/PWAD
+    Quake,    // WAD2
+    HalfLife, // WAD3, lumps carry their own palette
+}
+
+pub struct WadDirectory {
+    pub kind: WadKind,
+    pub iwad: bool,
+    pub entries: Vec<WadEntry>,
+}
+
+impl WadDirectory {
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let mut header = [0u8; 12];
+        reader.seek(SeekFrom::Start(0))?;
+        reader
+            .read_exact(&mut header)
+            .context("WAD header truncated")?;
+
+        let kind = wad_kind(&header).context("Not a WAD file")?;
+        let count = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let table_offset = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+        if count > MAX_LUMPS {
+            anyhow::bail!("WAD directory claims {count} lumps");
+        }
+
+        let record_size = if kind == WadKind::Doom { 16 } else { 32 };
+        let mut table = vec![0u8; count * record_size];
+        reader.seek(SeekFrom::Start(table_offset as u64))?;
+        reader
+            .read_exact(&mut table)
+            .context("WAD directory truncated")?;
+
+        let entries = table
+            .chunks_exact(record_size)
+            .map(|record| {
+                let u32_at = |pos: usize| {
+                    u32::from_le_bytes([
+                        record[pos],
+                        record[pos + 1],
+                        record[pos + 2],
+                        record[pos + 3],
+                    ])
+                };
+                match kind {
+                    WadKind::Doom => WadEntry {
+                        name: lump_name(&record[8..16]),
+                        offset: u32_at(0) as u64,
+                        size: u32_at(4) as u64,
+                        lump_type: 0,
+                    },
+                    _ => WadEntry {
+                        name: lump_name(&record[16..32]),
+                        offset: u32_at(0) as u64,
+                        size: u32_at(4) as u64, // Stored size; lumps are never compressed
+                        lump_type: record[12],
+                    },
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            kind,
+            iwad: header.starts_with(b"IWAD"),
+            entries,
+        })
+    }
+
+    /// PLAYPAL (Doom) or the palette lump (Quake)
+    pub fn palette<R: Read + Seek>(&self, reader: &mut R) -> Option<Palette> {
+        let entry = self.entries.iter().find(|entry| match self.kind {
+            WadKind::Doom => entry.name.eq_ignore_ascii_case("PLAYPAL"),
+            _ => {
+                entry.lump_type == QUAKE_LUMP_PALETTE || entry.name.eq_ignore_ascii_case("PALETTE")
+            }
+        })?;
+        Palette::from_bytes(&read_lump(reader, entry).ok()?)
+    }
+}
+
+pub fn wad_kind(header: &[u8]) -> Option<WadKind> {
+    match header.get(..4)? {
+        b"IWAD" | b"PWAD" => Some(WadKind::Doom),
+        b"WAD2" => Some(WadKind::Quake),
+        b"WAD3" => Some(WadKind::HalfLife),
+        _ => None,
+    }
+}
+
+pub fn read_lump<R: Read + Seek>(reader: &mut R, entry: &WadEntry) -> Result<Vec<u8>> {
+    reader.seek(SeekFrom::Start(entry.offset))?;
+    let mut data = Vec::new();
+    reader.by_ref().take(entry.size).read_to_end(&mut data)?;
+    if (data.len() as u64) < entry.size {
+        anyhow::bail!("Lump {} is truncated", entry.name);
+    }
+    Ok(data)
+}
+
+fn lump_name(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// How to decode a Doom lump and its size, or None when it isn't a graphic
+pub fn classify_doom_lump(
+    name: &str,
+    namespace: DoomNamespace,
+    data: &[u8],
+) -> Option<(WadLump, u32, u32)> {
+    if imagesize::image_type(data).is_ok() {
+        let size = imagesize::blob_size(data).ok()?;
+        return Some((WadLump::Image, size.width as u32, size.height as u32));
+    }
+
+    if namespace == DoomNamespace::Flats {
+        let (width, height) = flat_size(data.len())?;
+        return Some((WadLump::DoomFlat, width, height));
+    }
+
+    // Outside the marker sections only lumps that fully decode as patches count
+    let upper_name = name.to_uppercase();
+    if namespace == DoomNamespace::Global
+        && (DOOM_DATA_LUMPS.contains(&upper_name.as_str())
+            || upper_name.starts_with("DEMO")
+            || upper_name.starts_with("MAP")
+            || is_episode_map(&upper_name))
+    {
+        return None;
+    }
+    let picture = decode_doom_picture(data, &Palette::grayscale())?;
+    Some((WadLump::DoomPicture, picture.width(), picture.height()))
+}
+
+fn is_episode_map(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    bytes.len() == 4
+        && bytes[0] == b'E'
+        && bytes[1].is_ascii_digit()
+        && bytes[2] == b'M'
+        && bytes[3].is_ascii_digit()
+}
+
+/// Size of a WAD2/WAD3 lump that holds a picture
+pub fn quake_lump_size(lump_type: u8, data: &[u8]) -> Option<(WadLump, u32, u32)> {
+    let u32_at = |pos: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+    };
+    let (lump, width, height) = match lump_type {
+        QUAKE_LUMP_MIPTEX | QUAKE_LUMP_MIPTEX_WAD3 => (
+            WadLump::MipTexture {
+                embedded_palette: lump_type == QUAKE_LUMP_MIPTEX_WAD3,
+            },
+            u32_at(16)?,
+            u32_at(20)?,
+        ),
+        QUAKE_LUMP_QPIC => (
+            WadLump::QuakePicture {
+                embedded_palette: false,
+            },
+            u32_at(0)?,
+            u32_at(4)?,
+        ),
+        _ => return None,
+    };
+    let valid = (1..=MAX_PICTURE_SIZE).contains(&width) && (1..=MAX_PICTURE_SIZE).contains(&height);
+    valid.then_some((lump, width, height))
+}
+
+/// Decode a lump to QOI so the standard parser picks it up
+/// The container palette is only looked up for lumps that don't carry their own
+pub fn decode_lump(
+    lump: WadLump,
+    name: &str,
+    data: &[u8],
+    palette: impl FnOnce() -> Palette,
+) -> Result<Vec<u8>> {
+    let image = match lump {
+        WadLump::Image => return Ok(data.to_vec()),
+        WadLump::DoomPicture => decode_doom_picture(data, &palette()),
+        WadLump::DoomFlat => decode_flat(data, &palette()),
+        WadLump::MipTexture { embedded_palette } => {
+            decode_miptex(data, embedded_palette, palette, name.starts_with('{'))
+        }
+        WadLump::QuakePicture { embedded_palette } => decode_qpic(data, embedded_palette, palette),
+    }
+    .with_context(|| format!("Malformed lump {name}"))?;
+
+    let mut encoded = Vec::new();
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Qoi)
+        .context("Failed to encode lump")?;
+    Ok(encoded)
+}
+
+/// Flats are raw index arrays; the size gives the dimensions
+fn flat_size(length: usize) -> Option<(u32, u32)> {
+    match length {
+        4096 => Some((64, 64)),
+        4160 => Some((64, 65)), // Heretic/Hexen scrolling flats
+        8192 => Some((64, 128)),
+        16384 => Some((128, 128)),
+        65536 => Some((256, 256)),
+        _ => None,
+    }
+}
+
+fn decode_flat(data: &[u8], palette: &Palette) -> Option<RgbaImage> {
+    let (width, height) = flat_size(data.len())?;
+    Some(indexed_image(width, height, data, palette, None))
+}
+
+/// Column-based patch/sprite format: posts of palette indices per column, gaps transparent
+fn decode_doom_picture(data: &[u8], palette: &Palette) -> Option<RgbaImage> {
+    let u16_at = |pos: usize| -> Option<u16> {
+        Some(u16::from_le_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+    };
+    let width = u16_at(0)? as u32;
+    let height = u16_at(2)? as u32;
+    if !(1..=MAX_PICTURE_SIZE).contains(&width) || !(1..=MAX_PICTURE_SIZE).contains(&height) {
+        return None;
+    }
+
+    let mut image = RgbaImage::new(width, height);
+    for x in 0..width {
+        let column_pos = 8 + x as usize * 4;
+        let mut pos =
+            u32::from_le_bytes(data.get(column_pos..column_pos + 4)?.try_into().ok()?) as usize;
+        let mut top: i64 = -1;
+
+        loop {
+            let top_delta = *data.get(pos)?;
+            if top_delta == 0xFF {
+                break;
+            }
+            let length = *data.get(pos + 1)? as usize;
+            // Tall patches: a delta not below the previous post is relative to it
+            top = if (top_delta as i64) <= top {
+                top + top_delta as i64
+            } else {
+                top_delta as i64
+            };
+
+            // Skip the padding byte on each side of the post
+            let pixels = data.get(pos + 3..pos + 3 + length)?;
+            for (i, &index) in pixels.iter().enumerate() {
+                let y = u32::try_from(top + i as i64).ok()?;
+                if y >= height {
+                    return None;
+                }
+                image.put_pixel(x, y, palette.rgba(index));
+            }
+            pos += length + 4;
+        }
+    }
+    Some(image)
+}
+
+/// Quake/Half-Life miptex: top mip only
+fn decode_miptex(
+    data: &[u8],
+    embedded_palette: bool,
+    palette: impl FnOnce() -> Palette,
+    transparent: bool,
+) -> Option<RgbaImage> {
+    let u32_at = |pos: usize| -> Option<usize> {
+        Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?) as usize)
+    };
+    let width = u32_at(16)? as u32;
+    let height = u32_at(20)? as u32;
+    if width > MAX_PICTURE_SIZE || height > MAX_PICTURE_SIZE {
+        return None;
+    }
+    let mip_offset = u32_at(24)?;
+    let pixels = data.get(mip_offset..mip_offset + (width * height) as usize)?;
+
+    let palette = if embedded_palette {
+        // The palette follows the smallest mip: u16 color count, then RGB triples
+        let last_mip = u32_at(36)? + (width / 8 * (height / 8)) as usize;
+        Palette::from_bytes(data.get(last_mip + 2..)?)?
+    } else {
+        palette()
+    };
+
+    // Half-Life '{' textures use the last palette entry as cut-out transparency
+    let transparent_index = transparent.then_some(255);
+    Some(indexed_image(
+        width,
+        height,
+        pixels,
+        &palette,
+        transparent_index,
+    ))
+}
+
+/// Quake status bar/menu picture; index 255 is transparent
+fn decode_qpic(
+    data: &[u8],
+    embedded_palette: bool,
+    palette: impl FnOnce() -> Palette,
+) -> Option<RgbaImage> {
+    let width = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+    let height = u32::from_le_bytes(data.get(4..8)?.try_into().ok()?);
+    if width > MAX_PICTURE_SIZE || height > MAX_PICTURE_SIZE {
+        return None;
+    }
+    let end = 8 + (width * height) as usize;
+    let pixels = data.get(8..end)?;
+
+    let palette = if embedded_palette {
+        Palette::from_bytes(data.get(end + 2..)?)?
+    } else {
+        palette()
+    };
+    Some(indexed_image(width, height, pixels, &palette, Some(255)))
+}
+
+fn indexed_image(
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    palette: &Palette,
+    transparent_index: Option<u8>,
+) -> RgbaImage {
+    RgbaImage::from_fn(width, height, |x, y| {
+        let index = pixels[(y * width + x) as usize];
+        if Some(index) == transparent_index {
+            Rgba([0, 0, 0, 0])
+        } else {
+            palette.rgba(index)
+        }
+    })
+}
+
+/// Palette for a container, found once and cached for later loads
+pub fn container_palette(container: &Path, find: impl FnOnce() -> Option<Palette>) -> Palette {
+    static PALETTES: OnceLock<Mutex<HashMap<PathBuf, Palette>>> = OnceLock::new();
+    let cache = PALETTES.get_or_init(Default::default);
+    if let Some(palette) = cache
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(container).cloned())
+    {
+        return palette;
+    }
+
+    let palette = find()
+        .or_else(|| sibling_palette(container))
+        .unwrap_or_else(|| {
+            log::warn!(
+                "🎨 No palette found for {}, showing lumps in grayscale",
+                container.display()
+            );
+            Palette::grayscale()
+        });
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(container.to_path_buf(), palette.clone());
+    }
+    palette
+}
+
+/// Palette next to a mod: Quake's gfx/palette.lmp, or PLAYPAL from an IWAD in the same folder
+fn sibling_palette(container: &Path) -> Option<Palette> {
+    let directory = container.parent()?;
+    for candidate in [
+        directory.join("palette.lmp"),
+        directory.join("gfx/palette.lmp"),
+        directory.join("../gfx/palette.lmp"),
+    ] {
+        if let Some(palette) = vfs::read(&candidate)
+            .ok()
+            .and_then(|data| Palette::from_bytes(&data))
+        {
+            return Some(palette);
+        }
+    }
+
+    vfs::read_dir(directory)
+        .ok()?
+        .into_iter()
+        .filter(|path| {
+            path.as_path() != container
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("wad"))
+        })
+        .find_map(|path| {
+            let mut file = vfs::open(&path).ok()?;
+            let wad = WadDirectory::read(&mut file).ok()?;
+            if !wad.iwad {
+                return None;
+            }
+            log::info!("🎨 Using PLAYPAL from {}", path.display());
+            wad.palette(&mut file)
+        })
+}
@@ -1,20 +1,51 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use macroquad::prelude::*;
+use std::io::{Read, Seek};
 use std::path::{Path, PathBuf};
 
 // Sub-modules
+pub mod animation;
+pub mod cubemap;
 pub mod hint;
+pub mod idtech;
 pub mod parsers;
+/// Third-party sources registered at compile time or loaded from plugin libraries
+pub mod plugins;
+pub mod progressive;
 pub mod registry;
+pub mod retry;
 pub mod source;
 pub mod sources;
+pub mod unreal;
+/// Read-only virtual filesystem: sources read through mounts (archives, memory buffers)
+/// with unmounted paths falling through to the OS filesystem
+pub mod vfs;
+pub mod vtf;
+pub mod wrapper;
 
 // Re-export key types for external use
-pub use hint::{EmbeddedHint, EmbeddedMetadata, FbxHint, FileHint, GlbHint, ZipHint};
+pub use hint::{
+    ApkHint, AtlasHint, AtlasRotation, ContainerSegment, EmbeddedHint, EmbeddedMetadata, FbxHint,
+    FileHint, GlbHint, IsoHint, PakCompression, PakHint, PdfHint, Pk3Hint, SevenZipHint,
+    StreamWrapper, TarCompression, TarHint, TextureSampler, UassetHint, UassetPakParts, UrlHint,
+    UsdzHint, VpkHint, WadHint, WadLump, WrapMode, WrappedHint, XcfHint, ZipHint,
+};
 pub use registry::SourceRegistry;
+pub use retry::{RetryLater, RetryPolicy};
 pub use source::{BufReadSeek, Source};
+pub use sources::{
+    DEFAULT_SCAN_FILTER_FILE, DirectoryCounts, DirectoryEvent, DirectorySource, SCAN_FILTER_ENV,
+    ScanFilter, StdinSource, UrlSource, is_remote_url, is_stdin_path,
+};
 
-use sources::{FbxSource, GlbSource, ImageSource, ZipSource};
+use sources::{
+    ApkSource, AtlasSource, FbxSource, GlbSource, ImageSource, IsoSource, ObjSource, PakSource,
+    PdfSource, Pk3Source, PmxSource, SevenZipSource, TarSource, ThreeDsSource, UassetSource,
+    UsdzSource, VpkSource, WadSource, XcfSource, ZipSource,
+};
+
+/// How far into an entry dimensions are searched for when its header was cut short
+const HEADER_FALLBACK_LIMIT: usize = 4 * 1024 * 1024;
 
 /// Raw image data loaded by a source with pre-detected format and dimensions
 #[derive(Debug, Clone)]
@@ -26,6 +57,7 @@ pub struct LoadedImageData {
     pub format: imagesize::ImageType, // Pre-detected format (PNG, JPEG, etc.)
     pub width: usize,                 // Pre-detected width
     pub height: usize,                // Pre-detected height
+    pub transcode_target: parsers::TranscodeTarget, // Basis Universal KTX2 transcode format
 }
 
 /// Processed image information after parsing
@@ -35,12 +67,26 @@ pub struct ImageInfo {
     pub height: u32,
     pub file_size: u64,
     pub color_space: String,
+    pub hdr_headroom: f32, // Scale restoring scene-linear values from the stored texture (1.0 for LDR)
 }
 
 /// Trait for parsing raw image data into macroquad-compatible format
 pub trait ImageDataParser: Send + Sync {
     fn can_parse(&self, data: &LoadedImageData) -> bool;
     fn parse(&self, data: &LoadedImageData) -> Result<(Image, ImageInfo)>;
+
+    /// Layers of a texture array; 1 for plain 2D images
+    fn layer_count(&self, _data: &LoadedImageData) -> usize {
+        1
+    }
+
+    /// Decode one layer of a texture array; `parse` decodes layer 0
+    fn parse_layer(&self, data: &LoadedImageData, layer: usize) -> Result<(Image, ImageInfo)> {
+        if layer != 0 {
+            anyhow::bail!("{} has no layer {layer}", data.name);
+        }
+        self.parse(data)
+    }
 }
 
 /// Main texture loading pipeline - replaces the old TextureLoader entirely
@@ -61,12 +107,45 @@ impl Pipeline {
         let mut source_registry = SourceRegistry::new();
 
         // Add sources in priority order:
-        // 1. Container sources (GLB, FBX, ZIP) - handle specific formats first
+        // 0. Location sources - remote URLs are fetched, stdin read, folders walked, and
+        // their content handed to the sources below
+        source_registry.add_source(Box::new(UrlSource));
+        source_registry.add_source(Box::new(StdinSource));
+        source_registry.add_source(Box::new(DirectorySource));
+
+        // Plugin sources next, so they can claim formats built on ZIP and other containers
+        for source in plugins::plugin_sources() {
+            source_registry.add_shared_source(source);
+        }
+
+        // 1. Container sources (GLB, FBX, OBJ, PMX/PMD, 3DS, USDZ, APK, PK3, ZIP, TAR, 7z, ISO, PAK, VPK, WAD) - handle specific formats first
+        // USDZ, APK and PK3 must come before ZIP since all are ZIP archives
         source_registry.add_source(Box::new(GlbSource));
         source_registry.add_source(Box::new(FbxSource));
+        source_registry.add_source(Box::new(ObjSource));
+        source_registry.add_source(Box::new(PmxSource));
+        source_registry.add_source(Box::new(ThreeDsSource));
+        source_registry.add_source(Box::new(UsdzSource));
+        source_registry.add_source(Box::new(ApkSource));
+        source_registry.add_source(Box::new(Pk3Source));
         source_registry.add_source(Box::new(ZipSource));
-
-        // 2. Universal image source - handles all remaining image formats via imagesize
+        source_registry.add_source(Box::new(TarSource));
+        source_registry.add_source(Box::new(SevenZipSource));
+        source_registry.add_source(Box::new(IsoSource));
+        source_registry.add_source(Box::new(PakSource));
+        source_registry.add_source(Box::new(VpkSource));
+        source_registry.add_source(Box::new(WadSource));
+
+        // 2. Document sources - layered files flattened to a composite, PDF images extracted,
+        // cooked Unreal textures decoded to their top mip
+        source_registry.add_source(Box::new(XcfSource));
+        source_registry.add_source(Box::new(PdfSource));
+        source_registry.add_source(Box::new(UassetSource));
+
+        // 3. Sprite atlas descriptors - split the paired image into one entry per sprite
+        source_registry.add_source(Box::new(AtlasSource));
+
+        // 4. Universal image source - handles all remaining image formats via imagesize
         source_registry.add_source(Box::new(ImageSource));
 
         // Register data parsers
@@ -86,8 +165,8 @@ impl Pipeline {
     /// Fast metadata extraction for a single path using SourceRegistry
     /// Parse container ONCE and create hints with direct access information
     pub fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
-        if let Some(source) = self.source_registry.find_source(path) {
-            return source.extract_metadata(path);
+        if let Some(result) = self.source_registry.extract_metadata(path) {
+            return result;
         }
 
         // Return empty vec for unsupported formats instead of error
@@ -128,8 +207,8 @@ impl Pipeline {
 
         // Start with initial file paths - convert to metadata entries for uniform processing
         for path in paths {
-            if let Some(source) = self.source_registry.find_source(&path) {
-                match source.extract_metadata(&path) {
+            if let Some(result) = self.source_registry.extract_metadata(&path) {
+                match result {
                     Ok(metadata_list) => {
                         for meta in metadata_list {
                             processing_queue.push_back(meta);
@@ -152,12 +231,16 @@ impl Pipeline {
                 let header_vec = header.to_vec(); // Copy to avoid borrow issues
                 // Try direct image detection first
                 if let Ok(detected_format) = imagesize::image_type(&header_vec) {
-                    // This is a direct image - finalize and add to results
+                    // This is a direct image - finalize with its real format and size so
+                    // placeholders and hover info are right before the pixels load
                     let mut updated_meta = meta;
                     updated_meta.format = detected_format;
-                    if let Ok(size) = imagesize::blob_size(&header_vec) {
-                        updated_meta.width = size.width;
-                        updated_meta.height = size.height;
+                    match self.header_dimensions(&updated_meta, &header_vec) {
+                        Some((width, height)) => {
+                            updated_meta.width = width;
+                            updated_meta.height = height;
+                        }
+                        None => log::debug!("No dimensions found for {}", updated_meta.name),
                     }
                     final_metadata.push(updated_meta);
                 } else {
@@ -174,11 +257,7 @@ impl Pipeline {
                         );
 
                         // Load container data and extract its contents
-                        if let Some(parent_source) =
-                            self.source_registry.find_source(&meta.source_path)
-                            && let Ok(container_data) =
-                                parent_source.load_bytes(meta.embedded_hint.as_ref())
-                        {
+                        if let Ok(container_data) = self.load_bytes(&meta) {
                             let mut container_cursor = std::io::Cursor::new(&container_data);
                             if let Ok(expanded_metadata) = container_source
                                 .extract_metadata_from_reader(
@@ -187,8 +266,14 @@ impl Pipeline {
                                     &meta.source_path,
                                 )
                             {
-                                // Push expanded entries to back of queue for processing
-                                for expanded_meta in expanded_metadata {
+                                // Push expanded entries to back of queue for processing,
+                                // remembering the container they came from
+                                let mut parents = meta.parents.clone();
+                                parents.push(ContainerSegment {
+                                    name: meta.name.clone(),
+                                });
+                                for mut expanded_meta in expanded_metadata {
+                                    expanded_meta.parents = parents.clone();
                                     processing_queue.push_back(expanded_meta);
                                 }
                                 log::info!(
@@ -198,6 +283,33 @@ impl Pipeline {
                                 );
                             }
                         }
+                    } else if let Some(wrapper) = StreamWrapper::detect(&header_vec) {
+                        // Compressed single-stream entry - unwrap and re-dispatch
+                        match self.load_bytes(&meta) {
+                            Ok(wrapped_data) => {
+                                let expanded = self.source_registry.extract_unwrapped(
+                                    wrapper,
+                                    &wrapped_data,
+                                    &meta.name,
+                                    &meta.source_path,
+                                    true,
+                                );
+                                match expanded {
+                                    // Unwrapping doesn't add a level; the inner stream
+                                    // stands in for the wrapped entry
+                                    Ok(expanded_metadata) => processing_queue.extend(
+                                        expanded_metadata.into_iter().map(|mut expanded| {
+                                            expanded.parents = meta.parents.clone();
+                                            expanded
+                                        }),
+                                    ),
+                                    Err(e) => {
+                                        log::warn!("Failed to unwrap {}: {}", meta.name, e)
+                                    }
+                                }
+                            }
+                            Err(e) => log::warn!("Failed to load {}: {}", meta.name, e),
+                        }
                     } else {
                         // Unknown format - skip
                         log::debug!("Skipping unknown format: {}", meta.name);
@@ -212,6 +324,29 @@ impl Pipeline {
         final_metadata
     }
 
+    /// Dimensions of an image entry from its header bytes, reading further into the entry
+    /// when the header was cut off before them (e.g. JPEGs with large EXIF/ICC blocks).
+    /// Entries that can't be read by range are left unsized until they load
+    fn header_dimensions(
+        &self,
+        metadata: &EmbeddedMetadata,
+        header: &[u8],
+    ) -> Option<(usize, usize)> {
+        if let Ok(size) = imagesize::blob_size(header) {
+            return Some((size.width, size.height));
+        }
+        if header.len() as u64 >= metadata.file_size {
+            return None;
+        }
+
+        let data = self
+            .read_range_direct(metadata, 0, HEADER_FALLBACK_LIMIT)?
+            .ok()?;
+        imagesize::blob_size(&data)
+            .ok()
+            .map(|size| (size.width, size.height))
+    }
+
     /// Phase 4: Async Load Phase - Load raw image data using hint
     /// Uses the hint system to efficiently load specific content using direct access
     pub fn load_bytes(&self, metadata: &EmbeddedMetadata) -> Result<Vec<u8>> {
@@ -233,15 +368,144 @@ impl Pipeline {
             return Ok(fbx_hint.texture_data.clone());
         }
 
-        // Find the source that can handle this hint
-        if let Some(source) = self.source_registry.find_source(&metadata.source_path) {
-            return source.load_bytes(metadata.embedded_hint.as_ref());
+        if let Some(xcf_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::XcfHint>()
+            && let Some(ref flattened_data) = xcf_hint.flattened_data
+        {
+            return Ok(flattened_data.clone());
+        }
+
+        if let Some(tar_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::TarHint>()
+            && let Some(ref entry_data) = tar_hint.entry_data
+        {
+            return Ok(entry_data.clone());
         }
 
-        anyhow::bail!(
-            "No source found for file: {}",
-            metadata.source_path.display()
-        );
+        if let Some(sevenz_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::SevenZipHint>()
+            && let Some(ref entry_data) = sevenz_hint.entry_data
+        {
+            return Ok(entry_data.clone());
+        }
+
+        if let Some(iso_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::IsoHint>()
+            && let Some(ref entry_data) = iso_hint.entry_data
+        {
+            return Ok(entry_data.clone());
+        }
+
+        if let Some(usdz_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::UsdzHint>()
+            && let Some(ref entry_data) = usdz_hint.entry_data
+        {
+            return Ok(entry_data.clone());
+        }
+
+        if let Some(pak_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::PakHint>()
+            && let Some(ref entry_data) = pak_hint.entry_data
+        {
+            return Ok(entry_data.clone());
+        }
+
+        if let Some(uasset_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::UassetHint>()
+            && let Some(ref pixel_data) = uasset_hint.pixel_data
+        {
+            return Ok(pixel_data.clone());
+        }
+
+        if let Some(pdf_hint) = metadata
+            .embedded_hint
+            .as_any()
+            .downcast_ref::<crate::texture_pipeline::PdfHint>()
+            && let Some(ref image_data) = pdf_hint.image_data
+        {
+            return Ok(image_data.clone());
+        }
+
+        // Everything below reads the file on disk, which may be on a network drive or
+        // an online-only cloud placeholder, so transient failures are retried
+        RetryPolicy::default().run(&metadata.source_path, || {
+            if let Some(wrapped_hint) = metadata
+                .embedded_hint
+                .as_any()
+                .downcast_ref::<crate::texture_pipeline::WrappedHint>()
+            {
+                return wrapper::load_wrapped(wrapped_hint);
+            }
+
+            // Find the source that can handle this hint
+            if let Some(source) = self.source_registry.find_source(&metadata.source_path) {
+                return source.load_bytes(metadata.embedded_hint.as_ref());
+            }
+
+            // Sources reject files they can't open; surface that error so it can be retried
+            vfs::open(&metadata.source_path)?;
+            anyhow::bail!(
+                "No source found for file: {}",
+                metadata.source_path.display()
+            );
+        })
+    }
+
+    /// Read `length` bytes at `offset` within an entry, cut short at its end
+    /// Entries stored contiguously in a file are read by range; the rest load whole
+    pub fn read_range(
+        &self,
+        metadata: &EmbeddedMetadata,
+        offset: u64,
+        length: usize,
+    ) -> Result<Vec<u8>> {
+        if let Some(result) = self.read_range_direct(metadata, offset, length) {
+            return result;
+        }
+
+        let data = self.load_bytes(metadata)?;
+        Ok(source::slice_range(&data, offset, length).to_vec())
+    }
+
+    /// `read_range` for entries that can be read by range, or `None` when reading
+    /// any part of the entry would mean loading all of it
+    fn read_range_direct(
+        &self,
+        metadata: &EmbeddedMetadata,
+        offset: u64,
+        length: usize,
+    ) -> Option<Result<Vec<u8>>> {
+        let hint = metadata.embedded_hint.as_ref();
+        if let Some(slice) = hint.file_slice() {
+            return Some(
+                RetryPolicy::default()
+                    .run(slice.0, || source::read_file_slice(slice, offset, length)),
+            );
+        }
+
+        if hint.as_any().is::<FileHint>()
+            && let Some(source) = self.source_registry.find_source(&metadata.source_path)
+        {
+            return Some(RetryPolicy::default().run(&metadata.source_path, || {
+                source.read_range(hint, offset, length)
+            }));
+        }
+
+        None
     }
 
     /// Parse loaded image data to macroquad format
@@ -256,22 +520,75 @@ impl Pipeline {
         anyhow::bail!("No parser found for image format: {:?}", data.format);
     }
 
+    /// Number of texture array layers in loaded image data
+    pub fn layer_count(&self, data: &LoadedImageData) -> usize {
+        self.parsers
+            .iter()
+            .find(|parser| parser.can_parse(data))
+            .map_or(1, |parser| parser.layer_count(data))
+    }
+
+    /// Parse one layer of a texture array to macroquad format
+    pub fn parse_image_layer(
+        &self,
+        data: &LoadedImageData,
+        layer: usize,
+    ) -> Result<(Image, ImageInfo)> {
+        for parser in &self.parsers {
+            if parser.can_parse(data) {
+                return parser.parse_layer(data, layer);
+            }
+        }
+
+        anyhow::bail!("No parser found for image format: {:?}", data.format);
+    }
+
     /// Convenience method: Convert EmbeddedMetadata to LoadedImageData
     /// This combines the load_bytes and metadata phases for easier usage
     pub fn metadata_to_loaded_data(&self, metadata: &EmbeddedMetadata) -> Result<LoadedImageData> {
         let data = self.load_bytes(metadata)?;
+        let (format, width, height) = if metadata.embedded_hint.deferred_probe() {
+            Self::probe_deferred(metadata, &data)?
+        } else {
+            (metadata.format, metadata.width, metadata.height)
+        };
 
         Ok(LoadedImageData {
             name: metadata.name.clone(),
             data,
             file_size: metadata.file_size as usize,
             source_file: metadata.source_path.clone(),
-            format: metadata.format,
-            width: metadata.width,
-            height: metadata.height,
+            format,
+            width,
+            height,
+            transcode_target: parsers::TranscodeTarget::default(),
         })
     }
 
+    /// Fill in the format and size of a lazily listed entry by reading its bytes
+    pub fn resolve_deferred(&self, metadata: &mut EmbeddedMetadata) -> Result<()> {
+        if !metadata.embedded_hint.deferred_probe() {
+            return Ok(());
+        }
+
+        let data = self.load_bytes(metadata)?;
+        (metadata.format, metadata.width, metadata.height) = Self::probe_deferred(metadata, &data)?;
+        Ok(())
+    }
+
+    /// Format and dimensions of a lazily listed entry from its loaded bytes
+    fn probe_deferred(
+        metadata: &EmbeddedMetadata,
+        data: &[u8],
+    ) -> Result<(imagesize::ImageType, usize, usize)> {
+        let format = imagesize::image_type(data)
+            .with_context(|| format!("{} is not a supported image", metadata.name))?;
+        let (width, height) = imagesize::blob_size(data)
+            .map(|size| (size.width, size.height))
+            .unwrap_or((0, 0));
+        Ok((format, width, height))
+    }
+
     /// Process raw extracted data to detect containers/images recursively
     /// This is the core method for recursive container support
     pub fn extract_metadata_from_reader(
@@ -284,6 +601,22 @@ impl Pipeline {
             return source.extract_metadata_from_reader(reader, entry_name, parent_source_path);
         }
 
+        // Single-file compression wrappers (e.g. texture.png.gz) re-enter format detection
+        let mut header = [0u8; 4];
+        let header_len = reader.read(&mut header)?;
+        reader.seek(std::io::SeekFrom::Start(0))?;
+        if let Some(wrapper) = StreamWrapper::detect(&header[..header_len]) {
+            let mut wrapped_data = Vec::new();
+            reader.read_to_end(&mut wrapped_data)?;
+            return self.source_registry.extract_unwrapped(
+                wrapper,
+                &wrapped_data,
+                entry_name,
+                parent_source_path,
+                true,
+            );
+        }
+
         // Return empty vec for unsupported formats instead of error
         log::debug!(
             "No source found for raw data entry: {} from {}",
@@ -4,8 +4,118 @@ use macroquad::prelude::*;
 
 use crate::texture_pipeline::{ImageDataParser, ImageInfo, LoadedImageData};
 
+/// DDSCAPS2_CUBEMAP in the legacy header's caps2 field
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+
+/// D3D11_RESOURCE_MISC_TEXTURECUBE in the DX10 header's misc flags
+const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
 pub struct CompressedFormat;
 
+/// Where the surfaces of a DDS file sit: array layers, or the six faces of a cubemap,
+/// each followed by its mip chain
+pub struct DdsLayout {
+    pub data_offset: usize,    // First surface, after the header (and DX10 header)
+    pub surface_stride: usize, // Bytes per surface including its mips
+    pub level_size: usize,     // Bytes of a surface's top mip
+    pub array_size: usize,     // Layers from the DX10 header, 1 for legacy files
+    pub is_cubemap: bool,
+}
+
+impl DdsLayout {
+    /// Read the surface layout from a DDS header; None for headerless payloads
+    /// (e.g. decoded VTF/Unreal mips) and unknown compression
+    pub fn parse(data: &LoadedImageData, compression: DdsCompression) -> Option<Self> {
+        let bytes = &data.data;
+        if !bytes.starts_with(b"DDS ") {
+            return None;
+        }
+        let read_u32 = |offset: usize| {
+            bytes
+                .get(offset..offset + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        };
+
+        // Header fields are offset by the 4-byte "DDS " magic
+        let mip_count = read_u32(28)?.max(1) as usize;
+        let (data_offset, array_size, is_cubemap) = if bytes.get(84..88) == Some(&b"DX10"[..]) {
+            let misc_flags = read_u32(136)?;
+            let array_size = read_u32(140)?.max(1) as usize;
+            (
+                148,
+                array_size,
+                misc_flags & DDS_RESOURCE_MISC_TEXTURECUBE != 0,
+            )
+        } else {
+            (128, 1, read_u32(112)? & DDSCAPS2_CUBEMAP != 0)
+        };
+
+        let level_size = |level: usize| {
+            let width = (data.width >> level).max(1);
+            let height = (data.height >> level).max(1);
+            dds_level_size(compression, width, height)
+        };
+
+        Some(Self {
+            data_offset,
+            surface_stride: (0..mip_count).map(level_size).sum::<Option<usize>>()?,
+            level_size: level_size(0)?,
+            array_size,
+            is_cubemap,
+        })
+    }
+
+    /// Top mip of the `index`th surface, as raw block data
+    pub fn surface<'a>(&self, data: &'a [u8], index: usize) -> Option<&'a [u8]> {
+        let start = self.data_offset + index * self.surface_stride;
+        data.get(start..start + self.level_size)
+    }
+}
+
+/// Block-compressed formats the GPU can sample directly
+/// Limited to formats whose hardware decode displays the same as the CPU decoders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuFormat {
+    Bc1,
+    Bc3,
+    Bc7,
+    Etc2Rgb,
+    Etc2RgbA1,
+    Etc2Rgba8,
+    Astc { block_width: u8, block_height: u8 },
+}
+
+/// Top mip of a compressed texture, ready for a compressed texture upload
+pub struct GpuPayload {
+    pub format: GpuFormat,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+/// PKM ("PKM 10"/"PKM 20") header before ETC block data
+const PKM_HEADER_SIZE: usize = 16;
+
+/// .astc header: magic, block dimensions and image size
+const ASTC_MAGIC: [u8; 4] = [0x13, 0xAB, 0xA1, 0x5C];
+const ASTC_HEADER_SIZE: usize = 16;
+
+/// Bytes in one mip level of a DDS surface
+fn dds_level_size(compression: DdsCompression, width: usize, height: usize) -> Option<usize> {
+    let blocks = width.div_ceil(4) * height.div_ceil(4);
+    match compression {
+        DdsCompression::Bc1 | DdsCompression::Bc4 => Some(blocks * 8),
+        DdsCompression::Bc2
+        | DdsCompression::Bc3
+        | DdsCompression::Bc5
+        | DdsCompression::Bc6h
+        | DdsCompression::Bc7 => Some(blocks * 16),
+        DdsCompression::Rgba32 => Some(width * height * 4),
+        DdsCompression::Rgb24 => Some(width * height * 3),
+        DdsCompression::Unknown => None,
+    }
+}
+
 impl ImageDataParser for CompressedFormat {
     fn can_parse(&self, data: &LoadedImageData) -> bool {
         matches!(
@@ -46,13 +156,99 @@ impl ImageDataParser for CompressedFormat {
             height: data.height as u32,
             file_size: data.file_size as u64,
             color_space,
+            hdr_headroom: 1.0,
         };
 
         Ok((macroquad_image, info))
     }
+
+    fn layer_count(&self, data: &LoadedImageData) -> usize {
+        match data.format {
+            // Cubemaps are browsed through their face layouts instead
+            ImageType::Dds(compression) => DdsLayout::parse(data, compression)
+                .filter(|layout| !layout.is_cubemap)
+                .map_or(1, |layout| layout.array_size),
+            _ => 1,
+        }
+    }
+
+    fn parse_layer(&self, data: &LoadedImageData, layer: usize) -> Result<(Image, ImageInfo)> {
+        if layer == 0 {
+            return self.parse(data);
+        }
+
+        let ImageType::Dds(compression) = data.format else {
+            return Err(anyhow!("{} has no layer {}", data.name, layer));
+        };
+        let surface = DdsLayout::parse(data, compression)
+            .filter(|layout| layer < layout.array_size)
+            .and_then(|layout| layout.surface(&data.data, layer))
+            .ok_or_else(|| anyhow!("{} has no layer {}", data.name, layer))?;
+
+        self.parse(&LoadedImageData {
+            data: surface.to_vec(),
+            ..data.clone()
+        })
+    }
 }
 
 impl CompressedFormat {
+    /// Raw block data of the top mip with its GPU format, for formats that can skip
+    /// the CPU decode; None when the format or its container header isn't supported
+    pub fn gpu_payload(data: &LoadedImageData) -> Option<GpuPayload> {
+        let bytes = &data.data;
+        let (format, blocks) = match data.format {
+            ImageType::Dds(compression) => {
+                let format = match compression {
+                    DdsCompression::Bc1 => GpuFormat::Bc1,
+                    DdsCompression::Bc3 => GpuFormat::Bc3,
+                    DdsCompression::Bc7 => GpuFormat::Bc7,
+                    _ => return None,
+                };
+                // Files carry a header; decoded VTF/Unreal mips are bare block data
+                let blocks = match DdsLayout::parse(data, compression) {
+                    Some(layout) => layout.surface(bytes, 0)?,
+                    None if !bytes.starts_with(b"DDS ") => bytes.as_slice(),
+                    None => return None,
+                };
+                (format, blocks)
+            }
+            ImageType::Etc2(compression) => {
+                let format = match compression {
+                    PkmCompression::Etc2 => GpuFormat::Etc2Rgb,
+                    PkmCompression::Etc2A1 => GpuFormat::Etc2RgbA1,
+                    PkmCompression::Etc2A8 => GpuFormat::Etc2Rgba8,
+                    _ => return None,
+                };
+                let blocks = if bytes.starts_with(b"PKM ") {
+                    bytes.get(PKM_HEADER_SIZE..)?
+                } else {
+                    bytes.as_slice()
+                };
+                (format, blocks)
+            }
+            ImageType::Astc => {
+                // Block size is only known from the .astc header
+                if !bytes.starts_with(&ASTC_MAGIC) {
+                    return None;
+                }
+                let format = GpuFormat::Astc {
+                    block_width: *bytes.get(4)?,
+                    block_height: *bytes.get(5)?,
+                };
+                (format, bytes.get(ASTC_HEADER_SIZE..)?)
+            }
+            _ => return None,
+        };
+
+        Some(GpuPayload {
+            format,
+            width: data.width as u32,
+            height: data.height as u32,
+            data: blocks.to_vec(),
+        })
+    }
+
     fn decompress_texture(&self, data: &LoadedImageData) -> Result<(Vec<u8>, String)> {
         let width = data.width;
         let height = data.height;
@@ -1,9 +1,75 @@
 use anyhow::Result;
-use imagesize::ImageType;
+use imagesize::{DdsCompression, ImageType, PkmCompression};
 use macroquad::prelude::*;
 
+use crate::texture_pipeline::parsers::CompressedFormat;
 use crate::texture_pipeline::{ImageDataParser, ImageInfo, LoadedImageData};
 
+/// Environment variable selecting the startup Basis transcode target (rgba, bc7, astc or etc2)
+pub const TRANSCODE_TARGET_ENV: &str = "GTEXVIEWER_TRANSCODE";
+
+/// Format Basis Universal KTX2 textures are transcoded to
+/// GPU formats are decoded back to RGBA for display, so their artifacts show as on device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TranscodeTarget {
+    #[default]
+    Rgba32, // Uncompressed, the reference quality
+    Bc7,     // Desktop GPUs
+    Astc4x4, // Modern mobile GPUs
+    Etc2,    // Older mobile GPUs (ETC2 RGBA8)
+}
+
+impl TranscodeTarget {
+    /// Startup target from `$GTEXVIEWER_TRANSCODE`, RGBA32 when unset or unknown
+    pub fn from_env() -> Self {
+        match std::env::var(TRANSCODE_TARGET_ENV)
+            .map(|value| value.to_lowercase())
+            .as_deref()
+        {
+            Ok("bc7") => TranscodeTarget::Bc7,
+            Ok("astc") => TranscodeTarget::Astc4x4,
+            Ok("etc2") => TranscodeTarget::Etc2,
+            _ => TranscodeTarget::Rgba32,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            TranscodeTarget::Rgba32 => TranscodeTarget::Bc7,
+            TranscodeTarget::Bc7 => TranscodeTarget::Astc4x4,
+            TranscodeTarget::Astc4x4 => TranscodeTarget::Etc2,
+            TranscodeTarget::Etc2 => TranscodeTarget::Rgba32,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TranscodeTarget::Rgba32 => "RGBA32",
+            TranscodeTarget::Bc7 => "BC7",
+            TranscodeTarget::Astc4x4 => "ASTC 4x4",
+            TranscodeTarget::Etc2 => "ETC2",
+        }
+    }
+
+    /// Basis transcoder output and the compressed format it's decoded from for display
+    fn formats(self) -> Option<(ktx2_rw::TranscodeFormat, ImageType)> {
+        match self {
+            TranscodeTarget::Rgba32 => None,
+            TranscodeTarget::Bc7 => Some((
+                ktx2_rw::TranscodeFormat::Bc7Rgba,
+                ImageType::Dds(DdsCompression::Bc7),
+            )),
+            TranscodeTarget::Astc4x4 => {
+                Some((ktx2_rw::TranscodeFormat::Astc4x4Rgba, ImageType::Astc))
+            }
+            TranscodeTarget::Etc2 => Some((
+                ktx2_rw::TranscodeFormat::Etc2Rgba,
+                ImageType::Etc2(PkmCompression::Etc2A8),
+            )),
+        }
+    }
+}
+
 pub struct Ktx2Format;
 
 impl ImageDataParser for Ktx2Format {
@@ -12,19 +78,47 @@ impl ImageDataParser for Ktx2Format {
     }
 
     fn parse(&self, data: &LoadedImageData) -> Result<(Image, ImageInfo)> {
+        self.parse_layer(data, 0)
+    }
+
+    fn layer_count(&self, data: &LoadedImageData) -> usize {
+        ktx2_rw::Ktx2Texture::from_memory(&data.data)
+            .map(|ktx2| (ktx2.layers() as usize).max(1))
+            .unwrap_or(1)
+    }
+
+    fn parse_layer(&self, data: &LoadedImageData, layer: usize) -> Result<(Image, ImageInfo)> {
         // Parse KTX2 file
         let mut ktx2 = ktx2_rw::Ktx2Texture::from_memory(&data.data)?;
 
         let width = ktx2.width();
         let height = ktx2.height();
 
+        // Basis Universal goes through the chosen GPU format, decoded back on the CPU
+        if ktx2.needs_transcoding()
+            && let Some((transcode_format, compressed_format)) = data.transcode_target.formats()
+        {
+            ktx2.transcode_basis(transcode_format)?;
+            let compressed = LoadedImageData {
+                data: ktx2.get_image_data(0, layer as u32, 0)?.to_vec(),
+                format: compressed_format,
+                width: width as usize,
+                height: height as usize,
+                ..data.clone()
+            };
+
+            let (image, mut info) = CompressedFormat.parse(&compressed)?;
+            info.color_space = format!("{} (Basis transcode)", info.color_space);
+            return Ok((image, info));
+        }
+
         // Transcode basis universal to RGBA8 if needed
         if ktx2.needs_transcoding() {
             ktx2.transcode_basis(ktx2_rw::TranscodeFormat::Rgba32)?;
         }
 
         // Get raw image data
-        let image_data = ktx2.get_image_data(0, 0, 0)?;
+        let image_data = ktx2.get_image_data(0, layer as u32, 0)?;
 
         // Create macroquad Image from raw data
         let macroquad_image = Image {
@@ -38,6 +132,7 @@ impl ImageDataParser for Ktx2Format {
             height,
             file_size: data.file_size as u64,
             color_space: "RGBA".to_string(), // KTX2 transcoded to RGBA
+            hdr_headroom: 1.0,
         };
 
         Ok((macroquad_image, info))
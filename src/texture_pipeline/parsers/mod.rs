@@ -2,6 +2,6 @@ mod compressed;
 mod ktx2;
 mod standard;
 
-pub use compressed::CompressedFormat;
-pub use ktx2::Ktx2Format;
+pub use compressed::{CompressedFormat, DdsLayout, GpuFormat, GpuPayload};
+pub use ktx2::{Ktx2Format, TRANSCODE_TARGET_ENV, TranscodeTarget};
 pub use standard::StandardFormat;
@@ -39,14 +39,17 @@ impl ImageDataParser for StandardFormat {
         };
 
         // Convert to macroquad Image
-        let rgba_img = dynamic_image.to_rgba8();
-
-        let (width, height) = rgba_img.dimensions();
+        let (width, height) = (dynamic_image.width(), dynamic_image.height());
+        let (bytes, hdr_headroom) = if matches!(data.format, ImageType::Hdr | ImageType::Exr) {
+            self.encode_scene_linear(&dynamic_image)
+        } else {
+            (dynamic_image.to_rgba8().into_raw(), 1.0)
+        };
 
         let macroquad_image = Image {
-            width: rgba_img.width() as u16,
-            height: rgba_img.height() as u16,
-            bytes: rgba_img.into_raw(),
+            width: width as u16,
+            height: height as u16,
+            bytes,
         };
 
         // Detect color space from the parsed image
@@ -57,13 +60,45 @@ impl ImageDataParser for StandardFormat {
             height,
             file_size: data.file_size as u64,
             color_space,
+            hdr_headroom,
         };
 
         Ok((macroquad_image, info))
     }
 }
 
+/// Upper bound for HDR headroom so 8-bit storage keeps usable precision near black
+const MAX_HDR_HEADROOM: f32 = 64.0;
+
 impl StandardFormat {
+    /// Store scene-linear HDR data as sRGB-encoded 8-bit, scaled down by the image peak
+    /// The display shader multiplies the headroom back in before applying the view transform
+    fn encode_scene_linear(&self, img: &DynamicImage) -> (Vec<u8>, f32) {
+        let rgba = img.to_rgba32f();
+
+        let peak = rgba
+            .pixels()
+            .flat_map(|pixel| pixel.0[..3].iter().copied())
+            .filter(|value| value.is_finite())
+            .fold(0.0_f32, f32::max);
+        let headroom = peak.clamp(1.0, MAX_HDR_HEADROOM);
+
+        let bytes = rgba
+            .pixels()
+            .flat_map(|pixel| {
+                let [r, g, b, a] = pixel.0;
+                [
+                    linear_to_srgb_u8(r / headroom),
+                    linear_to_srgb_u8(g / headroom),
+                    linear_to_srgb_u8(b / headroom),
+                    (a.clamp(0.0, 1.0) * 255.0).round() as u8,
+                ]
+            })
+            .collect();
+
+        (bytes, headroom)
+    }
+
     fn detect_color_space(&self, img: &DynamicImage) -> String {
         match img {
             DynamicImage::ImageLuma8(_) => "Grayscale",
@@ -81,3 +116,17 @@ impl StandardFormat {
         .to_string()
     }
 }
+
+fn linear_to_srgb_u8(value: f32) -> u8 {
+    let v = if value.is_finite() {
+        value.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let encoded = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
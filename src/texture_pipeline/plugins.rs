@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::texture_pipeline::Source;
+
+/// Bumped whenever `Source`, `EmbeddedHint` or the registration entry points change;
+/// libraries built against another version are skipped
+pub const PLUGIN_API_VERSION: u32 = 2;
+
+/// gTexViewer and compiler version a plugin was built with, NUL-terminated for the
+/// C entry point. Sources cross the library boundary as Rust trait objects, whose
+/// layout is only stable within one compiler and crate version, so both must match
+pub const PLUGIN_ABI: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " / ",
+    env!("GTEXVIEWER_RUSTC_VERSION"),
+    "\0"
+);
+
+/// Environment variable overriding the folder plugin libraries are loaded from
+pub const PLUGINS_ENV: &str = "GTEXVIEWER_PLUGINS";
+
+/// Folder under the config directory holding plugin libraries
+pub const DEFAULT_PLUGINS_DIR: &str = "plugins";
+
+/// Sources added by plugins, shared by every pipeline created afterwards
+static PLUGIN_SOURCES: OnceLock<RwLock<Vec<Arc<dyn Source>>>> = OnceLock::new();
+
+fn plugin_sources_lock() -> &'static RwLock<Vec<Arc<dyn Source>>> {
+    PLUGIN_SOURCES.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Handed to a plugin's register function to collect its sources
+#[derive(Default)]
+pub struct PluginRegistrar {
+    sources: Vec<Arc<dyn Source>>,
+}
+
+impl PluginRegistrar {
+    pub fn add_source(&mut self, source: Box<dyn Source>) {
+        self.sources.push(Arc::from(source));
+    }
+}
+
+/// Register a source compiled into the binary; call before the first `Pipeline::new`
+/// Embedders linking gTexViewer as a library use this instead of a dynamic plugin
+pub fn register_source(source: Box<dyn Source>) {
+    if let Ok(mut sources) = plugin_sources_lock().write() {
+        sources.push(Arc::from(source));
+    }
+}
+
+/// Sources registered so far, in registration order
+pub fn plugin_sources() -> Vec<Arc<dyn Source>> {
+    plugin_sources_lock()
+        .read()
+        .map(|sources| sources.clone())
+        .unwrap_or_default()
+}
+
+/// `$GTEXVIEWER_PLUGINS`, else `plugins` in the config folder
+pub fn plugins_dir() -> Option<PathBuf> {
+    match std::env::var(PLUGINS_ENV) {
+        Ok(path) => Some(crate::portable::resolve_config_path(PathBuf::from(path))),
+        Err(_) => crate::portable::config_dir().map(|dir| dir.join(DEFAULT_PLUGINS_DIR)),
+    }
+}
+
+/// Declare a dynamic plugin: exports the API version, the build's `PLUGIN_ABI` and a
+/// register function calling `$register(&mut PluginRegistrar)`. Build the crate as a
+/// `cdylib` with the same compiler and gTexViewer version as the host; libraries whose
+/// ABI string differs are refused rather than loaded
+#[macro_export]
+macro_rules! declare_plugin {
+    ($register:path) => {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn gtexviewer_plugin_api_version() -> u32 {
+            $crate::texture_pipeline::plugins::PLUGIN_API_VERSION
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn gtexviewer_plugin_abi() -> *const ::std::ffi::c_char {
+            $crate::texture_pipeline::plugins::PLUGIN_ABI
+                .as_ptr()
+                .cast()
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn gtexviewer_register_plugin(
+            registrar: &mut $crate::texture_pipeline::plugins::PluginRegistrar,
+        ) {
+            $register(registrar)
+        }
+    };
+}
+
+/// Load every plugin library in the plugins folder; call once at startup
+#[cfg(feature = "plugins")]
+pub fn init() {
+    let Some(dir) = plugins_dir() else {
+        return;
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return; // No plugins installed
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        match load_library(&path) {
+            Ok(count) => log::info!("🔌 Loaded plugin {} ({count} sources)", path.display()),
+            Err(e) => log::warn!("Skipping plugin {}: {e:#}", path.display()),
+        }
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+pub fn init() {}
+
+/// Open a plugin library, check its API version and register its sources
+/// The library is never unloaded: its code backs the registered sources and hints
+#[cfg(feature = "plugins")]
+fn load_library(path: &std::path::Path) -> anyhow::Result<usize> {
+    use anyhow::Context;
+
+    type VersionFn = extern "C" fn() -> u32;
+    type AbiFn = extern "C" fn() -> *const std::ffi::c_char;
+    type RegisterFn = extern "C" fn(&mut PluginRegistrar);
+
+    // SAFETY: plugins are trusted code the user installed; initialisers run on load
+    let library = unsafe { libloading::Library::new(path) }.context("Failed to load library")?;
+
+    // SAFETY: the symbols are declared by `declare_plugin!` with these signatures
+    let api_version = *unsafe { library.get::<VersionFn>(b"gtexviewer_plugin_api_version") }
+        .context("Not a gTexViewer plugin (missing gtexviewer_plugin_api_version)")?;
+    let version = api_version();
+    if version != PLUGIN_API_VERSION {
+        anyhow::bail!("Built for plugin API {version}, this build uses {PLUGIN_API_VERSION}");
+    }
+    let abi = *unsafe { library.get::<AbiFn>(b"gtexviewer_plugin_abi") }
+        .context("Missing gtexviewer_plugin_abi")?;
+    // SAFETY: `declare_plugin!` returns a pointer to the NUL-terminated `PLUGIN_ABI`
+    let plugin_abi = unsafe { std::ffi::CStr::from_ptr(abi()) }.to_string_lossy();
+    let host_abi = PLUGIN_ABI.trim_end_matches('\0');
+    if plugin_abi != host_abi {
+        anyhow::bail!("Built with {plugin_abi}, this build is {host_abi}; rebuild the plugin");
+    }
+    let register = *unsafe { library.get::<RegisterFn>(b"gtexviewer_register_plugin") }
+        .context("Missing gtexviewer_register_plugin")?;
+
+    let mut registrar = PluginRegistrar::default();
+    register(&mut registrar);
+    let count = registrar.sources.len();
+    if let Ok(mut sources) = plugin_sources_lock().write() {
+        sources.extend(registrar.sources);
+    }
+
+    std::mem::forget(library);
+    Ok(count)
+}
@@ -0,0 +1,174 @@
+use image::{DynamicImage, GrayImage, RgbImage};
+use imagesize::ImageType;
+use jpeg_decoder::{Decoder, PixelFormat};
+use macroquad::prelude::Image;
+use std::io::Read;
+
+use crate::texture_pipeline::LoadedImageData;
+
+/// Images below this many pixels decode fast enough that a preview isn't worth it
+const PREVIEW_MIN_PIXELS: usize = 4 * 1024 * 1024;
+
+const MARKER_SOF2: u8 = 0xC2; // Start of frame, progressive DCT
+const MARKER_SOS: u8 = 0xDA; // Start of scan
+const MARKER_EOI: u8 = 0xD9; // End of image
+const MARKER_APP1: u8 = 0xE1; // EXIF
+
+const EXIF_JPEG_OFFSET_TAG: u16 = 0x0201; // JPEGInterchangeFormat
+const EXIF_JPEG_LENGTH_TAG: u16 = 0x0202; // JPEGInterchangeFormatLength
+
+/// Coarse stand-in for a large JPEG, available well before the full decode finishes:
+/// the embedded EXIF thumbnail, else the first scan of a progressive file at 1/8 scale
+pub fn decode_preview(data: &LoadedImageData) -> Option<Image> {
+    if data.format != ImageType::Jpeg || data.width * data.height < PREVIEW_MIN_PIXELS {
+        return None;
+    }
+
+    let markers = JpegMarkers::scan(&data.data)?;
+    let preview = markers.exif.and_then(decode_exif_thumbnail).or_else(|| {
+        markers
+            .first_scan
+            .and_then(|end| decode_first_scan(&data.data, end))
+    })?;
+
+    let rgba = preview.to_rgba8();
+    Some(Image {
+        width: rgba.width() as u16,
+        height: rgba.height() as u16,
+        bytes: rgba.into_raw(),
+    })
+}
+
+/// Where the preview sources sit in a JPEG stream
+#[derive(Default)]
+struct JpegMarkers<'a> {
+    first_scan: Option<usize>, // End of the first scan of a progressive file
+    exif: Option<&'a [u8]>,    // TIFF payload of the EXIF segment
+}
+
+impl<'a> JpegMarkers<'a> {
+    fn scan(bytes: &'a [u8]) -> Option<Self> {
+        if !bytes.starts_with(&[0xFF, 0xD8]) {
+            return None;
+        }
+
+        let mut markers = Self::default();
+        let mut progressive = false;
+        let mut pos = 2;
+
+        while pos + 4 <= bytes.len() {
+            if bytes[pos] != 0xFF {
+                return Some(markers);
+            }
+            let marker = bytes[pos + 1];
+            if marker == 0xFF {
+                // Fill byte
+                pos += 1;
+                continue;
+            }
+
+            let length = u16::from_be_bytes([bytes[pos + 2], bytes[pos + 3]]) as usize;
+            let segment = bytes.get(pos + 4..pos + 2 + length)?;
+
+            match marker {
+                MARKER_SOF2 => progressive = true,
+                MARKER_APP1 => {
+                    markers.exif = markers.exif.or(segment.strip_prefix(b"Exif\0\0"));
+                }
+                MARKER_SOS => {
+                    if progressive {
+                        markers.first_scan = scan_end(bytes, pos + 2 + length);
+                    }
+                    return Some(markers);
+                }
+                MARKER_EOI => return Some(markers),
+                _ => {}
+            }
+            pos += 2 + length;
+        }
+
+        Some(markers)
+    }
+}
+
+/// Offset of the marker that ends the entropy-coded data starting at `start`
+fn scan_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut pos = start;
+    while pos + 1 < bytes.len() {
+        // Stuffed zero bytes and restart markers belong to the scan
+        if bytes[pos] == 0xFF && bytes[pos + 1] != 0x00 && !(0xD0..=0xD7).contains(&bytes[pos + 1])
+        {
+            return Some(pos);
+        }
+        pos += 1;
+    }
+    None
+}
+
+/// Decode a progressive JPEG cut off after its first scan (usually the DC coefficients)
+/// IDCT scaling to 1/8 turns each 8x8 block into one pixel, so the full-size image is
+/// never reconstructed
+fn decode_first_scan(bytes: &[u8], scan_end: usize) -> Option<DynamicImage> {
+    let eoi = [0xFF, MARKER_EOI];
+    let mut decoder = Decoder::new(bytes[..scan_end].chain(&eoi[..]));
+    decoder.read_info().ok()?;
+    let info = decoder.info()?;
+    let (width, height) = decoder
+        .scale(info.width.div_ceil(8), info.height.div_ceil(8))
+        .ok()?;
+    let pixels = decoder.decode().ok()?;
+
+    let (width, height) = (width as u32, height as u32);
+    match info.pixel_format {
+        PixelFormat::L8 => GrayImage::from_raw(width, height, pixels).map(DynamicImage::from),
+        PixelFormat::RGB24 => RgbImage::from_raw(width, height, pixels).map(DynamicImage::from),
+        _ => None,
+    }
+}
+
+/// Decode the JPEG thumbnail IFD1 of an EXIF block points at
+fn decode_exif_thumbnail(tiff: &[u8]) -> Option<DynamicImage> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| -> Option<u16> {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?];
+        Some(if little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes: [u8; 4] = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    };
+
+    // IFD1 follows IFD0, linked by the offset after IFD0's entries
+    let ifd0 = read_u32(4)? as usize;
+    let ifd0_entries = read_u16(ifd0)? as usize;
+    let ifd1 = read_u32(ifd0 + 2 + ifd0_entries * 12)? as usize;
+    if ifd1 == 0 {
+        return None;
+    }
+
+    let (mut offset, mut length) = (None, None);
+    for entry in 0..read_u16(ifd1)? as usize {
+        let entry = ifd1 + 2 + entry * 12;
+        match read_u16(entry)? {
+            EXIF_JPEG_OFFSET_TAG => offset = read_u32(entry + 8),
+            EXIF_JPEG_LENGTH_TAG => length = read_u32(entry + 8),
+            _ => {}
+        }
+    }
+
+    let (offset, length) = (offset? as usize, length? as usize);
+    let thumbnail = tiff.get(offset..offset + length)?;
+    image::load_from_memory_with_format(thumbnail, image::ImageFormat::Jpeg).ok()
+}
@@ -1,11 +1,19 @@
+use anyhow::Result;
 use rayon::prelude::*;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::texture_pipeline::{EmbeddedMetadata, Source};
+use crate::texture_pipeline::wrapper::wrapped_image_metadata;
+use crate::texture_pipeline::{EmbeddedMetadata, Source, StreamWrapper, vfs};
+
+/// Bytes read from the start of a file when sniffing its format by content;
+/// covers the ISO9660/UDF volume descriptors at 32 KiB
+const SNIFF_LENGTH: u64 = 64 * 1024;
 
 /// Registry that holds all available texture sources
 pub struct SourceRegistry {
-    sources: Vec<Box<dyn Source>>,
+    sources: Vec<Arc<dyn Source>>,
 }
 
 impl SourceRegistry {
@@ -17,15 +25,52 @@ impl SourceRegistry {
 
     /// Add a source to the registry
     pub fn add_source(&mut self, source: Box<dyn Source>) {
+        self.sources.push(Arc::from(source));
+    }
+
+    /// Add a source shared with other registries, e.g. one loaded from a plugin
+    pub fn add_shared_source(&mut self, source: Arc<dyn Source>) {
         self.sources.push(source);
     }
 
     /// Find the first source that can handle the given path
+    /// Falls back to sniffing the file's content when no source accepts its extension,
+    /// so a GLB saved as `.bin` or a ZIP without extension still opens
     pub fn find_source(&self, path: &Path) -> Option<&dyn Source> {
         self.sources
             .iter()
             .find(|source| source.can_load_path(path).unwrap_or(false))
             .map(|s| s.as_ref())
+            .or_else(|| self.find_source_by_content(path))
+    }
+
+    /// Magic-bytes detection on the first `SNIFF_LENGTH` bytes of a file
+    fn find_source_by_content(&self, path: &Path) -> Option<&dyn Source> {
+        if !vfs::is_file(path) {
+            return None;
+        }
+
+        let mut header = Vec::new();
+        vfs::open(path)
+            .ok()?
+            .take(SNIFF_LENGTH)
+            .read_to_end(&mut header)
+            .ok()?;
+        if header.is_empty() {
+            return None;
+        }
+
+        let mut cursor = std::io::Cursor::new(header);
+        let source = self.sources.iter().find(|source| {
+            cursor.set_position(0);
+            source.can_load_reader(&mut cursor).unwrap_or(false)
+        })?;
+
+        log::debug!(
+            "Detected format of {} from its content, extension didn't match",
+            path.display()
+        );
+        Some(source.as_ref())
     }
 
     /// Find source for raw data (enables recursive processing)
@@ -40,6 +85,63 @@ impl SourceRegistry {
             .map(|s| s.as_ref())
     }
 
+    /// Extract metadata for a path, unwrapping single-stream compression when no source
+    /// accepts the path directly. Returns None for unsupported files
+    pub fn extract_metadata(&self, path: &Path) -> Option<Result<Vec<EmbeddedMetadata>>> {
+        if let Some(source) = self.find_source(path) {
+            return Some(source.extract_metadata(path));
+        }
+
+        let wrapper = StreamWrapper::detect_path(path)?;
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown");
+
+        Some(
+            vfs::read(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|data| self.extract_unwrapped(wrapper, &data, file_name, path, false)),
+        )
+    }
+
+    /// Decompression layer: unwrap the stream and re-dispatch the payload
+    /// Plain images become wrapped entries, containers go through `find_source_for_reader`
+    pub fn extract_unwrapped(
+        &self,
+        wrapper: StreamWrapper,
+        wrapped_data: &[u8],
+        entry_name: &str,
+        source_path: &Path,
+        keep_data: bool,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let decoded = wrapper.decode(wrapped_data)?;
+        let inner_name = wrapper.inner_name(entry_name);
+
+        log::debug!(
+            "Unwrapped {:?} stream {} -> {} ({} bytes)",
+            wrapper,
+            entry_name,
+            inner_name,
+            decoded.len()
+        );
+
+        let mut cursor = std::io::Cursor::new(&decoded);
+        if imagesize::image_type(&decoded).is_err()
+            && let Some(source) = self.find_source_for_reader(&mut cursor)
+        {
+            return source.extract_metadata_from_reader(&mut cursor, &inner_name, source_path);
+        }
+
+        match wrapped_image_metadata(wrapper, decoded, &inner_name, source_path, keep_data) {
+            Some(metadata) => Ok(vec![metadata]),
+            None => {
+                log::debug!("Unsupported payload in wrapped stream: {inner_name}");
+                Ok(Vec::new())
+            }
+        }
+    }
+
     /// Parallel metadata extraction for multiple files
     /// Uses rayon to process files concurrently for better performance
     /// According to refact_pipeline.md - each source parses container once and creates proper hints
@@ -47,14 +149,8 @@ impl SourceRegistry {
         paths
             .into_par_iter()
             .filter_map(|path| {
-                // Find source in parallel - each thread searches independently
-                let source = self
-                    .sources
-                    .par_iter()
-                    .find_any(|s| s.can_load_path(&path).unwrap_or(false))?;
-
-                // Extract metadata using the found source
-                match source.extract_metadata(&path) {
+                // Find source (or unwrap compression) and extract metadata
+                match self.extract_metadata(&path)? {
                     Ok(metadata_list) => Some(metadata_list),
                     Err(e) => {
                         log::warn!("Failed to extract metadata from {}: {}", path.display(), e);
@@ -67,7 +163,7 @@ impl SourceRegistry {
     }
 
     /// Get all registered sources (for debugging/testing)
-    pub fn sources(&self) -> &[Box<dyn Source>] {
+    pub fn sources(&self) -> &[Arc<dyn Source>] {
         &self.sources
     }
 }
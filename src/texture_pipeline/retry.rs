@@ -0,0 +1,230 @@
+use anyhow::Result;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::time::Duration;
+
+/// OS error codes worth retrying that don't map to a dedicated `ErrorKind`
+#[cfg(target_os = "windows")]
+const TRANSIENT_OS_ERRORS: &[i32] = &[
+    32,  // ERROR_SHARING_VIOLATION: sync client or antivirus holds the file
+    33,  // ERROR_LOCK_VIOLATION
+    59,  // ERROR_UNEXP_NET_ERR
+    64,  // ERROR_NETNAME_DELETED: SMB share dropped the connection
+    121, // ERROR_SEM_TIMEOUT
+    362, // ERROR_CLOUD_FILE_PROVIDER_NOT_RUNNING
+    391, // ERROR_CLOUD_FILE_IN_USE
+    393, // ERROR_CLOUD_FILE_REQUEST_ABORTED
+    426, // ERROR_CLOUD_FILE_REQUEST_TIMEOUT
+];
+
+#[cfg(unix)]
+const TRANSIENT_OS_ERRORS: &[i32] = &[
+    5, // EIO: network and FUSE-backed (cloud) filesystems report dropped reads this way
+];
+
+#[cfg(not(any(unix, target_os = "windows")))]
+const TRANSIENT_OS_ERRORS: &[i32] = &[];
+
+/// Placeholder attributes set by OneDrive/Dropbox/iCloud on files not yet downloaded
+#[cfg(target_os = "windows")]
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+#[cfg(target_os = "windows")]
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+#[cfg(target_os = "windows")]
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+
+/// Context on a transient failure `RetryPolicy::run` hit on the rayon pool, where it
+/// doesn't wait between attempts: a sleeping pool thread stalls every other load, so
+/// the caller re-queues the work after `RetryPolicy::delay` instead
+#[derive(Debug, Clone, Copy)]
+pub struct RetryLater {
+    pub max_attempts: u32,
+}
+
+impl std::fmt::Display for RetryLater {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Transient failure, retrying later")
+    }
+}
+
+/// Bounded exponential backoff for reads that can fail transiently
+/// (network drives, cloud-synced folders with online-only placeholders)
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub hydration_attempts: u32, // Used instead once a cloud download was triggered
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            hydration_attempts: 10,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Run `load` until it succeeds, fails with a non-transient error or runs out of attempts
+    /// When `path` is an online-only cloud file, its download is requested before retrying
+    /// On the rayon pool only one attempt is made; its transient error carries `RetryLater`
+    pub fn run<T>(&self, path: &Path, mut load: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut hydrating = false;
+        let mut attempt = 1;
+
+        loop {
+            let error = match load() {
+                Ok(value) => {
+                    if attempt > 1 {
+                        log::info!("🔁 Read {} on attempt {attempt}", path.display());
+                    }
+                    return Ok(value);
+                }
+                Err(error) => error,
+            };
+
+            if !hydrating && is_cloud_placeholder(path) {
+                hydrating = request_hydration(path);
+            }
+            // A file being downloaded may also be missing until it lands
+            if !is_transient(&error) && !(hydrating && is_not_found(&error)) {
+                return Err(error);
+            }
+
+            let max_attempts = if hydrating {
+                self.hydration_attempts
+            } else {
+                self.max_attempts
+            };
+            if attempt >= max_attempts {
+                return Err(error.context(format!("Still failing after {attempt} attempts")));
+            }
+            if rayon::current_thread_index().is_some() {
+                return Err(error.context(RetryLater { max_attempts }));
+            }
+
+            let delay = self.delay(attempt);
+            log::warn!(
+                "🔁 Read of {} failed (attempt {attempt}/{max_attempts}), retrying in {delay:?}: {error:#}",
+                path.display()
+            );
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Wait after failed attempt `attempt` (from 1), doubling up to `max_delay`
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay)
+    }
+}
+
+/// Whether any I/O error in the chain is likely to go away on its own
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<io::Error>())
+        .any(|io_error| {
+            matches!(
+                io_error.kind(),
+                ErrorKind::Interrupted
+                    | ErrorKind::WouldBlock
+                    | ErrorKind::TimedOut
+                    | ErrorKind::ResourceBusy
+                    | ErrorKind::NetworkDown
+                    | ErrorKind::NetworkUnreachable
+                    | ErrorKind::HostUnreachable
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::StaleNetworkFileHandle
+            ) || io_error
+                .raw_os_error()
+                .is_some_and(|code| TRANSIENT_OS_ERRORS.contains(&code))
+        })
+}
+
+fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<io::Error>())
+        .any(|io_error| io_error.kind() == ErrorKind::NotFound)
+}
+
+/// Whether `path` is a cloud file whose contents are not on disk yet
+pub fn is_cloud_placeholder(path: &Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        std::fs::metadata(path).is_ok_and(|metadata| {
+            metadata.file_attributes()
+                & (FILE_ATTRIBUTE_OFFLINE
+                    | FILE_ATTRIBUTE_RECALL_ON_OPEN
+                    | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+                != 0
+        })
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        icloud_stub(path).is_some_and(|stub| stub.exists())
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// Ask the sync client to download a placeholder; true when a download is under way
+fn request_hydration(path: &Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        // Opening and reading a placeholder makes the cloud filter fetch it;
+        // the failed read already did that, so the retries just wait for it
+        log::info!("☁️ Waiting for {} to download", path.display());
+        true
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        // Evicted iCloud files are replaced by a hidden `.name.icloud` stub
+        match std::process::Command::new("brctl")
+            .arg("download")
+            .arg(path)
+            .status()
+        {
+            Ok(status) if status.success() => {
+                log::info!("☁️ Requested iCloud download of {}", path.display());
+                true
+            }
+            Ok(status) => {
+                log::warn!("⚠️ brctl download {} exited with {status}", path.display());
+                false
+            }
+            Err(e) => {
+                log::warn!("⚠️ Failed to run brctl for {}: {e}", path.display());
+                false
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn icloud_stub(path: &Path) -> Option<std::path::PathBuf> {
+    let file_name = path.file_name()?.to_str()?;
+    Some(path.with_file_name(format!(".{file_name}.icloud")))
+}
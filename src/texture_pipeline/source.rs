@@ -1,8 +1,8 @@
-use anyhow::Result;
-use std::io::{BufRead, Seek};
+use anyhow::{Context, Result};
+use std::io::{BufRead, Read, Seek, SeekFrom};
 use std::path::Path;
 
-use crate::texture_pipeline::{EmbeddedHint, EmbeddedMetadata};
+use crate::texture_pipeline::{EmbeddedHint, EmbeddedMetadata, vfs};
 
 /// Helper trait that combines BufRead + Seek for imagesize compatibility
 pub trait BufReadSeek: BufRead + Seek {}
@@ -36,4 +36,109 @@ pub trait Source: Send + Sync {
     /// Load raw bytes using hint (works for both embedded and direct files)
     /// Use hint's direct access information - no re-parsing needed
     fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>>;
+
+    /// Read `length` bytes at `offset` within the entry, cut short at its end
+    /// Entries stored contiguously on disk are read by range; others load whole and slice
+    fn read_range(&self, hint: &dyn EmbeddedHint, offset: u64, length: usize) -> Result<Vec<u8>> {
+        if let Some(slice) = hint.file_slice() {
+            return read_file_slice(slice, offset, length);
+        }
+        Ok(slice_range(&self.load_bytes(hint)?, offset, length).to_vec())
+    }
+}
+
+/// Part of an in-memory entry, clamped to its bounds
+pub fn slice_range(data: &[u8], offset: u64, length: usize) -> &[u8] {
+    let start = (offset as usize).min(data.len());
+    let end = start.saturating_add(length).min(data.len());
+    &data[start..end]
+}
+
+/// Read `length` bytes at `offset` within a file slice from `EmbeddedHint::file_slice`,
+/// clamped to the slice so neighbouring entries are never returned
+pub fn read_file_slice(
+    (path, slice_offset, slice_length): (&Path, u64, u64),
+    offset: u64,
+    length: usize,
+) -> Result<Vec<u8>> {
+    let length = (length as u64).min(slice_length.saturating_sub(offset));
+    let mut file = vfs::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    file.seek(SeekFrom::Start(slice_offset + offset))
+        .with_context(|| format!("Failed to seek in {}", path.display()))?;
+
+    let mut data = vec![0u8; length as usize];
+    file.read_exact(&mut data).with_context(|| {
+        format!(
+            "Failed to read {length} bytes at {} from {}",
+            slice_offset + offset,
+            path.display()
+        )
+    })?;
+
+    log::debug!(
+        "Ranged read: {length} bytes at {} in {}",
+        slice_offset + offset,
+        path.display()
+    );
+    Ok(data)
+}
+
+/// Read header bytes incrementally until imagesize can determine dimensions
+/// or we reach a reasonable maximum size
+/// Shared by container sources that stream entries (ZIP, TAR, ...)
+pub fn read_header_incrementally<R: Read + ?Sized>(
+    entry: &mut R,
+    max_size: usize,
+) -> Result<Option<Vec<u8>>> {
+    let mut header_size = 128; // Start small - most formats store dimensions early
+    let max_header_size = std::cmp::min(65536, max_size); // Cap at 64KB or file size
+    let mut accumulated_buffer = Vec::new();
+
+    while header_size <= max_header_size {
+        // Calculate how much more we need to read
+        let bytes_to_read = header_size.saturating_sub(accumulated_buffer.len());
+        if bytes_to_read == 0 {
+            break;
+        }
+
+        // Read additional bytes
+        let mut temp_buffer = vec![0u8; bytes_to_read];
+        let bytes_read = entry.read(&mut temp_buffer)?;
+
+        if bytes_read == 0 {
+            // No more data available
+            break;
+        }
+
+        temp_buffer.truncate(bytes_read);
+        accumulated_buffer.extend(temp_buffer);
+
+        // Try to determine image dimensions with current buffer
+        if let Ok(_dimensions) = imagesize::blob_size(&accumulated_buffer) {
+            log::debug!(
+                "Header size determined with {} bytes (started at {}, max {})",
+                accumulated_buffer.len(),
+                128,
+                max_header_size
+            );
+            return Ok(Some(accumulated_buffer));
+        }
+
+        // If we've read all available data, stop trying
+        if accumulated_buffer.len() >= max_size {
+            break;
+        }
+
+        // Increase buffer size for next iteration
+        header_size = std::cmp::min(header_size + 1024, max_header_size);
+    }
+
+    log::debug!(
+        "Header reading completed with {} bytes (imagesize couldn't determine dimensions)",
+        accumulated_buffer.len()
+    );
+
+    // Return whatever we have, even if imagesize couldn't determine dimensions
+    // The pipeline might still be able to process it
+    Ok(Some(accumulated_buffer))
 }
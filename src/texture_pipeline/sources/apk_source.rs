@@ -0,0 +1,534 @@
+use anyhow::{Context, Result};
+use image::ImageFormat;
+use std::collections::HashMap;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+use crate::texture_pipeline::source::read_header_incrementally;
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::{ApkHint, BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, vfs};
+
+/// File extensions listed from `res/` and `assets/`
+const TEXTURE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "webp", "gif", "bmp", "ktx", "ktx2", "astc", "pkm", "dds",
+];
+
+// resources.arsc chunk types
+const RES_STRING_POOL_TYPE: u16 = 0x0001;
+const RES_TABLE_TYPE: u16 = 0x0002;
+const RES_TABLE_PACKAGE_TYPE: u16 = 0x0200;
+const RES_TABLE_TYPE_TYPE: u16 = 0x0201;
+
+const STRING_POOL_UTF8_FLAG: u32 = 0x100;
+const TYPE_FLAG_SPARSE: u8 = 0x01;
+const TYPE_FLAG_OFFSET16: u8 = 0x02;
+const ENTRY_FLAG_COMPLEX: u16 = 0x0001;
+const ENTRY_FLAG_COMPACT: u16 = 0x0008;
+const NO_ENTRY: u32 = 0xFFFF_FFFF;
+const VALUE_TYPE_STRING: u8 = 0x03;
+
+/// Android APK source
+///
+/// APKs are ZIPs, but only drawables under `res/` and textures under `assets/` are listed.
+/// Entries are named by resource path (`res/drawable-xhdpi/icon.png`) from resources.arsc,
+/// which also recovers names shortened by resource obfuscation (`res/a1.png`).
+/// Nine-patches still carrying their 1px guide border have it cropped on load;
+/// aapt-compiled ones already moved it into an `npTc` chunk and decode as-is.
+pub struct ApkSource;
+
+impl Source for ApkSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_apk_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("apk"))
+            .unwrap_or(false);
+
+        if !has_apk_extension {
+            return Ok(false);
+        }
+
+        let file = vfs::open(path)?;
+        match ZipArchive::new(BufReader::new(file)) {
+            Ok(mut archive) => Ok(archive.by_name("AndroidManifest.xml").is_ok()),
+            Err(_) => Ok(false),
+        }
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Nested APKs are only seen through their ZIP header; the ZIP source takes them
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let file = vfs::open(path).context("Failed to open APK file")?;
+        let mut archive =
+            ZipArchive::new(BufReader::new(file)).context("Failed to read APK archive")?;
+
+        let resource_names = match Self::read_resource_names(&mut archive) {
+            Ok(names) => names,
+            Err(e) => {
+                log::debug!("No resource table in {}: {e:#}", path.display());
+                HashMap::new()
+            }
+        };
+
+        let mut metadata_list = Vec::new();
+        for i in 0..archive.len() {
+            let entry_result = (|| -> Result<Option<EmbeddedMetadata>> {
+                let mut entry = archive.by_index(i)?;
+                if entry.is_dir() || entry.size() == 0 {
+                    return Ok(None);
+                }
+
+                let entry_name = entry.name().to_string();
+                if !Self::is_texture_entry(&entry_name, &resource_names) {
+                    return Ok(None);
+                }
+
+                let compressed_size = entry.compressed_size();
+                let uncompressed_size = entry.size();
+                let data_offset = entry.data_start();
+                let crc32 = entry.crc32();
+
+                let Some(mut header) =
+                    read_header_incrementally(&mut entry, uncompressed_size as usize)?
+                else {
+                    return Ok(None);
+                };
+                let Ok(format) = imagesize::image_type(&header) else {
+                    return Ok(None);
+                };
+                let (mut width, mut height) = imagesize::blob_size(&header)
+                    .map(|size| (size.width, size.height))
+                    .unwrap_or((0, 0));
+
+                let nine_patch_border = if entry_name.to_lowercase().ends_with(".9.png") {
+                    entry.read_to_end(&mut header)?;
+                    !Self::has_png_chunk(&header, b"npTc")
+                } else {
+                    false
+                };
+                if nine_patch_border {
+                    width = width.saturating_sub(2);
+                    height = height.saturating_sub(2);
+                }
+
+                let name = resource_names
+                    .get(&entry_name)
+                    .cloned()
+                    .unwrap_or_else(|| entry_name.clone());
+
+                let hint = Box::new(ApkHint {
+                    container_path: path.to_path_buf(),
+                    entry_name,
+                    entry_index: i,
+                    compressed_size,
+                    data_offset,
+                    crc32,
+                    nine_patch_border,
+                }) as Box<dyn EmbeddedHint>;
+
+                Ok(Some(EmbeddedMetadata {
+                    name,
+                    format,
+                    width,
+                    height,
+                    file_size: uncompressed_size,
+                    embedded_hint: hint,
+                    source_path: path.to_path_buf(),
+                    parents: Vec::new(),
+                }))
+            })();
+
+            match entry_result {
+                Ok(Some(metadata)) => metadata_list.push(metadata),
+                Ok(None) => {}
+                Err(e) => log::debug!("Failed to extract metadata from APK entry {i}: {e}"),
+            }
+        }
+
+        log::info!(
+            "APK extraction completed: {} textures ({} named from resources.arsc) from {}",
+            metadata_list.len(),
+            resource_names.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        log::debug!("APK processing from reader not supported for entry: {entry_name}");
+        Ok(Vec::new())
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(apk_hint) = hint.as_any().downcast_ref::<ApkHint>() {
+            return Self::read_apk_entry(apk_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for APK source: {}", hint.debug_info())
+    }
+}
+
+impl ApkSource {
+    fn is_texture_entry(entry_name: &str, resource_names: &HashMap<String, String>) -> bool {
+        if !entry_name.starts_with("res/") && !entry_name.starts_with("assets/") {
+            return false;
+        }
+
+        let file_name = entry_name.rsplit('/').next().unwrap_or(entry_name);
+        match file_name.rsplit_once('.') {
+            Some((_, ext)) => TEXTURE_EXTENSIONS.contains(&ext.to_lowercase().as_str()),
+            // Shrunk resources may lose their extension; keep the ones the table knows
+            None => resource_names.contains_key(entry_name),
+        }
+    }
+
+    fn read_apk_entry(hint: &ApkHint) -> Result<Vec<u8>> {
+        let file =
+            vfs::open(&hint.container_path).context("Failed to open APK file for reading entry")?;
+        let mut archive =
+            ZipArchive::new(BufReader::new(file)).context("Failed to read APK archive")?;
+        let mut entry = archive
+            .by_index(hint.entry_index)
+            .with_context(|| format!("Failed to find APK entry at index {}", hint.entry_index))?;
+
+        if entry.name() != hint.entry_name {
+            anyhow::bail!(
+                "APK entry name mismatch: expected '{}', found '{}'",
+                hint.entry_name,
+                entry.name()
+            );
+        }
+
+        let mut buffer = entry_buffer(entry.size());
+        entry
+            .read_to_end(&mut buffer)
+            .with_context(|| format!("Failed to read APK entry: {}", hint.entry_name))?;
+
+        if hint.nine_patch_border {
+            return Self::strip_nine_patch_border(&buffer)
+                .with_context(|| format!("Failed to decode nine-patch {}", hint.entry_name));
+        }
+
+        Ok(buffer)
+    }
+
+    /// Crop the 1px stretch/padding guide border off a source nine-patch
+    fn strip_nine_patch_border(png: &[u8]) -> Result<Vec<u8>> {
+        let image = image::load_from_memory_with_format(png, ImageFormat::Png)?;
+        if image.width() < 3 || image.height() < 3 {
+            anyhow::bail!("{}x{} is too small", image.width(), image.height());
+        }
+
+        let content = image.crop_imm(1, 1, image.width() - 2, image.height() - 2);
+        let mut encoded = Vec::new();
+        content
+            .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png)
+            .context("Failed to encode nine-patch content as PNG")?;
+        Ok(encoded)
+    }
+
+    fn has_png_chunk(png: &[u8], chunk_type: &[u8; 4]) -> bool {
+        let mut offset = 8; // PNG signature
+        while let Some(header) = png.get(offset..offset + 8) {
+            if &header[4..8] == chunk_type {
+                return true;
+            }
+            if &header[4..8] == b"IEND" {
+                break;
+            }
+            let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+            // Length, type, data and CRC
+            offset = offset.saturating_add(length as usize).saturating_add(12);
+        }
+        false
+    }
+
+    /// Resource paths keyed by the file path stored in the APK
+    fn read_resource_names<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+    ) -> Result<HashMap<String, String>> {
+        let mut table = Vec::new();
+        archive
+            .by_name("resources.arsc")?
+            .read_to_end(&mut table)
+            .context("Failed to read resources.arsc")?;
+        ResourceTable::file_names(&table).context("Malformed resources.arsc")
+    }
+}
+
+/// Minimal reader for the compiled resource table (resources.arsc)
+/// Only file-backed entries are resolved: `res/<type>[-<qualifiers>]/<key>.<ext>`
+struct ResourceTable;
+
+impl ResourceTable {
+    fn file_names(data: &[u8]) -> Option<HashMap<String, String>> {
+        if u16_at(data, 0)? != RES_TABLE_TYPE {
+            return None;
+        }
+
+        let mut names = HashMap::new();
+        let mut global_strings = Vec::new();
+        for (chunk_type, chunk) in chunks(data, u16_at(data, 2)? as usize) {
+            match chunk_type {
+                RES_STRING_POOL_TYPE => global_strings = string_pool(chunk)?,
+                RES_TABLE_PACKAGE_TYPE => Self::package(chunk, &global_strings, &mut names)?,
+                _ => {}
+            }
+        }
+        Some(names)
+    }
+
+    fn package(
+        chunk: &[u8],
+        global_strings: &[String],
+        names: &mut HashMap<String, String>,
+    ) -> Option<()> {
+        // Chunk header, package id and the 128-character UTF-16 package name come first
+        let type_strings = string_pool(chunk_at(chunk, u32_at(chunk, 268)? as usize)?)?;
+        let key_strings = string_pool(chunk_at(chunk, u32_at(chunk, 276)? as usize)?)?;
+
+        for (chunk_type, child) in chunks(chunk, u16_at(chunk, 2)? as usize) {
+            if chunk_type == RES_TABLE_TYPE_TYPE
+                && Self::type_chunk(child, &type_strings, &key_strings, global_strings, names)
+                    .is_none()
+            {
+                log::debug!("Skipping malformed resource type chunk");
+            }
+        }
+        Some(())
+    }
+
+    fn type_chunk(
+        chunk: &[u8],
+        type_strings: &[String],
+        key_strings: &[String],
+        global_strings: &[String],
+        names: &mut HashMap<String, String>,
+    ) -> Option<()> {
+        let header_size = u16_at(chunk, 2)? as usize;
+        let type_id = *chunk.get(8)? as usize;
+        let flags = *chunk.get(9)?;
+        // Bounded by what could fit in the chunk
+        let entry_count = (u32_at(chunk, 12)? as usize).min(chunk.len() / 2);
+        let entries_start = u32_at(chunk, 16)? as usize;
+        let config = chunk.get(20..header_size)?;
+
+        let type_name = type_strings.get(type_id.checked_sub(1)?)?;
+        let qualifiers = config_qualifiers(config);
+        let directory = if qualifiers.is_empty() {
+            type_name.clone()
+        } else {
+            format!("{type_name}-{qualifiers}")
+        };
+
+        let offsets: Vec<usize> = if flags & TYPE_FLAG_SPARSE != 0 {
+            // (entry index, offset / 4) pairs
+            (0..entry_count)
+                .map_while(|i| u16_at(chunk, header_size + i * 4 + 2))
+                .map(|offset| offset as usize * 4)
+                .collect()
+        } else if flags & TYPE_FLAG_OFFSET16 != 0 {
+            (0..entry_count)
+                .map_while(|i| u16_at(chunk, header_size + i * 2))
+                .filter(|&offset| offset != 0xFFFF)
+                .map(|offset| offset as usize * 4)
+                .collect()
+        } else {
+            (0..entry_count)
+                .map_while(|i| u32_at(chunk, header_size + i * 4))
+                .filter(|&offset| offset != NO_ENTRY)
+                .map(|offset| offset as usize)
+                .collect()
+        };
+
+        for offset in offsets {
+            let Some((file, key)) = Self::file_entry(chunk, entries_start.checked_add(offset)?)
+            else {
+                continue;
+            };
+            let (Some(file), Some(key)) = (global_strings.get(file), key_strings.get(key)) else {
+                continue;
+            };
+            if !file.starts_with("res/") {
+                continue;
+            }
+
+            // Keep compound extensions such as .9.png
+            let file_name = file.rsplit('/').next().unwrap_or(file);
+            let extension = file_name.find('.').map_or("", |dot| &file_name[dot..]);
+            names.insert(file.clone(), format!("res/{directory}/{key}{extension}"));
+        }
+        Some(())
+    }
+
+    /// (global string index, key string index) of a simple string-valued entry
+    fn file_entry(chunk: &[u8], position: usize) -> Option<(usize, usize)> {
+        let size = u16_at(chunk, position)?;
+        let flags = u16_at(chunk, position + 2)?;
+        if flags & ENTRY_FLAG_COMPLEX != 0 {
+            return None;
+        }
+
+        let (key, data_type, data) = if flags & ENTRY_FLAG_COMPACT != 0 {
+            // Key index in the size field, value type in the high flag byte
+            (
+                size as u32,
+                (flags >> 8) as u8,
+                u32_at(chunk, position + 4)?,
+            )
+        } else {
+            let value = position + size as usize;
+            (
+                u32_at(chunk, position + 4)?,
+                *chunk.get(value + 3)?,
+                u32_at(chunk, value + 4)?,
+            )
+        };
+
+        (data_type == VALUE_TYPE_STRING).then_some((data as usize, key as usize))
+    }
+}
+
+/// Qualifier suffix (`en-rUS-night-xhdpi-v21`) for the parts of ResTable_config that
+/// matter for drawables
+fn config_qualifiers(config: &[u8]) -> String {
+    let byte = |offset: usize| config.get(offset).copied().unwrap_or(0);
+    let mut parts = Vec::new();
+
+    if byte(8) != 0 && byte(8) & 0x80 == 0 {
+        parts.push(format!("{}{}", byte(8) as char, byte(9) as char));
+    }
+    if byte(10) != 0 && byte(10) & 0x80 == 0 {
+        parts.push(format!("r{}{}", byte(10) as char, byte(11) as char));
+    }
+    if let Some(smallest_width) = u16_at(config, 30).filter(|&dp| dp != 0) {
+        parts.push(format!("sw{smallest_width}dp"));
+    }
+    match byte(12) {
+        1 => parts.push("port".to_string()),
+        2 => parts.push("land".to_string()),
+        _ => {}
+    }
+    match byte(29) & 0x30 {
+        0x10 => parts.push("notnight".to_string()),
+        0x20 => parts.push("night".to_string()),
+        _ => {}
+    }
+    match u16_at(config, 14).unwrap_or(0) {
+        0 => {}
+        120 => parts.push("ldpi".to_string()),
+        160 => parts.push("mdpi".to_string()),
+        213 => parts.push("tvdpi".to_string()),
+        240 => parts.push("hdpi".to_string()),
+        320 => parts.push("xhdpi".to_string()),
+        480 => parts.push("xxhdpi".to_string()),
+        640 => parts.push("xxxhdpi".to_string()),
+        0xFFFE => parts.push("anydpi".to_string()),
+        0xFFFF => parts.push("nodpi".to_string()),
+        density => parts.push(format!("{density}dpi")),
+    }
+    if let Some(sdk) = u16_at(config, 24).filter(|&sdk| sdk != 0) {
+        parts.push(format!("v{sdk}"));
+    }
+
+    parts.join("-")
+}
+
+/// Child chunks (type, bytes) laid out back to back from `start`
+fn chunks(data: &[u8], start: usize) -> impl Iterator<Item = (u16, &[u8])> {
+    let mut offset = start;
+    std::iter::from_fn(move || {
+        let chunk = chunk_at(data, offset)?;
+        offset += chunk.len();
+        Some((u16_at(chunk, 0)?, chunk))
+    })
+}
+
+/// The whole chunk starting at `offset`, sized by its header
+fn chunk_at(data: &[u8], offset: usize) -> Option<&[u8]> {
+    let size = u32_at(data, offset.checked_add(4)?)? as usize;
+    if size < 8 {
+        return None;
+    }
+    data.get(offset..offset.checked_add(size)?)
+}
+
+fn string_pool(chunk: &[u8]) -> Option<Vec<String>> {
+    if u16_at(chunk, 0)? != RES_STRING_POOL_TYPE {
+        return None;
+    }
+    let header_size = u16_at(chunk, 2)? as usize;
+    let count = u32_at(chunk, 8)? as usize;
+    let utf8 = u32_at(chunk, 16)? & STRING_POOL_UTF8_FLAG != 0;
+    let strings_start = u32_at(chunk, 20)? as usize;
+    if header_size.checked_add(count.checked_mul(4)?)? > chunk.len() {
+        return None;
+    }
+
+    let strings = (0..count)
+        .map(|i| {
+            let position =
+                strings_start.checked_add(u32_at(chunk, header_size + i * 4)? as usize)?;
+            if utf8 {
+                utf8_string(chunk, position)
+            } else {
+                utf16_string(chunk, position)
+            }
+        })
+        .map(Option::unwrap_or_default)
+        .collect();
+    Some(strings)
+}
+
+fn utf8_string(data: &[u8], position: usize) -> Option<String> {
+    // Length in characters, then in bytes; either takes two bytes when the high bit is set
+    let length = |position: usize| -> Option<(usize, usize)> {
+        let first = *data.get(position)? as usize;
+        if first & 0x80 != 0 {
+            Some((((first & 0x7F) << 8) | *data.get(position + 1)? as usize, 2))
+        } else {
+            Some((first, 1))
+        }
+    };
+    let (_, char_count_size) = length(position)?;
+    let (byte_count, byte_count_size) = length(position + char_count_size)?;
+    let start = position + char_count_size + byte_count_size;
+    let bytes = data.get(start..start.checked_add(byte_count)?)?;
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn utf16_string(data: &[u8], position: usize) -> Option<String> {
+    let first = u16_at(data, position)? as usize;
+    let (length, start) = if first & 0x8000 != 0 {
+        (
+            ((first & 0x7FFF) << 16) | u16_at(data, position + 2)? as usize,
+            position + 4,
+        )
+    } else {
+        (first, position + 2)
+    };
+    let units = (0..length)
+        .map(|i| u16_at(data, start + i * 2))
+        .collect::<Option<Vec<u16>>>()?;
+    Some(String::from_utf16_lossy(&units))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
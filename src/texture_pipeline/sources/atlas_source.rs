@@ -0,0 +1,408 @@
+use anyhow::{Context, Result};
+use image::{DynamicImage, GenericImageView, ImageFormat};
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::{
+    AtlasHint, AtlasRotation, BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, vfs,
+};
+
+/// Atlas descriptors larger than this are not sniffed
+const MAX_DESCRIPTOR_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Sprite atlas source - pairs a TexturePacker JSON/XML/.plist or libGDX/Spine `.atlas`
+/// descriptor with its image and exposes every sprite region as its own entry
+pub struct AtlasSource;
+
+/// One sprite region as stored in the atlas image
+#[derive(Debug, Clone)]
+struct AtlasSprite {
+    name: String,
+    rect: [u32; 4], // x, y, width, height of the unrotated sprite
+    rotation: AtlasRotation,
+}
+
+/// Sprites packed into one atlas image
+#[derive(Debug, Clone)]
+struct AtlasPage {
+    image: String, // Relative to the descriptor
+    sprites: Vec<AtlasSprite>,
+}
+
+impl Source for AtlasSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let is_descriptor = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| {
+                matches!(
+                    ext.to_lowercase().as_str(),
+                    "json" | "xml" | "plist" | "atlas"
+                )
+            })
+            .unwrap_or(false);
+
+        if !is_descriptor || vfs::file_size(path)? > MAX_DESCRIPTOR_SIZE {
+            return Ok(false);
+        }
+
+        // Only descriptors whose atlas image sits next to them
+        Ok(Self::parse(path).is_ok_and(|pages| {
+            pages.iter().any(|page| {
+                !page.sprites.is_empty() && vfs::is_file(Self::page_image_path(path, page))
+            })
+        }))
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Descriptors reference their image by relative path, which can't be resolved
+        // inside another container
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let pages = Self::parse(path)?;
+        let mut metadata_list = Vec::new();
+
+        for page in &pages {
+            let image_path = Self::page_image_path(path, page);
+            if !vfs::is_file(&image_path) {
+                log::warn!("Atlas image not found: {}", image_path.display());
+                continue;
+            }
+
+            for sprite in &page.sprites {
+                let [_, _, width, height] = sprite.rect;
+                let hint = Box::new(AtlasHint {
+                    container_path: path.to_path_buf(),
+                    image_path: image_path.clone(),
+                    sprite_name: sprite.name.clone(),
+                    rect: sprite.rect,
+                    rotation: sprite.rotation,
+                }) as Box<dyn EmbeddedHint>;
+
+                metadata_list.push(EmbeddedMetadata {
+                    name: sprite.name.clone(),
+                    format: imagesize::ImageType::Png, // Sprites are re-encoded as PNG
+                    width: width as usize,
+                    height: height as usize,
+                    file_size: width as u64 * height as u64 * 4,
+                    embedded_hint: hint,
+                    source_path: path.to_path_buf(),
+                    parents: Vec::new(),
+                });
+            }
+        }
+
+        if metadata_list.is_empty() {
+            anyhow::bail!("No sprites found in atlas {}", path.display());
+        }
+
+        log::info!(
+            "Atlas extraction completed: {} sprites from {}",
+            metadata_list.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        log::debug!("Atlas descriptors inside containers are not supported: {entry_name}");
+        Ok(Vec::new())
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(atlas_hint) = hint.as_any().downcast_ref::<AtlasHint>() {
+            return Self::crop_sprite(atlas_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for atlas source: {}", hint.debug_info())
+    }
+}
+
+impl AtlasSource {
+    fn page_image_path(descriptor: &Path, page: &AtlasPage) -> PathBuf {
+        descriptor
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(&page.image)
+    }
+
+    /// Cut the sprite out of the atlas image, undo packing rotation and encode it as PNG
+    fn crop_sprite(hint: &AtlasHint) -> Result<Vec<u8>> {
+        let atlas = vfs::read(&hint.image_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| Ok(image::load_from_memory(&data)?))
+            .with_context(|| format!("Failed to open atlas image {}", hint.image_path.display()))?;
+
+        let [x, y, width, height] = hint.rect;
+        // Rotated sprites occupy a transposed region in the atlas
+        let (region_width, region_height) = match hint.rotation {
+            AtlasRotation::None => (width, height),
+            _ => (height, width),
+        };
+        if x + region_width > atlas.width() || y + region_height > atlas.height() {
+            anyhow::bail!(
+                "Sprite {} lies outside the {}x{} atlas",
+                hint.sprite_name,
+                atlas.width(),
+                atlas.height()
+            );
+        }
+
+        let region = DynamicImage::from(atlas.view(x, y, region_width, region_height).to_image());
+        let sprite = match hint.rotation {
+            AtlasRotation::None => region,
+            AtlasRotation::Clockwise => region.rotate270(),
+            AtlasRotation::CounterClockwise => region.rotate90(),
+        };
+
+        let mut png = Vec::new();
+        sprite
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .context("Failed to encode sprite as PNG")?;
+        Ok(png)
+    }
+
+    fn parse(path: &Path) -> Result<Vec<AtlasPage>> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        match extension.as_str() {
+            "plist" => Self::parse_plist(path),
+            "json" => Self::parse_json(&vfs::read_to_string(path)?),
+            "xml" => Self::parse_xml(&vfs::read_to_string(path)?),
+            _ => Ok(Self::parse_gdx_atlas(&vfs::read_to_string(path)?)),
+        }
+    }
+
+    /// TexturePacker JSON (hash or array): `frames` with `frame: {x, y, w, h}` and `meta.image`
+    fn parse_json(content: &str) -> Result<Vec<AtlasPage>> {
+        let root: JsonValue = serde_json::from_str(content)?;
+        let image = root["meta"]["image"]
+            .as_str()
+            .context("Missing meta.image")?
+            .to_string();
+
+        let frames: Vec<(String, &JsonValue)> = match &root["frames"] {
+            JsonValue::Object(frames) => frames
+                .iter()
+                .map(|(name, frame)| (name.clone(), frame))
+                .collect(),
+            JsonValue::Array(frames) => frames
+                .iter()
+                .filter_map(|frame| Some((frame["filename"].as_str()?.to_string(), frame)))
+                .collect(),
+            _ => anyhow::bail!("Missing frames"),
+        };
+
+        let sprites = frames
+            .into_iter()
+            .filter_map(|(name, frame)| {
+                let rect = &frame["frame"];
+                let value = |key: &str| rect[key].as_u64().map(|v| v as u32);
+                Some(AtlasSprite {
+                    name,
+                    rect: [value("x")?, value("y")?, value("w")?, value("h")?],
+                    rotation: if frame["rotated"].as_bool().unwrap_or(false) {
+                        AtlasRotation::Clockwise
+                    } else {
+                        AtlasRotation::None
+                    },
+                })
+            })
+            .collect();
+
+        Ok(vec![AtlasPage { image, sprites }])
+    }
+
+    /// Starling/Sparrow XML: `<TextureAtlas imagePath>` with `<SubTexture name x y width height>`
+    fn parse_xml(content: &str) -> Result<Vec<AtlasPage>> {
+        let attribute = |element: &str, name: &str| -> Option<String> {
+            let pattern = Regex::new(&format!(r#"\b{name}\s*=\s*"([^"]*)""#)).ok()?;
+            Some(pattern.captures(element)?[1].to_string())
+        };
+
+        let atlas_element = Regex::new(r"<TextureAtlas\b[^>]*>")?
+            .find(content)
+            .context("Missing <TextureAtlas>")?;
+        let image = attribute(atlas_element.as_str(), "imagePath").context("Missing imagePath")?;
+
+        let sprites = Regex::new(r"<SubTexture\b[^>]*>")?
+            .find_iter(content)
+            .filter_map(|element| {
+                let element = element.as_str();
+                let number = |name: &str| attribute(element, name)?.trim().parse::<u32>().ok();
+                Some(AtlasSprite {
+                    name: attribute(element, "name")?,
+                    rect: [
+                        number("x")?,
+                        number("y")?,
+                        number("width")?,
+                        number("height")?,
+                    ],
+                    rotation: if attribute(element, "rotated").as_deref() == Some("true") {
+                        AtlasRotation::Clockwise
+                    } else {
+                        AtlasRotation::None
+                    },
+                })
+            })
+            .collect();
+
+        Ok(vec![AtlasPage { image, sprites }])
+    }
+
+    /// Cocos2d/TexturePacker .plist: `frames` dict with `frame`/`textureRect` strings
+    /// like `{{x,y},{w,h}}` and the image in `metadata.textureFileName`
+    fn parse_plist(path: &Path) -> Result<Vec<AtlasPage>> {
+        let root = plist::Value::from_file(path)?;
+        let root = root
+            .as_dictionary()
+            .context("Plist root is not a dictionary")?;
+        let metadata = root
+            .get("metadata")
+            .and_then(plist::Value::as_dictionary)
+            .context("Missing metadata")?;
+        let image = ["realTextureFileName", "textureFileName"]
+            .iter()
+            .find_map(|key| metadata.get(key)?.as_string())
+            .context("Missing textureFileName")?
+            .to_string();
+
+        let frames = root
+            .get("frames")
+            .and_then(plist::Value::as_dictionary)
+            .context("Missing frames")?;
+        let sprites = frames
+            .iter()
+            .filter_map(|(name, frame)| {
+                let frame = frame.as_dictionary()?;
+                let rect = ["frame", "textureRect"]
+                    .iter()
+                    .find_map(|key| frame.get(key)?.as_string())?;
+                let numbers: Vec<u32> = rect
+                    .split(|c: char| !c.is_ascii_digit())
+                    .filter_map(|part| part.parse().ok())
+                    .collect();
+                let rotated = ["rotated", "textureRotated"]
+                    .iter()
+                    .find_map(|key| frame.get(key)?.as_boolean())
+                    .unwrap_or(false);
+
+                Some(AtlasSprite {
+                    name: name.clone(),
+                    rect: numbers.get(..4)?.try_into().ok()?,
+                    rotation: if rotated {
+                        AtlasRotation::Clockwise
+                    } else {
+                        AtlasRotation::None
+                    },
+                })
+            })
+            .collect();
+
+        Ok(vec![AtlasPage { image, sprites }])
+    }
+
+    /// libGDX/Spine `.atlas`: pages start with the image file name followed by
+    /// `key: value` lines; regions are unindented names with indented properties
+    fn parse_gdx_atlas(content: &str) -> Vec<AtlasPage> {
+        let mut pages: Vec<AtlasPage> = Vec::new();
+        let mut in_page_header = false;
+        let mut region: Option<(String, Vec<(String, String)>)> = None;
+
+        fn finish_region(pages: &mut [AtlasPage], region: Option<(String, Vec<(String, String)>)>) {
+            let (Some(page), Some((name, properties))) = (pages.last_mut(), region) else {
+                return;
+            };
+            let numbers = |key: &str| -> Option<Vec<u32>> {
+                let (_, value) = properties.iter().find(|(k, _)| k == key)?;
+                value.split(',').map(|v| v.trim().parse().ok()).collect()
+            };
+
+            // Spine 4 uses `bounds: x,y,w,h`; older files use `xy` and `size`
+            let rect = match numbers("bounds") {
+                Some(bounds) if bounds.len() == 4 => [bounds[0], bounds[1], bounds[2], bounds[3]],
+                _ => match (numbers("xy"), numbers("size")) {
+                    (Some(xy), Some(size)) if xy.len() == 2 && size.len() == 2 => {
+                        [xy[0], xy[1], size[0], size[1]]
+                    }
+                    _ => return,
+                },
+            };
+            let rotate = properties
+                .iter()
+                .find(|(key, _)| key == "rotate")
+                .map(|(_, value)| value.as_str());
+
+            page.sprites.push(AtlasSprite {
+                name,
+                rect,
+                rotation: match rotate {
+                    Some("true") | Some("90") => AtlasRotation::CounterClockwise,
+                    _ => AtlasRotation::None,
+                },
+            });
+        }
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                // Blank line ends the page; the next non-empty line names a new image
+                finish_region(&mut pages, region.take());
+                in_page_header = false;
+                pages.push(AtlasPage {
+                    image: String::new(),
+                    sprites: Vec::new(),
+                });
+                continue;
+            }
+
+            if pages.last().is_none_or(|page| page.image.is_empty()) {
+                if pages.is_empty() {
+                    pages.push(AtlasPage {
+                        image: String::new(),
+                        sprites: Vec::new(),
+                    });
+                }
+                if let Some(page) = pages.last_mut() {
+                    page.image = trimmed.to_string();
+                }
+                in_page_header = true;
+                continue;
+            }
+
+            match trimmed.split_once(':') {
+                // Spine 4 writes region properties unindented, so any `key: value` line
+                // after a region name belongs to it
+                Some((key, value)) if in_page_header || region.is_some() => {
+                    if let Some((_, properties)) = region.as_mut() {
+                        properties.push((key.trim().to_string(), value.trim().to_string()));
+                    }
+                }
+                _ => {
+                    // Line without a key: a new region
+                    finish_region(&mut pages, region.take());
+                    in_page_header = false;
+                    region = Some((trimmed.to_string(), Vec::new()));
+                }
+            }
+        }
+        finish_region(&mut pages, region);
+
+        pages.retain(|page| !page.image.is_empty());
+        pages
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, Pipeline, Source, SourceRegistry, StreamWrapper,
+};
+
+/// Ignore file (gitignore syntax) excluding paths from directory walks
+const GTEXIGNORE_FILE: &str = ".gtexignore";
+
+/// Environment variable pointing at a scan filter file
+pub const SCAN_FILTER_ENV: &str = "GTEXVIEWER_SCAN";
+
+/// Scan filter picked up from the working directory when no override is given
+pub const DEFAULT_SCAN_FILTER_FILE: &str = "gtexviewer-scan.toml";
+
+/// Rules narrowing which files a directory walk reports
+///
+/// ```toml
+/// include = ["**/Textures/**"]          # only files matching one of these (default: all)
+/// exclude = ["**/Cache/**", "*_bake*"]  # never files or folders matching these
+/// extensions = ["png", "dds", "ktx2"]   # only these extensions (default: all supported)
+/// respect_gitignore = true              # also skip what .gitignore excludes in repositories
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub extensions: Vec<String>,
+    pub respect_gitignore: bool,
+}
+
+impl ScanFilter {
+    /// Filter from `$GTEXVIEWER_SCAN` or the working directory, else no filtering
+    /// beyond `.gtexignore`; relative paths fall back to the executable's folder in portable mode
+    pub fn load_default() -> Self {
+        let path = crate::portable::resolve_config_path(
+            std::env::var(SCAN_FILTER_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(DEFAULT_SCAN_FILTER_FILE)),
+        );
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::load(&path) {
+            Ok(filter) => {
+                log::info!("🔎 Scan filter loaded from {}", path.display());
+                filter
+            }
+            Err(e) => {
+                log::warn!("⚠️ Failed to load scan filter {}: {e:#}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read scan filter: {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid scan filter: {}", path.display()))
+    }
+
+    /// Include/exclude globs as walker overrides, relative to `root`
+    fn overrides(&self, root: &Path) -> Result<ignore::overrides::Override> {
+        let mut builder = ignore::overrides::OverrideBuilder::new(root);
+        for glob in &self.include {
+            builder.add(glob)?;
+        }
+        for glob in &self.exclude {
+            builder.add(&format!("!{glob}"))?;
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Whether a file passes the extension whitelist
+    /// Compressed files (`albedo.dds.zst`) are judged by their inner extension too
+    fn allows_extension(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+
+        let allowed = |name: &str| {
+            Path::new(name)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    self.extensions
+                        .iter()
+                        .any(|allowed| allowed.trim_start_matches('.').eq_ignore_ascii_case(ext))
+                })
+        };
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        allowed(&name)
+            || StreamWrapper::detect_path(path)
+                .is_some_and(|wrapper| allowed(&wrapper.inner_name(&name)))
+    }
+}
+
+/// What a directory walk reports, in name order
+pub enum DirectoryEvent {
+    Entered(PathBuf), // A folder was reached (including the roots)
+    Found(PathBuf),   // A file some source (or a stream wrapper) can handle
+}
+
+/// Folder and file totals of a walk, known before any file is expanded
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectoryCounts {
+    pub visited_dirs: usize,
+    pub found_files: usize,
+}
+
+/// Directory source - walks folders (honoring `.gtexignore`) and extracts metadata
+/// for every supported file in parallel through the other registered sources
+pub struct DirectorySource;
+
+impl Source for DirectorySource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        Ok(path.is_dir())
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Directories only exist on the filesystem
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        // Files are dispatched through a full registry; the walk never yields folders,
+        // so this source isn't re-entered
+        let pipeline = Pipeline::new();
+        let registry = pipeline.source_registry();
+
+        let mut files = Vec::new();
+        let counts = Self::walk(
+            std::slice::from_ref(&path.to_path_buf()),
+            registry,
+            |event| {
+                if let DirectoryEvent::Found(file) = event {
+                    files.push(file);
+                }
+                ControlFlow::Continue(())
+            },
+        );
+
+        log::info!(
+            "📂 Directory {}: {} supported files in {} folders",
+            path.display(),
+            counts.found_files,
+            counts.visited_dirs
+        );
+
+        Ok(registry.extract_all_metadata_parallel(files))
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        anyhow::bail!("Directories can't be read from a stream: {entry_name}")
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        // Entries keep the path of their own file, so loads go to that file's source
+        anyhow::bail!(
+            "Invalid hint type for directory source: {}",
+            hint.debug_info()
+        )
+    }
+}
+
+impl DirectorySource {
+    /// Walk `roots`, reporting each folder entered and each supported file found
+    /// that passes the scan filter. Stops early when `visit` breaks; returns the totals
+    /// seen so far
+    pub fn walk(
+        roots: &[PathBuf],
+        registry: &SourceRegistry,
+        mut visit: impl FnMut(DirectoryEvent) -> ControlFlow<()>,
+    ) -> DirectoryCounts {
+        let filter = ScanFilter::load_default();
+        let mut counts = DirectoryCounts::default();
+
+        for entry in build_walker(roots, &filter).flatten() {
+            let path = entry.into_path();
+            let event = if path.is_file() {
+                if !filter.allows_extension(&path) || !is_supported_file(registry, &path) {
+                    continue;
+                }
+                counts.found_files += 1;
+                DirectoryEvent::Found(path)
+            } else if path.is_dir() {
+                counts.visited_dirs += 1;
+                DirectoryEvent::Entered(path)
+            } else {
+                continue;
+            };
+
+            if visit(event).is_break() {
+                break;
+            }
+        }
+
+        counts
+    }
+}
+
+/// Name-ordered walk over roots honoring `.gtexignore` files (gitignore syntax)
+/// in each directory and its parents, plus the filter's globs. Hidden files are walked,
+/// and `.gitignore` rules only apply when the filter asks for them, so by default drops
+/// behave the same inside and outside of repositories
+fn build_walker(roots: &[PathBuf], filter: &ScanFilter) -> ignore::Walk {
+    let first_root = roots
+        .first()
+        .map(PathBuf::as_path)
+        .unwrap_or(Path::new("."));
+    let mut builder = ignore::WalkBuilder::new(first_root);
+    for root in roots.iter().skip(1) {
+        builder.add(root);
+    }
+
+    match filter.overrides(first_root) {
+        Ok(overrides) => {
+            builder.overrides(overrides);
+        }
+        Err(e) => log::warn!("⚠️ Ignoring invalid scan filter globs: {e:#}"),
+    }
+
+    builder
+        .standard_filters(false)
+        .git_ignore(filter.respect_gitignore)
+        .git_exclude(filter.respect_gitignore)
+        .parents(true)
+        .add_custom_ignore_filename(GTEXIGNORE_FILE)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build()
+}
+
+/// Lightweight check whether a file can be handled by any source or unwrapped
+fn is_supported_file(registry: &SourceRegistry, path: &Path) -> bool {
+    registry
+        .find_source(path)
+        .is_some_and(|source| source.can_load_path(path).unwrap_or(false))
+        || StreamWrapper::detect_path(path).is_some()
+}
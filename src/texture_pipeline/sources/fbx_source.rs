@@ -1,8 +1,9 @@
 use anyhow::Result;
 use rayon::prelude::*;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 
-use super::ultra_fast_fbx_parser::{TextureData, UltraFastFbxParser};
+use super::ultra_fast_fbx_parser::{FBX_BINARY_MAGIC, TextureData, UltraFastFbxParser};
 use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, FbxHint, Source};
 
 pub struct FbxSource;
@@ -25,11 +26,13 @@ impl Source for FbxSource {
         Ok(true)
     }
 
-    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
-        // FBX files have complex binary format, checking magic bytes is not trivial
-        // The ultra-fast parser can handle this check during actual parsing
-        // For now, we'll return false since we primarily work with file paths
-        Ok(false)
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Binary FBX starts with "Kaydara FBX Binary"; ASCII FBX has no embedded content
+        let mut header = [0u8; FBX_BINARY_MAGIC.len()];
+        reader.seek(SeekFrom::Start(0))?;
+        let matches = reader.read_exact(&mut header).is_ok() && header == FBX_BINARY_MAGIC;
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(matches)
     }
 
     fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
@@ -37,24 +40,7 @@ impl Source for FbxSource {
         let mut parser = UltraFastFbxParser::new(path)?;
         let textures = parser.extract_textures()?;
 
-        // Use rayon to parallelize texture processing
-        let results: Result<Vec<_>, _> = textures
-            .into_par_iter()
-            .enumerate()
-            .filter_map(|(index, texture_data)| {
-                // Only process textures that have embedded content
-                if texture_data.content.is_some() {
-                    Some(self.convert_texture_to_metadata(texture_data, index, path))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        let mut final_results = results?;
-
-        // Make texture names unique if there are duplicates
-        self.ensure_unique_names(&mut final_results);
+        let final_results = self.textures_to_metadata(textures, path)?;
 
         if final_results.is_empty() {
             log::warn!("No textures found in FBX file: {path:?}");
@@ -83,17 +69,56 @@ impl Source for FbxSource {
 
     fn extract_metadata_from_reader(
         &self,
-        _reader: &mut dyn BufReadSeek,
+        reader: &mut dyn BufReadSeek,
         entry_name: &str,
-        _parent_path: &Path,
+        parent_path: &Path,
     ) -> Result<Vec<EmbeddedMetadata>> {
-        // FBX processing from reader not yet implemented
-        log::debug!("FBX processing from reader not yet implemented for entry: {entry_name}");
-        Ok(Vec::new())
+        // FBX nested in another container (FBX-in-ZIP, FBX-in-7z, ...)
+        let mut parser = UltraFastFbxParser::from_reader(reader)?;
+        let textures = parser.extract_textures()?;
+
+        let mut results = self.textures_to_metadata(textures, parent_path)?;
+        for metadata in &mut results {
+            metadata.name = format!("{entry_name} - {}", metadata.name);
+        }
+
+        log::info!(
+            "FBX source extracted {} textures from nested entry {entry_name}",
+            results.len()
+        );
+
+        Ok(results)
     }
 }
 
 impl FbxSource {
+    /// Convert parsed textures with embedded content to metadata, in parallel
+    fn textures_to_metadata(
+        &self,
+        textures: Vec<TextureData>,
+        base_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let results: Result<Vec<_>, _> = textures
+            .into_par_iter()
+            .enumerate()
+            .filter_map(|(index, texture_data)| {
+                // Only process textures that have embedded content
+                if texture_data.content.is_some() {
+                    Some(self.convert_texture_to_metadata(texture_data, index, base_path))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let mut final_results = results?;
+
+        // Make texture names unique if there are duplicates
+        self.ensure_unique_names(&mut final_results);
+
+        Ok(final_results)
+    }
+
     /// Convert TextureData from ultra-fast parser to EmbeddedMetadata
     fn convert_texture_to_metadata(
         &self,
@@ -135,6 +160,7 @@ impl FbxSource {
             file_size: content.len() as u64,
             embedded_hint: hint,
             source_path: base_path.to_path_buf(),
+            parents: Vec::new(),
         })
     }
 
@@ -1,13 +1,20 @@
 use anyhow::{Context, Result};
-use gltf::{Gltf, buffer::Data, texture::Info as TextureInfo};
+use base64::Engine;
+use gltf::Gltf;
+use gltf::texture::{Info as TextureInfo, WrappingMode};
 use std::collections::HashSet;
 use std::io::{BufReader, Read, Seek, SeekFrom};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::texture_pipeline::{
-    BufReadSeek, EmbeddedHint, EmbeddedMetadata, FileHint, GlbHint, Source,
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, FileHint, GlbHint, Source, TextureSampler,
+    WrapMode, source::read_file_slice, vfs,
 };
 
+/// Extension storing textures as KTX2 (Basis Universal) next to an optional fallback
+const BASISU_EXTENSION: &str = "KHR_texture_basisu";
+const KTX2_MIME_TYPE: &str = "image/ktx2";
+
 pub struct GlbSource;
 
 impl Source for GlbSource {
@@ -25,7 +32,7 @@ impl Source for GlbSource {
 
         // For GLB files, check magic bytes
         if path.extension().and_then(|ext| ext.to_str()) == Some("glb") {
-            let mut file = std::fs::File::open(path)?;
+            let mut file = vfs::open(path)?;
             let mut header = [0u8; 4];
             file.read_exact(&mut header)?;
             Ok(&header == b"glTF")
@@ -44,27 +51,21 @@ impl Source for GlbSource {
     fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
         // Load the GLB/GLTF file without validation to support KTX2 extensions
         // Parse container ONCE and create hints with absolute file offsets for direct access
-        let file = std::fs::File::open(path).context("Failed to open GLB/GLTF file")?;
-        let reader = BufReader::new(file);
-        let gltf = Gltf::from_reader_without_validation(reader)
-            .context("Failed to parse GLB/GLTF file")?;
-
-        // Import buffers and calculate absolute file offsets
-        let buffers_result = gltf::import_buffers(
-            &gltf.document,
-            Some(path.parent().unwrap_or(Path::new("."))),
-            gltf.blob.clone(),
-        )
-        .context("Failed to import GLB buffers")?;
+        let bytes = vfs::read(path).context("Failed to open GLB/GLTF file")?;
+        let gltf = parse_gltf(&bytes).context("Failed to parse GLB/GLTF file")?;
+
+        // Locate buffers without loading external .bin files; textures are read by range
+        let buffers = buffer_sources(&gltf, path).context("Failed to resolve GLB buffers")?;
 
         // Calculate absolute file offsets for GLB blob data (if it's a GLB file)
-        let glb_blob_offset = if path.extension().and_then(|e| e.to_str()) == Some("glb") {
+        // Checked by magic rather than extension, so GLBs saved under another name work
+        let glb_blob_offset = if bytes.starts_with(b"glTF") {
             // GLB file structure: 12-byte header + JSON chunk + BIN chunk
             // JSON chunk: 8-byte chunk header + JSON data (padded to 4-byte boundary)
             // BIN chunk starts after JSON chunk
 
             // Re-read the GLB header to calculate offsets
-            let mut file = std::fs::File::open(path)?;
+            let mut file = vfs::open(path)?;
             let mut header = [0u8; 12];
             file.read_exact(&mut header)?;
 
@@ -84,8 +85,6 @@ impl Source for GlbSource {
             0 // GLTF files don't have embedded binary data in same file
         };
 
-        let buffers = buffers_result;
-
         // Track processed texture indices to avoid duplicates
         let mut processed_texture_indices: HashSet<usize> = HashSet::new();
 
@@ -226,16 +225,16 @@ impl Source for GlbSource {
             }
 
             // Otherwise use direct file access with absolute offset - NO RE-PARSING!
-            return self.read_direct_file_slice(
-                &glb_hint.container_path,
-                glb_hint.absolute_file_offset,
-                glb_hint.length,
-            );
+            // .gltf textures live in their external .bin buffer, GLB ones in the file itself
+            if let Some(slice) = glb_hint.file_slice() {
+                return read_file_slice(slice, 0, glb_hint.length)
+                    .context("Failed to read texture data from GLB file");
+            }
         }
 
         // Try to downcast to FileHint for external textures
         if let Some(file_hint) = hint.as_any().downcast_ref::<FileHint>() {
-            return std::fs::read(&file_hint.path).map_err(|e| {
+            return vfs::read(&file_hint.path).map_err(|e| {
                 anyhow::anyhow!(
                     "Failed to read GLB external file {}: {}",
                     file_hint.path.display(),
@@ -254,19 +253,18 @@ impl Source for GlbSource {
         parent_path: &Path,
     ) -> Result<Vec<EmbeddedMetadata>> {
         // Load the GLB/GLTF from reader without validation
-        let gltf = Gltf::from_reader_without_validation(reader)
-            .context("Failed to parse GLB/GLTF from reader")?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let gltf = parse_gltf(&bytes).context("Failed to parse GLB/GLTF from reader")?;
 
         // For reader-based GLB processing, we assume it's GLB format (has blob)
         // Import buffers with blob data
-        let buffers_result = if let Some(blob) = gltf.blob {
-            vec![gltf::buffer::Data(blob)]
+        let buffers = if let Some(blob) = gltf.blob {
+            vec![BufferSource::Blob(blob)]
         } else {
             anyhow::bail!("GLB data from reader has no blob data");
         };
 
-        let buffers = buffers_result;
-
         // Track processed texture indices to avoid duplicates
         let mut processed_texture_indices: HashSet<usize> = HashSet::new();
         let mut material_textures = Vec::new();
@@ -376,7 +374,7 @@ impl GlbSource {
         &self,
         texture_info: &TextureInfo,
         texture_type: &str,
-        buffers: &[Data],
+        buffers: &[BufferSource],
         base_path: &Path,
         glb_blob_offset: usize,
     ) -> Result<EmbeddedMetadata> {
@@ -393,7 +391,7 @@ impl GlbSource {
         &self,
         texture: &gltf::Texture,
         texture_type: &str,
-        buffers: &[Data],
+        buffers: &[BufferSource],
         base_path: &Path,
         glb_blob_offset: usize,
     ) -> Result<EmbeddedMetadata> {
@@ -401,18 +399,19 @@ impl GlbSource {
         let source = image.source();
 
         match source {
-            gltf::image::Source::View { view, mime_type: _ } => {
+            gltf::image::Source::View { view, mime_type } => {
                 // Get image data size from buffer view (without actually reading the full data)
                 let file_size = view.length() as u64;
 
                 // For embedded textures, we need to read a small header for format detection
-                let buffer_data = &buffers[view.buffer().index()];
-                let start = view.offset();
-                let end = start + std::cmp::min(view.length(), 1024); // Read max 1KB for format detection
-                let header_data = &buffer_data.0[start..end];
+                let buffer = buffers
+                    .get(view.buffer().index())
+                    .context("Buffer view points past the GLB buffers")?;
+                let header_len = std::cmp::min(view.length(), 1024); // Read max 1KB for format detection
+                let header_data = &buffer.read(view.offset(), header_len)?;
 
                 // Detect format from the header data
-                let format = imagesize::image_type(header_data)?;
+                let format = image_format(header_data, Some(mime_type))?;
 
                 // Try to get dimensions from the header data
                 let dimension = imagesize::blob_size(header_data)?;
@@ -427,13 +426,24 @@ impl GlbSource {
                     );
                 }
 
+                // External .bin files and the GLB blob are read by range on load
+                let (buffer_path, absolute_file_offset, texture_data) = match buffer {
+                    BufferSource::Blob(_) => (None, glb_blob_offset + view.offset(), None),
+                    BufferSource::File(bin_path) => (Some(bin_path.clone()), view.offset(), None),
+                    BufferSource::Memory(_) => {
+                        (None, 0, Some(buffer.read(view.offset(), view.length())?))
+                    }
+                };
+
                 let hint = Box::new(GlbHint {
                     container_path: base_path.to_path_buf(),
+                    buffer_path,
                     buffer_index: view.buffer().index(),
-                    absolute_file_offset: (glb_blob_offset + view.offset()) as u64,
+                    absolute_file_offset: absolute_file_offset as u64,
                     length: view.length(),
                     relative_buffer_offset: view.offset(),
-                    texture_data: None, // No direct data for file-based access
+                    texture_data,
+                    sampler: texture_sampler(texture),
                 }) as Box<dyn EmbeddedHint>;
 
                 Ok(EmbeddedMetadata {
@@ -444,9 +454,19 @@ impl GlbSource {
                     file_size,
                     embedded_hint: hint,
                     source_path: base_path.to_path_buf(),
+                    parents: Vec::new(),
                 })
             }
-            gltf::image::Source::Uri { uri, mime_type: _ } => {
+            gltf::image::Source::Uri { uri, mime_type: _ } if uri.starts_with("data:") => {
+                let data = decode_data_uri(uri)?;
+                self.data_uri_metadata(
+                    data,
+                    texture_type.to_string(),
+                    base_path,
+                    texture_sampler(texture),
+                )
+            }
+            gltf::image::Source::Uri { uri, mime_type } => {
                 // Handle external image files referenced by URI
                 let image_path = if Path::new(uri).is_absolute() {
                     Path::new(uri).to_path_buf()
@@ -455,13 +475,16 @@ impl GlbSource {
                 };
 
                 // For external files, read just the header for metadata
-                let file = std::fs::File::open(&image_path)?;
+                let file = vfs::open(&image_path)?;
                 let mut reader = BufReader::new(file);
 
-                let format = imagesize::reader_type(&mut reader)?;
+                let format = match mime_type {
+                    Some(KTX2_MIME_TYPE) => imagesize::ImageType::Ktx2,
+                    _ => imagesize::reader_type(&mut reader)?,
+                };
                 reader.seek(SeekFrom::Start(0))?;
                 let dimension = imagesize::reader_size(&mut reader)?;
-                let file_size = std::fs::metadata(&image_path)?.len();
+                let file_size = vfs::file_size(&image_path)?;
 
                 // Skip textures with invalid dimensions
                 if dimension.width == 0 || dimension.height == 0 {
@@ -476,6 +499,7 @@ impl GlbSource {
                 // For external files, use FileHint
                 let hint = Box::new(FileHint {
                     path: image_path.clone(),
+                    sampler: Some(texture_sampler(texture)),
                 }) as Box<dyn EmbeddedHint>;
 
                 Ok(EmbeddedMetadata {
@@ -486,41 +510,55 @@ impl GlbSource {
                     file_size,
                     embedded_hint: hint,
                     source_path: image_path,
+                    parents: Vec::new(),
                 })
             }
         }
     }
 
-    /// Direct file access using absolute file offset - NO RE-PARSING!
-    /// This is the key to the hint system working properly
-    fn read_direct_file_slice(
+    /// Metadata for an image embedded as a data URI; the decoded bytes live in the hint
+    fn data_uri_metadata(
         &self,
-        glb_path: &Path,
-        absolute_offset: u64,
-        length: usize,
-    ) -> Result<Vec<u8>> {
-        use std::fs::File;
-        use std::io::{Read, Seek, SeekFrom};
-
-        let mut file = File::open(glb_path).context("Failed to open GLB file for direct access")?;
-
-        // Seek to the absolute offset
-        file.seek(SeekFrom::Start(absolute_offset))
-            .context("Failed to seek to texture data offset")?;
-
-        // Read the exact number of bytes
-        let mut buffer = vec![0u8; length];
-        file.read_exact(&mut buffer)
-            .context("Failed to read texture data from GLB file")?;
-
-        log::debug!(
-            "Direct GLB access: read {} bytes from offset {} in {}",
-            length,
-            absolute_offset,
-            glb_path.display()
-        );
-
-        Ok(buffer)
+        data: Vec<u8>,
+        name: String,
+        container_path: &Path,
+        sampler: TextureSampler,
+    ) -> Result<EmbeddedMetadata> {
+        let format = imagesize::image_type(&data)?;
+        let dimension = imagesize::blob_size(&data)?;
+
+        // Skip textures with invalid dimensions
+        if dimension.width == 0 || dimension.height == 0 {
+            anyhow::bail!(
+                "Invalid dimensions for data URI texture {}: {}x{}",
+                name,
+                dimension.width,
+                dimension.height
+            );
+        }
+
+        let file_size = data.len() as u64;
+        let hint = Box::new(GlbHint {
+            container_path: container_path.to_path_buf(),
+            buffer_path: None,
+            buffer_index: 0,         // Not backed by a buffer
+            absolute_file_offset: 0, // Not applicable for data URIs
+            length: data.len(),
+            relative_buffer_offset: 0,
+            texture_data: Some(data), // Decoded image bytes
+            sampler,
+        }) as Box<dyn EmbeddedHint>;
+
+        Ok(EmbeddedMetadata {
+            name,
+            format,
+            width: dimension.width,
+            height: dimension.height,
+            file_size,
+            embedded_hint: hint,
+            source_path: container_path.to_path_buf(),
+            parents: Vec::new(),
+        })
     }
 
     /// Extract texture metadata from reader-based GLB processing
@@ -528,7 +566,7 @@ impl GlbSource {
         &self,
         texture: &gltf::Texture,
         texture_type: &str,
-        buffers: &[gltf::buffer::Data],
+        buffers: &[BufferSource],
         parent_path: &Path,
         container_name: &str,
     ) -> Result<EmbeddedMetadata> {
@@ -536,18 +574,19 @@ impl GlbSource {
         let source = image.source();
 
         match source {
-            gltf::image::Source::View { view, mime_type: _ } => {
+            gltf::image::Source::View { view, mime_type } => {
                 // Get image data size from buffer view
                 let file_size = view.length() as u64;
 
                 // For embedded textures, read header for format detection
-                let buffer_data = &buffers[view.buffer().index()];
-                let start = view.offset();
-                let end = start + std::cmp::min(view.length(), 1024);
-                let header_data = &buffer_data.0[start..end];
+                let buffer = buffers
+                    .get(view.buffer().index())
+                    .context("Buffer view points past the GLB buffers")?;
+                let header_len = std::cmp::min(view.length(), 1024);
+                let header_data = &buffer.read(view.offset(), header_len)?;
 
                 // Detect format and dimensions
-                let format = imagesize::image_type(header_data)?;
+                let format = image_format(header_data, Some(mime_type))?;
                 let dimension = imagesize::blob_size(header_data)?;
 
                 // Skip textures with invalid dimensions
@@ -562,17 +601,17 @@ impl GlbSource {
 
                 // Extract the actual texture data and store it in GlbHint
                 // This avoids the hint mismatch issue with nested containers
-                let buffer_data = &buffers[view.buffer().index()];
-                let texture_data =
-                    buffer_data.0[view.offset()..view.offset() + view.length()].to_vec();
+                let texture_data = buffer.read(view.offset(), view.length())?;
 
                 let hint = Box::new(GlbHint {
                     container_path: parent_path.to_path_buf(),
+                    buffer_path: None,
                     buffer_index: view.buffer().index(),
                     absolute_file_offset: 0, // Not applicable for nested container
                     length: view.length(),
                     relative_buffer_offset: view.offset(),
                     texture_data: Some(texture_data), // Store actual data for nested container
+                    sampler: texture_sampler(texture),
                 }) as Box<dyn EmbeddedHint>;
 
                 Ok(EmbeddedMetadata {
@@ -583,8 +622,18 @@ impl GlbSource {
                     file_size,
                     embedded_hint: hint,
                     source_path: parent_path.to_path_buf(), // Keep original path for reference
+                    parents: Vec::new(),
                 })
             }
+            gltf::image::Source::Uri { uri, mime_type: _ } if uri.starts_with("data:") => {
+                let data = decode_data_uri(uri)?;
+                self.data_uri_metadata(
+                    data,
+                    format!("{container_name} - {texture_type}"),
+                    parent_path,
+                    texture_sampler(texture),
+                )
+            }
             gltf::image::Source::Uri {
                 uri: _,
                 mime_type: _,
@@ -594,3 +643,135 @@ impl GlbSource {
         }
     }
 }
+
+/// Parse a GLB/glTF without validation, with textures using `KHR_texture_basisu`
+/// pointed at their KTX2 image
+fn parse_gltf(bytes: &[u8]) -> Result<Gltf> {
+    let (json, blob) = if bytes.starts_with(b"glTF") {
+        let glb = gltf::Glb::from_slice(bytes)?;
+        (glb.json.into_owned(), glb.bin.map(|bin| bin.into_owned()))
+    } else {
+        (bytes.to_vec(), None)
+    };
+
+    let mut value: serde_json::Value = serde_json::from_slice(&json)?;
+    let redirected = redirect_basisu_sources(&mut value);
+    if redirected > 0 {
+        log::debug!("{redirected} texture(s) use {BASISU_EXTENSION}, reading their KTX2 images");
+    }
+
+    let root: gltf::json::Root = serde_json::from_value(value)?;
+    Ok(Gltf {
+        document: gltf::Document::from_json_without_validation(root),
+        blob,
+    })
+}
+
+/// Replace each basisu texture's core `source` (a PNG/JPEG fallback, or missing when
+/// the extension is required) with the extension's KTX2 image; returns how many changed
+fn redirect_basisu_sources(json: &mut serde_json::Value) -> usize {
+    let Some(textures) = json
+        .get_mut("textures")
+        .and_then(|textures| textures.as_array_mut())
+    else {
+        return 0;
+    };
+
+    let pointer = format!("/extensions/{BASISU_EXTENSION}/source");
+    let mut redirected = 0;
+    for texture in textures {
+        if let Some(source) = texture.pointer(&pointer).cloned() {
+            texture["source"] = source;
+            redirected += 1;
+        }
+    }
+    redirected
+}
+
+/// Format of a texture image; the `image/ktx2` MIME type of basisu images is trusted
+/// over sniffing so they always reach the KTX2 parser
+fn image_format(header: &[u8], mime_type: Option<&str>) -> Result<imagesize::ImageType> {
+    if mime_type == Some(KTX2_MIME_TYPE) {
+        return Ok(imagesize::ImageType::Ktx2);
+    }
+    Ok(imagesize::image_type(header)?)
+}
+
+/// Wrap modes of the texture's glTF sampler (Repeat when it has none)
+fn texture_sampler(texture: &gltf::Texture) -> TextureSampler {
+    let wrap = |mode| match mode {
+        WrappingMode::Repeat => WrapMode::Repeat,
+        WrappingMode::MirroredRepeat => WrapMode::MirroredRepeat,
+        WrappingMode::ClampToEdge => WrapMode::ClampToEdge,
+    };
+    let sampler = texture.sampler();
+    TextureSampler {
+        wrap_s: wrap(sampler.wrap_s()),
+        wrap_t: wrap(sampler.wrap_t()),
+    }
+}
+
+/// Where a glTF buffer's bytes come from
+enum BufferSource {
+    Blob(Vec<u8>),   // GLB BIN chunk, read by range from the GLB file on load
+    Memory(Vec<u8>), // Decoded data URI
+    File(PathBuf),   // External .bin file, never loaded whole
+}
+
+impl BufferSource {
+    /// `length` bytes at `offset` within the buffer
+    fn read(&self, offset: usize, length: usize) -> Result<Vec<u8>> {
+        match self {
+            Self::Blob(data) | Self::Memory(data) => data
+                .get(offset..offset + length)
+                .map(<[u8]>::to_vec)
+                .context("Buffer view exceeds buffer length"),
+            Self::File(path) => {
+                let mut file = vfs::open(path)
+                    .with_context(|| format!("Failed to open glTF buffer {}", path.display()))?;
+                file.seek(SeekFrom::Start(offset as u64))?;
+                let mut data = vec![0u8; length];
+                file.read_exact(&mut data)
+                    .with_context(|| format!("Failed to read glTF buffer {}", path.display()))?;
+                Ok(data)
+            }
+        }
+    }
+}
+
+/// Resolve every buffer of a glTF document; external .bin files are only located
+fn buffer_sources(gltf: &Gltf, path: &Path) -> Result<Vec<BufferSource>> {
+    gltf.document
+        .buffers()
+        .map(|buffer| match buffer.source() {
+            gltf::buffer::Source::Bin => gltf
+                .blob
+                .clone()
+                .map(BufferSource::Blob)
+                .context("GLB references a missing BIN chunk"),
+            gltf::buffer::Source::Uri(uri) if uri.starts_with("data:") => {
+                decode_data_uri(uri).map(BufferSource::Memory)
+            }
+            gltf::buffer::Source::Uri(uri) => Ok(BufferSource::File(
+                path.parent().unwrap_or(Path::new(".")).join(uri),
+            )),
+        })
+        .collect()
+}
+
+/// Decode a `data:[<mime>][;base64],<payload>` URI into raw bytes
+fn decode_data_uri(uri: &str) -> Result<Vec<u8>> {
+    let (header, payload) = uri
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .context("Malformed data URI")?;
+
+    // Image payloads are always base64 in practice
+    if !header.ends_with(";base64") {
+        anyhow::bail!("Unsupported data URI encoding: {header}");
+    }
+
+    base64::engine::general_purpose::STANDARD
+        .decode(payload.trim())
+        .context("Failed to decode base64 data URI")
+}
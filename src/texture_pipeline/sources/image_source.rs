@@ -1,8 +1,8 @@
 use anyhow::Result;
-use std::io::{BufReader, Seek, SeekFrom};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 
-use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, FileHint, Source};
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, FileHint, Source, vfs};
 
 /// Universal image source that handles all standard image formats via imagesize
 pub struct ImageSource;
@@ -10,7 +10,7 @@ pub struct ImageSource;
 impl Source for ImageSource {
     fn can_load_path(&self, path: &Path) -> Result<bool> {
         // Use imagesize to detect if this is a valid image file
-        let file = std::fs::File::open(path)?;
+        let file = vfs::open(path)?;
         let mut reader = BufReader::new(file);
         Ok(imagesize::reader_type(&mut reader).is_ok())
     }
@@ -22,7 +22,7 @@ impl Source for ImageSource {
 
     fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
         // Open file and create buffered reader
-        let file = std::fs::File::open(path)?;
+        let file = vfs::open(path)?;
         let mut reader = BufReader::new(file);
 
         // Use imagesize for format detection
@@ -33,7 +33,7 @@ impl Source for ImageSource {
         let dimension = imagesize::reader_size(&mut reader)?;
 
         // Get file size
-        let file_size = std::fs::metadata(path)?.len();
+        let file_size = vfs::file_size(path)?;
 
         // Skip files with invalid dimensions
         if dimension.width == 0 || dimension.height == 0 {
@@ -48,6 +48,7 @@ impl Source for ImageSource {
         // Create file hint for direct file loading
         let hint = Box::new(FileHint {
             path: path.to_path_buf(),
+            sampler: None,
         }) as Box<dyn EmbeddedHint>;
 
         let metadata = EmbeddedMetadata {
@@ -62,6 +63,7 @@ impl Source for ImageSource {
             file_size,
             embedded_hint: hint,
             source_path: path.to_path_buf(),
+            parents: Vec::new(),
         };
 
         Ok(vec![metadata])
@@ -95,6 +97,7 @@ impl Source for ImageSource {
         // Note: This is a simplification - in a full implementation, we'd need nested hints
         let hint = Box::new(FileHint {
             path: parent_path.to_path_buf(),
+            sampler: None,
         }) as Box<dyn EmbeddedHint>;
 
         let metadata = EmbeddedMetadata {
@@ -105,6 +108,7 @@ impl Source for ImageSource {
             file_size: 0, // Will be set by the container source
             embedded_hint: hint,
             source_path: parent_path.to_path_buf(),
+            parents: Vec::new(),
         };
 
         Ok(vec![metadata])
@@ -113,7 +117,7 @@ impl Source for ImageSource {
     fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
         // Try to downcast to FileHint
         if let Some(file_hint) = hint.as_any().downcast_ref::<FileHint>() {
-            return std::fs::read(&file_hint.path).map_err(|e| {
+            return vfs::read(&file_hint.path).map_err(|e| {
                 anyhow::anyhow!(
                     "Failed to read image file {}: {}",
                     file_hint.path.display(),
@@ -124,4 +128,17 @@ impl Source for ImageSource {
 
         anyhow::bail!("Invalid hint type for Image source: {}", hint.debug_info())
     }
+
+    fn read_range(&self, hint: &dyn EmbeddedHint, offset: u64, length: usize) -> Result<Vec<u8>> {
+        let Some(file_hint) = hint.as_any().downcast_ref::<FileHint>() else {
+            anyhow::bail!("Invalid hint type for Image source: {}", hint.debug_info())
+        };
+
+        // Plain files are read by range straight from disk, cut short at the end
+        let mut file = vfs::open(&file_hint.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut data = Vec::with_capacity(length);
+        file.take(length as u64).read_to_end(&mut data)?;
+        Ok(data)
+    }
 }
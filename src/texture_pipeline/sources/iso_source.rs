@@ -0,0 +1,752 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::texture_pipeline::source::read_header_incrementally;
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, IsoHint, Source, vfs};
+
+const SECTOR_SIZE: u64 = 2048;
+/// Volume descriptors start at sector 16, after the system area
+const FIRST_DESCRIPTOR_SECTOR: u64 = 16;
+const MAX_DESCRIPTORS: u64 = 64;
+/// UDF anchor volume descriptor pointer
+const UDF_ANCHOR_SECTOR: u64 = 256;
+
+const MAX_DIRECTORY_DEPTH: usize = 64;
+const MAX_DIRECTORY_SIZE: u64 = 64 * 1024 * 1024;
+const MAX_FILES: usize = 200_000;
+/// Bytes read from each file of a nested image to tell whether it's an image
+const SNIFF_SIZE: usize = 1024;
+
+const UDF_TAG_ANCHOR: u16 = 2;
+const UDF_TAG_PARTITION: u16 = 5;
+const UDF_TAG_LOGICAL_VOLUME: u16 = 6;
+const UDF_TAG_TERMINATOR: u16 = 8;
+const UDF_TAG_FILE_SET: u16 = 256;
+const UDF_TAG_FILE_IDENTIFIER: u16 = 257;
+const UDF_TAG_FILE_ENTRY: u16 = 261;
+const UDF_TAG_EXTENDED_FILE_ENTRY: u16 = 266;
+const UDF_FILE_TYPE_DIRECTORY: u8 = 4;
+
+/// A regular file in a disc image
+struct DiscFile {
+    path: String,
+    extents: Vec<(u64, u64)>, // (byte offset, length) runs, in file order
+    size: u64,
+    embedded: Option<Vec<u8>>, // UDF files small enough to live inside their file entry
+}
+
+/// ISO9660/UDF disc image source
+/// Files are exposed like ZIP entries so the pipeline can detect and recurse into them;
+/// UDF is preferred when present, falling back to the ISO9660 tree (Joliet/Rock Ridge names)
+pub struct IsoSource;
+
+impl Source for IsoSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_iso_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| matches!(ext.to_lowercase().as_str(), "iso" | "udf"))
+            .unwrap_or(false);
+
+        if !has_iso_extension {
+            return Ok(false);
+        }
+
+        let file = vfs::open(path)?;
+        let mut reader = BufReader::new(file);
+        self.can_load_reader(&mut reader)
+    }
+
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Volume recognition sequence: "CD001" for ISO9660, "BEA01"/"NSR0x" for UDF
+        let mut found = false;
+        for sector in FIRST_DESCRIPTOR_SECTOR..FIRST_DESCRIPTOR_SECTOR + 4 {
+            let mut identifier = [0u8; 6];
+            reader.seek(SeekFrom::Start(sector * SECTOR_SIZE))?;
+            if reader.read_exact(&mut identifier).is_err() {
+                break;
+            }
+            if matches!(&identifier[1..6], b"CD001" | b"BEA01" | b"NSR02" | b"NSR03") {
+                found = true;
+                break;
+            }
+        }
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(found)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let file = vfs::open(path).context("Failed to open disc image")?;
+        let mut reader = BufReader::new(file);
+        let files = Self::list_files(&mut reader)?;
+
+        let mut metadata_list = Vec::new();
+        for file in files {
+            let result = (|| -> Result<EmbeddedMetadata> {
+                let header_bytes = match file.embedded {
+                    Some(ref data) => Some(data[..data.len().min(65536)].to_vec()),
+                    None => {
+                        let mut entry = ExtentReader::new(&mut reader, &file.extents, file.size);
+                        read_header_incrementally(&mut entry, file.size as usize)?
+                    }
+                };
+
+                let hint = Box::new(IsoHint {
+                    container_path: path.to_path_buf(),
+                    entry_path: file.path.clone(),
+                    extents: file.extents.clone(),
+                    size: file.size,
+                    header_bytes,
+                    entry_data: file.embedded.clone(),
+                }) as Box<dyn EmbeddedHint>;
+
+                // Pipeline determines the real format and dimensions from header bytes
+                Ok(EmbeddedMetadata {
+                    name: file.path.clone(),
+                    format: imagesize::ImageType::Png, // Placeholder - Pipeline will determine actual format
+                    width: 0,
+                    height: 0,
+                    file_size: file.size,
+                    embedded_hint: hint,
+                    source_path: path.to_path_buf(),
+                    parents: Vec::new(),
+                })
+            })();
+
+            match result {
+                Ok(metadata) => metadata_list.push(metadata),
+                Err(e) => log::debug!(
+                    "Failed to extract metadata from ISO entry {}: {e}",
+                    file.path
+                ),
+            }
+        }
+
+        if metadata_list.is_empty() {
+            anyhow::bail!("No files found in disc image");
+        }
+
+        log::info!(
+            "ISO container extraction completed: {} entries from {}",
+            metadata_list.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let files = Self::list_files(reader)?;
+        let mut metadata_list = Vec::new();
+
+        // Nested images can't be re-opened by path, so the data of files that sniff as
+        // images is captured now; the rest of the disc is left unread
+        for file in files {
+            let header = match &file.embedded {
+                Some(data) => data[..data.len().min(SNIFF_SIZE)].to_vec(),
+                None => {
+                    let mut header = Vec::new();
+                    ExtentReader::new(&mut *reader, &file.extents, file.size)
+                        .take(SNIFF_SIZE as u64)
+                        .read_to_end(&mut header)?;
+                    header
+                }
+            };
+            if imagesize::image_type(&header).is_err() {
+                continue;
+            }
+
+            let entry_data = match file.embedded {
+                Some(data) => data,
+                None => {
+                    let mut data = entry_buffer(file.size);
+                    ExtentReader::new(&mut *reader, &file.extents, file.size)
+                        .read_to_end(&mut data)?;
+                    data
+                }
+            };
+
+            let header_len = entry_data.len().min(65536);
+            let hint = Box::new(IsoHint {
+                container_path: parent_path.to_path_buf(),
+                entry_path: file.path.clone(),
+                extents: file.extents,
+                size: file.size,
+                header_bytes: Some(entry_data[..header_len].to_vec()),
+                entry_data: Some(entry_data),
+            }) as Box<dyn EmbeddedHint>;
+
+            metadata_list.push(EmbeddedMetadata {
+                name: format!("{entry_name}/{}", file.path),
+                format: imagesize::ImageType::Png, // Placeholder - Pipeline will determine actual format
+                width: 0,
+                height: 0,
+                file_size: file.size,
+                embedded_hint: hint,
+                source_path: parent_path.to_path_buf(),
+                parents: Vec::new(),
+            });
+        }
+
+        Ok(metadata_list)
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(iso_hint) = hint.as_any().downcast_ref::<IsoHint>() {
+            if let Some(ref entry_data) = iso_hint.entry_data {
+                return Ok(entry_data.clone());
+            }
+
+            let file = vfs::open(&iso_hint.container_path)
+                .context("Failed to open disc image for reading entry")?;
+            let mut reader = BufReader::new(file);
+            let mut buffer = entry_buffer(iso_hint.size);
+            ExtentReader::new(&mut reader, &iso_hint.extents, iso_hint.size)
+                .read_to_end(&mut buffer)
+                .with_context(|| format!("Failed to read ISO entry: {}", iso_hint.entry_path))?;
+
+            if (buffer.len() as u64) < iso_hint.size {
+                anyhow::bail!(
+                    "ISO entry {} truncated: {} of {} bytes",
+                    iso_hint.entry_path,
+                    buffer.len(),
+                    iso_hint.size
+                );
+            }
+            return Ok(buffer);
+        }
+
+        anyhow::bail!("Invalid hint type for ISO source: {}", hint.debug_info())
+    }
+}
+
+impl IsoSource {
+    /// Every regular file in the image, from the UDF tree when there is one
+    fn list_files<R: Read + Seek + ?Sized>(reader: &mut R) -> Result<Vec<DiscFile>> {
+        match udf::list_files(reader) {
+            Ok(Some(files)) if !files.is_empty() => return Ok(files),
+            Ok(_) => {}
+            Err(e) => log::debug!("UDF tree unreadable, falling back to ISO9660: {e:#}"),
+        }
+        iso9660::list_files(reader)
+    }
+}
+
+/// Reads a file's bytes across its extents, stopping at the file size
+struct ExtentReader<'a, R: Read + Seek + ?Sized> {
+    reader: &'a mut R,
+    extents: &'a [(u64, u64)],
+    extent_index: usize,
+    extent_pos: u64,
+    remaining: u64,
+}
+
+impl<'a, R: Read + Seek + ?Sized> ExtentReader<'a, R> {
+    fn new(reader: &'a mut R, extents: &'a [(u64, u64)], size: u64) -> Self {
+        Self {
+            reader,
+            extents,
+            extent_index: 0,
+            extent_pos: 0,
+            remaining: size,
+        }
+    }
+}
+
+impl<R: Read + Seek + ?Sized> Read for ExtentReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.remaining > 0 {
+            let Some(&(offset, length)) = self.extents.get(self.extent_index) else {
+                return Ok(0);
+            };
+            if self.extent_pos >= length {
+                self.extent_index += 1;
+                self.extent_pos = 0;
+                continue;
+            }
+
+            let want = (length - self.extent_pos)
+                .min(self.remaining)
+                .min(buf.len() as u64);
+            self.reader
+                .seek(SeekFrom::Start(offset + self.extent_pos))?;
+            let read = self.reader.read(&mut buf[..want as usize])?;
+            if read == 0 {
+                return Ok(0);
+            }
+            self.extent_pos += read as u64;
+            self.remaining -= read as u64;
+            return Ok(read);
+        }
+        Ok(0)
+    }
+}
+
+fn read_at<R: Read + Seek + ?Sized>(reader: &mut R, offset: u64, len: usize) -> Result<Vec<u8>> {
+    let mut buffer = vec![0u8; len];
+    reader.seek(SeekFrom::Start(offset))?;
+    reader
+        .read_exact(&mut buffer)
+        .with_context(|| format!("Disc image truncated reading {len} bytes at {offset}"))?;
+    Ok(buffer)
+}
+
+fn u16_at(data: &[u8], pos: usize) -> Result<u16> {
+    let bytes = data.get(pos..pos + 2).context("Disc structure truncated")?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], pos: usize) -> Result<u32> {
+    let bytes = data.get(pos..pos + 4).context("Disc structure truncated")?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn u64_at(data: &[u8], pos: usize) -> Result<u64> {
+    Ok(u32_at(data, pos)? as u64 | (u32_at(data, pos + 4)? as u64) << 32)
+}
+
+fn join_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{parent}/{name}")
+    }
+}
+
+/// ISO9660 directory tree, with Joliet (preferred) or Rock Ridge long names
+mod iso9660 {
+    use super::*;
+
+    const DIRECTORY_RECORD_ROOT: usize = 156;
+    const FLAG_DIRECTORY: u8 = 0x02;
+    const FLAG_MULTI_EXTENT: u8 = 0x80;
+
+    pub(super) fn list_files<R: Read + Seek + ?Sized>(reader: &mut R) -> Result<Vec<DiscFile>> {
+        let mut primary_root = None;
+        let mut joliet_root = None;
+
+        for sector in FIRST_DESCRIPTOR_SECTOR..FIRST_DESCRIPTOR_SECTOR + MAX_DESCRIPTORS {
+            let descriptor = read_at(reader, sector * SECTOR_SIZE, SECTOR_SIZE as usize)?;
+            if &descriptor[1..6] != b"CD001" {
+                break;
+            }
+
+            let root = &descriptor[DIRECTORY_RECORD_ROOT..DIRECTORY_RECORD_ROOT + 34];
+            let root = (u32_at(root, 2)? as u64, u32_at(root, 10)? as u64);
+            match descriptor[0] {
+                1 => primary_root = Some(root),
+                // Supplementary descriptor with a UCS-2 escape sequence is Joliet
+                2 if is_joliet(&descriptor) => joliet_root = Some(root),
+                255 => break,
+                _ => {}
+            }
+        }
+
+        let (root, joliet) = match (joliet_root, primary_root) {
+            (Some(root), _) => (root, true),
+            (None, Some(root)) => (root, false),
+            (None, None) => anyhow::bail!("No ISO9660 volume descriptor"),
+        };
+
+        let mut files = Vec::new();
+        let mut visited = HashSet::new();
+        walk(reader, root, "", joliet, 0, &mut visited, &mut files)?;
+        Ok(files)
+    }
+
+    fn is_joliet(descriptor: &[u8]) -> bool {
+        let escapes = &descriptor[88..120];
+        escapes
+            .windows(3)
+            .any(|seq| matches!(seq, b"%/@" | b"%/C" | b"%/E"))
+    }
+
+    fn walk<R: Read + Seek + ?Sized>(
+        reader: &mut R,
+        (lba, size): (u64, u64),
+        parent: &str,
+        joliet: bool,
+        depth: usize,
+        visited: &mut HashSet<u64>,
+        files: &mut Vec<DiscFile>,
+    ) -> Result<()> {
+        if depth > MAX_DIRECTORY_DEPTH || !visited.insert(lba) || files.len() >= MAX_FILES {
+            return Ok(());
+        }
+        let directory = read_at(
+            reader,
+            lba * SECTOR_SIZE,
+            size.min(MAX_DIRECTORY_SIZE) as usize,
+        )?;
+
+        let mut subdirectories = Vec::new();
+        let mut pending_extents = Vec::new();
+        let mut pending_size = 0u64;
+        let mut pos = 0usize;
+
+        while pos < directory.len() {
+            let record_len = directory[pos] as usize;
+            if record_len == 0 {
+                // Records never span sectors; the rest of this sector is padding
+                pos = (pos / SECTOR_SIZE as usize + 1) * SECTOR_SIZE as usize;
+                continue;
+            }
+            let Some(record) = directory.get(pos..pos + record_len) else {
+                break;
+            };
+            pos += record_len;
+
+            if record.len() < 34 {
+                continue;
+            }
+            let extended_attributes = record[1] as u64;
+            let extent_lba = u32_at(record, 2)? as u64 + extended_attributes;
+            let data_len = u32_at(record, 10)? as u64;
+            let flags = record[25];
+            let name_len = record[32] as usize;
+            let Some(raw_name) = record.get(33..33 + name_len) else {
+                continue;
+            };
+
+            // "\0" and "\1" are the directory itself and its parent
+            if name_len == 1 && raw_name[0] <= 1 {
+                continue;
+            }
+
+            let name = if joliet {
+                decode_ucs2(raw_name)
+            } else {
+                // System use area (Rock Ridge) starts after the name, padded to even length
+                let system_use = record
+                    .get(33 + name_len + (1 - name_len % 2)..)
+                    .unwrap_or(&[]);
+                rock_ridge_name(system_use)
+                    .unwrap_or_else(|| String::from_utf8_lossy(raw_name).into_owned())
+            };
+            let name = clean_name(&name);
+            let path = join_path(parent, &name);
+
+            if flags & FLAG_DIRECTORY != 0 {
+                subdirectories.push(((extent_lba, data_len), path));
+                continue;
+            }
+
+            // Files over 4 GiB are split into records flagged as continuing
+            pending_extents.push((extent_lba * SECTOR_SIZE, data_len));
+            pending_size += data_len;
+            if flags & FLAG_MULTI_EXTENT != 0 {
+                continue;
+            }
+
+            if pending_size > 0 {
+                files.push(DiscFile {
+                    path,
+                    extents: std::mem::take(&mut pending_extents),
+                    size: pending_size,
+                    embedded: None,
+                });
+            } else {
+                pending_extents.clear();
+            }
+            pending_size = 0;
+        }
+
+        for (extent, path) in subdirectories {
+            walk(reader, extent, &path, joliet, depth + 1, visited, files)?;
+        }
+        Ok(())
+    }
+
+    fn decode_ucs2(bytes: &[u8]) -> String {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    }
+
+    /// Rock Ridge "NM" alternate name from the system use area, if present
+    fn rock_ridge_name(mut system_use: &[u8]) -> Option<String> {
+        let mut name = Vec::new();
+        while system_use.len() >= 4 {
+            let len = system_use[2] as usize;
+            if len < 4 || len > system_use.len() {
+                break;
+            }
+            if &system_use[..2] == b"NM" && len >= 5 {
+                name.extend_from_slice(&system_use[5..len]);
+            }
+            system_use = &system_use[len..];
+        }
+        (!name.is_empty()).then(|| String::from_utf8_lossy(&name).into_owned())
+    }
+
+    /// Drop the ";1" version suffix and the trailing dot of extensionless names
+    fn clean_name(name: &str) -> String {
+        let name = name.split_once(';').map_or(name, |(base, _)| base);
+        name.strip_suffix('.').unwrap_or(name).to_string()
+    }
+}
+
+/// UDF (ECMA-167) file tree on a plain Type 1 partition; metadata and virtual
+/// partitions (Blu-ray, packet-written discs) are left to the ISO9660 bridge
+mod udf {
+    use super::*;
+
+    /// Partition start and logical block size, for translating logical block numbers
+    struct Volume {
+        partition_start: u64, // In sectors
+        block_size: u64,
+    }
+
+    impl Volume {
+        fn offset(&self, block: u32) -> u64 {
+            (self.partition_start * SECTOR_SIZE) + block as u64 * self.block_size
+        }
+    }
+
+    /// A file entry's contents: extents on disc or data embedded in the entry
+    struct Allocation {
+        is_directory: bool,
+        size: u64,
+        extents: Vec<(u64, u64)>,
+        embedded: Option<Vec<u8>>,
+    }
+
+    pub(super) fn list_files<R: Read + Seek + ?Sized>(
+        reader: &mut R,
+    ) -> Result<Option<Vec<DiscFile>>> {
+        if !has_nsr_descriptor(reader)? {
+            return Ok(None);
+        }
+
+        let anchor = read_at(
+            reader,
+            UDF_ANCHOR_SECTOR * SECTOR_SIZE,
+            SECTOR_SIZE as usize,
+        )?;
+        if u16_at(&anchor, 0)? != UDF_TAG_ANCHOR {
+            anyhow::bail!("No UDF anchor at sector {UDF_ANCHOR_SECTOR}");
+        }
+        let vds_length = u32_at(&anchor, 16)? as u64;
+        let vds_location = u32_at(&anchor, 20)? as u64;
+
+        let mut partition_start = None;
+        let mut logical_volume = None;
+        for sector in vds_location..vds_location + (vds_length / SECTOR_SIZE).min(MAX_DESCRIPTORS) {
+            let descriptor = read_at(reader, sector * SECTOR_SIZE, SECTOR_SIZE as usize)?;
+            match u16_at(&descriptor, 0)? {
+                UDF_TAG_PARTITION => partition_start = Some(u32_at(&descriptor, 188)? as u64),
+                UDF_TAG_LOGICAL_VOLUME => logical_volume = Some(descriptor),
+                UDF_TAG_TERMINATOR => break,
+                _ => {}
+            }
+        }
+
+        let partition_start = partition_start.context("UDF has no partition descriptor")?;
+        let logical_volume = logical_volume.context("UDF has no logical volume descriptor")?;
+        let block_size = u32_at(&logical_volume, 212)? as u64;
+        let map_count = u32_at(&logical_volume, 268)?;
+        let first_map_type = *logical_volume
+            .get(440)
+            .context("UDF partition map missing")?;
+        if map_count != 1 || first_map_type != 1 || block_size != SECTOR_SIZE {
+            anyhow::bail!(
+                "Unsupported UDF layout ({map_count} partition maps, type {first_map_type}, {block_size}-byte blocks)"
+            );
+        }
+
+        let volume = Volume {
+            partition_start,
+            block_size,
+        };
+
+        // File set descriptor location is a long_ad in the logical volume contents use
+        let file_set_block = u32_at(&logical_volume, 252)?;
+        let file_set = read_at(reader, volume.offset(file_set_block), block_size as usize)?;
+        if u16_at(&file_set, 0)? != UDF_TAG_FILE_SET {
+            anyhow::bail!("UDF file set descriptor not found");
+        }
+        let root_block = u32_at(&file_set, 404)?;
+
+        let mut files = Vec::new();
+        let mut visited = HashSet::new();
+        walk(reader, &volume, root_block, "", 0, &mut visited, &mut files)?;
+        Ok(Some(files))
+    }
+
+    fn has_nsr_descriptor<R: Read + Seek + ?Sized>(reader: &mut R) -> Result<bool> {
+        for sector in FIRST_DESCRIPTOR_SECTOR..FIRST_DESCRIPTOR_SECTOR + MAX_DESCRIPTORS {
+            let Ok(descriptor) = read_at(reader, sector * SECTOR_SIZE, 6) else {
+                return Ok(false);
+            };
+            match &descriptor[1..6] {
+                b"NSR02" | b"NSR03" => return Ok(true),
+                b"TEA01" => return Ok(false),
+                b"CD001" | b"BEA01" | b"BOOT2" | b"CDW02" => {}
+                _ => return Ok(false),
+            }
+        }
+        Ok(false)
+    }
+
+    fn walk<R: Read + Seek + ?Sized>(
+        reader: &mut R,
+        volume: &Volume,
+        directory_block: u32,
+        parent: &str,
+        depth: usize,
+        visited: &mut HashSet<u32>,
+        files: &mut Vec<DiscFile>,
+    ) -> Result<()> {
+        if depth > MAX_DIRECTORY_DEPTH || !visited.insert(directory_block) {
+            return Ok(());
+        }
+
+        let directory = read_entry(reader, volume, directory_block)?;
+        if directory.size > MAX_DIRECTORY_SIZE {
+            anyhow::bail!("UDF directory too large: {} bytes", directory.size);
+        }
+        let contents = match directory.embedded {
+            Some(data) => data,
+            None => {
+                let mut data = entry_buffer(directory.size);
+                ExtentReader::new(&mut *reader, &directory.extents, directory.size)
+                    .read_to_end(&mut data)?;
+                data
+            }
+        };
+
+        let mut pos = 0usize;
+        while pos + 38 <= contents.len() && files.len() < MAX_FILES {
+            let identifier = &contents[pos..];
+            if u16_at(identifier, 0)? != UDF_TAG_FILE_IDENTIFIER {
+                break;
+            }
+            let characteristics = identifier[18];
+            let name_len = identifier[19] as usize;
+            let entry_block = u32_at(identifier, 24)?;
+            let implementation_len = u16_at(identifier, 36)? as usize;
+            let name_start = 38 + implementation_len;
+            pos += (name_start + name_len + 3) & !3;
+
+            // Skip deleted entries and the parent link
+            if characteristics & 0x0C != 0 || name_len == 0 {
+                continue;
+            }
+            let Some(raw_name) = identifier.get(name_start..name_start + name_len) else {
+                break;
+            };
+            let path = join_path(parent, &decode_dstring(raw_name));
+
+            let entry = match read_entry(reader, volume, entry_block) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    log::debug!("Skipping UDF entry {path}: {e}");
+                    continue;
+                }
+            };
+
+            if entry.is_directory {
+                walk(
+                    reader,
+                    volume,
+                    entry_block,
+                    &path,
+                    depth + 1,
+                    visited,
+                    files,
+                )?;
+            } else if entry.size > 0 {
+                files.push(DiscFile {
+                    path,
+                    extents: entry.extents,
+                    size: entry.size,
+                    embedded: entry.embedded,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a (extended) file entry and its allocation descriptors
+    fn read_entry<R: Read + Seek + ?Sized>(
+        reader: &mut R,
+        volume: &Volume,
+        block: u32,
+    ) -> Result<Allocation> {
+        let entry = read_at(reader, volume.offset(block), volume.block_size as usize)?;
+        let (attributes_len_at, descriptors_len_at, descriptors_start) = match u16_at(&entry, 0)? {
+            UDF_TAG_FILE_ENTRY => (168, 172, 176),
+            UDF_TAG_EXTENDED_FILE_ENTRY => (208, 212, 216),
+            tag => anyhow::bail!("Expected UDF file entry at block {block}, found tag {tag}"),
+        };
+
+        let is_directory = entry[27] == UDF_FILE_TYPE_DIRECTORY;
+        let allocation_type = u16_at(&entry, 34)? & 0x7;
+        let size = u64_at(&entry, 56)?;
+        let start = descriptors_start + u32_at(&entry, attributes_len_at)? as usize;
+        let descriptors = entry
+            .get(start..start + u32_at(&entry, descriptors_len_at)? as usize)
+            .context("UDF allocation descriptors overflow the file entry")?;
+
+        let descriptor_size = match allocation_type {
+            0 => 8,  // short_ad
+            1 => 16, // long_ad
+            3 => {
+                return Ok(Allocation {
+                    is_directory,
+                    size,
+                    extents: Vec::new(),
+                    embedded: Some(descriptors[..(size as usize).min(descriptors.len())].to_vec()),
+                });
+            }
+            other => anyhow::bail!("Unsupported UDF allocation type {other}"),
+        };
+
+        let mut extents = Vec::new();
+        for descriptor in descriptors.chunks_exact(descriptor_size) {
+            let raw_length = u32_at(descriptor, 0)?;
+            let length = (raw_length & 0x3FFF_FFFF) as u64;
+            let extent_type = raw_length >> 30;
+            if length == 0 {
+                break;
+            }
+            // 3 continues the descriptor list elsewhere; 1/2 are unrecorded (sparse) extents
+            if extent_type != 0 {
+                anyhow::bail!("Unsupported UDF extent type {extent_type}");
+            }
+            extents.push((volume.offset(u32_at(descriptor, 4)?), length));
+        }
+
+        Ok(Allocation {
+            is_directory,
+            size,
+            extents,
+            embedded: None,
+        })
+    }
+
+    /// OSTA compressed unicode: 8 = one byte per character, 16 = UCS-2 big endian
+    fn decode_dstring(bytes: &[u8]) -> String {
+        match bytes.split_first() {
+            Some((&16, rest)) => {
+                let units: Vec<u16> = rest
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            Some((_, rest)) => rest.iter().map(|&b| b as char).collect(),
+            None => String::new(),
+        }
+    }
+}
@@ -1,10 +1,60 @@
+mod apk_source;
+mod atlas_source;
+mod directory_source;
 mod fbx_source;
 mod glb_source;
 mod image_source;
+mod iso_source;
+mod obj_source;
+mod pak_source;
+mod pdf_source;
+mod pk3_source;
+mod pmx_source;
+mod sevenz_source;
+mod stdin_source;
+mod tar_source;
+mod three_ds_source;
+mod uasset_source;
 pub mod ultra_fast_fbx_parser;
+mod url_source;
+mod usdz_source;
+mod vpk_source;
+mod wad_source;
+mod xcf_source;
 mod zip_source;
 
+pub use apk_source::ApkSource;
+pub use atlas_source::AtlasSource;
+pub use directory_source::{
+    DEFAULT_SCAN_FILTER_FILE, DirectoryCounts, DirectoryEvent, DirectorySource, SCAN_FILTER_ENV,
+    ScanFilter,
+};
 pub use fbx_source::FbxSource;
 pub use glb_source::GlbSource;
 pub use image_source::ImageSource;
+pub use iso_source::IsoSource;
+pub use obj_source::ObjSource;
+pub use pak_source::PakSource;
+pub use pdf_source::PdfSource;
+pub use pk3_source::Pk3Source;
+pub use pmx_source::PmxSource;
+pub use sevenz_source::SevenZipSource;
+pub use stdin_source::{StdinSource, is_stdin_path};
+pub use tar_source::TarSource;
+pub use three_ds_source::ThreeDsSource;
+pub use uasset_source::UassetSource;
+pub use url_source::{UrlSource, is_remote_url};
+pub use usdz_source::UsdzSource;
+pub use vpk_source::VpkSource;
+pub use wad_source::WadSource;
+pub use xcf_source::XcfSource;
 pub use zip_source::ZipSource;
+
+/// Most memory reserved up front for an entry; sizes from archive headers are untrusted,
+/// so larger entries grow the buffer as they're read
+const MAX_ENTRY_PREALLOCATION: u64 = 64 * 1024 * 1024;
+
+/// Buffer for reading an entry of the size an archive header claims
+pub(crate) fn entry_buffer(size: u64) -> Vec<u8> {
+    Vec::with_capacity(size.min(MAX_ENTRY_PREALLOCATION) as usize)
+}
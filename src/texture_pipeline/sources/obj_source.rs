@@ -0,0 +1,271 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::io::{BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, FileHint, Source, vfs};
+
+/// MTL texture map statements and the map type used in entry names
+const MAP_STATEMENTS: &[(&str, &str)] = &[
+    ("map_kd", "Diffuse"),
+    ("map_ka", "Ambient"),
+    ("map_ks", "Specular"),
+    ("map_ns", "Specular Exponent"),
+    ("map_d", "Alpha"),
+    ("map_bump", "Bump"),
+    ("bump", "Bump"),
+    ("norm", "Normal"),
+    ("map_kn", "Normal"),
+    ("disp", "Displacement"),
+    ("decal", "Decal"),
+    ("refl", "Reflection"),
+    ("map_pr", "Roughness"),
+    ("map_pm", "Metallic"),
+    ("map_ps", "Sheen"),
+    ("map_ke", "Emissive"),
+];
+
+/// Number of arguments taken by each MTL texture map option
+const MAP_OPTIONS: &[(&str, usize)] = &[
+    ("-blendu", 1),
+    ("-blendv", 1),
+    ("-boost", 1),
+    ("-bm", 1),
+    ("-cc", 1),
+    ("-clamp", 1),
+    ("-imfchan", 1),
+    ("-texres", 1),
+    ("-type", 1),
+    ("-mm", 2),
+    ("-o", 3),
+    ("-s", 3),
+    ("-t", 3),
+];
+
+/// Wavefront OBJ source - follows `mtllib` references and loads every texture
+/// map referenced by the materials as `<material> - <map type>` entries
+pub struct ObjSource;
+
+impl Source for ObjSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        Ok(path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| matches!(ext.to_lowercase().as_str(), "obj" | "mtl"))
+            .unwrap_or(false))
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // OBJ/MTL are plain text without a signature, and their texture references
+        // are relative paths that can't be resolved inside another container
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let is_mtl = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("mtl"))
+            .unwrap_or(false);
+
+        let mtl_paths = if is_mtl {
+            vec![path.to_path_buf()]
+        } else {
+            Self::find_material_libraries(path)?
+        };
+
+        let mut processed_textures: HashSet<PathBuf> = HashSet::new();
+        let mut results = Vec::new();
+
+        for mtl_path in mtl_paths {
+            let content = match vfs::read(&mtl_path) {
+                Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                Err(e) => {
+                    log::warn!("Failed to read MTL file {}: {}", mtl_path.display(), e);
+                    continue;
+                }
+            };
+
+            let mtl_dir = mtl_path.parent().unwrap_or(Path::new("."));
+
+            for (material_name, map_type, texture_ref) in Self::parse_material_maps(&content) {
+                let texture_path = Self::resolve_texture_path(mtl_dir, &texture_ref);
+                if !processed_textures.insert(texture_path.clone()) {
+                    continue;
+                }
+
+                match self.extract_texture_metadata(
+                    &texture_path,
+                    &format!("{material_name} - {map_type}"),
+                ) {
+                    Ok(metadata) => results.push(metadata),
+                    Err(e) => log::warn!(
+                        "Skipping OBJ texture {} ({material_name} - {map_type}): {e}",
+                        texture_path.display()
+                    ),
+                }
+            }
+        }
+
+        if results.is_empty() {
+            anyhow::bail!("No valid textures found in OBJ/MTL file");
+        }
+
+        Ok(results)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        anyhow::bail!("OBJ/MTL textures can't be resolved from reader: {entry_name}")
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(file_hint) = hint.as_any().downcast_ref::<FileHint>() {
+            return vfs::read(&file_hint.path).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to read OBJ texture file {}: {}",
+                    file_hint.path.display(),
+                    e
+                )
+            });
+        }
+
+        anyhow::bail!("Invalid hint type for OBJ source: {}", hint.debug_info())
+    }
+}
+
+impl ObjSource {
+    /// Collect `mtllib` references, falling back to a sibling `<name>.mtl`
+    fn find_material_libraries(obj_path: &Path) -> Result<Vec<PathBuf>> {
+        let bytes = vfs::read(obj_path).context("Failed to read OBJ file")?;
+        let content = String::from_utf8_lossy(&bytes);
+        let obj_dir = obj_path.parent().unwrap_or(Path::new("."));
+
+        let mut libraries: Vec<PathBuf> = content
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("mtllib"))
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map(|rest| Self::resolve_texture_path(obj_dir, rest.trim()))
+            .collect();
+
+        if libraries.is_empty() {
+            let sibling = obj_path.with_extension("mtl");
+            if sibling.exists() {
+                libraries.push(sibling);
+            }
+        }
+
+        Ok(libraries)
+    }
+
+    /// Parse `(material, map type, texture reference)` triples from MTL content
+    fn parse_material_maps(content: &str) -> Vec<(String, &'static str, String)> {
+        let mut maps = Vec::new();
+        let mut material_name = "default".to_string();
+
+        for line in content.lines() {
+            let line = line.trim();
+            let Some((keyword, rest)) = line.split_once(char::is_whitespace) else {
+                continue;
+            };
+
+            let keyword = keyword.to_lowercase();
+            if keyword == "newmtl" {
+                material_name = rest.trim().to_string();
+                continue;
+            }
+
+            if let Some((_, map_type)) = MAP_STATEMENTS.iter().find(|(stmt, _)| *stmt == keyword)
+                && let Some(texture_ref) = Self::strip_map_options(rest)
+            {
+                maps.push((material_name.clone(), *map_type, texture_ref));
+            }
+        }
+
+        maps
+    }
+
+    /// Remove `-option value...` prefixes, leaving the (possibly space-containing) file name
+    fn strip_map_options(args: &str) -> Option<String> {
+        let tokens: Vec<&str> = args.split_whitespace().collect();
+        let mut index = 0;
+
+        while index < tokens.len() {
+            let Some((_, arg_count)) = MAP_OPTIONS
+                .iter()
+                .find(|(option, _)| tokens[index].eq_ignore_ascii_case(option))
+            else {
+                break;
+            };
+
+            index += 1;
+            // -o/-s/-t take up to three numbers; stop at the first non-numeric token
+            let mut consumed = 0;
+            while consumed < *arg_count && index < tokens.len() {
+                let token = tokens[index];
+                let is_value = token.parse::<f32>().is_ok()
+                    || matches!(token, "on" | "off")
+                    || (*arg_count == 1 && consumed == 0);
+                if !is_value {
+                    break;
+                }
+                index += 1;
+                consumed += 1;
+            }
+        }
+
+        let file_name = tokens[index.min(tokens.len())..].join(" ");
+        (!file_name.is_empty()).then_some(file_name)
+    }
+
+    /// MTL files from Windows tools often use backslashes
+    fn resolve_texture_path(base_dir: &Path, reference: &str) -> PathBuf {
+        let normalized = reference.replace('\\', "/");
+        let path = Path::new(&normalized);
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            base_dir.join(path)
+        }
+    }
+
+    fn extract_texture_metadata(&self, image_path: &Path, name: &str) -> Result<EmbeddedMetadata> {
+        let file = vfs::open(image_path)?;
+        let mut reader = BufReader::new(file);
+
+        let format = imagesize::reader_type(&mut reader)?;
+        reader.seek(SeekFrom::Start(0))?;
+        let dimension = imagesize::reader_size(&mut reader)?;
+        let file_size = vfs::file_size(image_path)?;
+
+        // Skip textures with invalid dimensions
+        if dimension.width == 0 || dimension.height == 0 {
+            anyhow::bail!(
+                "Invalid dimensions for OBJ texture {}: {}x{}",
+                name,
+                dimension.width,
+                dimension.height
+            );
+        }
+
+        let hint = Box::new(FileHint {
+            path: image_path.to_path_buf(),
+            sampler: None,
+        }) as Box<dyn EmbeddedHint>;
+
+        Ok(EmbeddedMetadata {
+            name: name.to_string(),
+            format,
+            width: dimension.width,
+            height: dimension.height,
+            file_size,
+            embedded_hint: hint,
+            source_path: image_path.to_path_buf(),
+            parents: Vec::new(),
+        })
+    }
+}
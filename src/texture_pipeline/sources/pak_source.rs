@@ -0,0 +1,791 @@
+use anyhow::{Context, Result};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::texture_pipeline::source::read_header_incrementally;
+use crate::texture_pipeline::sources::{UassetSource, entry_buffer};
+use crate::texture_pipeline::unreal;
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, PakCompression, PakHint, Source, UassetHint,
+    UassetPakParts, vfs,
+};
+
+const PAK_MAGIC: [u8; 4] = 0x5A6F12E1u32.to_le_bytes();
+
+/// Magic, version, index offset/size and index hash
+const FOOTER_INFO_SIZE: usize = 4 + 4 + 8 + 8 + 20;
+
+/// The footer is searched for in this many trailing bytes (covers the compression name table)
+const FOOTER_SEARCH_SIZE: u64 = 512;
+
+/// First version that stores compressed block offsets relative to the entry
+const VERSION_RELATIVE_CHUNK_OFFSETS: u32 = 5;
+
+/// First version with the compression method name table in the footer
+const VERSION_FNAME_BASED_COMPRESSION: u32 = 8;
+
+/// First version with the path hash index and bit-packed entries
+const VERSION_PATH_HASH_INDEX: u32 = 10;
+
+/// Unreal Engine .pak archive source
+/// Reads the footer and index, then exposes every file so .uasset/.ubulk payloads and
+/// plain images inside shipped games and mods reach the recursive pipeline
+pub struct PakSource;
+
+/// Trailer of a pak file pointing at the index
+#[derive(Debug)]
+struct PakFooter {
+    version: u32,
+    index_offset: u64,
+    index_size: u64,
+    index_encrypted: bool,
+    compression_names: Vec<String>, // Method table for version 8+; entries index it 1-based
+    compression_index_u8: bool,     // Early version 8 paks store the method index as one byte
+}
+
+/// One file as described by the pak index
+#[derive(Debug)]
+struct PakEntry {
+    name: String,
+    offset: u64, // Start of the entry's in-data record header
+    size: u64,
+    uncompressed_size: u64,
+    compression: PakCompression,
+    blocks: Vec<(u64, u64)>, // Absolute start/end of each compressed block
+    encrypted: bool,
+    header_size: u64, // Size of the record header preceding the data
+}
+
+impl PakEntry {
+    fn data_offset(&self) -> u64 {
+        self.offset + self.header_size
+    }
+}
+
+impl Source for PakSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_pak_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase() == "pak")
+            .unwrap_or(false);
+
+        if !has_pak_extension {
+            return Ok(false);
+        }
+
+        let mut file = vfs::open(path)?;
+        Ok(Self::read_footer(&mut file).is_ok())
+    }
+
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // The magic lives in the footer, so this only succeeds when the whole pak is available
+        let result = Self::read_footer(reader);
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(result.is_ok())
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let mut file = vfs::open(path).context("Failed to open pak file")?;
+        let metadata_list = Self::collect_entries(&mut file, path, None, false)?;
+
+        if metadata_list.is_empty() {
+            anyhow::bail!("No readable entries found in pak archive");
+        }
+
+        log::info!(
+            "Pak container extraction completed: {} entries from {}",
+            metadata_list.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        // Nested paks can't be re-opened by path, so entry data is captured now
+        Self::collect_entries(&mut Cursor::new(data), parent_path, Some(entry_name), true)
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(pak_hint) = hint.as_any().downcast_ref::<PakHint>() {
+            return Self::read_entry(pak_hint);
+        }
+
+        // Cooked textures found in the pak are decoded from their package files
+        if let Some(uasset_hint) = hint.as_any().downcast_ref::<UassetHint>() {
+            return UassetSource::load_texture(uasset_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for pak source: {}", hint.debug_info())
+    }
+}
+
+impl PakSource {
+    /// Bytes of one entry, from the hint's captured data or the pak on disk
+    pub fn read_entry(hint: &PakHint) -> Result<Vec<u8>> {
+        if let Some(ref entry_data) = hint.entry_data {
+            return Ok(entry_data.clone());
+        }
+
+        let mut file =
+            vfs::open(&hint.container_path).context("Failed to open pak file for reading entry")?;
+        Self::read_entry_data(
+            &mut file,
+            &hint.compression,
+            hint.data_offset,
+            hint.size,
+            hint.uncompressed_size,
+            &hint.blocks,
+        )
+        .with_context(|| format!("Failed to read pak entry {}", hint.entry_name))
+    }
+
+    /// Walk the index once, capturing header bytes (and optionally full data)
+    fn collect_entries<R: Read + Seek>(
+        reader: &mut R,
+        container_path: &Path,
+        parent_entry: Option<&str>,
+        capture_data: bool,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let footer = Self::read_footer(reader)?;
+        if footer.index_encrypted {
+            anyhow::bail!("Pak index is encrypted (AES key required)");
+        }
+
+        let entries = Self::read_index(reader, &footer)?;
+        let by_name: HashMap<String, &PakEntry> = entries
+            .iter()
+            .map(|entry| (entry.name.to_lowercase(), entry))
+            .collect();
+        let mut metadata_list = Vec::new();
+        let mut skipped = 0;
+
+        for entry in &entries {
+            if entry.encrypted || matches!(entry.compression, PakCompression::Unsupported(_)) {
+                skipped += 1;
+                continue;
+            }
+
+            let name = match parent_entry {
+                Some(parent) => format!("{parent}/{}", entry.name),
+                None => entry.name.clone(),
+            };
+
+            // Cooked packages: the .uasset stands for its .uexp/.ubulk companions
+            let lower_name = entry.name.to_lowercase();
+            if let Some(stem) = lower_name
+                .strip_suffix(".uexp")
+                .or_else(|| lower_name.strip_suffix(".ubulk"))
+                && by_name.contains_key(&format!("{stem}.uasset"))
+            {
+                continue;
+            }
+            if let Some(stem) = lower_name.strip_suffix(".uasset") {
+                let companion = |extension: &str| {
+                    by_name
+                        .get(&format!("{stem}.{extension}"))
+                        .copied()
+                        .filter(|companion| {
+                            !companion.encrypted
+                                && !matches!(companion.compression, PakCompression::Unsupported(_))
+                        })
+                };
+                match Self::texture_entry(
+                    reader,
+                    container_path,
+                    name,
+                    entry,
+                    companion("uexp"),
+                    companion("ubulk"),
+                    capture_data,
+                ) {
+                    Ok(Some(metadata)) => metadata_list.push(metadata),
+                    Ok(None) => {}
+                    Err(e) => log::debug!("Skipping Unreal asset {}: {e}", entry.name),
+                }
+                continue;
+            }
+
+            let data = if capture_data {
+                Some(Self::read_pak_entry(reader, entry)?)
+            } else {
+                None
+            };
+            let header_bytes = match data {
+                Some(ref data) => Some(data[..data.len().min(65536)].to_vec()),
+                None => match Self::read_entry_header(reader, &entry) {
+                    Ok(header) => header,
+                    Err(e) => {
+                        log::debug!("Skipping pak entry {}: {e}", entry.name);
+                        continue;
+                    }
+                },
+            };
+
+            let hint = Box::new(PakHint {
+                header_bytes,
+                entry_data: data,
+                ..Self::pak_hint(container_path, entry)
+            }) as Box<dyn EmbeddedHint>;
+
+            metadata_list.push(EmbeddedMetadata {
+                name,
+                format: imagesize::ImageType::Png, // Placeholder - Pipeline will determine actual format
+                width: 0,
+                height: 0,
+                file_size: entry.uncompressed_size,
+                embedded_hint: hint,
+                source_path: container_path.to_path_buf(),
+                parents: Vec::new(),
+            });
+        }
+
+        if skipped > 0 {
+            log::warn!(
+                "Skipped {skipped} encrypted or Oodle-compressed entries in {}",
+                container_path.display()
+            );
+        }
+
+        Ok(metadata_list)
+    }
+
+    /// Hint locating an entry in the pak on disk
+    fn pak_hint(container_path: &Path, entry: &PakEntry) -> PakHint {
+        PakHint {
+            container_path: container_path.to_path_buf(),
+            entry_name: entry.name.clone(),
+            data_offset: entry.data_offset(),
+            size: entry.size,
+            uncompressed_size: entry.uncompressed_size,
+            compression: entry.compression.clone(),
+            blocks: entry.blocks.clone(),
+            header_bytes: None,
+            entry_data: None,
+        }
+    }
+
+    /// Cooked Texture2D package as one entry decoded by `UassetSource`
+    /// Packages of other asset types yield None
+    fn texture_entry<R: Read + Seek>(
+        reader: &mut R,
+        container_path: &Path,
+        name: String,
+        uasset: &PakEntry,
+        uexp: Option<&PakEntry>,
+        ubulk: Option<&PakEntry>,
+        capture_data: bool,
+    ) -> Result<Option<EmbeddedMetadata>> {
+        // The class name sits in the .uasset name table; checking it avoids reading
+        // the export data of meshes, materials and the like
+        let mut package = Self::read_pak_entry(reader, uasset)?;
+        if !package.windows(9).any(|window| window == b"Texture2D") {
+            return Ok(None);
+        }
+        if let Some(uexp) = uexp {
+            package.extend(Self::read_pak_entry(reader, uexp)?);
+        }
+
+        let info = unreal::texture_info(&package, ubulk.is_some())?;
+        let (pak_parts, pixel_data) = if capture_data {
+            // Nested paks can't be re-opened by path, so the mip is decoded now
+            let bulk = ubulk
+                .map(|ubulk| Self::read_pak_entry(reader, ubulk))
+                .transpose()?;
+            let pixel_data = unreal::read_texture(&package, bulk.as_deref())?;
+            (None, Some(pixel_data))
+        } else {
+            let parts = UassetPakParts {
+                uasset: Self::pak_hint(container_path, uasset),
+                uexp: uexp.map(|uexp| Self::pak_hint(container_path, uexp)),
+                ubulk: ubulk.map(|ubulk| Self::pak_hint(container_path, ubulk)),
+            };
+            (Some(parts), None)
+        };
+
+        let hint = Box::new(UassetHint {
+            container_path: container_path.to_path_buf(),
+            asset_name: uasset.name.clone(),
+            pixel_format: info.pixel_format,
+            pak_parts,
+            pixel_data,
+        }) as Box<dyn EmbeddedHint>;
+
+        Ok(Some(EmbeddedMetadata {
+            name,
+            format: info.image_type,
+            width: info.width as usize,
+            height: info.height as usize,
+            file_size: [Some(uasset), uexp, ubulk]
+                .into_iter()
+                .flatten()
+                .map(|part| part.uncompressed_size)
+                .sum(),
+            embedded_hint: hint,
+            source_path: container_path.to_path_buf(),
+            parents: Vec::new(),
+        }))
+    }
+
+    /// Find the footer by its magic near the end of the file
+    fn read_footer<R: Read + Seek + ?Sized>(reader: &mut R) -> Result<PakFooter> {
+        let file_size = reader.seek(SeekFrom::End(0))?;
+        let tail_size = file_size.min(FOOTER_SEARCH_SIZE);
+        reader.seek(SeekFrom::Start(file_size - tail_size))?;
+        let mut tail = vec![0u8; tail_size as usize];
+        reader.read_exact(&mut tail)?;
+
+        let magic_pos = (0..=tail.len().saturating_sub(FOOTER_INFO_SIZE))
+            .rev()
+            .find(|&pos| tail[pos..].starts_with(&PAK_MAGIC))
+            .context("Pak footer magic not found")?;
+
+        let mut info = ByteReader::new(&tail[magic_pos + 4..]);
+        let version = info.u32()?;
+        let index_offset = info.u64()?;
+        let index_size = info.u64()?;
+        if version == 0 || index_offset.saturating_add(index_size) > file_size {
+            anyhow::bail!("Invalid pak footer (version {version})");
+        }
+
+        // Version 4+ stores the encrypted-index flag right before the magic
+        let index_encrypted = version >= 4 && magic_pos > 0 && tail[magic_pos - 1] != 0;
+
+        // Version 8+ appends a table of 32-byte compression method names
+        let mut names_start = magic_pos + FOOTER_INFO_SIZE;
+        if version == 9 {
+            names_start += 1; // bIndexIsFrozen
+        }
+        let names_region = tail.get(names_start..).unwrap_or_default();
+        let compression_names = if version >= VERSION_FNAME_BASED_COMPRESSION {
+            names_region
+                .chunks_exact(32)
+                .map(|chunk| {
+                    let end = chunk.iter().position(|&b| b == 0).unwrap_or(chunk.len());
+                    String::from_utf8_lossy(&chunk[..end]).into_owned()
+                })
+                .filter(|name| !name.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(PakFooter {
+            version,
+            index_offset,
+            index_size,
+            index_encrypted,
+            compression_names,
+            compression_index_u8: version == VERSION_FNAME_BASED_COMPRESSION
+                && names_region.len() == 4 * 32,
+        })
+    }
+
+    fn read_index<R: Read + Seek>(reader: &mut R, footer: &PakFooter) -> Result<Vec<PakEntry>> {
+        let index = Self::read_range(reader, footer.index_offset, footer.index_size)?;
+        let mut index = ByteReader::new(&index);
+        let mount_point = index.fstring()?;
+
+        if footer.version < VERSION_PATH_HASH_INDEX {
+            let entry_count = index.u32()?;
+            let mut entries = Vec::new();
+            for _ in 0..entry_count {
+                let file_name = index.fstring()?;
+                let mut entry = Self::read_legacy_entry(&mut index, footer)?;
+                entry.name = Self::entry_path(&mount_point, &file_name);
+                entries.push(entry);
+            }
+            return Ok(entries);
+        }
+
+        // Path hash index layout: names only live in the full directory index
+        let _entry_count = index.u32()?;
+        let _path_hash_seed = index.u64()?;
+        if index.u32()? != 0 {
+            index.skip(8 + 8 + 20)?; // Path hash index offset, size and hash
+        }
+        let directory_index = if index.u32()? != 0 {
+            let offset = index.u64()?;
+            let size = index.u64()?;
+            index.skip(20)?;
+            Some((offset, size))
+        } else {
+            None
+        };
+
+        let encoded_size = index.u32()? as usize;
+        let encoded_entries = index.bytes(encoded_size)?;
+        let unencoded_count = index.u32()?;
+        let mut unencoded_entries = Vec::new();
+        for _ in 0..unencoded_count {
+            unencoded_entries.push(Self::read_legacy_entry(&mut index, footer)?);
+        }
+
+        let (directory_offset, directory_size) =
+            directory_index.context("Pak has no full directory index")?;
+        let directory_data = Self::read_range(reader, directory_offset, directory_size)?;
+        let mut directory = ByteReader::new(&directory_data);
+
+        let mut entries = Vec::new();
+        for _ in 0..directory.u32()? {
+            let directory_name = directory.fstring()?;
+            for _ in 0..directory.u32()? {
+                let file_name = directory.fstring()?;
+                let location = directory.i32()?;
+
+                // Negative locations index the entries that didn't fit the packed encoding
+                let mut entry = if location < 0 {
+                    let index = (-(location as i64) - 1) as usize;
+                    let Some(entry) = unencoded_entries.get(index) else {
+                        continue;
+                    };
+                    PakEntry {
+                        name: String::new(),
+                        offset: entry.offset,
+                        size: entry.size,
+                        uncompressed_size: entry.uncompressed_size,
+                        compression: entry.compression.clone(),
+                        blocks: entry.blocks.clone(),
+                        encrypted: entry.encrypted,
+                        header_size: entry.header_size,
+                    }
+                } else {
+                    let encoded = encoded_entries
+                        .get(location as usize..)
+                        .context("Encoded pak entry out of range")?;
+                    Self::read_encoded_entry(&mut ByteReader::new(encoded), footer)?
+                };
+
+                let relative = format!("{}{}", directory_name.trim_start_matches('/'), file_name);
+                entry.name = Self::entry_path(&mount_point, &relative);
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// FPakEntry as serialized in the legacy index (and in front of every entry's data)
+    fn read_legacy_entry(reader: &mut ByteReader, footer: &PakFooter) -> Result<PakEntry> {
+        let offset = reader.u64()?;
+        let size = reader.u64()?;
+        let uncompressed_size = reader.u64()?;
+        let compression_index = if footer.compression_index_u8 {
+            reader.u8()? as u32
+        } else {
+            reader.u32()?
+        };
+        if footer.version == 1 {
+            reader.skip(8)?; // Timestamp
+        }
+        reader.skip(20)?; // SHA-1
+
+        let compression = Self::compression(footer, compression_index);
+        let mut blocks = Vec::new();
+        let mut encrypted = false;
+        if footer.version >= 3 {
+            if compression != PakCompression::None {
+                let base = if footer.version >= VERSION_RELATIVE_CHUNK_OFFSETS {
+                    offset
+                } else {
+                    0
+                };
+                for _ in 0..reader.u32()? {
+                    let start = reader.u64()?;
+                    let end = reader.u64()?;
+                    blocks.push((base + start, base + end));
+                }
+            }
+            encrypted = reader.u8()? & 1 != 0;
+            let _block_size = reader.u32()?;
+        }
+
+        Ok(PakEntry {
+            name: String::new(),
+            offset,
+            size,
+            uncompressed_size,
+            header_size: Self::record_header_size(footer, &compression, blocks.len()),
+            compression,
+            blocks,
+            encrypted,
+        })
+    }
+
+    /// Bit-packed FPakEntry of the path hash index (version 10+)
+    fn read_encoded_entry(reader: &mut ByteReader, footer: &PakFooter) -> Result<PakEntry> {
+        let bits = reader.u32()?;
+        let compression_index = (bits >> 23) & 0x3f;
+        let encrypted = bits & (1 << 22) != 0;
+        let block_count = ((bits >> 6) & 0xffff) as usize;
+        if bits & 0x3f == 0x3f {
+            let _block_size = reader.u32()?;
+        }
+
+        let mut value = |is_u32: bool| -> Result<u64> {
+            if is_u32 {
+                Ok(reader.u32()? as u64)
+            } else {
+                reader.u64()
+            }
+        };
+        let offset = value(bits & (1 << 31) != 0)?;
+        let uncompressed_size = value(bits & (1 << 30) != 0)?;
+        let compression = Self::compression(footer, compression_index);
+        let size = if compression_index != 0 {
+            value(bits & (1 << 29) != 0)?
+        } else {
+            uncompressed_size
+        };
+
+        let header_size = Self::record_header_size(footer, &compression, block_count);
+        let mut blocks = Vec::with_capacity(block_count);
+        if compression_index != 0 {
+            let mut start = offset + header_size;
+            if block_count == 1 && !encrypted {
+                blocks.push((start, start + size));
+            } else {
+                for _ in 0..block_count {
+                    let length = reader.u32()? as u64;
+                    blocks.push((start, start + length));
+                    // Encrypted blocks are padded to the AES block size
+                    start += if encrypted {
+                        length.next_multiple_of(16)
+                    } else {
+                        length
+                    };
+                }
+            }
+        }
+
+        Ok(PakEntry {
+            name: String::new(),
+            offset,
+            size,
+            uncompressed_size,
+            compression,
+            blocks,
+            encrypted,
+            header_size,
+        })
+    }
+
+    /// Size of the FPakEntry record written in front of each entry's data
+    fn record_header_size(
+        footer: &PakFooter,
+        compression: &PakCompression,
+        block_count: usize,
+    ) -> u64 {
+        let compression_field = if footer.compression_index_u8 { 1 } else { 4 };
+        let mut size = 8 + 8 + 8 + compression_field + 20;
+        if footer.version == 1 {
+            size += 8;
+        }
+        if footer.version >= 3 {
+            if *compression != PakCompression::None {
+                size += 4 + 16 * block_count as u64;
+            }
+            size += 1 + 4;
+        }
+        size
+    }
+
+    fn compression(footer: &PakFooter, index: u32) -> PakCompression {
+        if index == 0 {
+            return PakCompression::None;
+        }
+
+        // Before version 8 the field holds ECompressionFlags
+        let name = if footer.version < VERSION_FNAME_BASED_COMPRESSION {
+            match index {
+                0x01 => "Zlib".to_string(),
+                0x02 => "Gzip".to_string(),
+                flags => format!("flags {flags:#x}"),
+            }
+        } else {
+            footer
+                .compression_names
+                .get(index as usize - 1)
+                .cloned()
+                .unwrap_or_else(|| format!("method {index}"))
+        };
+
+        match name.to_lowercase().as_str() {
+            "zlib" => PakCompression::Zlib,
+            "gzip" => PakCompression::Gzip,
+            _ => PakCompression::Unsupported(name),
+        }
+    }
+
+    /// Mount points look like `../../../Game/Content/`; keep the project-relative part
+    fn entry_path(mount_point: &str, file_name: &str) -> String {
+        let mut mount_point = mount_point;
+        while let Some(rest) = mount_point.strip_prefix("../") {
+            mount_point = rest;
+        }
+        format!("{}{}", mount_point.trim_start_matches('/'), file_name)
+    }
+
+    /// First bytes of an entry for format detection; only the first block is decompressed
+    fn read_entry_header<R: Read + Seek>(
+        reader: &mut R,
+        entry: &PakEntry,
+    ) -> Result<Option<Vec<u8>>> {
+        if entry.uncompressed_size == 0 {
+            return Ok(None);
+        }
+
+        if entry.compression == PakCompression::None {
+            reader.seek(SeekFrom::Start(entry.data_offset()))?;
+            let mut data = reader.by_ref().take(entry.size);
+            return read_header_incrementally(&mut data, entry.size as usize);
+        }
+
+        let &(start, end) = entry
+            .blocks
+            .first()
+            .context("Compressed entry has no blocks")?;
+        let block = Self::read_range(reader, start, end.saturating_sub(start))?;
+        let mut decompressed = Vec::new();
+        Self::decompress_block(&entry.compression, &block, &mut decompressed)?;
+        read_header_incrementally(
+            &mut Cursor::new(decompressed),
+            entry.uncompressed_size as usize,
+        )
+    }
+
+    fn read_pak_entry<R: Read + Seek>(reader: &mut R, entry: &PakEntry) -> Result<Vec<u8>> {
+        Self::read_entry_data(
+            reader,
+            &entry.compression,
+            entry.data_offset(),
+            entry.size,
+            entry.uncompressed_size,
+            &entry.blocks,
+        )
+    }
+
+    fn read_entry_data<R: Read + Seek + ?Sized>(
+        reader: &mut R,
+        compression: &PakCompression,
+        data_offset: u64,
+        size: u64,
+        uncompressed_size: u64,
+        blocks: &[(u64, u64)],
+    ) -> Result<Vec<u8>> {
+        if *compression == PakCompression::None {
+            return Self::read_range(reader, data_offset, size);
+        }
+
+        let mut data = entry_buffer(uncompressed_size);
+        for &(start, end) in blocks {
+            let block = Self::read_range(reader, start, end.saturating_sub(start))?;
+            Self::decompress_block(compression, &block, &mut data)?;
+        }
+        data.truncate(uncompressed_size as usize);
+        Ok(data)
+    }
+
+    fn decompress_block(
+        compression: &PakCompression,
+        block: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        match compression {
+            PakCompression::None => output.extend_from_slice(block),
+            PakCompression::Zlib => {
+                ZlibDecoder::new(block).read_to_end(output)?;
+            }
+            PakCompression::Gzip => {
+                GzDecoder::new(block).read_to_end(output)?;
+            }
+            PakCompression::Unsupported(name) => {
+                anyhow::bail!("Unsupported pak compression: {name}")
+            }
+        }
+        Ok(())
+    }
+
+    fn read_range<R: Read + Seek + ?Sized>(
+        reader: &mut R,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>> {
+        reader.seek(SeekFrom::Start(offset))?;
+        // Read through `take` so corrupt lengths can't trigger huge allocations
+        let mut data = Vec::new();
+        reader.take(length).read_to_end(&mut data)?;
+        if data.len() as u64 != length {
+            anyhow::bail!("Pak range {offset}+{length} extends past the end of the file");
+        }
+        Ok(data)
+    }
+}
+
+/// Little-endian reader over an in-memory pak index
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .context("Unexpected end of pak index")?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into()?))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.bytes(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into()?))
+    }
+
+    /// FString: length including the terminator, negative for UTF-16
+    fn fstring(&mut self) -> Result<String> {
+        let len = self.i32()?;
+        let text = if len >= 0 {
+            String::from_utf8_lossy(self.bytes(len as usize)?).into_owned()
+        } else {
+            let units: Vec<u16> = self
+                .bytes(len.unsigned_abs() as usize * 2)?
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        };
+        Ok(text.trim_end_matches('\0').to_string())
+    }
+}
@@ -0,0 +1,318 @@
+use anyhow::{Context, Result};
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
+use std::collections::HashSet;
+use std::io::{Cursor, Read, SeekFrom};
+use std::path::Path;
+
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, PdfHint, Source, vfs};
+
+const PDF_MAGIC: &[u8] = b"%PDF-";
+
+/// PDF document source
+/// Image XObjects become entries: DCT streams are passed through as JPEG,
+/// raw/Flate-compressed pixel data is re-encoded as PNG
+pub struct PdfSource;
+
+/// How an image XObject's stream turns into a loadable file
+enum PdfImageEncoding {
+    Jpeg,
+    Raw { components: u8, bits: u8 },
+}
+
+impl Source for PdfSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_pdf_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase() == "pdf")
+            .unwrap_or(false);
+
+        if !has_pdf_extension {
+            return Ok(false);
+        }
+
+        let mut file = vfs::open(path)?;
+        let mut header = [0u8; 5];
+        file.read_exact(&mut header)?;
+        Ok(header == PDF_MAGIC)
+    }
+
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        let mut header = [0u8; 5];
+        let result = reader.read_exact(&mut header);
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(result.is_ok() && header == PDF_MAGIC)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let document = Document::load(path).context("Failed to parse PDF document")?;
+        let metadata_list = Self::collect_images(&document, path, None, false);
+
+        if metadata_list.is_empty() {
+            anyhow::bail!("No supported images found in PDF document");
+        }
+
+        log::info!(
+            "PDF extraction completed: {} images from {}",
+            metadata_list.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let document = Document::load_mem(&data)
+            .with_context(|| format!("Failed to parse nested PDF {entry_name}"))?;
+
+        // Nested documents can't be re-opened by path, so image data is captured now
+        Ok(Self::collect_images(
+            &document,
+            parent_path,
+            Some(entry_name),
+            true,
+        ))
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(pdf_hint) = hint.as_any().downcast_ref::<PdfHint>() {
+            if let Some(ref image_data) = pdf_hint.image_data {
+                return Ok(image_data.clone());
+            }
+
+            let document = Document::load(&pdf_hint.container_path)
+                .context("Failed to parse PDF document for reading image")?;
+            let stream = document
+                .get_object(pdf_hint.object_id)
+                .and_then(Object::as_stream)
+                .with_context(|| format!("PDF image object not found: {:?}", pdf_hint.object_id))?;
+            return Self::image_bytes(&document, stream);
+        }
+
+        anyhow::bail!("Invalid hint type for PDF source: {}", hint.debug_info())
+    }
+}
+
+impl PdfSource {
+    /// Walk all image XObjects once; soft masks are skipped since they belong to another image
+    fn collect_images(
+        document: &Document,
+        container_path: &Path,
+        parent_entry: Option<&str>,
+        capture_data: bool,
+    ) -> Vec<EmbeddedMetadata> {
+        let image_streams: Vec<(ObjectId, &Stream)> = document
+            .objects
+            .iter()
+            .filter_map(|(id, object)| Some((*id, object.as_stream().ok()?)))
+            .filter(|(_, stream)| {
+                stream
+                    .dict
+                    .get(b"Subtype")
+                    .and_then(Object::as_name)
+                    .is_ok_and(|subtype| subtype == b"Image")
+            })
+            .collect();
+
+        let soft_masks: HashSet<ObjectId> = image_streams
+            .iter()
+            .filter_map(|(_, stream)| {
+                stream
+                    .dict
+                    .get(b"SMask")
+                    .and_then(Object::as_reference)
+                    .ok()
+            })
+            .collect();
+
+        let mut metadata_list = Vec::new();
+        for (object_id, stream) in image_streams {
+            if soft_masks.contains(&object_id) {
+                continue;
+            }
+
+            match Self::image_metadata(
+                document,
+                object_id,
+                stream,
+                container_path,
+                parent_entry,
+                capture_data,
+            ) {
+                Ok(metadata) => metadata_list.push(metadata),
+                Err(e) => log::debug!("Skipping PDF image {object_id:?}: {e}"),
+            }
+        }
+
+        metadata_list
+    }
+
+    fn image_metadata(
+        document: &Document,
+        object_id: ObjectId,
+        stream: &Stream,
+        container_path: &Path,
+        parent_entry: Option<&str>,
+        capture_data: bool,
+    ) -> Result<EmbeddedMetadata> {
+        let width = dict_integer(&stream.dict, b"Width")? as usize;
+        let height = dict_integer(&stream.dict, b"Height")? as usize;
+        if width == 0 || height == 0 {
+            anyhow::bail!("Invalid dimensions {width}x{height}");
+        }
+
+        let format = match Self::encoding(document, stream)? {
+            PdfImageEncoding::Jpeg => imagesize::ImageType::Jpeg,
+            PdfImageEncoding::Raw { .. } => imagesize::ImageType::Png,
+        };
+
+        let image_data = if capture_data {
+            Some(Self::image_bytes(document, stream)?)
+        } else {
+            None
+        };
+
+        let hint = Box::new(PdfHint {
+            container_path: container_path.to_path_buf(),
+            object_id,
+            size: stream.content.len() as u64,
+            image_data,
+        }) as Box<dyn EmbeddedHint>;
+
+        let image_name = format!("Image {}", object_id.0);
+        let name = match parent_entry {
+            Some(parent) => format!("{parent} - {image_name}"),
+            None => image_name,
+        };
+
+        Ok(EmbeddedMetadata {
+            name,
+            format,
+            width,
+            height,
+            file_size: stream.content.len() as u64,
+            embedded_hint: hint,
+            source_path: container_path.to_path_buf(),
+            parents: Vec::new(),
+        })
+    }
+
+    fn encoding(document: &Document, stream: &Stream) -> Result<PdfImageEncoding> {
+        let filters = stream_filters(&stream.dict);
+        match filters.last().map(Vec::as_slice) {
+            Some(b"DCTDecode") if filters.len() == 1 => return Ok(PdfImageEncoding::Jpeg),
+            Some(b"DCTDecode") => anyhow::bail!("Chained filters before DCTDecode"),
+            Some(b"JPXDecode") => anyhow::bail!("JPEG 2000 images are not supported"),
+            Some(b"JBIG2Decode") | Some(b"CCITTFaxDecode") => {
+                anyhow::bail!("Bilevel fax/JBIG2 images are not supported")
+            }
+            _ => {}
+        }
+
+        let bits = dict_integer(&stream.dict, b"BitsPerComponent")? as u8;
+        let components = color_space_components(document, &stream.dict)?;
+
+        match (components, bits) {
+            (1, 1) | (1, 8) | (3, 8) => Ok(PdfImageEncoding::Raw { components, bits }),
+            _ => anyhow::bail!("Unsupported pixel layout: {components} components at {bits} bits"),
+        }
+    }
+
+    /// Produce a standalone JPEG/PNG file from an image XObject
+    fn image_bytes(document: &Document, stream: &Stream) -> Result<Vec<u8>> {
+        let (components, bits) = match Self::encoding(document, stream)? {
+            PdfImageEncoding::Jpeg => return Ok(stream.content.clone()),
+            PdfImageEncoding::Raw { components, bits } => (components, bits),
+        };
+
+        let width = dict_integer(&stream.dict, b"Width")? as u32;
+        let height = dict_integer(&stream.dict, b"Height")? as u32;
+        let pixels = if stream_filters(&stream.dict).is_empty() {
+            stream.content.clone()
+        } else {
+            stream
+                .decompressed_content()
+                .context("Failed to decompress PDF image stream")?
+        };
+
+        let image = match (components, bits) {
+            (1, 1) => {
+                // 1-bit rows are byte-aligned; expand to 8-bit gray
+                let row_bytes = (width as usize).div_ceil(8);
+                let gray: Vec<u8> = (0..height as usize)
+                    .flat_map(|y| {
+                        let row = pixels.get(y * row_bytes..(y + 1) * row_bytes);
+                        (0..width as usize).map(move |x| {
+                            let bit = row.map_or(0, |row| (row[x / 8] >> (7 - x % 8)) & 1);
+                            bit * 255
+                        })
+                    })
+                    .collect();
+                image::GrayImage::from_raw(width, height, gray).map(image::DynamicImage::from)
+            }
+            (1, 8) => {
+                image::GrayImage::from_raw(width, height, pixels).map(image::DynamicImage::from)
+            }
+            _ => image::RgbImage::from_raw(width, height, pixels).map(image::DynamicImage::from),
+        }
+        .context("PDF image stream is shorter than its dimensions")?;
+
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .context("Failed to encode PDF image as PNG")?;
+        Ok(png)
+    }
+}
+
+fn dict_integer(dict: &Dictionary, key: &[u8]) -> Result<i64> {
+    dict.get(key)
+        .and_then(Object::as_i64)
+        .with_context(|| format!("Missing /{}", String::from_utf8_lossy(key)))
+}
+
+/// Stream filter names in application order (a single name or an array)
+fn stream_filters(dict: &Dictionary) -> Vec<Vec<u8>> {
+    match dict.get(b"Filter") {
+        Ok(Object::Name(name)) => vec![name.clone()],
+        Ok(Object::Array(filters)) => filters
+            .iter()
+            .filter_map(|filter| filter.as_name().ok().map(<[u8]>::to_vec))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Number of color components for DeviceGray/DeviceRGB/ICCBased color spaces
+fn color_space_components(document: &Document, dict: &Dictionary) -> Result<u8> {
+    let color_space = dict.get(b"ColorSpace").context("Missing /ColorSpace")?;
+    let (_, color_space) = document.dereference(color_space)?;
+
+    match color_space {
+        Object::Name(name) => match name.as_slice() {
+            b"DeviceGray" | b"CalGray" => Ok(1),
+            b"DeviceRGB" | b"CalRGB" => Ok(3),
+            other => anyhow::bail!("Unsupported color space {}", String::from_utf8_lossy(other)),
+        },
+        Object::Array(items) => match items.first().and_then(|kind| kind.as_name().ok()) {
+            Some(b"ICCBased") => {
+                let profile = items
+                    .get(1)
+                    .context("ICCBased color space without profile")?;
+                let (_, profile) = document.dereference(profile)?;
+                Ok(dict_integer(&profile.as_stream()?.dict, b"N")? as u8)
+            }
+            Some(b"CalGray") => Ok(1),
+            Some(b"CalRGB") => Ok(3),
+            _ => anyhow::bail!("Unsupported color space array"),
+        },
+        _ => anyhow::bail!("Invalid color space"),
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use imagesize::ImageType;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+use crate::texture_pipeline::idtech::{self, DoomNamespace, Palette};
+use crate::texture_pipeline::source::read_header_incrementally;
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, Pk3Hint, Source, WadLump, vfs,
+};
+
+/// Extensions of idTech ZIP packages (Quake 3, Doom 3 and ZDoom-family mods)
+const PK3_EXTENSIONS: &[&str] = &["pk3", "pk4", "ipk3"];
+
+/// Largest entry read during listing to classify raw Doom lumps
+const MAX_LUMP_SIZE: u64 = 1 << 20;
+
+/// idTech PK3/PK4 package source
+///
+/// Images are listed from every folder. Raw Doom lumps under `flats/`, `sprites/`,
+/// `patches/`, `textures/` and `graphics/` are decoded through the package's PLAYPAL,
+/// or one from an IWAD next to it.
+pub struct Pk3Source;
+
+impl Source for Pk3Source {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_pk3_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| PK3_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            .unwrap_or(false);
+
+        if !has_pk3_extension {
+            return Ok(false);
+        }
+
+        let file = vfs::open(path)?;
+        Ok(ZipArchive::new(BufReader::new(file)).is_ok())
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Nested packages are only seen through their ZIP header; the ZIP source takes them
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let file = vfs::open(path).context("Failed to open PK3 file")?;
+        let mut archive =
+            ZipArchive::new(BufReader::new(file)).context("Failed to read PK3 archive")?;
+
+        let mut metadata_list = Vec::new();
+        for i in 0..archive.len() {
+            let entry_result = (|| -> Result<Option<EmbeddedMetadata>> {
+                let mut entry = archive.by_index(i)?;
+                if entry.is_dir() || entry.size() == 0 {
+                    return Ok(None);
+                }
+
+                let entry_name = entry.name().to_string();
+                let entry_size = entry.size();
+                let classified = match DoomNamespace::from_folder(&entry_name) {
+                    Some(namespace) if entry_size <= MAX_LUMP_SIZE => {
+                        let mut data = entry_buffer(entry_size);
+                        entry.read_to_end(&mut data)?;
+                        idtech::classify_doom_lump(&Self::lump_name(&entry_name), namespace, &data)
+                            .map(|classified| (classified, data))
+                    }
+                    // Outside the lump folders only real images are listed
+                    _ => read_header_incrementally(&mut entry, entry_size as usize)?.and_then(
+                        |header| {
+                            let size = imagesize::blob_size(&header).ok()?;
+                            Some((
+                                (WadLump::Image, size.width as u32, size.height as u32),
+                                header,
+                            ))
+                        },
+                    ),
+                };
+                let Some(((lump, width, height), data)) = classified else {
+                    return Ok(None);
+                };
+
+                let format = match lump {
+                    WadLump::Image => imagesize::image_type(&data)?,
+                    _ => ImageType::Qoi,
+                };
+
+                let hint = Box::new(Pk3Hint {
+                    container_path: path.to_path_buf(),
+                    entry_name: entry_name.clone(),
+                    entry_index: i,
+                    compressed_size: entry.compressed_size(),
+                    data_offset: entry.data_start(),
+                    crc32: entry.crc32(),
+                    lump,
+                }) as Box<dyn EmbeddedHint>;
+
+                Ok(Some(EmbeddedMetadata {
+                    name: entry_name,
+                    format,
+                    width: width as usize,
+                    height: height as usize,
+                    file_size: entry_size,
+                    embedded_hint: hint,
+                    source_path: path.to_path_buf(),
+                    parents: Vec::new(),
+                }))
+            })();
+
+            match entry_result {
+                Ok(Some(metadata)) => metadata_list.push(metadata),
+                Ok(None) => {}
+                Err(e) => log::debug!("Failed to extract metadata from PK3 entry {i}: {e}"),
+            }
+        }
+
+        log::info!(
+            "PK3 extraction completed: {} textures from {}",
+            metadata_list.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        log::debug!("PK3 processing from reader not supported for entry: {entry_name}");
+        Ok(Vec::new())
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(pk3_hint) = hint.as_any().downcast_ref::<Pk3Hint>() {
+            return Self::read_pk3_entry(pk3_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for PK3 source: {}", hint.debug_info())
+    }
+}
+
+impl Pk3Source {
+    /// Lump name as Doom sees it: the file name without folder or extension
+    fn lump_name(entry_name: &str) -> String {
+        let file_name = entry_name.rsplit('/').next().unwrap_or(entry_name);
+        let stem = file_name.split('.').next().unwrap_or(file_name);
+        stem.to_uppercase()
+    }
+
+    fn read_pk3_entry(hint: &Pk3Hint) -> Result<Vec<u8>> {
+        let file =
+            vfs::open(&hint.container_path).context("Failed to open PK3 file for reading entry")?;
+        let mut archive =
+            ZipArchive::new(BufReader::new(file)).context("Failed to read PK3 archive")?;
+
+        let data = {
+            let mut entry = archive.by_index(hint.entry_index).with_context(|| {
+                format!("Failed to find PK3 entry at index {}", hint.entry_index)
+            })?;
+
+            if entry.name() != hint.entry_name {
+                anyhow::bail!(
+                    "PK3 entry name mismatch: expected '{}', found '{}'",
+                    hint.entry_name,
+                    entry.name()
+                );
+            }
+
+            let mut buffer = entry_buffer(entry.size());
+            entry
+                .read_to_end(&mut buffer)
+                .with_context(|| format!("Failed to read PK3 entry: {}", hint.entry_name))?;
+            buffer
+        };
+
+        idtech::decode_lump(hint.lump, &hint.entry_name, &data, || {
+            idtech::container_palette(&hint.container_path, || Self::read_playpal(&mut archive))
+        })
+    }
+
+    /// PLAYPAL shipped at the package root, as ZDoom looks for it
+    fn read_playpal<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Option<Palette> {
+        let index = (0..archive.len()).find(|&i| {
+            archive
+                .name_for_index(i)
+                .is_some_and(|name| !name.contains('/') && Self::lump_name(name) == "PLAYPAL")
+        })?;
+
+        let mut data = Vec::new();
+        archive.by_index(index).ok()?.read_to_end(&mut data).ok()?;
+        Palette::from_bytes(&data)
+    }
+}
@@ -0,0 +1,368 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::io::{BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, FileHint, Source, vfs};
+
+const PMX_SIGNATURE: &[u8; 4] = b"PMX ";
+const PMD_SIGNATURE: &[u8; 3] = b"Pmd";
+
+/// PMD header: signature, version, 20-byte name and 256-byte comment
+const PMD_HEADER_SIZE: usize = 3 + 4 + 20 + 256;
+const PMD_VERTEX_SIZE: usize = 38;
+const PMD_MATERIAL_SIZE: usize = 70;
+/// Offset of the 20-byte texture file name inside a PMD material
+const PMD_TEXTURE_NAME_OFFSET: usize = 50;
+const PMD_TEXTURE_NAME_SIZE: usize = 20;
+
+/// One texture slot of a material, as `(material, map type, texture reference)`
+type MaterialTexture = (String, &'static str, String);
+
+/// MikuMikuDance PMX/PMD source - reads the model's texture references and loads
+/// the image files next to the model as `<material> - <map type>` entries
+pub struct PmxSource;
+
+impl Source for PmxSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        Ok(path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| matches!(ext.to_lowercase().as_str(), "pmx" | "pmd"))
+            .unwrap_or(false))
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Textures are separate files relative to the model, which a reader can't resolve
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let data = vfs::read(path).context("Failed to read MMD model")?;
+        let textures = if data.starts_with(PMX_SIGNATURE) {
+            Self::parse_pmx(&data)?
+        } else if data.starts_with(PMD_SIGNATURE) {
+            Self::parse_pmd(&data)?
+        } else {
+            anyhow::bail!("Not a PMX/PMD model");
+        };
+
+        let model_dir = path.parent().unwrap_or(Path::new("."));
+        let mut processed_textures: HashSet<PathBuf> = HashSet::new();
+        let mut results = Vec::new();
+
+        for (material_name, map_type, texture_ref) in textures {
+            let texture_path = Self::resolve_texture_path(model_dir, &texture_ref);
+            if !processed_textures.insert(texture_path.clone()) {
+                continue;
+            }
+
+            match self
+                .extract_texture_metadata(&texture_path, &format!("{material_name} - {map_type}"))
+            {
+                Ok(metadata) => results.push(metadata),
+                Err(e) => log::warn!(
+                    "Skipping MMD texture {} ({material_name} - {map_type}): {e}",
+                    texture_path.display()
+                ),
+            }
+        }
+
+        if results.is_empty() {
+            anyhow::bail!("No valid textures found in PMX/PMD model");
+        }
+
+        Ok(results)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        anyhow::bail!("PMX/PMD textures can't be resolved from reader: {entry_name}")
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(file_hint) = hint.as_any().downcast_ref::<FileHint>() {
+            return vfs::read(&file_hint.path).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to read MMD texture file {}: {}",
+                    file_hint.path.display(),
+                    e
+                )
+            });
+        }
+
+        anyhow::bail!("Invalid hint type for PMX source: {}", hint.debug_info())
+    }
+}
+
+impl PmxSource {
+    /// Walk a PMX model up to the material list, pairing each material with its
+    /// texture, sphere map and non-shared toon; unused table entries come last
+    fn parse_pmx(data: &[u8]) -> Result<Vec<MaterialTexture>> {
+        let mut reader = ModelReader { data, pos: 8 };
+        let globals_count = reader.u8()? as usize;
+        let globals = reader.bytes(globals_count)?.to_vec();
+        if globals.len() < 8 {
+            anyhow::bail!("PMX header has only {} globals", globals.len());
+        }
+        let utf16 = globals[0] == 0;
+        let additional_vec4 = globals[1] as usize;
+        let vertex_index_size = globals[2] as usize;
+        let texture_index_size = globals[3] as usize;
+        let bone_index_size = globals[5] as usize;
+
+        // Model name and comment, local and universal
+        for _ in 0..4 {
+            reader.text(utf16)?;
+        }
+
+        let vertex_count = reader.count()?;
+        for _ in 0..vertex_count {
+            // Position, normal, UV and additional UVs
+            reader.skip(32 + additional_vec4 * 16)?;
+            let bones = match reader.u8()? {
+                0 => bone_index_size,              // BDEF1
+                1 => bone_index_size * 2 + 4,      // BDEF2
+                2 | 4 => bone_index_size * 4 + 16, // BDEF4, QDEF
+                3 => bone_index_size * 2 + 4 + 36, // SDEF: weights plus C, R0, R1
+                other => anyhow::bail!("Unknown PMX weight deform type {other}"),
+            };
+            // Bone weights and edge scale
+            reader.skip(bones + 4)?;
+        }
+
+        let index_count = reader.count()?;
+        reader.skip(index_count * vertex_index_size)?;
+
+        let texture_count = reader.count()?;
+        let texture_table = (0..texture_count)
+            .map(|_| reader.text(utf16))
+            .collect::<Result<Vec<_>>>()?;
+        let texture_ref = |index: i32| -> Option<String> {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| texture_table.get(index))
+                .filter(|name| !name.trim().is_empty())
+                .cloned()
+        };
+
+        let mut textures = Vec::new();
+        let mut referenced = vec![false; texture_table.len()];
+        let material_count = reader.count()?;
+        for material_index in 0..material_count {
+            let local_name = reader.text(utf16)?;
+            let universal_name = reader.text(utf16)?;
+            // Diffuse, specular, specular strength, ambient, flags, edge colour and scale
+            reader.skip(16 + 12 + 4 + 12 + 1 + 16 + 4)?;
+            let texture_index = reader.index(texture_index_size)?;
+            let sphere_index = reader.index(texture_index_size)?;
+            reader.skip(1)?; // Sphere blend mode
+            let toon_index = if reader.u8()? == 0 {
+                reader.index(texture_index_size)?
+            } else {
+                // Shared toon01-10.bmp from the MMD install, not the model
+                reader.skip(1)?;
+                -1
+            };
+            reader.text(utf16)?; // Memo
+            reader.skip(4)?; // Surface count
+
+            let material_name = [local_name, universal_name]
+                .into_iter()
+                .find(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| format!("Material {material_index}"));
+
+            for (index, map_type) in [
+                (texture_index, "Texture"),
+                (sphere_index, "Sphere"),
+                (toon_index, "Toon"),
+            ] {
+                if let Some(reference) = texture_ref(index) {
+                    referenced[index as usize] = true;
+                    textures.push((material_name.clone(), map_type, reference));
+                }
+            }
+        }
+
+        for (reference, _) in texture_table
+            .iter()
+            .zip(&referenced)
+            .filter(|(name, used)| !**used && !name.trim().is_empty())
+        {
+            textures.push(("Unused".to_string(), "Texture", reference.clone()));
+        }
+
+        Ok(textures)
+    }
+
+    /// PMD stores one `texture*sphere` file name per material instead of a texture table
+    fn parse_pmd(data: &[u8]) -> Result<Vec<MaterialTexture>> {
+        let mut reader = ModelReader {
+            data,
+            pos: PMD_HEADER_SIZE,
+        };
+
+        let vertex_count = reader.count()?;
+        reader.skip(vertex_count * PMD_VERTEX_SIZE)?;
+        let index_count = reader.count()?;
+        reader.skip(index_count * 2)?;
+
+        let mut textures = Vec::new();
+        let material_count = reader.count()?;
+        for material_index in 0..material_count {
+            let material = reader.bytes(PMD_MATERIAL_SIZE)?;
+            let field =
+                &material[PMD_TEXTURE_NAME_OFFSET..PMD_TEXTURE_NAME_OFFSET + PMD_TEXTURE_NAME_SIZE];
+            let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+            let (file_names, _, _) = encoding_rs::SHIFT_JIS.decode(&field[..end]);
+
+            for file_name in file_names.split('*').filter(|name| !name.trim().is_empty()) {
+                let is_sphere = Path::new(file_name)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "sph" | "spa"));
+                let map_type = if is_sphere { "Sphere" } else { "Texture" };
+                textures.push((
+                    format!("Material {material_index}"),
+                    map_type,
+                    file_name.to_string(),
+                ));
+            }
+        }
+
+        Ok(textures)
+    }
+
+    /// References are Windows paths; fall back to a case-insensitive match of the file name
+    /// since models authored on Windows rarely match the on-disk case
+    fn resolve_texture_path(model_dir: &Path, reference: &str) -> PathBuf {
+        let normalized = reference.trim().replace('\\', "/");
+        let path = Path::new(&normalized);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            model_dir.join(path)
+        };
+
+        if vfs::is_file(&path) {
+            return path;
+        }
+
+        let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+            return path;
+        };
+        let file_name = file_name.to_string_lossy().to_lowercase();
+        vfs::read_dir(dir)
+            .ok()
+            .and_then(|entries| {
+                entries.into_iter().find(|entry| {
+                    entry
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().to_lowercase() == file_name)
+                })
+            })
+            .unwrap_or(path)
+    }
+
+    fn extract_texture_metadata(&self, image_path: &Path, name: &str) -> Result<EmbeddedMetadata> {
+        let file = vfs::open(image_path)?;
+        let mut reader = BufReader::new(file);
+
+        let format = imagesize::reader_type(&mut reader)?;
+        reader.seek(SeekFrom::Start(0))?;
+        let dimension = imagesize::reader_size(&mut reader)?;
+        let file_size = vfs::file_size(image_path)?;
+
+        if dimension.width == 0 || dimension.height == 0 {
+            anyhow::bail!(
+                "Invalid dimensions for MMD texture {}: {}x{}",
+                name,
+                dimension.width,
+                dimension.height
+            );
+        }
+
+        let hint = Box::new(FileHint {
+            path: image_path.to_path_buf(),
+            sampler: None,
+        }) as Box<dyn EmbeddedHint>;
+
+        Ok(EmbeddedMetadata {
+            name: name.to_string(),
+            format,
+            width: dimension.width,
+            height: dimension.height,
+            file_size,
+            embedded_hint: hint,
+            source_path: image_path.to_path_buf(),
+            parents: Vec::new(),
+        })
+    }
+}
+
+/// Little-endian cursor over a model file
+struct ModelReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ModelReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| self.data.get(self.pos..end))
+            .with_context(|| format!("Model truncated at offset {}", self.pos))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        let bytes = self.bytes(4)?;
+        Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Element count, rejecting negative values
+    fn count(&mut self) -> Result<usize> {
+        let count = self.i32()?;
+        usize::try_from(count).with_context(|| format!("Invalid element count {count}"))
+    }
+
+    /// Signed PMX index of 1, 2 or 4 bytes; -1 means none
+    fn index(&mut self, size: usize) -> Result<i32> {
+        let bytes = self.bytes(size)?;
+        Ok(match size {
+            1 => bytes[0] as i8 as i32,
+            2 => i16::from_le_bytes([bytes[0], bytes[1]]) as i32,
+            4 => i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            _ => anyhow::bail!("Invalid PMX index size {size}"),
+        })
+    }
+
+    /// Length-prefixed PMX text in UTF-16LE or UTF-8
+    fn text(&mut self, utf16: bool) -> Result<String> {
+        let len = self.count()?;
+        let bytes = self.bytes(len)?;
+        Ok(if utf16 {
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(bytes).into_owned()
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result};
+use sevenz_rust::{Password, SevenZReader};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::texture_pipeline::source::read_header_incrementally;
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, SevenZipHint, Source, vfs,
+};
+
+const SEVENZ_MAGIC: &[u8] = &[b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C];
+
+/// 7-Zip archive source
+/// Entries expand like ZIP entries; `load_bytes` decodes only up to the requested entry
+pub struct SevenZipSource;
+
+impl Source for SevenZipSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_7z_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase() == "7z")
+            .unwrap_or(false);
+
+        if !has_7z_extension {
+            return Ok(false);
+        }
+
+        let mut file = vfs::open(path)?;
+        let mut header = [0u8; 6];
+        file.read_exact(&mut header)?;
+        Ok(header == SEVENZ_MAGIC)
+    }
+
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        let mut header = [0u8; 6];
+        let result = reader.read_exact(&mut header);
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(result.is_ok() && header == SEVENZ_MAGIC)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let mut archive = SevenZReader::open(path, Password::empty())
+            .map_err(|e| anyhow::anyhow!("Failed to read 7z archive: {e}"))?;
+
+        let metadata_list = Self::collect_entries(&mut archive, path, None, false)?;
+
+        if metadata_list.is_empty() {
+            anyhow::bail!("No entries found in 7z archive");
+        }
+
+        log::info!(
+            "7z container extraction completed: {} entries from {}",
+            metadata_list.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let data_len = data.len() as u64;
+
+        let mut archive = SevenZReader::new(Cursor::new(data), data_len, Password::empty())
+            .map_err(|e| anyhow::anyhow!("Failed to read nested 7z archive {entry_name}: {e}"))?;
+
+        // Nested archives can't be re-opened by path, so entry data is captured now
+        Self::collect_entries(&mut archive, parent_path, Some(entry_name), true)
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(sevenz_hint) = hint.as_any().downcast_ref::<SevenZipHint>() {
+            if let Some(ref entry_data) = sevenz_hint.entry_data {
+                return Ok(entry_data.clone());
+            }
+            return self.read_sevenz_entry(sevenz_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for 7z source: {}", hint.debug_info())
+    }
+}
+
+impl SevenZipSource {
+    /// Walk all entries once, capturing header bytes (and optionally full data)
+    fn collect_entries<R: Read + Seek>(
+        archive: &mut SevenZReader<R>,
+        container_path: &Path,
+        parent_entry: Option<&str>,
+        capture_data: bool,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        // Map entry names to their index in the archive's file table
+        let entry_indices: std::collections::HashMap<String, usize> = archive
+            .archive()
+            .files
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.name().to_string(), index))
+            .collect();
+
+        let mut metadata_list = Vec::new();
+
+        archive
+            .for_each_entries(|entry, entry_reader| {
+                if entry.is_directory() || !entry.has_stream() || entry.size() == 0 {
+                    return Ok(true);
+                }
+
+                let entry_name = entry.name().to_string();
+                let size = entry.size();
+
+                let (header_bytes, entry_data) = if capture_data {
+                    let mut data = entry_buffer(size);
+                    entry_reader.read_to_end(&mut data)?;
+                    let header_len = data.len().min(65536);
+                    (Some(data[..header_len].to_vec()), Some(data))
+                } else {
+                    let header = read_header_incrementally(entry_reader, size as usize)
+                        .map_err(|e| std::io::Error::other(e.to_string()))?;
+                    (header, None)
+                };
+
+                let hint = Box::new(SevenZipHint {
+                    container_path: container_path.to_path_buf(),
+                    entry_name: entry_name.clone(),
+                    entry_index: entry_indices.get(&entry_name).copied().unwrap_or(0),
+                    size,
+                    header_bytes,
+                    entry_data,
+                }) as Box<dyn EmbeddedHint>;
+
+                let name = match parent_entry {
+                    Some(parent) => format!("{parent}/{entry_name}"),
+                    None => entry_name,
+                };
+
+                metadata_list.push(EmbeddedMetadata {
+                    name,
+                    format: imagesize::ImageType::Png, // Placeholder - Pipeline will determine actual format
+                    width: 0,
+                    height: 0,
+                    file_size: size,
+                    embedded_hint: hint,
+                    source_path: container_path.to_path_buf(),
+                    parents: Vec::new(),
+                });
+
+                Ok(true)
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to enumerate 7z entries: {e}"))?;
+
+        Ok(metadata_list)
+    }
+
+    /// Selectively extract a single entry, stopping as soon as it has been read
+    fn read_sevenz_entry(&self, hint: &SevenZipHint) -> Result<Vec<u8>> {
+        let mut archive = SevenZReader::open(&hint.container_path, Password::empty())
+            .map_err(|e| anyhow::anyhow!("Failed to open 7z archive for reading entry: {e}"))?;
+
+        let mut buffer = None;
+        archive
+            .for_each_entries(|entry, entry_reader| {
+                if entry.name() != hint.entry_name {
+                    return Ok(true); // Keep going - non-matching entries are skipped
+                }
+
+                let mut data = entry_buffer(hint.size);
+                entry_reader.read_to_end(&mut data)?;
+                buffer = Some(data);
+                Ok(false) // Found it - stop decoding
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to read 7z entry {}: {e}", hint.entry_name))?;
+
+        let buffer = buffer.with_context(|| format!("7z entry not found: {}", hint.entry_name))?;
+
+        log::debug!(
+            "7z entry read: {} bytes from entry '{}' in {}",
+            buffer.len(),
+            hint.entry_name,
+            hint.container_path.display()
+        );
+
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use std::io::{Cursor, IsTerminal, Read};
+use std::path::Path;
+
+use crate::texture_pipeline::vfs::{self, MemoryMount};
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, Pipeline, Source, StreamWrapper,
+};
+
+/// Path standing for standard input, as in `cat texture.ktx2 | gtexviewer -`
+pub const STDIN_PATH: &str = "-";
+
+/// Whether a path names standard input
+pub fn is_stdin_path(path: &Path) -> bool {
+    path == Path::new(STDIN_PATH)
+}
+
+/// Standard input source - reads the piped bytes once, detects their format from the
+/// content and serves them from memory through the VFS so the regular sources take over
+/// The mount stays until no slot comes from standard input (see `release_unused_mounts`)
+pub struct StdinSource;
+
+impl Source for StdinSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        // Once stdin is read and mounted, the regular sources own the path
+        Ok(is_stdin_path(path) && !vfs::is_file(path))
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Standard input only comes from the command line
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let stdin = std::io::stdin();
+        if stdin.is_terminal() {
+            anyhow::bail!("Nothing piped to standard input");
+        }
+
+        let mut data = Vec::new();
+        stdin
+            .lock()
+            .read_to_end(&mut data)
+            .context("Failed to read standard input")?;
+        log::info!("📥 Read {} bytes from standard input", data.len());
+
+        // There's no file name to go by, so the content alone decides the source
+        let pipeline = Pipeline::new();
+        let source = pipeline.find_source_for_reader(&mut Cursor::new(&data));
+        let wrapper = StreamWrapper::detect(&data);
+
+        let mut memory = MemoryMount::new();
+        memory.insert("", data);
+        vfs::mount(path, memory);
+
+        match (source, wrapper) {
+            (Some(source), _) => source.extract_metadata(path),
+            (None, Some(wrapper)) => pipeline.source_registry().extract_unwrapped(
+                wrapper,
+                &vfs::read(path)?,
+                STDIN_PATH,
+                path,
+                false,
+            ),
+            (None, None) => anyhow::bail!("Unsupported data on standard input"),
+        }
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        anyhow::bail!("Standard input can't be nested in a container: {entry_name}")
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        // Entries are extracted after mounting, so loads go to the content's own source
+        anyhow::bail!("Invalid hint type for stdin source: {}", hint.debug_info())
+    }
+}
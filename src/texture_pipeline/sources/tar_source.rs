@@ -0,0 +1,265 @@
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use xz2::read::XzDecoder;
+
+use crate::texture_pipeline::source::read_header_incrementally;
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, TarCompression, TarHint, vfs,
+};
+
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+const XZ_MAGIC: &[u8] = &[0xFD, b'7', b'z', b'X', b'Z', 0x00];
+const USTAR_MAGIC_OFFSET: usize = 257;
+
+/// TAR archive source (plain, gzip and xz compressed)
+/// Entries are exposed like ZIP entries so the pipeline can detect and recurse into them
+pub struct TarSource;
+
+impl Source for TarSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.to_lowercase())
+            .unwrap_or_default();
+
+        let has_tar_extension = [".tar", ".tar.gz", ".tgz", ".tar.xz", ".txz"]
+            .iter()
+            .any(|ext| file_name.ends_with(ext));
+
+        if !has_tar_extension {
+            return Ok(false);
+        }
+
+        // Verify the content actually is a tarball (through the compression layer)
+        let file = vfs::open(path)?;
+        let mut reader = BufReader::new(file);
+        self.can_load_reader(&mut reader)
+    }
+
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        let compression = Self::detect_compression(reader)?;
+
+        let mut block = [0u8; 512];
+        let read_result = Self::decoder(compression, &mut *reader).read_exact(&mut block);
+        reader.seek(SeekFrom::Start(0))?;
+
+        Ok(read_result.is_ok() && Self::is_tar_header(&block))
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let file = vfs::open(path).context("Failed to open TAR file")?;
+        let mut reader = BufReader::new(file);
+        let compression = Self::detect_compression(&mut reader)?;
+
+        let mut archive = tar::Archive::new(Self::decoder(compression, reader));
+        let mut metadata_list = Vec::new();
+
+        for (entry_index, entry_result) in archive
+            .entries()
+            .context("Failed to read TAR archive")?
+            .enumerate()
+        {
+            let result = (|| -> Result<Option<EmbeddedMetadata>> {
+                let mut entry = entry_result?;
+
+                // Skip directories, links and other special entries
+                if !entry.header().entry_type().is_file() {
+                    return Ok(None);
+                }
+
+                let entry_path = entry.path()?.to_string_lossy().to_string();
+                let size = entry.size();
+                if size == 0 {
+                    return Ok(None);
+                }
+
+                let data_offset = entry.raw_file_position();
+                let header_bytes = read_header_incrementally(&mut entry, size as usize)?;
+
+                let hint = Box::new(TarHint {
+                    container_path: path.to_path_buf(),
+                    entry_path: entry_path.clone(),
+                    entry_index,
+                    data_offset,
+                    size,
+                    compression,
+                    header_bytes,
+                    entry_data: None,
+                }) as Box<dyn EmbeddedHint>;
+
+                // Pipeline determines the real format and dimensions from header bytes
+                Ok(Some(EmbeddedMetadata {
+                    name: entry_path,
+                    format: imagesize::ImageType::Png, // Placeholder - Pipeline will determine actual format
+                    width: 0,
+                    height: 0,
+                    file_size: size,
+                    embedded_hint: hint,
+                    source_path: path.to_path_buf(),
+                    parents: Vec::new(),
+                }))
+            })();
+
+            match result {
+                Ok(Some(metadata)) => metadata_list.push(metadata),
+                Ok(None) => {}
+                Err(e) => {
+                    log::debug!("Failed to extract metadata from TAR entry {entry_index}: {e}")
+                }
+            }
+        }
+
+        if metadata_list.is_empty() {
+            anyhow::bail!("No entries found in TAR archive");
+        }
+
+        log::info!(
+            "TAR container extraction completed: {} entries from {} ({:?})",
+            metadata_list.len(),
+            path.display(),
+            compression
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let compression = Self::detect_compression(reader)?;
+        let mut archive = tar::Archive::new(Self::decoder(compression, &mut *reader));
+        let mut metadata_list = Vec::new();
+
+        // Nested tarballs can't be re-opened by path, so entry data is captured now
+        for (entry_index, entry_result) in archive.entries()?.enumerate() {
+            let mut entry = entry_result?;
+            if !entry.header().entry_type().is_file() || entry.size() == 0 {
+                continue;
+            }
+
+            let entry_path = entry.path()?.to_string_lossy().to_string();
+            let mut entry_data = entry_buffer(entry.size());
+            entry.read_to_end(&mut entry_data)?;
+
+            let header_len = entry_data.len().min(65536);
+            let hint = Box::new(TarHint {
+                container_path: parent_path.to_path_buf(),
+                entry_path: entry_path.clone(),
+                entry_index,
+                data_offset: entry.raw_file_position(),
+                size: entry_data.len() as u64,
+                compression,
+                header_bytes: Some(entry_data[..header_len].to_vec()),
+                entry_data: Some(entry_data),
+            }) as Box<dyn EmbeddedHint>;
+
+            metadata_list.push(EmbeddedMetadata {
+                name: format!("{entry_name}/{entry_path}"),
+                format: imagesize::ImageType::Png, // Placeholder - Pipeline will determine actual format
+                width: 0,
+                height: 0,
+                file_size: entry.size(),
+                embedded_hint: hint,
+                source_path: parent_path.to_path_buf(),
+                parents: Vec::new(),
+            });
+        }
+
+        Ok(metadata_list)
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(tar_hint) = hint.as_any().downcast_ref::<TarHint>() {
+            if let Some(ref entry_data) = tar_hint.entry_data {
+                return Ok(entry_data.clone());
+            }
+            return self.read_tar_entry(tar_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for TAR source: {}", hint.debug_info())
+    }
+}
+
+impl TarSource {
+    /// Sniff the compression layer from magic bytes, leaving the reader rewound
+    fn detect_compression(reader: &mut dyn BufReadSeek) -> Result<TarCompression> {
+        let mut magic = [0u8; 6];
+        let bytes_read = reader.read(&mut magic)?;
+        reader.seek(SeekFrom::Start(0))?;
+
+        let magic = &magic[..bytes_read];
+        Ok(if magic.starts_with(GZIP_MAGIC) {
+            TarCompression::Gzip
+        } else if magic.starts_with(XZ_MAGIC) {
+            TarCompression::Xz
+        } else {
+            TarCompression::None
+        })
+    }
+
+    /// Wrap a reader with the matching decompressor
+    fn decoder<'a, R: Read + 'a>(compression: TarCompression, reader: R) -> Box<dyn Read + 'a> {
+        match compression {
+            TarCompression::None => Box::new(reader),
+            TarCompression::Gzip => Box::new(GzDecoder::new(reader)),
+            TarCompression::Xz => Box::new(XzDecoder::new(reader)),
+        }
+    }
+
+    /// POSIX ustar and GNU tar both carry "ustar" at offset 257 of the first header block
+    fn is_tar_header(block: &[u8; 512]) -> bool {
+        &block[USTAR_MAGIC_OFFSET..USTAR_MAGIC_OFFSET + 5] == b"ustar"
+    }
+
+    /// Read a specific entry using the hint information
+    fn read_tar_entry(&self, hint: &TarHint) -> Result<Vec<u8>> {
+        let mut file =
+            vfs::open(&hint.container_path).context("Failed to open TAR file for reading entry")?;
+
+        // Uncompressed tarballs allow a direct ranged read - no re-scanning needed
+        if hint.compression == TarCompression::None {
+            file.seek(SeekFrom::Start(hint.data_offset))?;
+            let mut buffer = vec![0u8; hint.size as usize];
+            file.read_exact(&mut buffer)
+                .with_context(|| format!("Failed to read TAR entry: {}", hint.entry_path))?;
+            return Ok(buffer);
+        }
+
+        // Compressed streams must be decoded up to the entry
+        let mut archive = tar::Archive::new(Self::decoder(hint.compression, BufReader::new(file)));
+        let mut entry = archive
+            .entries()?
+            .nth(hint.entry_index)
+            .with_context(|| format!("Failed to find TAR entry at index {}", hint.entry_index))??;
+
+        let entry_path = entry.path()?.to_string_lossy().to_string();
+        if entry_path != hint.entry_path {
+            anyhow::bail!(
+                "TAR entry name mismatch: expected '{}', found '{}'",
+                hint.entry_path,
+                entry_path
+            );
+        }
+
+        let mut buffer = entry_buffer(hint.size);
+        entry
+            .read_to_end(&mut buffer)
+            .with_context(|| format!("Failed to read TAR entry: {}", hint.entry_path))?;
+
+        log::debug!(
+            "TAR entry read: {} bytes from entry '{}' in {}",
+            buffer.len(),
+            hint.entry_path,
+            hint.container_path.display()
+        );
+
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,245 @@
+use anyhow::{Context, Result};
+use std::io::{BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, FileHint, Source, vfs};
+
+/// Chunk header: 2-byte id and 4-byte length, the length counting the header itself
+const CHUNK_HEADER_SIZE: usize = 6;
+
+const MAIN_CHUNK: u16 = 0x4D4D;
+const EDITOR_CHUNK: u16 = 0x3D3D;
+const MATERIAL_CHUNK: u16 = 0xAFFF;
+const MATERIAL_NAME_CHUNK: u16 = 0xA000;
+const MAP_FILENAME_CHUNK: u16 = 0xA300;
+
+/// Material map chunks that carry a texture file name, with the map type they feed
+const MAP_CHUNKS: &[(u16, &str)] = &[
+    (0xA200, "Diffuse"),
+    (0xA33A, "Diffuse 2"),
+    (0xA204, "Specular"),
+    (0xA210, "Opacity"),
+    (0xA220, "Reflection"),
+    (0xA230, "Bump"),
+    (0xA33C, "Shininess"),
+    (0xA33D, "Self Illumination"),
+];
+
+/// One texture slot of a material, as `(material, map type, texture reference)`
+type MaterialTexture = (String, &'static str, String);
+
+/// Autodesk 3DS source - walks the material chunks for texture map file names and loads
+/// the image files next to the model as `<material> - <map type>` entries
+pub struct ThreeDsSource;
+
+impl Source for ThreeDsSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        Ok(path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("3ds"))
+            .unwrap_or(false))
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Textures are separate files relative to the model, which a reader can't resolve
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let data = vfs::read(path).context("Failed to read 3DS model")?;
+        let textures = Self::parse_materials(&data)?;
+
+        // A texture shared by several maps is loaded once and named after all of them
+        let model_dir = path.parent().unwrap_or(Path::new("."));
+        let mut shared_textures: Vec<(PathBuf, Vec<String>)> = Vec::new();
+        for (material_name, map_type, texture_ref) in textures {
+            let texture_path = Self::resolve_texture_path(model_dir, &texture_ref);
+            let label = format!("{material_name} - {map_type}");
+            match shared_textures
+                .iter_mut()
+                .find(|(known_path, _)| *known_path == texture_path)
+            {
+                Some((_, labels)) if !labels.contains(&label) => labels.push(label),
+                Some(_) => {}
+                None => shared_textures.push((texture_path, vec![label])),
+            }
+        }
+
+        let mut results = Vec::new();
+        for (texture_path, labels) in shared_textures {
+            let name = labels.join(", ");
+            match self.extract_texture_metadata(&texture_path, &name) {
+                Ok(metadata) => results.push(metadata),
+                Err(e) => log::warn!(
+                    "Skipping 3DS texture {} ({name}): {e}",
+                    texture_path.display()
+                ),
+            }
+        }
+
+        if results.is_empty() {
+            anyhow::bail!("No valid textures found in 3DS model");
+        }
+
+        Ok(results)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        anyhow::bail!("3DS textures can't be resolved from reader: {entry_name}")
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(file_hint) = hint.as_any().downcast_ref::<FileHint>() {
+            return vfs::read(&file_hint.path).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to read 3DS texture file {}: {}",
+                    file_hint.path.display(),
+                    e
+                )
+            });
+        }
+
+        anyhow::bail!("Invalid hint type for 3DS source: {}", hint.debug_info())
+    }
+}
+
+impl ThreeDsSource {
+    /// Descend main → editor → material chunks and collect each material's map file names;
+    /// mesh and keyframer chunks are skipped whole
+    fn parse_materials(data: &[u8]) -> Result<Vec<MaterialTexture>> {
+        let main = chunks(data)
+            .find(|chunk| chunk.id == MAIN_CHUNK)
+            .context("Not a 3DS model")?;
+
+        let mut textures = Vec::new();
+        let mut material_index = 0;
+        for editor in chunks(main.body).filter(|chunk| chunk.id == EDITOR_CHUNK) {
+            for material in chunks(editor.body).filter(|chunk| chunk.id == MATERIAL_CHUNK) {
+                let material_name = chunks(material.body)
+                    .find(|chunk| chunk.id == MATERIAL_NAME_CHUNK)
+                    .map(|chunk| c_string(chunk.body))
+                    .filter(|name| !name.trim().is_empty())
+                    .unwrap_or_else(|| format!("Material {material_index}"));
+                material_index += 1;
+
+                for map in chunks(material.body) {
+                    let Some(&(_, map_type)) = MAP_CHUNKS.iter().find(|(id, _)| *id == map.id)
+                    else {
+                        continue;
+                    };
+                    if let Some(file_name) = chunks(map.body)
+                        .find(|chunk| chunk.id == MAP_FILENAME_CHUNK)
+                        .map(|chunk| c_string(chunk.body))
+                        .filter(|name| !name.trim().is_empty())
+                    {
+                        textures.push((material_name.clone(), map_type, file_name));
+                    }
+                }
+            }
+        }
+
+        Ok(textures)
+    }
+
+    /// References are bare, often upper-case 8.3 DOS names; fall back to a case-insensitive
+    /// match of the file name since they rarely match the on-disk case
+    fn resolve_texture_path(model_dir: &Path, reference: &str) -> PathBuf {
+        let normalized = reference.trim().replace('\\', "/");
+        let path = Path::new(&normalized);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            model_dir.join(path)
+        };
+
+        if vfs::is_file(&path) {
+            return path;
+        }
+
+        let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+            return path;
+        };
+        let file_name = file_name.to_string_lossy().to_lowercase();
+        vfs::read_dir(dir)
+            .ok()
+            .and_then(|entries| {
+                entries.into_iter().find(|entry| {
+                    entry
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().to_lowercase() == file_name)
+                })
+            })
+            .unwrap_or(path)
+    }
+
+    fn extract_texture_metadata(&self, image_path: &Path, name: &str) -> Result<EmbeddedMetadata> {
+        let file = vfs::open(image_path)?;
+        let mut reader = BufReader::new(file);
+
+        let format = imagesize::reader_type(&mut reader)?;
+        reader.seek(SeekFrom::Start(0))?;
+        let dimension = imagesize::reader_size(&mut reader)?;
+        let file_size = vfs::file_size(image_path)?;
+
+        if dimension.width == 0 || dimension.height == 0 {
+            anyhow::bail!(
+                "Invalid dimensions for 3DS texture {}: {}x{}",
+                name,
+                dimension.width,
+                dimension.height
+            );
+        }
+
+        let hint = Box::new(FileHint {
+            path: image_path.to_path_buf(),
+            sampler: None,
+        }) as Box<dyn EmbeddedHint>;
+
+        Ok(EmbeddedMetadata {
+            name: name.to_string(),
+            format,
+            width: dimension.width,
+            height: dimension.height,
+            file_size,
+            embedded_hint: hint,
+            source_path: image_path.to_path_buf(),
+            parents: Vec::new(),
+        })
+    }
+}
+
+/// A chunk's id and the bytes after its header
+struct Chunk<'a> {
+    id: u16,
+    body: &'a [u8],
+}
+
+/// Sibling chunks laid end to end in `data`; a length running past the end is cut short
+/// to what's there, and a broken length ends the walk
+fn chunks(data: &[u8]) -> impl Iterator<Item = Chunk<'_>> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let header = data.get(pos..pos + CHUNK_HEADER_SIZE)?;
+        let id = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+        if len < CHUNK_HEADER_SIZE {
+            return None;
+        }
+        let end = pos.saturating_add(len).min(data.len());
+        let body = &data[pos + CHUNK_HEADER_SIZE..end];
+        pos = end;
+        Some(Chunk { id, body })
+    })
+}
+
+/// Zero-terminated name, read as Latin-1 like the DOS-era tools that wrote it
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    bytes[..end].iter().map(|&b| b as char).collect()
+}
@@ -2,8 +2,14 @@ use anyhow::Result;
 use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 
-pub struct UltraFastFbxParser {
-    reader: BufReader<std::fs::File>,
+use crate::texture_pipeline::vfs;
+
+/// Magic at the start of every binary FBX file
+pub const FBX_BINARY_MAGIC: &[u8] = b"Kaydara FBX Binary";
+
+/// Works over any seekable stream: files on disk or entries of a parent container
+pub struct UltraFastFbxParser<R: Read + Seek = BufReader<vfs::File>> {
+    reader: R,
     file_size: u64,
     fbx_version: u32,
 }
@@ -25,9 +31,17 @@ pub struct TextureData {
 impl UltraFastFbxParser {
     /// Create new ultra-fast FBX parser
     pub fn new(path: &Path) -> Result<Self> {
-        let file = std::fs::File::open(path)?;
-        let file_size = file.metadata()?.len();
-        let reader = BufReader::new(file);
+        let file = vfs::open(path)?;
+        UltraFastFbxParser::from_reader(BufReader::new(file))
+    }
+}
+
+impl<R: Read + Seek> UltraFastFbxParser<R> {
+    /// Create a parser over a stream positioned anywhere; parsing starts from its beginning
+    /// since FBX node offsets are absolute
+    pub fn from_reader(mut reader: R) -> Result<Self> {
+        let file_size = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
 
         Ok(Self {
             reader,
@@ -45,8 +59,7 @@ impl UltraFastFbxParser {
         let mut magic = vec![0u8; 21];
         self.reader.read_exact(&mut magic)?;
 
-        let magic_str = String::from_utf8_lossy(&magic);
-        if !magic_str.starts_with("Kaydara FBX Binary") {
+        if !magic.starts_with(FBX_BINARY_MAGIC) {
             return Err(anyhow::anyhow!("Invalid FBX file: magic header mismatch"));
         }
 
@@ -0,0 +1,194 @@
+use anyhow::{Context, Result};
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::texture_pipeline::vfs::{self, MemoryMount};
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, Pipeline, Source, StreamWrapper, UrlHint,
+};
+
+/// Bytes requested up front to detect the format and read image dimensions
+const HEADER_RANGE: u64 = 65536;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Largest download accepted, so a huge or endless response can't exhaust memory
+const MAX_DOWNLOAD_SIZE: u64 = 1 << 30;
+
+/// Whether a path or pasted string is an http(s) URL
+pub fn is_remote_url(text: &str) -> bool {
+    let lower = text.trim().to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// HTTP(S) source - reads the header with a range request, leaving plain images to be
+/// downloaded on load; containers are downloaded once and served from memory through
+/// the VFS so the regular sources can parse them at the URL path
+pub struct UrlSource;
+
+impl Source for UrlSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        // Once a container is downloaded and mounted, the regular sources own its URL
+        Ok(is_remote_url(&path.to_string_lossy()) && !vfs::is_file(path))
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // URLs only come from the command line or the clipboard
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let url = path.to_string_lossy().into_owned();
+        let (header_bytes, size) = Self::fetch_header(&url)?;
+
+        // Images and single-stream wrappers finish detection from the header in the pipeline
+        if imagesize::image_type(&header_bytes).is_ok()
+            || StreamWrapper::detect(&header_bytes).is_some()
+        {
+            let hint = Box::new(UrlHint {
+                url: url.clone(),
+                size,
+                header_bytes: Some(header_bytes.clone()),
+            }) as Box<dyn EmbeddedHint>;
+
+            return Ok(vec![EmbeddedMetadata {
+                name: Self::file_name(&url),
+                format: imagesize::ImageType::Png, // Placeholder - Pipeline will determine actual format
+                width: 0,
+                height: 0,
+                file_size: size.unwrap_or(header_bytes.len() as u64),
+                embedded_hint: hint,
+                source_path: path.to_path_buf(),
+                parents: Vec::new(),
+            }]);
+        }
+
+        // Containers need random access (ZIP directories sit at the end), so fetch it all
+        let pipeline = Pipeline::new();
+        let source = Self::mounted_content_source(&pipeline, path)?;
+        source.extract_metadata(path)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        anyhow::bail!("URLs can't be read from a stream: {entry_name}")
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(url_hint) = hint.as_any().downcast_ref::<UrlHint>() {
+            return Self::download(&url_hint.url);
+        }
+
+        anyhow::bail!("Invalid hint type for URL source: {}", hint.debug_info())
+    }
+}
+
+impl UrlSource {
+    fn agent() -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .build()
+            .into()
+    }
+
+    /// First `HEADER_RANGE` bytes and the total size, if the server reports it
+    /// Servers ignoring the range answer with the whole body, which is cut short here
+    fn fetch_header(url: &str) -> Result<(Vec<u8>, Option<u64>)> {
+        let response = Self::agent()
+            .get(url)
+            .header("Range", format!("bytes=0-{}", HEADER_RANGE - 1))
+            .call()
+            .with_context(|| format!("Failed to request {url}"))?;
+
+        let header_value = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let size = match response.status().as_u16() {
+            // "bytes 0-65535/1234567"; the total may be "*" when unknown
+            206 => header_value("content-range")
+                .and_then(|range| range.rsplit_once('/').map(|(_, total)| total.to_string()))
+                .and_then(|total| total.parse().ok()),
+            _ => header_value("content-length").and_then(|length| length.parse().ok()),
+        };
+
+        let mut header = Vec::new();
+        response
+            .into_body()
+            .into_reader()
+            .take(HEADER_RANGE)
+            .read_to_end(&mut header)
+            .with_context(|| format!("Failed to read header of {url}"))?;
+
+        log::debug!(
+            "Fetched {} header bytes of {url} (total {size:?})",
+            header.len()
+        );
+        Ok((header, size))
+    }
+
+    fn download(url: &str) -> Result<Vec<u8>> {
+        let response = Self::agent()
+            .get(url)
+            .call()
+            .with_context(|| format!("Failed to request {url}"))?;
+
+        let length = response
+            .headers()
+            .get("content-length")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|length| length.parse::<u64>().ok());
+        if let Some(length) = length
+            && length > MAX_DOWNLOAD_SIZE
+        {
+            anyhow::bail!("{url} is too large to download ({length} bytes)");
+        }
+
+        // Servers may send no length or more than they announced, so the read is capped too
+        let mut data = Vec::new();
+        response
+            .into_body()
+            .into_reader()
+            .take(MAX_DOWNLOAD_SIZE + 1)
+            .read_to_end(&mut data)
+            .with_context(|| format!("Failed to download {url}"))?;
+        if data.len() as u64 > MAX_DOWNLOAD_SIZE {
+            anyhow::bail!("{url} is larger than {MAX_DOWNLOAD_SIZE} bytes");
+        }
+
+        log::info!("🌐 Downloaded {} bytes from {url}", data.len());
+        Ok(data)
+    }
+
+    /// Download the URL into a memory mount and find the source for its content
+    /// The mount stays until no slot comes from the URL (see `release_unused_mounts`)
+    fn mounted_content_source<'a>(pipeline: &'a Pipeline, path: &Path) -> Result<&'a dyn Source> {
+        let data = Self::download(&path.to_string_lossy())?;
+        let source = pipeline
+            .find_source_for_reader(&mut Cursor::new(&data))
+            .with_context(|| format!("Unsupported remote file: {}", path.display()))?;
+
+        let mut memory = MemoryMount::new();
+        memory.insert("", data);
+        vfs::mount(path, memory);
+        Ok(source)
+    }
+
+    /// Last path segment without query or fragment
+    fn file_name(url: &str) -> String {
+        let without_query = url.split(['?', '#']).next().unwrap_or(url);
+        without_query
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(url)
+            .to_string()
+    }
+}
@@ -0,0 +1,362 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+use zip::{CompressionMethod, ZipArchive};
+
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, UsdzHint, vfs};
+
+/// UsdPreviewSurface inputs and the slot names used in entry names
+const SURFACE_SLOTS: &[(&str, &str)] = &[
+    ("diffuseColor", "Base Color"),
+    ("emissiveColor", "Emissive"),
+    ("specularColor", "Specular"),
+    ("normal", "Normal"),
+    ("roughness", "Roughness"),
+    ("metallic", "Metallic"),
+    ("occlusion", "Occlusion"),
+    ("opacity", "Opacity"),
+    ("clearcoat", "Clearcoat"),
+    ("clearcoatRoughness", "Clearcoat Roughness"),
+    ("displacement", "Displacement"),
+];
+
+/// Texture reference found in the root layer
+#[derive(Debug, Clone)]
+struct TextureBinding {
+    material: String,
+    slot: String,
+}
+
+/// USDZ scene package source
+///
+/// USDZ is an uncompressed, 64-byte aligned ZIP whose first entry is the root layer.
+/// Texture entries are named after the material and UsdPreviewSurface input they feed,
+/// which requires a text (`.usda`) root layer; binary crate layers fall back to entry names.
+pub struct UsdzSource;
+
+impl Source for UsdzSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_usdz_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("usdz"))
+            .unwrap_or(false);
+
+        if !has_usdz_extension {
+            return Ok(false);
+        }
+
+        let mut file = vfs::open(path)?;
+        let mut header = [0u8; 4];
+        file.read_exact(&mut header)?;
+        Ok(&header == b"PK\x03\x04")
+    }
+
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // ZIP local header whose first entry is a USD layer
+        let mut header = [0u8; 30];
+        let result = reader.read_exact(&mut header);
+        let name_len = u16::from_le_bytes([header[26], header[27]]) as usize;
+        let mut name = vec![0u8; name_len];
+        let name_result = reader.read_exact(&mut name);
+        reader.seek(SeekFrom::Start(0))?;
+
+        if result.is_err() || name_result.is_err() || &header[0..4] != b"PK\x03\x04" {
+            return Ok(false);
+        }
+        Ok(Self::is_layer_name(&String::from_utf8_lossy(&name)))
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let file = vfs::open(path).context("Failed to open USDZ file")?;
+        let mut archive =
+            ZipArchive::new(BufReader::new(file)).context("Failed to read USDZ package")?;
+
+        let metadata_list = Self::collect_textures(&mut archive, path, None)?;
+
+        log::info!(
+            "USDZ extraction completed: {} textures from {}",
+            metadata_list.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let mut archive = ZipArchive::new(Cursor::new(data))
+            .with_context(|| format!("Failed to read nested USDZ package {entry_name}"))?;
+
+        Self::collect_textures(&mut archive, parent_path, Some(entry_name))
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(usdz_hint) = hint.as_any().downcast_ref::<UsdzHint>() {
+            if let Some(ref entry_data) = usdz_hint.entry_data {
+                return Ok(entry_data.clone());
+            }
+            return self.read_usdz_entry(usdz_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for USDZ source: {}", hint.debug_info())
+    }
+}
+
+impl UsdzSource {
+    fn is_layer_name(name: &str) -> bool {
+        let lower = name.to_lowercase();
+        lower.ends_with(".usda") || lower.ends_with(".usdc") || lower.ends_with(".usd")
+    }
+
+    fn collect_textures<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        container_path: &Path,
+        parent_entry: Option<&str>,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        // The first layer in the package is the default (root) layer
+        let root_layer = (0..archive.len())
+            .filter_map(|i| archive.name_for_index(i).map(str::to_string))
+            .find(|name| Self::is_layer_name(name))
+            .context("USDZ package has no USD layer")?;
+
+        let bindings = Self::read_layer_bindings(archive, &root_layer);
+
+        // Nested packages can't be re-opened by path, so entry data is captured now
+        let capture_data = parent_entry.is_some();
+        let mut metadata_list = Vec::new();
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            if entry.is_dir() || entry.size() == 0 {
+                continue;
+            }
+
+            let entry_name = entry.name().to_string();
+            let size = entry.size();
+            let stored = entry.compression() == CompressionMethod::Stored;
+            let data_offset = entry.data_start();
+            let crc32 = entry.crc32();
+
+            let mut data = entry_buffer(size);
+            entry.read_to_end(&mut data)?;
+
+            let Ok(format) = imagesize::image_type(&data) else {
+                continue; // Layers and other non-image assets
+            };
+            let Ok(dimension) = imagesize::blob_size(&data) else {
+                continue;
+            };
+
+            let texture_name = match bindings.get(&entry_name) {
+                Some(binding) => format!("{} - {}", binding.material, binding.slot),
+                None => entry_name.clone(),
+            };
+
+            let hint = Box::new(UsdzHint {
+                container_path: container_path.to_path_buf(),
+                entry_name: entry_name.clone(),
+                data_offset,
+                crc32,
+                size,
+                stored,
+                entry_data: capture_data.then_some(data),
+            }) as Box<dyn EmbeddedHint>;
+
+            metadata_list.push(EmbeddedMetadata {
+                name: match parent_entry {
+                    Some(parent) => format!("{parent}/{texture_name}"),
+                    None => texture_name,
+                },
+                format,
+                width: dimension.width,
+                height: dimension.height,
+                file_size: size,
+                embedded_hint: hint,
+                source_path: container_path.to_path_buf(),
+                parents: Vec::new(),
+            });
+        }
+
+        if metadata_list.is_empty() {
+            anyhow::bail!("No textures found in USDZ package");
+        }
+
+        Ok(metadata_list)
+    }
+
+    /// Map package-relative texture paths to the material/slot they are bound to
+    fn read_layer_bindings<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        layer_name: &str,
+    ) -> HashMap<String, TextureBinding> {
+        let mut layer = Vec::new();
+        let read_result = archive
+            .by_name(layer_name)
+            .map_err(anyhow::Error::from)
+            .and_then(|mut entry| Ok(entry.read_to_end(&mut layer)?));
+
+        if let Err(e) = read_result {
+            log::warn!("Failed to read USD layer {layer_name}: {e}");
+            return HashMap::new();
+        }
+
+        if layer.starts_with(b"PXR-USDC") {
+            log::debug!(
+                "USDZ root layer {layer_name} is a binary crate file, using entry names for textures"
+            );
+            return HashMap::new();
+        }
+
+        let layer_dir = layer_name
+            .rsplit_once('/')
+            .map(|(dir, _)| dir)
+            .unwrap_or("");
+        Self::parse_usda_bindings(&String::from_utf8_lossy(&layer), layer_dir)
+    }
+
+    /// Walk `def Material` / `def Shader` prims in a text layer, pairing
+    /// `asset inputs:file` textures with the surface inputs connected to them
+    fn parse_usda_bindings(layer: &str, layer_dir: &str) -> HashMap<String, TextureBinding> {
+        // (prim type, prim name) for every open scope
+        let mut scopes: Vec<(String, String)> = Vec::new();
+        let mut pending_prim: Option<(String, String)> = None;
+
+        // (material, shader) -> texture file
+        let mut shader_files: Vec<(String, String, String)> = Vec::new();
+        // shader name -> surface slot it feeds
+        let mut shader_slots: HashMap<String, String> = HashMap::new();
+
+        for line in layer.lines() {
+            let trimmed = line.trim();
+
+            if let Some(prim) = Self::parse_def(trimmed) {
+                pending_prim = Some(prim);
+            }
+
+            let current_material = scopes
+                .iter()
+                .rev()
+                .find(|(prim_type, _)| prim_type == "Material")
+                .map(|(_, name)| name.clone());
+            let current_shader = scopes
+                .last()
+                .filter(|(prim_type, _)| prim_type == "Shader")
+                .map(|(_, name)| name.clone());
+
+            if let Some(shader) = current_shader
+                && trimmed.contains("inputs:file")
+                && let Some(file) = Self::parse_asset_path(trimmed)
+            {
+                shader_files.push((
+                    current_material
+                        .clone()
+                        .unwrap_or_else(|| "Material".to_string()),
+                    shader,
+                    Self::resolve_asset_path(layer_dir, file),
+                ));
+            }
+
+            if let Some(slot) = Self::parse_surface_connection(trimmed) {
+                shader_slots.insert(slot.0, slot.1);
+            }
+
+            for ch in trimmed.chars() {
+                match ch {
+                    '{' => scopes.push(
+                        pending_prim
+                            .take()
+                            .unwrap_or_else(|| (String::new(), String::new())),
+                    ),
+                    '}' => {
+                        scopes.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        shader_files
+            .into_iter()
+            .map(|(material, shader, file)| {
+                let slot = shader_slots.get(&shader).cloned().unwrap_or(shader);
+                (file, TextureBinding { material, slot })
+            })
+            .collect()
+    }
+
+    /// `def Shader "DiffuseTexture"` -> ("Shader", "DiffuseTexture")
+    fn parse_def(line: &str) -> Option<(String, String)> {
+        let rest = line
+            .strip_prefix("def ")
+            .or_else(|| line.strip_prefix("over "))?;
+        let quote_start = rest.find('"')?;
+        let prim_type = rest[..quote_start].trim().to_string();
+        let name_rest = &rest[quote_start + 1..];
+        let name = &name_rest[..name_rest.find('"')?];
+        Some((prim_type, name.to_string()))
+    }
+
+    /// `asset inputs:file = @./textures/albedo.png@` -> `./textures/albedo.png`
+    fn parse_asset_path(line: &str) -> Option<&str> {
+        let start = line.find('@')? + 1;
+        let end = start + line[start..].find('@')?;
+        Some(&line[start..end])
+    }
+
+    /// `color3f inputs:diffuseColor.connect = </Mat/DiffuseTexture.outputs:rgb>`
+    /// -> ("DiffuseTexture", "Base Color")
+    fn parse_surface_connection(line: &str) -> Option<(String, String)> {
+        let input_start = line.find("inputs:")? + "inputs:".len();
+        let input_end = input_start + line[input_start..].find(".connect")?;
+        let input = &line[input_start..input_end];
+
+        let target_start = line.find('<')? + 1;
+        let target_end = target_start + line[target_start..].find('>')?;
+        let target = &line[target_start..target_end];
+        let shader = target.rsplit('/').next()?.split('.').next()?.to_string();
+
+        let slot = SURFACE_SLOTS
+            .iter()
+            .find(|(name, _)| *name == input)
+            .map(|(_, slot)| slot.to_string())?;
+        Some((shader, slot))
+    }
+
+    fn resolve_asset_path(layer_dir: &str, asset: &str) -> String {
+        let asset = asset.trim_start_matches("./");
+        if layer_dir.is_empty() {
+            asset.to_string()
+        } else {
+            format!("{layer_dir}/{asset}")
+        }
+    }
+
+    /// Stored entries are read directly at their data offset; anything else goes through the ZIP reader
+    fn read_usdz_entry(&self, hint: &UsdzHint) -> Result<Vec<u8>> {
+        let mut file = vfs::open(&hint.container_path)
+            .context("Failed to open USDZ file for reading entry")?;
+
+        if hint.stored {
+            file.seek(SeekFrom::Start(hint.data_offset))?;
+            let mut buffer = vec![0u8; hint.size as usize];
+            file.read_exact(&mut buffer)
+                .with_context(|| format!("Failed to read USDZ entry: {}", hint.entry_name))?;
+            return Ok(buffer);
+        }
+
+        let mut archive = ZipArchive::new(BufReader::new(file))?;
+        let mut entry = archive.by_name(&hint.entry_name)?;
+        let mut buffer = entry_buffer(hint.size);
+        entry.read_to_end(&mut buffer)?;
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,397 @@
+use anyhow::{Context, Result};
+use std::collections::hash_map::{self, HashMap};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::vtf;
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, VpkHint, vfs};
+
+const VPK_SIGNATURE: u32 = 0x55AA_1234;
+
+/// Header sizes of version 1 and 2 directories
+const HEADER_SIZE_V1: u64 = 12;
+const HEADER_SIZE_V2: u64 = 28;
+
+/// Archive index of entries stored after the tree in the directory file
+const DIR_ARCHIVE_INDEX: u16 = 0x7FFF;
+const ENTRY_TERMINATOR: u16 = 0xFFFF;
+
+/// Entry extensions listed from the directory tree
+const TEXTURE_EXTENSIONS: &[&str] = &["vtf", "png", "jpg", "jpeg", "tga"];
+
+/// Bytes read per entry to detect its format and size; retried larger for late JPEG headers
+const HEADER_PEEK_SIZE: u64 = 4096;
+const HEADER_PEEK_SIZE_MAX: u64 = 65536;
+
+/// Source engine VPK source
+///
+/// Reads the directory tree of a `_dir.vpk` (or single-file .vpk) and lists .vtf and image
+/// entries. Entry data is read straight from the numbered chunk file (`pak01_003.vpk`)
+/// named in the tree, after any preload bytes kept in the tree itself.
+pub struct VpkSource;
+
+/// One file as described by the directory tree
+#[derive(Debug)]
+struct VpkEntry {
+    path: String,
+    archive_index: u16,
+    offset: u64, // Relative to the chunk, or to the end of the tree for DIR_ARCHIVE_INDEX
+    length: u64,
+    preload_offset: u64, // Absolute offset in the directory file
+    preload_size: u64,
+}
+
+/// Parsed directory file
+struct VpkDirectory {
+    data_start: u64, // Where DIR_ARCHIVE_INDEX data begins in the directory file
+    entries: Vec<VpkEntry>,
+}
+
+impl Source for VpkSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_vpk_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("vpk"))
+            .unwrap_or(false);
+
+        if !has_vpk_extension {
+            return Ok(false);
+        }
+
+        // Numbered chunks have no header and are only read through their directory
+        let mut signature = [0u8; 4];
+        let mut file = vfs::open(path)?;
+        if file.read_exact(&mut signature).is_err() {
+            return Ok(false);
+        }
+        Ok(u32::from_le_bytes(signature) == VPK_SIGNATURE)
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // Entries live in sibling chunk files, so only VPKs on disk can be read
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let mut file = vfs::open(path).context("Failed to open VPK file")?;
+        let directory = Self::read_directory(&mut file).context("Failed to read VPK directory")?;
+
+        let mut archives: HashMap<u16, vfs::File> = HashMap::new();
+        let mut metadata_list = Vec::new();
+        for entry in &directory.entries {
+            let entry_result = (|| -> Result<Option<EmbeddedMetadata>> {
+                let extension = entry
+                    .path
+                    .rsplit_once('.')
+                    .map(|(_, ext)| ext.to_lowercase())
+                    .unwrap_or_default();
+                if !TEXTURE_EXTENSIONS.contains(&extension.as_str()) {
+                    return Ok(None);
+                }
+
+                let archive_path = Self::archive_path(path, entry.archive_index);
+                let archive = match archives.entry(entry.archive_index) {
+                    hash_map::Entry::Occupied(open) => open.into_mut(),
+                    hash_map::Entry::Vacant(slot) => {
+                        let chunk = vfs::open(&archive_path).with_context(|| {
+                            format!("Missing VPK chunk {}", archive_path.display())
+                        })?;
+                        slot.insert(chunk)
+                    }
+                };
+
+                let (format, width, height) = if extension == "vtf" {
+                    let header = Self::read_range(
+                        &mut file,
+                        archive,
+                        entry,
+                        directory.data_start,
+                        HEADER_PEEK_SIZE,
+                    )?;
+                    let info = vtf::texture_info(&header)?;
+                    (info.image_type, info.width as usize, info.height as usize)
+                } else {
+                    let mut header = Self::read_range(
+                        &mut file,
+                        archive,
+                        entry,
+                        directory.data_start,
+                        HEADER_PEEK_SIZE,
+                    )?;
+                    if imagesize::blob_size(&header).is_err() {
+                        header = Self::read_range(
+                            &mut file,
+                            archive,
+                            entry,
+                            directory.data_start,
+                            HEADER_PEEK_SIZE_MAX,
+                        )?;
+                    }
+                    let size = imagesize::blob_size(&header)?;
+                    (imagesize::image_type(&header)?, size.width, size.height)
+                };
+
+                let hint = Box::new(VpkHint {
+                    container_path: path.to_path_buf(),
+                    entry_path: entry.path.clone(),
+                    archive_path,
+                    data_offset: Self::data_offset(entry, directory.data_start),
+                    length: entry.length,
+                    preload_offset: entry.preload_offset,
+                    preload_size: entry.preload_size,
+                    vtf: extension == "vtf",
+                }) as Box<dyn EmbeddedHint>;
+
+                Ok(Some(EmbeddedMetadata {
+                    name: entry.path.clone(),
+                    format,
+                    width,
+                    height,
+                    file_size: entry.preload_size + entry.length,
+                    embedded_hint: hint,
+                    source_path: path.to_path_buf(),
+                    parents: Vec::new(),
+                }))
+            })();
+
+            match entry_result {
+                Ok(Some(metadata)) => metadata_list.push(metadata),
+                Ok(None) => {}
+                Err(e) => log::debug!(
+                    "Failed to extract metadata from VPK entry {}: {e}",
+                    entry.path
+                ),
+            }
+        }
+
+        log::info!(
+            "VPK extraction completed: {} textures from {} entries in {}",
+            metadata_list.len(),
+            directory.entries.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        log::debug!("VPK processing from reader not supported for entry: {entry_name}");
+        Ok(Vec::new())
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(vpk_hint) = hint.as_any().downcast_ref::<VpkHint>() {
+            let data = Self::read_entry(vpk_hint)?;
+            if vpk_hint.vtf {
+                return vtf::read_texture(&data).with_context(|| {
+                    format!("Failed to decode VTF texture {}", vpk_hint.entry_path)
+                });
+            }
+            return Ok(data);
+        }
+
+        anyhow::bail!("Invalid hint type for VPK source: {}", hint.debug_info())
+    }
+}
+
+impl VpkSource {
+    /// Preload bytes from the directory file followed by the data in its chunk
+    fn read_entry(hint: &VpkHint) -> Result<Vec<u8>> {
+        let size = hint
+            .preload_size
+            .checked_add(hint.length)
+            .context("VPK entry size overflows")?;
+        let mut data = entry_buffer(size);
+
+        if hint.preload_size > 0 {
+            let mut file = vfs::open(&hint.container_path)
+                .context("Failed to open VPK directory for reading preload data")?;
+            file.seek(SeekFrom::Start(hint.preload_offset))?;
+            file.take(hint.preload_size).read_to_end(&mut data)?;
+        }
+
+        if hint.length > 0 {
+            let mut chunk = vfs::open(&hint.archive_path).with_context(|| {
+                format!("Failed to open VPK chunk {}", hint.archive_path.display())
+            })?;
+            chunk.seek(SeekFrom::Start(hint.data_offset))?;
+            chunk.take(hint.length).read_to_end(&mut data)?;
+        }
+
+        if (data.len() as u64) < size {
+            anyhow::bail!("VPK entry {} is truncated", hint.entry_path);
+        }
+        Ok(data)
+    }
+
+    /// First `limit` bytes of an entry, spanning the preload and chunk data
+    fn read_range(
+        directory_file: &mut vfs::File,
+        archive: &mut vfs::File,
+        entry: &VpkEntry,
+        data_start: u64,
+        limit: u64,
+    ) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+
+        let preload = entry.preload_size.min(limit);
+        if preload > 0 {
+            directory_file.seek(SeekFrom::Start(entry.preload_offset))?;
+            directory_file
+                .by_ref()
+                .take(preload)
+                .read_to_end(&mut data)?;
+        }
+
+        let remaining = entry.length.min(limit - preload);
+        if remaining > 0 {
+            archive.seek(SeekFrom::Start(Self::data_offset(entry, data_start)))?;
+            archive.by_ref().take(remaining).read_to_end(&mut data)?;
+        }
+        Ok(data)
+    }
+
+    fn data_offset(entry: &VpkEntry, data_start: u64) -> u64 {
+        if entry.archive_index == DIR_ARCHIVE_INDEX {
+            data_start + entry.offset
+        } else {
+            entry.offset
+        }
+    }
+
+    /// `pak01_dir.vpk` keeps its data in `pak01_000.vpk`, `pak01_001.vpk`, ...
+    fn archive_path(dir_path: &Path, archive_index: u16) -> PathBuf {
+        if archive_index == DIR_ARCHIVE_INDEX {
+            return dir_path.to_path_buf();
+        }
+
+        let file_name = dir_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let stem = file_name
+            .len()
+            .checked_sub("_dir.vpk".len())
+            .filter(|&split| file_name[split..].eq_ignore_ascii_case("_dir.vpk"))
+            .map(|split| &file_name[..split])
+            .unwrap_or_else(|| file_name.trim_end_matches(".vpk"));
+        dir_path.with_file_name(format!("{stem}_{archive_index:03}.vpk"))
+    }
+
+    fn read_directory(file: &mut vfs::File) -> Result<VpkDirectory> {
+        let mut header = [0u8; 12];
+        file.seek(SeekFrom::Start(0))?;
+        file.read_exact(&mut header)
+            .context("VPK header truncated")?;
+
+        let signature = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        if signature != VPK_SIGNATURE {
+            anyhow::bail!("Not a VPK directory");
+        }
+        let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let tree_size = u32::from_le_bytes([header[8], header[9], header[10], header[11]]) as u64;
+        let header_size = match version {
+            1 => HEADER_SIZE_V1,
+            2 => HEADER_SIZE_V2,
+            version => anyhow::bail!("Unsupported VPK version {version}"),
+        };
+
+        // The tree size is untrusted; it has to fit in the directory file
+        let file_len = file.seek(SeekFrom::End(0))?;
+        if header_size + tree_size > file_len {
+            anyhow::bail!("VPK directory tree truncated");
+        }
+
+        let mut tree = Vec::with_capacity(tree_size as usize);
+        file.seek(SeekFrom::Start(header_size))?;
+        file.by_ref().take(tree_size).read_to_end(&mut tree)?;
+        if (tree.len() as u64) < tree_size {
+            anyhow::bail!("VPK directory tree truncated");
+        }
+
+        let entries = Self::parse_tree(&tree, header_size)?;
+        Ok(VpkDirectory {
+            data_start: header_size + tree_size,
+            entries,
+        })
+    }
+
+    /// Extension → path → file name levels, each list ended by an empty string
+    fn parse_tree(tree: &[u8], tree_start: u64) -> Result<Vec<VpkEntry>> {
+        let mut pos = 0;
+        let mut entries = Vec::new();
+
+        loop {
+            let extension = read_cstring(tree, &mut pos)?;
+            if extension.is_empty() {
+                break;
+            }
+            loop {
+                let directory = read_cstring(tree, &mut pos)?;
+                if directory.is_empty() {
+                    break;
+                }
+                loop {
+                    let file_name = read_cstring(tree, &mut pos)?;
+                    if file_name.is_empty() {
+                        break;
+                    }
+
+                    let record = tree.get(pos..pos + 18).context("VPK entry truncated")?;
+                    let preload_size = u16::from_le_bytes([record[4], record[5]]) as u64;
+                    let archive_index = u16::from_le_bytes([record[6], record[7]]);
+                    let offset =
+                        u32::from_le_bytes([record[8], record[9], record[10], record[11]]) as u64;
+                    let length =
+                        u32::from_le_bytes([record[12], record[13], record[14], record[15]]) as u64;
+                    if u16::from_le_bytes([record[16], record[17]]) != ENTRY_TERMINATOR {
+                        anyhow::bail!("Malformed VPK entry {directory}/{file_name}.{extension}");
+                    }
+                    pos += 18;
+
+                    // A single space stands for "none" at the path and extension levels
+                    let mut path = String::new();
+                    if directory != " " {
+                        path.push_str(&directory);
+                        path.push('/');
+                    }
+                    path.push_str(&file_name);
+                    if extension != " " {
+                        path.push('.');
+                        path.push_str(&extension);
+                    }
+
+                    entries.push(VpkEntry {
+                        path,
+                        archive_index,
+                        offset,
+                        length,
+                        preload_offset: tree_start + pos as u64,
+                        preload_size,
+                    });
+                    pos += preload_size as usize;
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+fn read_cstring(data: &[u8], pos: &mut usize) -> Result<String> {
+    let rest = data.get(*pos..).context("VPK directory tree truncated")?;
+    let end = rest
+        .iter()
+        .position(|&b| b == 0)
+        .context("Unterminated string in VPK directory tree")?;
+    let text = String::from_utf8_lossy(&rest[..end]).into_owned();
+    *pos += end + 1;
+    Ok(text)
+}
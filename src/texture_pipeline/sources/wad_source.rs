@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use imagesize::ImageType;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::texture_pipeline::idtech::{self, DoomNamespace, WadDirectory, WadKind};
+use crate::texture_pipeline::{
+    BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, WadHint, WadLump, vfs,
+};
+
+/// idTech WAD source (Doom IWAD/PWAD, Quake WAD2, Half-Life WAD3)
+///
+/// Doom lumps are classified by their marker section (F_START, S_START, P_START, TX_START);
+/// outside them only lumps that fully decode as pictures are listed. Paletted lumps are
+/// decoded through PLAYPAL, the Quake palette or the lump's own WAD3 palette on load.
+pub struct WadSource;
+
+impl Source for WadSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_wad_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("wad"))
+            .unwrap_or(false);
+
+        if !has_wad_extension {
+            return Ok(false);
+        }
+
+        let mut header = [0u8; 4];
+        let mut file = vfs::open(path)?;
+        if file.read_exact(&mut header).is_err() {
+            return Ok(false);
+        }
+        Ok(idtech::wad_kind(&header).is_some())
+    }
+
+    fn can_load_reader(&self, _reader: &mut dyn BufReadSeek) -> Result<bool> {
+        // WADs are only listed from disk so lumps can be decoded from the file on load
+        Ok(false)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let file = vfs::open(path).context("Failed to open WAD file")?;
+        let mut reader = BufReader::new(file);
+        let directory = WadDirectory::read(&mut reader).context("Failed to read WAD directory")?;
+
+        let mut namespace = DoomNamespace::Global;
+        let mut metadata_list = Vec::new();
+        for (i, entry) in directory.entries.iter().enumerate() {
+            if directory.kind == WadKind::Doom
+                && let Some((marker, start)) = DoomNamespace::from_marker(&entry.name)
+            {
+                namespace = if start { marker } else { DoomNamespace::Global };
+                continue;
+            }
+            if entry.size == 0 {
+                continue;
+            }
+
+            let entry_result = (|| -> Result<Option<EmbeddedMetadata>> {
+                let data = idtech::read_lump(&mut reader, entry)?;
+                let classified = match directory.kind {
+                    WadKind::Doom => idtech::classify_doom_lump(&entry.name, namespace, &data),
+                    _ => idtech::quake_lump_size(entry.lump_type, &data).map(
+                        |(lump, width, height)| match lump {
+                            // WAD3 pictures carry their palette like WAD3 miptex
+                            WadLump::QuakePicture { .. } => (
+                                WadLump::QuakePicture {
+                                    embedded_palette: directory.kind == WadKind::HalfLife,
+                                },
+                                width,
+                                height,
+                            ),
+                            lump => (lump, width, height),
+                        },
+                    ),
+                };
+                let Some((lump, width, height)) = classified else {
+                    return Ok(None);
+                };
+
+                let format = match lump {
+                    WadLump::Image => imagesize::image_type(&data)?,
+                    _ => ImageType::Qoi,
+                };
+
+                let hint = Box::new(WadHint {
+                    container_path: path.to_path_buf(),
+                    lump_name: entry.name.clone(),
+                    lump_index: i,
+                    data_offset: entry.offset,
+                    size: entry.size,
+                    lump,
+                }) as Box<dyn EmbeddedHint>;
+
+                Ok(Some(EmbeddedMetadata {
+                    name: entry.name.clone(),
+                    format,
+                    width: width as usize,
+                    height: height as usize,
+                    file_size: entry.size,
+                    embedded_hint: hint,
+                    source_path: path.to_path_buf(),
+                    parents: Vec::new(),
+                }))
+            })();
+
+            match entry_result {
+                Ok(Some(metadata)) => metadata_list.push(metadata),
+                Ok(None) => {}
+                Err(e) => log::debug!("Failed to extract metadata from WAD lump {i}: {e}"),
+            }
+        }
+
+        log::info!(
+            "WAD extraction completed: {} textures from {} lumps in {}",
+            metadata_list.len(),
+            directory.entries.len(),
+            path.display()
+        );
+
+        Ok(metadata_list)
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        _reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        _parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        log::debug!("WAD processing from reader not supported for entry: {entry_name}");
+        Ok(Vec::new())
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(wad_hint) = hint.as_any().downcast_ref::<WadHint>() {
+            return Self::read_wad_lump(wad_hint);
+        }
+
+        anyhow::bail!("Invalid hint type for WAD source: {}", hint.debug_info())
+    }
+}
+
+impl WadSource {
+    fn read_wad_lump(hint: &WadHint) -> Result<Vec<u8>> {
+        let mut file =
+            vfs::open(&hint.container_path).context("Failed to open WAD file for reading lump")?;
+        let entry = idtech::WadEntry {
+            name: hint.lump_name.clone(),
+            offset: hint.data_offset,
+            size: hint.size,
+            lump_type: 0,
+        };
+        let data = idtech::read_lump(&mut file, &entry)
+            .with_context(|| format!("Failed to read WAD lump: {}", hint.lump_name))?;
+
+        idtech::decode_lump(hint.lump, &hint.lump_name, &data, || {
+            idtech::container_palette(&hint.container_path, || {
+                let mut file = vfs::open(&hint.container_path).ok()?;
+                WadDirectory::read(&mut file).ok()?.palette(&mut file)
+            })
+        })
+    }
+}
@@ -0,0 +1,611 @@
+use anyhow::{Context, Result};
+use flate2::read::ZlibDecoder;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, XcfHint, vfs};
+
+const XCF_MAGIC: &[u8] = b"gimp xcf ";
+const TILE_SIZE: u32 = 64;
+/// Largest canvas width or height accepted
+const MAX_DIMENSION: u32 = 8192;
+/// Most values a layer or canvas buffer may hold (8192x8192 RGBA), so crafted
+/// dimensions fail instead of forcing a huge allocation
+const MAX_BUFFER_LEN: u64 = MAX_DIMENSION as u64 * MAX_DIMENSION as u64 * 4;
+
+// Property identifiers used while flattening (see GIMP's devel-docs/xcf.txt)
+const PROP_END: u32 = 0;
+const PROP_COLORMAP: u32 = 1;
+const PROP_OPACITY: u32 = 6;
+const PROP_VISIBLE: u32 = 8;
+const PROP_OFFSETS: u32 = 15;
+const PROP_COMPRESSION: u32 = 17;
+const PROP_GROUP_ITEM: u32 = 29;
+const PROP_FLOAT_OPACITY: u32 = 33;
+
+/// GIMP XCF source - produces the flattened composite of all visible layers
+///
+/// Layers are composited with normal blending only; blend modes, masks and
+/// non-8-bit precisions are not interpreted, so the result is a preview rather
+/// than a pixel-exact GIMP render.
+pub struct XcfSource;
+
+impl Source for XcfSource {
+    fn can_load_path(&self, path: &Path) -> Result<bool> {
+        let has_xcf_extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase() == "xcf")
+            .unwrap_or(false);
+
+        if !has_xcf_extension {
+            return Ok(false);
+        }
+
+        let mut file = vfs::open(path)?;
+        let mut header = [0u8; 9];
+        file.read_exact(&mut header)?;
+        Ok(header == XCF_MAGIC)
+    }
+
+    fn can_load_reader(&self, reader: &mut dyn BufReadSeek) -> Result<bool> {
+        let mut header = [0u8; 9];
+        let result = reader.read_exact(&mut header);
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(result.is_ok() && header == XCF_MAGIC)
+    }
+
+    fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
+        let mut file = vfs::open(path).context("Failed to open XCF file")?;
+        let mut header = [0u8; 26];
+        file.read_exact(&mut header)
+            .context("XCF file too small for header")?;
+        let (width, height) = XcfImage::read_dimensions(&header)?;
+
+        let hint = Box::new(XcfHint {
+            container_path: path.to_path_buf(),
+            flattened_data: None,
+        }) as Box<dyn EmbeddedHint>;
+
+        Ok(vec![EmbeddedMetadata {
+            name: path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            format: imagesize::ImageType::Qoi, // Flattened composite is re-encoded as QOI
+            width: width as usize,
+            height: height as usize,
+            file_size: vfs::file_size(path)?,
+            embedded_hint: hint,
+            source_path: path.to_path_buf(),
+            parents: Vec::new(),
+        }])
+    }
+
+    fn extract_metadata_from_reader(
+        &self,
+        reader: &mut dyn BufReadSeek,
+        entry_name: &str,
+        parent_path: &Path,
+    ) -> Result<Vec<EmbeddedMetadata>> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        // Nested XCF files have no path to come back to, so flatten right away
+        let image = XcfImage::parse(&data)?;
+        let flattened = image.flatten_to_qoi()?;
+
+        let hint = Box::new(XcfHint {
+            container_path: parent_path.to_path_buf(),
+            flattened_data: Some(flattened),
+        }) as Box<dyn EmbeddedHint>;
+
+        Ok(vec![EmbeddedMetadata {
+            name: entry_name.to_string(),
+            format: imagesize::ImageType::Qoi,
+            width: image.width as usize,
+            height: image.height as usize,
+            file_size: data.len() as u64,
+            embedded_hint: hint,
+            source_path: parent_path.to_path_buf(),
+            parents: Vec::new(),
+        }])
+    }
+
+    fn load_bytes(&self, hint: &dyn EmbeddedHint) -> Result<Vec<u8>> {
+        if let Some(xcf_hint) = hint.as_any().downcast_ref::<XcfHint>() {
+            if let Some(ref flattened) = xcf_hint.flattened_data {
+                return Ok(flattened.clone());
+            }
+
+            let data = vfs::read(&xcf_hint.container_path).with_context(|| {
+                format!(
+                    "Failed to read XCF file {}",
+                    xcf_hint.container_path.display()
+                )
+            })?;
+            let image = XcfImage::parse(&data)?;
+            return image.flatten_to_qoi();
+        }
+
+        anyhow::bail!("Invalid hint type for XCF source: {}", hint.debug_info())
+    }
+}
+
+/// Single layer with its pixels already converted to RGBA8
+struct XcfLayer {
+    width: u32,
+    height: u32,
+    offset_x: i32,
+    offset_y: i32,
+    opacity: f32,
+    visible: bool,
+    pixels: Vec<u8>,
+}
+
+/// Minimal XCF reader - enough structure to flatten visible layers
+struct XcfImage {
+    width: u32,
+    height: u32,
+    layers: Vec<XcfLayer>, // Top-most layer first, as stored in the file
+}
+
+/// Parsing state shared while walking the file
+struct XcfReader<'a> {
+    cursor: Cursor<&'a [u8]>,
+    version: u32,
+    compression: u8,
+    colormap: Vec<[u8; 3]>,
+}
+
+impl XcfImage {
+    /// Read canvas dimensions from the fixed-size file header
+    fn read_dimensions(header: &[u8]) -> Result<(u32, u32)> {
+        if header.len() < 22 || &header[..9] != XCF_MAGIC {
+            anyhow::bail!("Not a GIMP XCF file");
+        }
+        let width = u32::from_be_bytes([header[14], header[15], header[16], header[17]]);
+        let height = u32::from_be_bytes([header[18], header[19], header[20], header[21]]);
+
+        if width == 0 || height == 0 || width > MAX_DIMENSION || height > MAX_DIMENSION {
+            anyhow::bail!("Invalid XCF canvas dimensions: {width}x{height}");
+        }
+
+        Ok((width, height))
+    }
+
+    fn parse(data: &[u8]) -> Result<Self> {
+        let (width, height) = Self::read_dimensions(data)?;
+
+        // Version tag is "file" (v0) or "vNNN"
+        let version = match &data[9..13] {
+            b"file" => 0,
+            tag if tag[0] == b'v' => std::str::from_utf8(&tag[1..])
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .context("Invalid XCF version tag")?,
+            _ => anyhow::bail!("Invalid XCF version tag"),
+        };
+
+        let mut reader = XcfReader {
+            cursor: Cursor::new(data),
+            version,
+            compression: 0,
+            colormap: Vec::new(),
+        };
+        reader.cursor.set_position(22);
+
+        let base_type = reader.read_u32()?;
+        if version >= 4 {
+            let precision = reader.read_u32()?;
+            // 8-bit precision: 0 in v4, 100 (linear) / 150 (perceptual) from v5 onwards
+            let is_8bit = if version == 4 {
+                precision == 0
+            } else {
+                precision == 100 || precision == 150
+            };
+            if !is_8bit {
+                anyhow::bail!(
+                    "Unsupported XCF precision {precision} (only 8-bit images can be previewed)"
+                );
+            }
+        }
+        log::debug!("XCF v{version}: {width}x{height}, base type {base_type}");
+
+        // Image properties - we only care about compression and the colormap
+        loop {
+            let (prop_type, payload) = reader.read_property()?;
+            match prop_type {
+                PROP_END => break,
+                PROP_COMPRESSION => reader.compression = payload.first().copied().unwrap_or(0),
+                PROP_COLORMAP if payload.len() >= 4 => {
+                    let count =
+                        u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+                    reader.colormap = payload[4..]
+                        .chunks_exact(3)
+                        .take(count as usize)
+                        .map(|c| [c[0], c[1], c[2]])
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+
+        if reader.compression > 2 {
+            anyhow::bail!("Unsupported XCF compression {}", reader.compression);
+        }
+
+        let layer_pointers = reader.read_pointer_list()?;
+        let mut layers = Vec::with_capacity(layer_pointers.len());
+        for pointer in layer_pointers {
+            match reader.read_layer(pointer) {
+                Ok(Some(layer)) => layers.push(layer),
+                Ok(None) => {}
+                Err(e) => log::debug!("Skipping unreadable XCF layer at {pointer}: {e}"),
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            layers,
+        })
+    }
+
+    /// Composite visible layers bottom-up with normal "over" blending
+    fn flatten(&self) -> Result<Vec<u8>> {
+        let mut canvas = vec![0f32; buffer_len(self.width, self.height, 4)?];
+
+        for layer in self.layers.iter().rev().filter(|l| l.visible) {
+            for ly in 0..layer.height {
+                let cy = ly as i64 + layer.offset_y as i64;
+                if cy < 0 || cy >= self.height as i64 {
+                    continue;
+                }
+                for lx in 0..layer.width {
+                    let cx = lx as i64 + layer.offset_x as i64;
+                    if cx < 0 || cx >= self.width as i64 {
+                        continue;
+                    }
+
+                    let src = ((ly * layer.width + lx) * 4) as usize;
+                    let dst = ((cy as u32 * self.width + cx as u32) * 4) as usize;
+
+                    let src_a = layer.pixels[src + 3] as f32 / 255.0 * layer.opacity;
+                    if src_a <= 0.0 {
+                        continue;
+                    }
+                    let dst_a = canvas[dst + 3];
+                    let out_a = src_a + dst_a * (1.0 - src_a);
+
+                    for c in 0..3 {
+                        let src_c = layer.pixels[src + c] as f32 / 255.0;
+                        canvas[dst + c] = (src_c * src_a + canvas[dst + c] * dst_a * (1.0 - src_a))
+                            / out_a.max(f32::EPSILON);
+                    }
+                    canvas[dst + 3] = out_a;
+                }
+            }
+        }
+
+        Ok(canvas
+            .iter()
+            .map(|&v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+            .collect())
+    }
+
+    /// Flatten and re-encode as QOI so the standard parser can pick it up
+    fn flatten_to_qoi(&self) -> Result<Vec<u8>> {
+        let rgba = image::RgbaImage::from_raw(self.width, self.height, self.flatten()?)
+            .context("Flattened XCF buffer has unexpected size")?;
+
+        let mut encoded = Vec::new();
+        image::DynamicImage::ImageRgba8(rgba)
+            .write_to(&mut Cursor::new(&mut encoded), image::ImageFormat::Qoi)
+            .context("Failed to encode flattened XCF image")?;
+
+        log::debug!(
+            "XCF flattened: {} layers -> {}x{} ({} bytes QOI)",
+            self.layers.len(),
+            self.width,
+            self.height,
+            encoded.len()
+        );
+
+        Ok(encoded)
+    }
+}
+
+impl XcfReader<'_> {
+    fn read_u32(&mut self) -> Result<u32> {
+        let mut buf = [0u8; 4];
+        self.cursor.read_exact(&mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    /// Pointers are 32-bit before XCF v11 and 64-bit afterwards
+    fn read_pointer(&mut self) -> Result<u64> {
+        if self.version >= 11 {
+            let mut buf = [0u8; 8];
+            self.cursor.read_exact(&mut buf)?;
+            Ok(u64::from_be_bytes(buf))
+        } else {
+            Ok(self.read_u32()? as u64)
+        }
+    }
+
+    /// Read a zero-terminated list of pointers
+    fn read_pointer_list(&mut self) -> Result<Vec<u64>> {
+        let mut pointers = Vec::new();
+        loop {
+            let pointer = self.read_pointer()?;
+            if pointer == 0 {
+                break;
+            }
+            pointers.push(pointer);
+        }
+        Ok(pointers)
+    }
+
+    fn read_property(&mut self) -> Result<(u32, Vec<u8>)> {
+        let prop_type = self.read_u32()?;
+        let length = self.read_u32()? as usize;
+
+        let remaining = self.cursor.get_ref().len() as u64 - self.cursor.position();
+        if length as u64 > remaining {
+            anyhow::bail!("XCF property {prop_type} overruns file");
+        }
+
+        let mut payload = vec![0u8; length];
+        self.cursor.read_exact(&mut payload)?;
+        Ok((prop_type, payload))
+    }
+
+    fn skip_string(&mut self) -> Result<()> {
+        let length = self.read_u32()? as i64;
+        self.cursor.seek_relative(length)?;
+        Ok(())
+    }
+
+    fn read_layer(&mut self, pointer: u64) -> Result<Option<XcfLayer>> {
+        self.cursor.set_position(pointer);
+
+        let width = self.read_u32()?;
+        let height = self.read_u32()?;
+        let layer_type = self.read_u32()?;
+        self.skip_string()?; // Layer name
+
+        let mut opacity = 1.0;
+        let mut visible = true;
+        let mut is_group = false;
+        let (mut offset_x, mut offset_y) = (0i32, 0i32);
+
+        loop {
+            let (prop_type, payload) = self.read_property()?;
+            let be_u32 = |bytes: &[u8]| -> u32 {
+                bytes
+                    .get(..4)
+                    .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+                    .unwrap_or(0)
+            };
+            match prop_type {
+                PROP_END => break,
+                PROP_OPACITY => opacity = be_u32(&payload) as f32 / 255.0,
+                PROP_FLOAT_OPACITY => opacity = f32::from_bits(be_u32(&payload)),
+                PROP_VISIBLE => visible = be_u32(&payload) != 0,
+                PROP_GROUP_ITEM => is_group = true,
+                PROP_OFFSETS if payload.len() >= 8 => {
+                    offset_x = be_u32(&payload) as i32;
+                    offset_y = be_u32(&payload[4..]) as i32;
+                }
+                _ => {}
+            }
+        }
+
+        // Group layers carry no pixels of their own - their children are composited individually
+        if is_group || width == 0 || height == 0 {
+            return Ok(None);
+        }
+        // Pixels are converted to RGBA, so that's the size that has to fit
+        buffer_len(width, height, 4)?;
+
+        let hierarchy_pointer = self.read_pointer()?;
+        let _mask_pointer = self.read_pointer()?;
+
+        let raw = self.read_hierarchy(hierarchy_pointer, width, height)?;
+        let pixels = self.convert_to_rgba(&raw.0, raw.1, layer_type)?;
+
+        Ok(Some(XcfLayer {
+            width,
+            height,
+            offset_x,
+            offset_y,
+            opacity: opacity.clamp(0.0, 1.0),
+            visible,
+            pixels,
+        }))
+    }
+
+    /// Read the full-resolution level of a hierarchy; returns interleaved pixels and bpp
+    /// The level must match the layer's `width`x`height`, which compositing indexes by
+    fn read_hierarchy(&mut self, pointer: u64, width: u32, height: u32) -> Result<(Vec<u8>, u32)> {
+        self.cursor.set_position(pointer);
+
+        let _width = self.read_u32()?;
+        let _height = self.read_u32()?;
+        let bpp = self.read_u32()?;
+        if !(1..=4).contains(&bpp) {
+            anyhow::bail!("Unsupported XCF bytes per pixel: {bpp}");
+        }
+
+        // Only the first level is used; the rest are unused legacy mip levels
+        let level_pointer = self.read_pointer()?;
+        self.cursor.set_position(level_pointer);
+
+        let level_width = self.read_u32()?;
+        let level_height = self.read_u32()?;
+        if (level_width, level_height) != (width, height) {
+            anyhow::bail!(
+                "XCF level is {level_width}x{level_height} but its layer is {width}x{height}"
+            );
+        }
+        let mut pixels = vec![0u8; buffer_len(width, height, bpp)?];
+        let tile_pointers = self.read_pointer_list()?;
+
+        let tiles_x = width.div_ceil(TILE_SIZE);
+        let tiles_y = height.div_ceil(TILE_SIZE);
+        if tile_pointers.len() < (tiles_x * tiles_y) as usize {
+            anyhow::bail!("XCF level is missing tiles");
+        }
+
+        for tile_y in 0..tiles_y {
+            for tile_x in 0..tiles_x {
+                let index = (tile_y * tiles_x + tile_x) as usize;
+                let tile_w = (width - tile_x * TILE_SIZE).min(TILE_SIZE);
+                let tile_h = (height - tile_y * TILE_SIZE).min(TILE_SIZE);
+
+                let tile = self.read_tile(tile_pointers[index], tile_w, tile_h, bpp)?;
+
+                for row in 0..tile_h {
+                    let src = (row * tile_w * bpp) as usize;
+                    let dst =
+                        (((tile_y * TILE_SIZE + row) * width + tile_x * TILE_SIZE) * bpp) as usize;
+                    let len = (tile_w * bpp) as usize;
+                    pixels[dst..dst + len].copy_from_slice(&tile[src..src + len]);
+                }
+            }
+        }
+
+        Ok((pixels, bpp))
+    }
+
+    /// Decode one tile into interleaved pixels
+    fn read_tile(&mut self, pointer: u64, tile_w: u32, tile_h: u32, bpp: u32) -> Result<Vec<u8>> {
+        let pixel_count = (tile_w * tile_h) as usize;
+        let data = *self.cursor.get_ref();
+        let start = pointer as usize;
+        if start >= data.len() {
+            anyhow::bail!("XCF tile pointer out of range");
+        }
+        let tile_data = &data[start..];
+
+        match self.compression {
+            0 => {
+                let len = pixel_count * bpp as usize;
+                tile_data
+                    .get(..len)
+                    .map(|d| d.to_vec())
+                    .context("Truncated uncompressed XCF tile")
+            }
+            1 => Self::decode_rle_tile(tile_data, pixel_count, bpp as usize),
+            _ => {
+                let mut tile = vec![0u8; pixel_count * bpp as usize];
+                ZlibDecoder::new(tile_data)
+                    .read_exact(&mut tile)
+                    .context("Failed to inflate XCF tile")?;
+                Ok(tile)
+            }
+        }
+    }
+
+    /// XCF RLE stores each channel as a separate plane; re-interleave while decoding
+    fn decode_rle_tile(data: &[u8], pixel_count: usize, bpp: usize) -> Result<Vec<u8>> {
+        let mut tile = vec![0u8; pixel_count * bpp];
+        let mut pos = 0usize;
+        let next = |pos: &mut usize| -> Result<u8> {
+            let byte = *data.get(*pos).context("Truncated XCF RLE tile")?;
+            *pos += 1;
+            Ok(byte)
+        };
+
+        for channel in 0..bpp {
+            let mut written = 0usize;
+            while written < pixel_count {
+                let opcode = next(&mut pos)? as usize;
+                let (length, repeat) = match opcode {
+                    0..=126 => (opcode + 1, true),
+                    127 => {
+                        let high = next(&mut pos)? as usize;
+                        let low = next(&mut pos)? as usize;
+                        (high * 256 + low, true)
+                    }
+                    128 => {
+                        let high = next(&mut pos)? as usize;
+                        let low = next(&mut pos)? as usize;
+                        (high * 256 + low, false)
+                    }
+                    _ => (256 - opcode, false),
+                };
+
+                if written + length > pixel_count {
+                    anyhow::bail!("XCF RLE run overflows tile");
+                }
+
+                if repeat {
+                    let value = next(&mut pos)?;
+                    for i in 0..length {
+                        tile[(written + i) * bpp + channel] = value;
+                    }
+                } else {
+                    for i in 0..length {
+                        tile[(written + i) * bpp + channel] = next(&mut pos)?;
+                    }
+                }
+                written += length;
+            }
+        }
+
+        Ok(tile)
+    }
+
+    /// Convert a layer's native pixel type to RGBA8
+    fn convert_to_rgba(&self, pixels: &[u8], bpp: u32, layer_type: u32) -> Result<Vec<u8>> {
+        let bpp = bpp as usize;
+        let lookup = |index: u8| -> [u8; 3] {
+            self.colormap
+                .get(index as usize)
+                .copied()
+                .unwrap_or([0, 0, 0])
+        };
+
+        if !matches!(
+            (layer_type, bpp),
+            (0, 3) | (1, 4) | (2, 1) | (3, 2) | (4, 1) | (5, 2)
+        ) {
+            anyhow::bail!("Unsupported XCF layer type {layer_type} with {bpp} bytes per pixel");
+        }
+
+        let rgba = pixels
+            .chunks_exact(bpp)
+            .flat_map(|px| match (layer_type, bpp) {
+                (0, 3) => [px[0], px[1], px[2], 255],   // RGB
+                (1, 4) => [px[0], px[1], px[2], px[3]], // RGBA
+                (2, 1) => [px[0], px[0], px[0], 255],   // Gray
+                (3, 2) => [px[0], px[0], px[0], px[1]], // Gray + alpha
+                (4, 1) => {
+                    let [r, g, b] = lookup(px[0]);
+                    [r, g, b, 255] // Indexed
+                }
+                (5, 2) => {
+                    let [r, g, b] = lookup(px[0]);
+                    [r, g, b, px[1]] // Indexed + alpha
+                }
+                _ => [0, 0, 0, 0],
+            })
+            .collect();
+
+        Ok(rgba)
+    }
+}
+
+/// Length of a `width`x`height` buffer with `channels` values per pixel, computed without
+/// overflow and capped at `MAX_BUFFER_LEN`
+fn buffer_len(width: u32, height: u32, channels: u32) -> Result<usize> {
+    (width as u64)
+        .checked_mul(height as u64)
+        .and_then(|pixels| pixels.checked_mul(channels as u64))
+        .filter(|&len| len <= MAX_BUFFER_LEN)
+        .map(|len| len as usize)
+        .with_context(|| format!("XCF buffer for {width}x{height} pixels is too large"))
+}
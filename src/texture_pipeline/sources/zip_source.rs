@@ -2,8 +2,24 @@ use anyhow::{Context, Result};
 use std::io::{BufReader, Read, SeekFrom};
 use std::path::Path;
 use zip::ZipArchive;
+use zip::read::ZipFile;
+use zip::result::ZipError;
 
-use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, ZipHint};
+use crate::credentials;
+use crate::texture_pipeline::source::read_header_incrementally;
+use crate::texture_pipeline::sources::entry_buffer;
+use crate::texture_pipeline::{BufReadSeek, EmbeddedHint, EmbeddedMetadata, Source, ZipHint, vfs};
+
+/// Archives with more entries than this list their images lazily: names come from the
+/// central directory right away, headers are read when an entry is loaded
+const LAZY_ENTRY_THRESHOLD: usize = 2000;
+
+/// Extensions trusted to be plain images when listing lazily; anything else (nested
+/// archives, compressed streams, unknown files) still has its header probed up front
+const LAZY_IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "webp", "gif", "bmp", "tga", "dds", "ktx", "ktx2", "astc", "pkm", "hdr",
+    "exr", "tif", "tiff", "qoi", "avif",
+];
 
 pub struct ZipSource;
 
@@ -21,7 +37,7 @@ impl Source for ZipSource {
         }
 
         // Try to open as ZIP archive to verify format
-        let file = std::fs::File::open(path)?;
+        let file = vfs::open(path)?;
         let reader = BufReader::new(file);
         match ZipArchive::new(reader) {
             Ok(_) => Ok(true),
@@ -42,16 +58,37 @@ impl Source for ZipSource {
     }
 
     fn extract_metadata(&self, path: &Path) -> Result<Vec<EmbeddedMetadata>> {
-        let file = std::fs::File::open(path).context("Failed to open ZIP file")?;
+        let file = vfs::open(path).context("Failed to open ZIP file")?;
         let reader = BufReader::new(file);
         let mut archive = ZipArchive::new(reader).context("Failed to read ZIP archive")?;
 
+        // Encrypted archives need a remembered password; otherwise ask the UI for one
+        let credential_key = credentials::path_key(path);
+        let password = credentials::password(&credential_key);
+        if password.is_none() && Self::has_encrypted_entries(&mut archive) {
+            credentials::request_password(&credential_key);
+            anyhow::bail!("Password required for encrypted ZIP {}", path.display());
+        }
+
         let mut metadata_list = Vec::new();
+        let lazy = archive.len() > LAZY_ENTRY_THRESHOLD;
+        if lazy {
+            log::info!(
+                "ZIP {} has {} entries, listing images lazily",
+                path.display(),
+                archive.len()
+            );
+        }
 
         // Process entries with header extraction for fast format detection
         for i in 0..archive.len() {
+            if lazy && let Some(metadata) = Self::lazy_entry(&mut archive, i, path) {
+                metadata_list.push(metadata);
+                continue;
+            }
+
             let entry_result = (|| -> Result<Option<EmbeddedMetadata>> {
-                let mut entry = archive.by_index(i)?;
+                let mut entry = Self::open_entry(&mut archive, i, password.as_deref())?;
 
                 // Skip directories early
                 if entry.is_dir() {
@@ -61,10 +98,12 @@ impl Source for ZipSource {
                 let entry_name = entry.name().to_string();
                 let compressed_size = entry.compressed_size();
                 let uncompressed_size = entry.size();
+                let data_offset = entry.data_start();
+                let crc32 = entry.crc32();
 
                 // Extract header bytes incrementally for format detection
                 let header_bytes = if uncompressed_size > 0 {
-                    Self::read_header_incrementally(&mut entry, uncompressed_size as usize)?
+                    read_header_incrementally(&mut entry, uncompressed_size as usize)?
                 } else {
                     None
                 };
@@ -76,7 +115,10 @@ impl Source for ZipSource {
                     entry_index: i,
                     compressed_size,
                     uncompressed_size,
+                    data_offset,
+                    crc32,
                     header_bytes: header_bytes.clone(),
+                    deferred: false,
                 }) as Box<dyn EmbeddedHint>;
 
                 // Skip entries with no content
@@ -96,6 +138,7 @@ impl Source for ZipSource {
                     file_size: uncompressed_size,
                     embedded_hint: hint,
                     source_path: path.to_path_buf(),
+                    parents: Vec::new(),
                 };
 
                 Ok(Some(metadata))
@@ -104,6 +147,12 @@ impl Source for ZipSource {
             match entry_result {
                 Ok(Some(metadata)) => metadata_list.push(metadata),
                 Ok(None) => {} // Skip directories and non-images
+                Err(e) if Self::is_invalid_password(&e) => {
+                    // Wrong password: drop it and prompt again
+                    credentials::forget(&credential_key);
+                    credentials::request_password(&credential_key);
+                    anyhow::bail!("Invalid password for encrypted ZIP {}", path.display());
+                }
                 Err(e) => {
                     log::debug!("Failed to extract metadata from ZIP entry {i}: {e}");
                     // Continue processing other entries even if one fails
@@ -146,75 +195,84 @@ impl Source for ZipSource {
 }
 
 impl ZipSource {
-    /// Read header bytes incrementally until imagesize can determine dimensions
-    /// or we reach a reasonable maximum size
-    fn read_header_incrementally<R: Read>(
-        entry: &mut zip::read::ZipFile<R>,
-        max_size: usize,
-    ) -> Result<Option<Vec<u8>>> {
-        let mut header_size = 128; // Start small - most formats store dimensions early
-        let max_header_size = std::cmp::min(65536, max_size); // Cap at 64KB or file size
-        let mut accumulated_buffer = Vec::new();
-
-        while header_size <= max_header_size {
-            // Calculate how much more we need to read
-            let bytes_to_read = header_size.saturating_sub(accumulated_buffer.len());
-            if bytes_to_read == 0 {
-                break;
-            }
-
-            // Read additional bytes
-            let mut temp_buffer = vec![0u8; bytes_to_read];
-            let bytes_read = entry.read(&mut temp_buffer)?;
-
-            if bytes_read == 0 {
-                // No more data available
-                break;
-            }
-
-            temp_buffer.truncate(bytes_read);
-            accumulated_buffer.extend(temp_buffer);
-
-            // Try to determine image dimensions with current buffer
-            if let Ok(_dimensions) = imagesize::blob_size(&accumulated_buffer) {
-                log::debug!(
-                    "Header size determined with {} bytes (started at {}, max {})",
-                    accumulated_buffer.len(),
-                    128,
-                    max_header_size
-                );
-                return Ok(Some(accumulated_buffer));
-            }
+    /// List an image entry from the central directory alone, without decompressing it
+    /// None for folders, empty entries and anything not named like a plain image
+    fn lazy_entry<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+        index: usize,
+        path: &Path,
+    ) -> Option<EmbeddedMetadata> {
+        let entry = archive.by_index_raw(index).ok()?;
+        let is_image = Path::new(entry.name())
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                LAZY_IMAGE_EXTENSIONS
+                    .iter()
+                    .any(|image_ext| image_ext.eq_ignore_ascii_case(ext))
+            });
+        if entry.is_dir() || entry.size() == 0 || !is_image {
+            return None;
+        }
 
-            // If we've read all available data, stop trying
-            if accumulated_buffer.len() >= max_size {
-                break;
-            }
+        let hint = Box::new(ZipHint {
+            container_path: path.to_path_buf(),
+            entry_name: entry.name().to_string(),
+            entry_index: index,
+            compressed_size: entry.compressed_size(),
+            uncompressed_size: entry.size(),
+            data_offset: entry.data_start(),
+            crc32: entry.crc32(),
+            header_bytes: None,
+            deferred: true,
+        }) as Box<dyn EmbeddedHint>;
+
+        Some(EmbeddedMetadata {
+            name: entry.name().to_string(),
+            format: imagesize::ImageType::Png, // Placeholder - probed when the entry is loaded
+            width: 0,
+            height: 0,
+            file_size: entry.size(),
+            embedded_hint: hint,
+            source_path: path.to_path_buf(),
+            parents: Vec::new(),
+        })
+    }
 
-            // Increase buffer size for next iteration
-            header_size = std::cmp::min(header_size + 1024, max_header_size);
-        }
+    fn has_encrypted_entries<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> bool {
+        (0..archive.len()).any(|i| archive.by_index_raw(i).is_ok_and(|entry| entry.encrypted()))
+    }
 
-        log::debug!(
-            "Header reading completed with {} bytes (imagesize couldn't determine dimensions)",
-            accumulated_buffer.len()
-        );
+    /// Open an entry, decrypting it when a password is known
+    fn open_entry<'a, R: Read + std::io::Seek>(
+        archive: &'a mut ZipArchive<R>,
+        index: usize,
+        password: Option<&str>,
+    ) -> Result<ZipFile<'a, R>> {
+        let entry = match password {
+            Some(password) => archive.by_index_decrypt(index, password.as_bytes())?,
+            None => archive.by_index(index)?,
+        };
+        Ok(entry)
+    }
 
-        // Return whatever we have, even if imagesize couldn't determine dimensions
-        // The pipeline might still be able to process it
-        Ok(Some(accumulated_buffer))
+    fn is_invalid_password(error: &anyhow::Error) -> bool {
+        matches!(
+            error.downcast_ref::<ZipError>(),
+            Some(ZipError::InvalidPassword)
+        )
     }
 
     /// Read a specific entry from the ZIP archive using the hint information
     fn read_zip_entry(&self, hint: &ZipHint) -> Result<Vec<u8>> {
-        let file = std::fs::File::open(&hint.container_path)
-            .context("Failed to open ZIP file for reading entry")?;
+        let file =
+            vfs::open(&hint.container_path).context("Failed to open ZIP file for reading entry")?;
         let reader = BufReader::new(file);
         let mut archive =
             ZipArchive::new(reader).context("Failed to read ZIP archive for entry")?;
 
-        let mut entry = archive
-            .by_index(hint.entry_index)
+        let password = credentials::password(&credentials::path_key(&hint.container_path));
+        let mut entry = Self::open_entry(&mut archive, hint.entry_index, password.as_deref())
             .with_context(|| format!("Failed to find ZIP entry at index {}", hint.entry_index))?;
 
         // Verify entry name matches (safety check)
@@ -227,7 +285,7 @@ impl ZipSource {
         }
 
         // Read the entire entry
-        let mut buffer = Vec::with_capacity(hint.uncompressed_size as usize);
+        let mut buffer = entry_buffer(hint.uncompressed_size);
         entry
             .read_to_end(&mut buffer)
             .with_context(|| format!("Failed to read ZIP entry: {}", hint.entry_name))?;
@@ -0,0 +1,311 @@
+use anyhow::{Context, Result};
+use imagesize::{DdsCompression, ImageType};
+
+/// Tag at the start of every package (and at the end of split .uexp files)
+pub const PACKAGE_FILE_TAG: [u8; 4] = 0x9E2A83C1u32.to_le_bytes();
+
+/// FByteBulkData flags
+const BULKDATA_PAYLOAD_AT_END_OF_FILE: u32 = 0x0001;
+const BULKDATA_UNUSED: u32 = 0x0020;
+const BULKDATA_FORCE_INLINE_PAYLOAD: u32 = 0x0040;
+const BULKDATA_PAYLOAD_IN_SEPARATE_FILE: u32 = 0x0100;
+const BULKDATA_OPTIONAL_PAYLOAD: u32 = 0x0800;
+const BULKDATA_MEMORY_MAPPED_PAYLOAD: u32 = 0x1000;
+const BULKDATA_SIZE_64BIT: u32 = 0x2000;
+const BULKDATA_BAD_DATA_VERSION: u32 = 0x8000;
+
+/// FTexturePlatformData::PackedData bit marking extra mip-tail fields
+const HAS_OPT_DATA_BIT: u32 = 1 << 30;
+
+const MAX_TEXTURE_SIZE: i32 = 16384;
+
+/// Format and size of a cooked UTexture2D's largest available mip
+#[derive(Debug, Clone)]
+pub struct UnrealTextureInfo {
+    pub pixel_format: String, // e.g. PF_DXT5
+    pub image_type: ImageType,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where a mip's payload is stored
+#[derive(Debug, Clone, Copy)]
+enum MipPayload {
+    Package { offset: usize, size: usize }, // Inline, or at the end of the package
+    Bulk { offset: usize, size: usize },    // In the companion .ubulk
+    Missing,                                // Stripped, or in an unsupported .uptnl/.m.ubulk
+}
+
+#[derive(Debug)]
+struct MipRecord {
+    width: u32,
+    height: u32,
+    payload: MipPayload,
+}
+
+/// Whether the data starts like an Unreal package
+pub fn is_package(data: &[u8]) -> bool {
+    data.starts_with(&PACKAGE_FILE_TAG)
+}
+
+/// Format and size of the texture in a cooked package (.uasset with its .uexp appended)
+/// `has_bulk` says whether the companion .ubulk is available
+pub fn texture_info(package: &[u8], has_bulk: bool) -> Result<UnrealTextureInfo> {
+    let (pixel_format, mips) = parse_platform_data(package)?;
+    let mip = mips
+        .iter()
+        .find(|mip| match mip.payload {
+            MipPayload::Package { .. } => true,
+            MipPayload::Bulk { .. } => has_bulk,
+            MipPayload::Missing => false,
+        })
+        .context("No mip with pixel data available")?;
+
+    Ok(UnrealTextureInfo {
+        image_type: image_type(&pixel_format)?,
+        pixel_format,
+        width: mip.width,
+        height: mip.height,
+    })
+}
+
+/// Pixel data of the largest available mip (first slice), ready for `CompressedFormat`
+/// Uncompressed 8-bit formats are converted to the BGRA layout of DDS RGBA32
+pub fn read_texture(package: &[u8], bulk: Option<&[u8]>) -> Result<Vec<u8>> {
+    let (pixel_format, mips) = parse_platform_data(package)?;
+
+    let (mip, data) = mips
+        .iter()
+        .find_map(|mip| {
+            let data = match mip.payload {
+                MipPayload::Package { offset, size } => {
+                    package.get(offset..offset.checked_add(size)?)?
+                }
+                MipPayload::Bulk { offset, size } => {
+                    bulk?.get(offset..offset.checked_add(size)?)?
+                }
+                MipPayload::Missing => return None,
+            };
+            Some((mip, data))
+        })
+        .context("No mip with pixel data available")?;
+
+    let expected = mip_size(&pixel_format, mip.width, mip.height)?;
+    let data = data.get(..expected).with_context(|| {
+        format!(
+            "{pixel_format} mip {}x{} needs {expected} bytes, found {}",
+            mip.width,
+            mip.height,
+            data.len()
+        )
+    })?;
+
+    Ok(match pixel_format.as_str() {
+        "PF_R8G8B8A8" => data
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        "PF_G8" => data.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        _ => data.to_vec(),
+    })
+}
+
+fn image_type(pixel_format: &str) -> Result<ImageType> {
+    Ok(match pixel_format {
+        "PF_DXT1" => ImageType::Dds(DdsCompression::Bc1),
+        "PF_DXT3" => ImageType::Dds(DdsCompression::Bc2),
+        "PF_DXT5" => ImageType::Dds(DdsCompression::Bc3),
+        "PF_BC4" => ImageType::Dds(DdsCompression::Bc4),
+        "PF_BC5" => ImageType::Dds(DdsCompression::Bc5),
+        "PF_BC6H" => ImageType::Dds(DdsCompression::Bc6h),
+        "PF_BC7" => ImageType::Dds(DdsCompression::Bc7),
+        "PF_B8G8R8A8" | "PF_R8G8B8A8" | "PF_G8" => ImageType::Dds(DdsCompression::Rgba32),
+        format if format.starts_with("PF_ASTC_") => ImageType::Astc,
+        format => anyhow::bail!("Unsupported Unreal pixel format {format}"),
+    })
+}
+
+/// Bytes of one slice of a mip in the given pixel format
+fn mip_size(pixel_format: &str, width: u32, height: u32) -> Result<usize> {
+    let (block_width, block_height, block_bytes) = match pixel_format {
+        "PF_DXT1" | "PF_BC4" => (4, 4, 8),
+        "PF_DXT3" | "PF_DXT5" | "PF_BC5" | "PF_BC6H" | "PF_BC7" => (4, 4, 16),
+        "PF_B8G8R8A8" | "PF_R8G8B8A8" => (1, 1, 4),
+        "PF_G8" => (1, 1, 1),
+        format => {
+            // PF_ASTC_6x6, PF_ASTC_8x8_HDR, ...
+            let blocks = format
+                .strip_prefix("PF_ASTC_")
+                .and_then(|rest| rest.split('_').next())
+                .and_then(|size| size.split_once('x'))
+                .and_then(|(x, y)| Some((x.parse::<u32>().ok()?, y.parse::<u32>().ok()?)))
+                .with_context(|| format!("Unsupported Unreal pixel format {format}"))?;
+            (blocks.0, blocks.1, 16)
+        }
+    };
+
+    Ok(width.div_ceil(block_width) as usize * height.div_ceil(block_height) as usize * block_bytes)
+}
+
+/// Find the cooked FTexturePlatformData in the export data and list its mips
+///
+/// Rather than walking the export map, the serialized `SizeX, SizeY, PackedData,
+/// PixelFormat` sequence is located directly; the name table (which also holds
+/// `PF_*` strings) is skipped via the summary's total header size
+fn parse_platform_data(package: &[u8]) -> Result<(String, Vec<MipRecord>)> {
+    if !is_package(package) {
+        anyhow::bail!("Not an Unreal package");
+    }
+
+    let search_start = total_header_size(package).unwrap_or(4).max(16);
+    let (format_start, pixel_format) = (search_start..package.len().saturating_sub(3))
+        .find_map(|pos| platform_data_at(package, pos))
+        .context("No cooked texture data found (not a Texture2D?)")?;
+
+    let packed_data = read_u32(package, format_start - 8)?;
+    let mut pos = format_start + 4 + pixel_format.len() + 1;
+    if packed_data & HAS_OPT_DATA_BIT != 0 {
+        pos += 8; // ExtData, NumMipsInTail
+    }
+
+    let _first_mip_to_serialize = read_i32(package, pos)?;
+    let mip_count = read_i32(package, pos + 4)?;
+    pos += 8;
+    if !(1..=32).contains(&mip_count) {
+        anyhow::bail!("Implausible mip count {mip_count}");
+    }
+
+    let mut mips = Vec::new();
+    for _ in 0..mip_count {
+        let (mip, next) = parse_mip(package, pos)?;
+        mips.push(mip);
+        pos = next;
+    }
+
+    Ok((pixel_format, mips))
+}
+
+/// `SizeX, SizeY, PackedData` followed by a `PF_*` FString at `pos`
+fn platform_data_at(package: &[u8], pos: usize) -> Option<(usize, String)> {
+    if !package[pos..].starts_with(b"PF_") || pos < 16 {
+        return None;
+    }
+
+    let length = read_i32(package, pos - 4).ok()?;
+    if !(4..=48).contains(&length) {
+        return None;
+    }
+    let text = package.get(pos..pos + length as usize)?;
+    let (terminator, name) = text.split_last()?;
+    if *terminator != 0 || !name.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_') {
+        return None;
+    }
+
+    let width = read_i32(package, pos - 16).ok()?;
+    let height = read_i32(package, pos - 12).ok()?;
+    let slices = read_u32(package, pos - 8).ok()? & 0x3FFF_FFFF;
+    let valid_size = |size: i32| (1..=MAX_TEXTURE_SIZE).contains(&size);
+    if !valid_size(width) || !valid_size(height) || !(1..=4096).contains(&slices) {
+        return None;
+    }
+
+    Some((pos - 4, String::from_utf8_lossy(name).into_owned()))
+}
+
+/// FTexture2DMipMap: bCooked, FByteBulkData header (+ inline payload), SizeX/Y/Z
+fn parse_mip(package: &[u8], mut pos: usize) -> Result<(MipRecord, usize)> {
+    let _cooked = read_i32(package, pos)?;
+    let flags = read_u32(package, pos + 4)?;
+    pos += 8;
+
+    let size_on_disk = if flags & BULKDATA_SIZE_64BIT != 0 {
+        let size = read_u64(package, pos + 8)?;
+        pos += 16;
+        size
+    } else {
+        let size = read_i32(package, pos + 4)?.max(0) as u64;
+        pos += 8;
+        size
+    };
+    let offset = read_u64(package, pos)?;
+    pos += 8;
+    if flags & BULKDATA_BAD_DATA_VERSION != 0 {
+        pos += 2;
+    }
+
+    let size = size_on_disk as usize;
+    let offset = offset as usize;
+    let payload = if flags & BULKDATA_FORCE_INLINE_PAYLOAD != 0 {
+        let payload = MipPayload::Package { offset: pos, size };
+        pos = pos.saturating_add(size);
+        payload
+    } else if flags & BULKDATA_UNUSED != 0 || size == 0 {
+        MipPayload::Missing
+    } else if flags & BULKDATA_PAYLOAD_IN_SEPARATE_FILE != 0 {
+        if flags & (BULKDATA_OPTIONAL_PAYLOAD | BULKDATA_MEMORY_MAPPED_PAYLOAD) != 0 {
+            MipPayload::Missing
+        } else {
+            MipPayload::Bulk { offset, size }
+        }
+    } else if flags & BULKDATA_PAYLOAD_AT_END_OF_FILE != 0 {
+        // Cooked packages store this offset already fixed up against the whole package
+        MipPayload::Package { offset, size }
+    } else {
+        MipPayload::Missing
+    };
+
+    let width = read_i32(package, pos)?;
+    let height = read_i32(package, pos + 4)?;
+    pos += 12; // SizeX, SizeY, SizeZ
+    if !(1..=MAX_TEXTURE_SIZE).contains(&width) || !(1..=MAX_TEXTURE_SIZE).contains(&height) {
+        anyhow::bail!("Unrecognized mip layout (size {width}x{height})");
+    }
+
+    Ok((
+        MipRecord {
+            width: width as u32,
+            height: height as u32,
+            payload,
+        },
+        pos,
+    ))
+}
+
+/// FPackageFileSummary::TotalHeaderSize, i.e. where export data starts
+/// Only the optimized custom-version layout (UE 4.14+) is understood
+fn total_header_size(package: &[u8]) -> Option<usize> {
+    let legacy_version = read_i32(package, 4).ok()?;
+    if !(-8..=-6).contains(&legacy_version) {
+        return None;
+    }
+
+    let mut pos = 8 + 4; // LegacyUE3Version
+    pos += 4; // FileVersionUE4
+    if legacy_version <= -8 {
+        pos += 4; // FileVersionUE5
+    }
+    pos += 4; // FileVersionLicenseeUE4
+
+    let custom_version_count = read_i32(package, pos).ok()?;
+    pos += 4 + usize::try_from(custom_version_count).ok()? * 20;
+
+    usize::try_from(read_i32(package, pos).ok()?).ok()
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Result<u32> {
+    let bytes = data
+        .get(pos..pos + 4)
+        .context("Unexpected end of package")?;
+    Ok(u32::from_le_bytes(bytes.try_into()?))
+}
+
+fn read_i32(data: &[u8], pos: usize) -> Result<i32> {
+    Ok(read_u32(data, pos)? as i32)
+}
+
+fn read_u64(data: &[u8], pos: usize) -> Result<u64> {
+    let bytes = data
+        .get(pos..pos + 8)
+        .context("Unexpected end of package")?;
+    Ok(u64::from_le_bytes(bytes.try_into()?))
+}
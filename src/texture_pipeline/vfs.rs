@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Byte stream handed out by mounts
+pub trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// Open file on any mount
+pub struct File(Box<dyn ReadSeek>);
+
+impl File {
+    pub fn new(stream: impl ReadSeek + 'static) -> Self {
+        Self(Box::new(stream))
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+/// A read-only tree of files; paths are relative to the mount point
+pub trait Mount: Send + Sync {
+    fn open(&self, path: &Path) -> io::Result<File>;
+
+    fn file_size(&self, path: &Path) -> io::Result<u64>;
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.file_size(path).is_ok()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.open(path)?.read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    /// Names of the files and folders directly inside `path`
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{} can't be listed", path.display()),
+        ))
+    }
+}
+
+/// The OS filesystem, used for every path outside a mount point
+pub struct OsMount;
+
+impl Mount for OsMount {
+    fn open(&self, path: &Path) -> io::Result<File> {
+        Ok(File::new(std::fs::File::open(path)?))
+    }
+
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        Ok(std::fs::metadata(path)?.len())
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+        std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect()
+    }
+}
+
+/// Buffers served as files, e.g. downloads or data handed over by a host page
+#[derive(Default)]
+pub struct MemoryMount {
+    files: HashMap<PathBuf, Arc<[u8]>>,
+}
+
+impl MemoryMount {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: impl Into<PathBuf>, data: impl Into<Arc<[u8]>>) {
+        self.files.insert(path.into(), data.into());
+    }
+
+    fn get(&self, path: &Path) -> io::Result<&Arc<[u8]>> {
+        self.files.get(path).ok_or_else(|| not_found(path))
+    }
+}
+
+impl Mount for MemoryMount {
+    fn open(&self, path: &Path) -> io::Result<File> {
+        Ok(File::new(Cursor::new(self.get(path)?.clone())))
+    }
+
+    fn file_size(&self, path: &Path) -> io::Result<u64> {
+        Ok(self.get(path)?.len() as u64)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+        Ok(self
+            .files
+            .keys()
+            .filter(|file| file.parent() == Some(path))
+            .filter_map(|file| file.file_name().map(|name| name.to_os_string()))
+            .collect())
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} is not in the mount", path.display()),
+    )
+}
+
+fn mounts() -> &'static RwLock<Vec<(PathBuf, Arc<dyn Mount>)>> {
+    static MOUNTS: OnceLock<RwLock<Vec<(PathBuf, Arc<dyn Mount>)>>> = OnceLock::new();
+    MOUNTS.get_or_init(Default::default)
+}
+
+/// Serve `mount` under `point`, replacing anything mounted there before
+pub fn mount(point: impl Into<PathBuf>, mount: impl Mount + 'static) {
+    let point = point.into();
+    if let Ok(mut mounts) = mounts().write() {
+        mounts.retain(|(existing, _)| *existing != point);
+        log::info!("📂 Mounted {}", point.display());
+        mounts.push((point, Arc::new(mount)));
+    }
+}
+
+/// Points something is mounted at, in mount order
+pub fn mount_points() -> Vec<PathBuf> {
+    mounts()
+        .read()
+        .map(|mounts| mounts.iter().map(|(point, _)| point.clone()).collect())
+        .unwrap_or_default()
+}
+
+pub fn unmount(point: &Path) {
+    if let Ok(mut mounts) = mounts().write() {
+        mounts.retain(|(existing, _)| existing != point);
+        log::info!("📂 Unmounted {}", point.display());
+    }
+}
+
+/// Mount owning `path` and the path relative to it
+/// The lock is released before the mount is used so mounts can read through the VFS
+fn resolve(path: &Path) -> (Arc<dyn Mount>, PathBuf) {
+    let mounted = mounts().read().ok().and_then(|mounts| {
+        mounts
+            .iter()
+            .filter_map(|(point, mount)| {
+                let relative = path.strip_prefix(point).ok()?;
+                Some((
+                    point.components().count(),
+                    mount.clone(),
+                    relative.to_path_buf(),
+                ))
+            })
+            .max_by_key(|(depth, _, _)| *depth)
+    });
+
+    match mounted {
+        Some((_, mount, relative)) => (mount, relative),
+        None => (Arc::new(OsMount), path.to_path_buf()),
+    }
+}
+
+pub fn open(path: impl AsRef<Path>) -> io::Result<File> {
+    let (mount, relative) = resolve(path.as_ref());
+    mount.open(&relative)
+}
+
+pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let (mount, relative) = resolve(path.as_ref());
+    mount.read(&relative)
+}
+
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn file_size(path: impl AsRef<Path>) -> io::Result<u64> {
+    let (mount, relative) = resolve(path.as_ref());
+    mount.file_size(&relative)
+}
+
+pub fn is_file(path: impl AsRef<Path>) -> bool {
+    let (mount, relative) = resolve(path.as_ref());
+    mount.is_file(&relative)
+}
+
+/// Full paths of the entries directly inside `path`
+pub fn read_dir(path: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let path = path.as_ref();
+    let (mount, relative) = resolve(path);
+    Ok(mount
+        .read_dir(&relative)?
+        .into_iter()
+        .map(|name| path.join(name))
+        .collect())
+}
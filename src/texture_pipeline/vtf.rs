@@ -0,0 +1,277 @@
+use anyhow::{Context, Result};
+use imagesize::{DdsCompression, ImageType};
+
+/// Signature at the start of every Valve Texture Format file
+pub const VTF_SIGNATURE: &[u8; 4] = b"VTF\0";
+
+/// Resource tag of the image data in the 7.3+ resource directory
+const RESOURCE_HIGH_RES_IMAGE: [u8; 3] = [0x30, 0, 0];
+
+const TEXTUREFLAGS_ENVMAP: u32 = 0x4000;
+
+const MAX_TEXTURE_SIZE: u32 = 16384;
+
+/// Format, size and layout of a VTF's largest mip
+#[derive(Debug, Clone)]
+pub struct VtfInfo {
+    pub image_format: VtfFormat,
+    pub image_type: ImageType,
+    pub width: u32,
+    pub height: u32,
+    pub frames: u32,
+    mip_count: u32,
+    faces: u32,
+    depth: u32,
+    data_offset: usize, // Start of the high-res image data (smallest mip first)
+}
+
+/// VTF image formats that can be previewed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VtfFormat {
+    Rgba8888,
+    Abgr8888,
+    Rgb888,
+    Bgr888,
+    I8,
+    Ia88,
+    A8,
+    Rgb888Bluescreen,
+    Bgr888Bluescreen,
+    Argb8888,
+    Bgra8888,
+    Dxt1,
+    Dxt3,
+    Dxt5,
+    Bgrx8888,
+    Dxt1OneBitAlpha,
+    Uv88,
+}
+
+impl VtfFormat {
+    fn from_id(id: i32) -> Result<Self> {
+        Ok(match id {
+            0 => Self::Rgba8888,
+            1 => Self::Abgr8888,
+            2 => Self::Rgb888,
+            3 => Self::Bgr888,
+            5 => Self::I8,
+            6 => Self::Ia88,
+            8 => Self::A8,
+            9 => Self::Rgb888Bluescreen,
+            10 => Self::Bgr888Bluescreen,
+            11 => Self::Argb8888,
+            12 => Self::Bgra8888,
+            13 => Self::Dxt1,
+            14 => Self::Dxt3,
+            15 => Self::Dxt5,
+            16 => Self::Bgrx8888,
+            20 => Self::Dxt1OneBitAlpha,
+            22 => Self::Uv88,
+            id => anyhow::bail!("Unsupported VTF image format {id}"),
+        })
+    }
+
+    /// Block width/height and bytes per block
+    fn block(self) -> (u32, u32, usize) {
+        match self {
+            Self::Dxt1 | Self::Dxt1OneBitAlpha => (4, 4, 8),
+            Self::Dxt3 | Self::Dxt5 => (4, 4, 16),
+            Self::I8 | Self::A8 => (1, 1, 1),
+            Self::Ia88 | Self::Uv88 => (1, 1, 2),
+            Self::Rgb888 | Self::Bgr888 | Self::Rgb888Bluescreen | Self::Bgr888Bluescreen => {
+                (1, 1, 3)
+            }
+            _ => (1, 1, 4),
+        }
+    }
+
+    fn image_type(self) -> ImageType {
+        match self {
+            Self::Dxt1 | Self::Dxt1OneBitAlpha => ImageType::Dds(DdsCompression::Bc1),
+            Self::Dxt3 => ImageType::Dds(DdsCompression::Bc2),
+            Self::Dxt5 => ImageType::Dds(DdsCompression::Bc3),
+            _ => ImageType::Dds(DdsCompression::Rgba32),
+        }
+    }
+
+    fn mip_size(self, width: u32, height: u32) -> usize {
+        let (block_width, block_height, block_bytes) = self.block();
+        width.div_ceil(block_width) as usize * height.div_ceil(block_height) as usize * block_bytes
+    }
+}
+
+/// Whether the data starts like a VTF file
+pub fn is_vtf(data: &[u8]) -> bool {
+    data.starts_with(VTF_SIGNATURE)
+}
+
+/// Read the header; `header` only needs to cover the header and resource directory
+pub fn texture_info(header: &[u8]) -> Result<VtfInfo> {
+    if !is_vtf(header) {
+        anyhow::bail!("Not a VTF file");
+    }
+
+    let major = read_u32(header, 4)?;
+    let minor = read_u32(header, 8)?;
+    if major != 7 {
+        anyhow::bail!("Unsupported VTF version {major}.{minor}");
+    }
+
+    let header_size = read_u32(header, 12)? as usize;
+    let width = read_u16(header, 16)? as u32;
+    let height = read_u16(header, 18)? as u32;
+    let flags = read_u32(header, 20)?;
+    let frames = (read_u16(header, 24)? as u32).max(1);
+    let first_frame = read_u16(header, 26)?;
+    let image_format = VtfFormat::from_id(read_i32(header, 52)?)?;
+    let mip_count = (*header.get(56).context("VTF header truncated")? as u32).max(1);
+    let low_res_format = read_i32(header, 57)?;
+    let low_res_width = *header.get(61).context("VTF header truncated")? as u32;
+    let low_res_height = *header.get(62).context("VTF header truncated")? as u32;
+    let depth = if minor >= 2 {
+        (read_u16(header, 63)? as u32).max(1)
+    } else {
+        1
+    };
+
+    if !(1..=MAX_TEXTURE_SIZE).contains(&width) || !(1..=MAX_TEXTURE_SIZE).contains(&height) {
+        anyhow::bail!("Implausible VTF size {width}x{height}");
+    }
+
+    // Before 7.5 environment maps carry a seventh spheremap face
+    let faces = if flags & TEXTUREFLAGS_ENVMAP == 0 {
+        1
+    } else if minor < 5 && first_frame != 0xFFFF {
+        7
+    } else {
+        6
+    };
+
+    let data_offset = if minor >= 3 {
+        let resource_count = read_u32(header, 68)? as usize;
+        (0..resource_count.min(32))
+            .find_map(|i| {
+                let entry = header.get(80 + i * 8..80 + i * 8 + 8)?;
+                (entry[..3] == RESOURCE_HIGH_RES_IMAGE).then(|| read_u32(entry, 4).ok())?
+            })
+            .context("VTF has no high-res image resource")? as usize
+    } else {
+        // The DXT1 low-res thumbnail sits between the header and the image data
+        let low_res_size = if low_res_format == -1 {
+            0
+        } else {
+            VtfFormat::from_id(low_res_format)
+                .map(|format| format.mip_size(low_res_width, low_res_height))
+                .unwrap_or(0)
+        };
+        header_size + low_res_size
+    };
+
+    Ok(VtfInfo {
+        image_format,
+        image_type: image_format.image_type(),
+        width,
+        height,
+        frames,
+        mip_count,
+        faces,
+        depth,
+        data_offset,
+    })
+}
+
+/// Pixel data of the first frame/face/slice of the largest mip, ready for `CompressedFormat`
+/// 8-bit formats are converted to the BGRA layout of DDS RGBA32
+pub fn read_texture(data: &[u8]) -> Result<Vec<u8>> {
+    let info = texture_info(data)?;
+    let format = info.image_format;
+
+    // Mips are stored smallest first; every mip holds frames x faces x slices images
+    let images_per_mip = (info.frames * info.faces * info.depth) as usize;
+    let smaller_mips: usize = (1..info.mip_count)
+        .map(|level| {
+            format.mip_size((info.width >> level).max(1), (info.height >> level).max(1))
+                * images_per_mip
+        })
+        .sum();
+
+    let start = info.data_offset + smaller_mips;
+    let size = format.mip_size(info.width, info.height);
+    let pixels = data.get(start..start + size).with_context(|| {
+        format!(
+            "VTF {format:?} {}x{} needs {size} bytes at {start}, file has {}",
+            info.width,
+            info.height,
+            data.len()
+        )
+    })?;
+
+    let bluescreen = |[b, g, r]: [u8; 3]| {
+        let alpha = if [b, g, r] == [255, 0, 0] { 0 } else { 255 };
+        [b, g, r, alpha]
+    };
+
+    Ok(match format {
+        VtfFormat::Dxt1
+        | VtfFormat::Dxt1OneBitAlpha
+        | VtfFormat::Dxt3
+        | VtfFormat::Dxt5
+        | VtfFormat::Bgra8888 => pixels.to_vec(),
+        VtfFormat::Rgba8888 => pixels
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        VtfFormat::Abgr8888 => pixels
+            .chunks_exact(4)
+            .flat_map(|p| [p[1], p[2], p[3], p[0]])
+            .collect(),
+        VtfFormat::Argb8888 => pixels
+            .chunks_exact(4)
+            .flat_map(|p| [p[3], p[2], p[1], p[0]])
+            .collect(),
+        VtfFormat::Bgrx8888 => pixels
+            .chunks_exact(4)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        VtfFormat::Rgb888 => pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[2], p[1], p[0], 255])
+            .collect(),
+        VtfFormat::Bgr888 => pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        VtfFormat::Rgb888Bluescreen => pixels
+            .chunks_exact(3)
+            .flat_map(|p| bluescreen([p[2], p[1], p[0]]))
+            .collect(),
+        VtfFormat::Bgr888Bluescreen => pixels
+            .chunks_exact(3)
+            .flat_map(|p| bluescreen([p[0], p[1], p[2]]))
+            .collect(),
+        VtfFormat::I8 => pixels.iter().flat_map(|&i| [i, i, i, 255]).collect(),
+        VtfFormat::Ia88 => pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        VtfFormat::A8 => pixels.iter().flat_map(|&a| [255, 255, 255, a]).collect(),
+        VtfFormat::Uv88 => pixels
+            .chunks_exact(2)
+            .flat_map(|p| [0, p[1], p[0], 255])
+            .collect(),
+    })
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Result<u16> {
+    let bytes = data.get(pos..pos + 2).context("VTF header truncated")?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Result<u32> {
+    let bytes = data.get(pos..pos + 4).context("VTF header truncated")?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_i32(data: &[u8], pos: usize) -> Result<i32> {
+    Ok(read_u32(data, pos)? as i32)
+}
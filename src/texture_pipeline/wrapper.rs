@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use crate::texture_pipeline::{EmbeddedHint, EmbeddedMetadata, StreamWrapper, WrappedHint, vfs};
+
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+
+impl StreamWrapper {
+    /// Detect a single-stream compression wrapper from the leading bytes
+    pub fn detect(header: &[u8]) -> Option<Self> {
+        if header.starts_with(ZSTD_MAGIC) {
+            Some(StreamWrapper::Zstd)
+        } else if header.starts_with(GZIP_MAGIC) {
+            Some(StreamWrapper::Gzip)
+        } else {
+            None
+        }
+    }
+
+    /// Detect a wrapper from a file's magic bytes
+    pub fn detect_path(path: &Path) -> Option<Self> {
+        let mut file = vfs::open(path).ok()?;
+        let mut header = [0u8; 4];
+        file.read_exact(&mut header).ok()?;
+        Self::detect(&header)
+    }
+
+    pub fn decode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            StreamWrapper::Zstd => {
+                zstd::stream::decode_all(Cursor::new(data)).context("Failed to decode zstd stream")
+            }
+            StreamWrapper::Gzip => {
+                let mut decoded = Vec::new();
+                flate2::read::MultiGzDecoder::new(data)
+                    .read_to_end(&mut decoded)
+                    .context("Failed to decode gzip stream")?;
+                Ok(decoded)
+            }
+        }
+    }
+
+    /// Name of the wrapped payload, e.g. `texture.png.zst` -> `texture.png`
+    pub fn inner_name(&self, name: &str) -> String {
+        let lower = name.to_lowercase();
+        let (suffix, replacement) = match self {
+            StreamWrapper::Zstd if lower.ends_with(".tzst") => (".tzst", ".tar"),
+            StreamWrapper::Zstd => (".zst", ""),
+            StreamWrapper::Gzip if lower.ends_with(".tgz") => (".tgz", ".tar"),
+            StreamWrapper::Gzip => (".gz", ""),
+        };
+
+        if lower.ends_with(suffix) {
+            format!("{}{}", &name[..name.len() - suffix.len()], replacement)
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+/// Read and decode a wrapped file referenced by a hint
+pub fn load_wrapped(hint: &WrappedHint) -> Result<Vec<u8>> {
+    if let Some(ref inner_data) = hint.inner_data {
+        return Ok(inner_data.clone());
+    }
+
+    let data = vfs::read(&hint.container_path).with_context(|| {
+        format!(
+            "Failed to read wrapped file {}",
+            hint.container_path.display()
+        )
+    })?;
+    hint.wrapper.decode(&data)
+}
+
+/// Metadata for a wrapped payload that is itself a plain image
+/// Nested payloads keep their decoded data since they can't be re-read by path
+pub fn wrapped_image_metadata(
+    wrapper: StreamWrapper,
+    decoded: Vec<u8>,
+    entry_name: &str,
+    source_path: &Path,
+    keep_data: bool,
+) -> Option<EmbeddedMetadata> {
+    let format = imagesize::image_type(&decoded).ok()?;
+    let size = imagesize::blob_size(&decoded).ok()?;
+
+    let hint = Box::new(WrappedHint {
+        container_path: source_path.to_path_buf(),
+        entry_name: entry_name.to_string(),
+        wrapper,
+        inner_size: decoded.len() as u64,
+        inner_data: keep_data.then(|| decoded.clone()),
+    }) as Box<dyn EmbeddedHint>;
+
+    Some(EmbeddedMetadata {
+        name: entry_name.to_string(),
+        format,
+        width: size.width,
+        height: size.height,
+        file_size: decoded.len() as u64,
+        embedded_hint: hint,
+        source_path: source_path.to_path_buf(),
+        parents: Vec::new(),
+    })
+}
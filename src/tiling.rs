@@ -0,0 +1,178 @@
+use macroquad::miniquad::TextureWrap;
+use macroquad::prelude::*;
+
+use crate::texture_pipeline::{EmbeddedHint, TextureSampler, WrapMode};
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Tiles drawn along each axis by the tiling preview, stepped with Ctrl+U
+/// Always odd so the original sits in the middle
+const TILING_REPEAT_STEPS: [u32; 4] = [3, 5, 7, 9];
+
+/// Border colours engines offer for clamp-to-border (the fixed Vulkan/D3D set)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BorderColor {
+    TransparentBlack,
+    OpaqueBlack,
+    OpaqueWhite,
+}
+
+impl BorderColor {
+    pub fn name(self) -> &'static str {
+        match self {
+            BorderColor::TransparentBlack => "transparent black",
+            BorderColor::OpaqueBlack => "opaque black",
+            BorderColor::OpaqueWhite => "opaque white",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            BorderColor::TransparentBlack => BorderColor::OpaqueBlack,
+            BorderColor::OpaqueBlack => BorderColor::OpaqueWhite,
+            BorderColor::OpaqueWhite => BorderColor::TransparentBlack,
+        }
+    }
+
+    pub fn rgba(self) -> Vec4 {
+        match self {
+            BorderColor::TransparentBlack => vec4(0.0, 0.0, 0.0, 0.0),
+            BorderColor::OpaqueBlack => vec4(0.0, 0.0, 0.0, 1.0),
+            BorderColor::OpaqueWhite => vec4(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+/// Per-image sampler preview: wrap modes and border colour, shown as a UV tile grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplerPreview {
+    pub tiling: bool,            // Draw the image as a grid of UV tiles
+    pub repeats: u32,            // Tiles along each axis of the grid
+    pub sampler: TextureSampler, // Wrap modes used by the preview
+    pub border: BorderColor,     // Colour outside 0..1 for clamp-to-border
+    pub asset_sampler: Option<TextureSampler>, // Wrap modes declared by the asset (glTF)
+}
+
+impl SamplerPreview {
+    /// Start from the asset's own sampler, else Repeat like an untagged glTF texture
+    pub fn for_hint(hint: &dyn EmbeddedHint) -> Self {
+        let asset_sampler = hint.sampler();
+        Self {
+            tiling: false,
+            repeats: TILING_REPEAT_STEPS[0],
+            sampler: asset_sampler.unwrap_or(TextureSampler {
+                wrap_s: WrapMode::Repeat,
+                wrap_t: WrapMode::Repeat,
+            }),
+            border: BorderColor::TransparentBlack,
+            asset_sampler,
+        }
+    }
+
+    /// e.g. "Repeat (glTF)", "U Clamp / V Repeat (glTF: Repeat)", "Border, opaque white"
+    pub fn summary(&self) -> String {
+        let describe = |sampler: TextureSampler| {
+            if sampler.wrap_s == sampler.wrap_t {
+                sampler.wrap_s.name().to_string()
+            } else {
+                format!("U {} / V {}", sampler.wrap_s.name(), sampler.wrap_t.name())
+            }
+        };
+
+        let mut summary = describe(self.sampler);
+        if self.uses_border() {
+            summary.push_str(&format!(", {}", self.border.name()));
+        }
+        match self.asset_sampler {
+            Some(asset) if asset == self.sampler => summary.push_str(" (glTF)"),
+            Some(asset) => summary.push_str(&format!(" (glTF: {})", describe(asset))),
+            None => {}
+        }
+        summary
+    }
+
+    /// Tiles between the slot's top-left corner and the original tile
+    pub fn center_offset(&self) -> f32 {
+        (self.repeats - 1) as f32 / 2.0
+    }
+
+    pub fn uses_border(&self) -> bool {
+        self.sampler.wrap_s == WrapMode::ClampToBorder
+            || self.sampler.wrap_t == WrapMode::ClampToBorder
+    }
+}
+
+/// Hardware wrap for a mode; clamp-to-border clamps and the display shader fades to the border
+fn hardware_wrap(mode: WrapMode) -> TextureWrap {
+    match mode {
+        WrapMode::Repeat => TextureWrap::Repeat,
+        WrapMode::MirroredRepeat => TextureWrap::Mirror,
+        WrapMode::ClampToEdge | WrapMode::ClampToBorder => TextureWrap::Clamp,
+    }
+}
+
+/// Apply the preview's wrap modes to a texture, or the viewer's clamp when not tiling
+/// Edge texels blend with the opposite edge under Repeat, so this matters even for one tile
+pub fn set_texture_wrap(texture: &Texture2D, sampler: Option<TextureSampler>) {
+    let (wrap_s, wrap_t) = sampler.map_or((TextureWrap::Clamp, TextureWrap::Clamp), |sampler| {
+        (hardware_wrap(sampler.wrap_s), hardware_wrap(sampler.wrap_t))
+    });
+    let gl = unsafe { get_internal_gl() };
+    gl.quad_context
+        .texture_set_wrap(texture.raw_miniquad_id(), wrap_s, wrap_t);
+}
+
+impl GTexViewerApp {
+    /// U toggles the tiling preview of the image under the cursor,
+    /// Shift+U cycles its wrap mode, Alt+U its border colour, Ctrl+U its repeat count
+    pub fn handle_tiling_input(&mut self) {
+        if !is_key_pressed(KeyCode::U) {
+            return;
+        }
+
+        let mouse_screen = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+        let Some(index) = self.slot_at(mouse_world) else {
+            return;
+        };
+        let ImageState::Loaded { image } = &mut self.image_slots[index].state else {
+            return;
+        };
+
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let preview = &mut image.sampler_preview;
+
+        if ctrl_down {
+            let current = TILING_REPEAT_STEPS
+                .iter()
+                .position(|&repeats| repeats == preview.repeats);
+            preview.repeats = TILING_REPEAT_STEPS
+                [current.map_or(0, |index| (index + 1) % TILING_REPEAT_STEPS.len())];
+            preview.tiling = true;
+        } else if shift_down {
+            let wrap = preview.sampler.wrap_s.next();
+            preview.sampler = TextureSampler {
+                wrap_s: wrap,
+                wrap_t: wrap,
+            };
+            preview.tiling = true;
+        } else if alt_down {
+            preview.border = preview.border.next();
+            preview.tiling = true;
+        } else {
+            preview.tiling = !preview.tiling;
+        }
+
+        log::info!(
+            "🧱 Tiling preview {} for {}: {0}x{0}, {}",
+            if preview.tiling { "on" } else { "off" },
+            image.metadata.name,
+            preview.repeats,
+            preview.summary()
+        );
+    }
+}
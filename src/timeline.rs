@@ -0,0 +1,618 @@
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::sampling::upload_texture;
+use crate::texture_pipeline::animation::DecodedFrame;
+use crate::types::{GTexViewerApp, ImageSlot, ImageState};
+use crate::workspace::Panel;
+
+/// What happens when playback reaches the last frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopMode {
+    Loop,
+    PingPong,
+    Once,
+}
+
+impl LoopMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            LoopMode::Loop => "Loop",
+            LoopMode::PingPong => "Ping-pong",
+            LoopMode::Once => "Once",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            LoopMode::Loop => LoopMode::PingPong,
+            LoopMode::PingPong => LoopMode::Once,
+            LoopMode::Once => LoopMode::Loop,
+        }
+    }
+}
+
+/// How animated images show the change from the previous frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameCompare {
+    Off,
+    OnionSkin,  // Previous frame ghosted under the current one
+    Difference, // Amplified per-pixel difference to the previous frame
+}
+
+impl FrameCompare {
+    pub fn name(self) -> &'static str {
+        match self {
+            FrameCompare::Off => "Off",
+            FrameCompare::OnionSkin => "Onion skin",
+            FrameCompare::Difference => "Difference",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            FrameCompare::Off => FrameCompare::OnionSkin,
+            FrameCompare::OnionSkin => FrameCompare::Difference,
+            FrameCompare::Difference => FrameCompare::Off,
+        }
+    }
+
+    pub fn shader_index(self) -> i32 {
+        match self {
+            FrameCompare::Off => 0,
+            FrameCompare::OnionSkin => 1,
+            FrameCompare::Difference => 2,
+        }
+    }
+}
+
+/// Frames of an animated image plus its playback state
+/// Shared by every animated source so the scrubber works the same for all of them
+#[derive(Clone)]
+pub struct Animation {
+    pub frames: Vec<Texture2D>,
+    pub delays: Vec<Duration>,
+    pub current: usize,
+    pub playing: bool,
+    pub loop_mode: LoopMode,
+    pub fps_override: Option<f32>, // None plays at the per-frame delays from the file
+    forward: bool,
+    elapsed: Duration,
+}
+
+impl Animation {
+    pub fn from_frames(frames: Vec<DecodedFrame>) -> Self {
+        let delays = frames.iter().map(|frame| frame.delay).collect();
+        let frames = frames
+            .iter()
+            .map(|frame| {
+                let texture = upload_texture(&frame.image);
+                texture.set_filter(FilterMode::Linear);
+                texture
+            })
+            .collect();
+
+        Self {
+            frames,
+            delays,
+            current: 0,
+            playing: true,
+            loop_mode: LoopMode::Loop,
+            fps_override: None,
+            forward: true,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn current_texture(&self) -> &Texture2D {
+        &self.frames[self.current]
+    }
+
+    /// Frame before the current one (wrapping), compared against in onion-skin/difference modes
+    pub fn previous_texture(&self) -> &Texture2D {
+        &self.frames[(self.current + self.frame_count() - 1) % self.frame_count()]
+    }
+
+    /// Average frame rate from the file's own delays
+    pub fn native_fps(&self) -> f32 {
+        let total: Duration = self.delays.iter().sum();
+        self.frame_count() as f32 / total.as_secs_f32().max(1e-3)
+    }
+
+    fn frame_delay(&self) -> Duration {
+        match self.fps_override {
+            Some(fps) => Duration::from_secs_f32(1.0 / fps),
+            None => self.delays[self.current],
+        }
+    }
+
+    /// Advance playback by a frame time; returns true when the displayed frame changed
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        if !self.playing {
+            return false;
+        }
+
+        let previous = self.current;
+        self.elapsed += dt;
+
+        while self.playing && self.elapsed >= self.frame_delay() {
+            self.elapsed -= self.frame_delay();
+            self.step_playback();
+        }
+
+        self.current != previous
+    }
+
+    fn step_playback(&mut self) {
+        let last = self.frame_count() - 1;
+        match self.loop_mode {
+            LoopMode::Loop => self.current = (self.current + 1) % self.frame_count(),
+            LoopMode::Once => {
+                if self.current < last {
+                    self.current += 1;
+                } else {
+                    self.playing = false;
+                }
+            }
+            LoopMode::PingPong => {
+                if self.forward && self.current == last {
+                    self.forward = false;
+                } else if !self.forward && self.current == 0 {
+                    self.forward = true;
+                }
+                self.current = if self.forward {
+                    self.current + 1
+                } else {
+                    self.current - 1
+                };
+            }
+        }
+    }
+
+    /// Jump to a frame, restarting its delay
+    pub fn seek(&mut self, frame: usize) {
+        self.current = frame.min(self.frame_count() - 1);
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Step frames relative to the current one, wrapping around
+    pub fn step(&mut self, delta: isize) {
+        let count = self.frame_count() as isize;
+        self.seek((self.current as isize + delta).rem_euclid(count) as usize);
+    }
+
+    pub fn toggle_playing(&mut self) {
+        // Restart a finished one-shot animation from the beginning
+        if !self.playing
+            && self.loop_mode == LoopMode::Once
+            && self.current + 1 == self.frame_count()
+        {
+            self.seek(0);
+        }
+        self.playing = !self.playing;
+    }
+
+    pub fn adjust_fps(&mut self, delta: f32) {
+        let fps = self
+            .fps_override
+            .unwrap_or_else(|| self.native_fps().round());
+        self.fps_override = Some((fps + delta).clamp(1.0, 120.0));
+    }
+}
+
+/// Where an exported frame is written: next to its source, `<name>.frame0001.png`
+pub fn frame_export_path(source_path: &Path, entry_name: &str, frame: usize) -> PathBuf {
+    let stem = Path::new(entry_name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("frame");
+    let directory = if source_path.is_dir() {
+        source_path
+    } else {
+        source_path.parent().unwrap_or(Path::new("."))
+    };
+    directory.join(format!("{stem}.frame{:04}.png", frame + 1))
+}
+
+pub const TIMELINE_HEIGHT: f32 = 44.0;
+
+impl GTexViewerApp {
+    /// Advance every playing animation and swap in its current frame
+    pub fn update_animations(&mut self) {
+        let dt = Duration::from_secs_f32(get_frame_time().min(0.25));
+        let mut any_playing = false;
+
+        for slot in &mut self.image_slots {
+            if let ImageState::Loaded { image } = &mut slot.state
+                && image.expanded_frame.is_none()
+                && let Some(animation) = &mut image.animation
+            {
+                if animation.advance(dt) {
+                    image.texture = animation.current_texture().clone();
+                }
+                any_playing |= animation.playing;
+            }
+        }
+
+        // The event loop blocks between input events, so keep frames coming while playing
+        if any_playing {
+            macroquad::miniquad::window::schedule_update();
+        }
+
+        // The scrubber follows the animated image under the cursor
+        let mouse_screen = mouse_position();
+        let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+        if let Some(index) = self.slot_at(mouse_world)
+            && self.slot_animation(index).is_some()
+        {
+            self.animation_focus = Some(index);
+        } else if self
+            .animation_focus
+            .is_none_or(|index| self.slot_animation(index).is_none())
+        {
+            self.animation_focus =
+                (0..self.image_slots.len()).find(|&index| self.slot_animation(index).is_some());
+        }
+    }
+
+    /// Animation played back in a slot; None for frames of an expanded animation
+    fn slot_animation(&self, index: usize) -> Option<&Animation> {
+        match &self.image_slots.get(index)?.state {
+            ImageState::Loaded { image } if image.expanded_frame.is_none() => {
+                image.animation.as_ref()
+            }
+            _ => None,
+        }
+    }
+
+    /// K toggles between playing animated images and laying out one slot per frame
+    pub fn handle_frame_expansion_input(&mut self) {
+        if !is_key_pressed(KeyCode::K) {
+            return;
+        }
+
+        self.expand_frames = !self.expand_frames;
+        if self.expand_frames {
+            self.expand_animation_frames();
+        } else {
+            self.collapse_animation_frames();
+        }
+        log::info!(
+            "🎞️ Animation frames {}",
+            if self.expand_frames {
+                "expanded into slots"
+            } else {
+                "collapsed"
+            }
+        );
+    }
+
+    /// Replace each animated slot with a paused slot per frame, in frame order,
+    /// so frames can be compared side by side in the grid
+    pub fn expand_animation_frames(&mut self) {
+        let mut expanded_any = false;
+
+        for slot in std::mem::take(&mut self.image_slots) {
+            if let ImageState::Loaded { image } = &slot.state
+                && image.expanded_frame.is_none()
+                && let Some(animation) = &image.animation
+            {
+                for frame in 0..animation.frame_count() {
+                    let mut frame_image = image.clone();
+                    if let Some(frame_animation) = &mut frame_image.animation {
+                        frame_animation.playing = false;
+                        frame_animation.seek(frame);
+                        frame_image.texture = frame_animation.current_texture().clone();
+                    }
+                    frame_image.expanded_frame = Some(frame);
+
+                    self.image_slots.push(ImageSlot {
+                        state: ImageState::Loaded { image: frame_image },
+                        position: slot.position,
+                        size: slot.size,
+                        violations: slot.violations.clone(),
+                        hidden: slot.hidden,
+                    });
+                }
+                expanded_any = true;
+                continue;
+            }
+            self.image_slots.push(slot);
+        }
+
+        if expanded_any {
+            self.reset_slot_indices();
+        }
+    }
+
+    /// Fold expanded frames back into a single playing slot per animation
+    pub fn collapse_animation_frames(&mut self) {
+        let before = self.image_slots.len();
+        self.image_slots.retain(|slot| {
+            !matches!(
+                &slot.state,
+                ImageState::Loaded { image } if image.expanded_frame.is_some_and(|frame| frame > 0)
+            )
+        });
+
+        let mut collapsed_any = self.image_slots.len() != before;
+        for slot in &mut self.image_slots {
+            if let ImageState::Loaded { image } = &mut slot.state
+                && image.expanded_frame.take().is_some()
+                && let Some(animation) = &mut image.animation
+            {
+                animation.seek(0);
+                animation.playing = true;
+                image.texture = animation.current_texture().clone();
+                collapsed_any = true;
+            }
+        }
+
+        if collapsed_any {
+            self.reset_slot_indices();
+        }
+    }
+
+    /// Slot indices held across frames are stale once slots are added or removed
+    pub fn reset_slot_indices(&mut self) {
+        self.animation_focus = None;
+        self.crop_drag = None;
+        self.highlighted_slots.clear();
+        self.selected_slots.clear();
+        self.sync_compare = None;
+        self.focus = None;
+        if self.slideshow.take().is_some() {
+            set_fullscreen(false);
+        }
+        self.layout_needs_update = true;
+        macroquad::miniquad::window::schedule_update();
+    }
+
+    fn focused_animation_mut(&mut self) -> Option<&mut Animation> {
+        let index = self.animation_focus?;
+        match &mut self.image_slots.get_mut(index)?.state {
+            ImageState::Loaded { image } => image.animation.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Re-sync the focused slot's texture after seeking
+    fn show_focused_frame(&mut self) {
+        let Some(index) = self.animation_focus else {
+            return;
+        };
+        if let Some(slot) = self.image_slots.get_mut(index)
+            && let ImageState::Loaded { image } = &mut slot.state
+            && let Some(animation) = &image.animation
+        {
+            image.texture = animation.current_texture().clone();
+        }
+        macroquad::miniquad::window::schedule_update();
+    }
+
+    fn timeline_rect(&self) -> MacroRect {
+        self.panel_rect(Panel::Timeline)
+    }
+
+    fn timeline_track_rect(&self) -> MacroRect {
+        let panel = self.timeline_rect();
+        MacroRect::new(panel.x + 12.0, panel.y + 30.0, panel.w - 24.0, 6.0)
+    }
+
+    /// Scrubber keys and mouse: Space plays/pauses, ←/→ step frames, -/= change FPS,
+    /// O cycles loop modes, D cycles frame compare, X exports the current frame
+    /// Returns true when the mouse is captured
+    pub fn handle_timeline_input(&mut self) -> bool {
+        if self.animation_focus.is_none() {
+            self.timeline_dragging = false;
+            return false;
+        }
+
+        let track = self.timeline_track_rect();
+        let panel = self.timeline_rect();
+        let (mouse_x, mouse_y) = mouse_position();
+        let mouse_in_panel =
+            self.panel_shown(Panel::Timeline) && panel.contains(vec2(mouse_x, mouse_y));
+
+        if is_mouse_button_pressed(MouseButton::Left) && mouse_in_panel {
+            self.timeline_dragging = true;
+        }
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.timeline_dragging = false;
+        }
+
+        // Ctrl+arrows adjust exposure/gamma instead
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let mut frame_changed = false;
+        if let Some(animation) = self.focused_animation_mut() {
+            if is_key_pressed(KeyCode::Space) {
+                animation.toggle_playing();
+            }
+            if is_key_pressed(KeyCode::Right) && !ctrl_down {
+                animation.playing = false;
+                animation.step(1);
+                frame_changed = true;
+            }
+            if is_key_pressed(KeyCode::Left) && !ctrl_down {
+                animation.playing = false;
+                animation.step(-1);
+                frame_changed = true;
+            }
+            if is_key_pressed(KeyCode::Equal) {
+                animation.adjust_fps(1.0);
+            }
+            if is_key_pressed(KeyCode::Minus) {
+                animation.adjust_fps(-1.0);
+            }
+            if is_key_pressed(KeyCode::O) {
+                animation.loop_mode = animation.loop_mode.next();
+                log::info!("🎞️ Loop mode: {}", animation.loop_mode.name());
+            }
+        }
+
+        if self.timeline_dragging
+            && let Some(animation) = self.focused_animation_mut()
+        {
+            let t = ((mouse_x - track.x) / track.w).clamp(0.0, 1.0);
+            let frame = (t * (animation.frame_count() - 1) as f32).round() as usize;
+            if frame != animation.current {
+                animation.playing = false;
+                animation.seek(frame);
+                frame_changed = true;
+            }
+        }
+
+        if frame_changed {
+            self.show_focused_frame();
+        }
+
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if is_key_pressed(KeyCode::D) && !alt_down && !shift_down && !ctrl_down {
+            self.frame_compare = self.frame_compare.next();
+            log::info!("🎞️ Frame compare: {}", self.frame_compare.name());
+            macroquad::miniquad::window::schedule_update();
+        }
+
+        if is_key_pressed(KeyCode::X) {
+            self.export_current_frame();
+        }
+
+        // Keep the scrubber from leaking clicks into camera panning
+        self.timeline_dragging || (mouse_in_panel && is_mouse_button_down(MouseButton::Left))
+    }
+
+    /// Save the focused animation's current frame as a PNG next to its source
+    pub fn export_current_frame(&self) {
+        let Some(index) = self.animation_focus else {
+            return;
+        };
+        let Some(ImageState::Loaded { image }) =
+            self.image_slots.get(index).map(|slot| &slot.state)
+        else {
+            return;
+        };
+        let Some(ref animation) = image.animation else {
+            return;
+        };
+
+        let path = frame_export_path(
+            &image.metadata.source_path,
+            &image.metadata.name,
+            animation.current,
+        );
+        animation
+            .current_texture()
+            .get_texture_data()
+            .export_png(&path.to_string_lossy());
+        log::info!(
+            "🎞️ Exported frame {} to {}",
+            animation.current + 1,
+            path.display()
+        );
+    }
+
+    pub fn draw_timeline(&self) {
+        if !self.panel_shown(Panel::Timeline) {
+            return;
+        }
+        let Some(animation) = self
+            .animation_focus
+            .and_then(|index| self.slot_animation(index))
+        else {
+            return;
+        };
+
+        let panel = self.timeline_rect();
+        let track = self.timeline_track_rect();
+
+        draw_rectangle(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            Color::new(0.1, 0.1, 0.1, 0.9),
+        );
+        draw_rectangle_lines(
+            panel.x,
+            panel.y,
+            panel.w,
+            panel.h,
+            2.0,
+            Color::new(0.3, 0.3, 0.3, 0.9),
+        );
+
+        let fps = match animation.fps_override {
+            Some(fps) => format!("{fps:.0} fps"),
+            None => format!("{:.1} fps (native)", animation.native_fps()),
+        };
+        let mut status = format!(
+            "{}  Frame {}/{}  {}  {}",
+            if animation.playing {
+                "Playing"
+            } else {
+                "Paused"
+            },
+            animation.current + 1,
+            animation.frame_count(),
+            fps,
+            animation.loop_mode.name()
+        );
+        if self.frame_compare != FrameCompare::Off {
+            status.push_str(&format!("  Compare: {}", self.frame_compare.name()));
+        }
+        draw_text_ex(
+            &status,
+            panel.x + 12.0,
+            panel.y + 18.0,
+            TextParams {
+                font: self.ui_font.as_ref(),
+                font_size: 14,
+                color: WHITE,
+                ..Default::default()
+            },
+        );
+
+        // Track with a tick per frame when they fit, and the playhead
+        draw_rectangle(
+            track.x,
+            track.y,
+            track.w,
+            track.h,
+            Color::new(0.25, 0.25, 0.25, 1.0),
+        );
+        let last = (animation.frame_count() - 1).max(1) as f32;
+        if track.w / last >= 4.0 {
+            for frame in 0..animation.frame_count() {
+                let x = track.x + track.w * frame as f32 / last;
+                draw_line(
+                    x,
+                    track.y - 2.0,
+                    x,
+                    track.y,
+                    1.0,
+                    Color::new(0.5, 0.5, 0.5, 1.0),
+                );
+            }
+        }
+
+        let playhead_x = track.x + track.w * animation.current as f32 / last;
+        draw_rectangle(
+            track.x,
+            track.y,
+            playhead_x - track.x,
+            track.h,
+            Color::new(0.3, 0.6, 1.0, 1.0),
+        );
+        draw_circle(playhead_x, track.y + track.h / 2.0, 6.0, WHITE);
+    }
+}
@@ -0,0 +1,102 @@
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+use crate::texture_pipeline::EmbeddedMetadata;
+use crate::texture_pipeline::parsers::TranscodeTarget;
+use crate::types::{GTexViewerApp, ImageState};
+
+/// Basis transcode target for every KTX2, with per-image overrides keyed by entry
+#[derive(Debug, Clone, Default)]
+pub struct TranscodeSettings {
+    pub default: TranscodeTarget,
+    pub overrides: HashMap<String, TranscodeTarget>,
+}
+
+impl TranscodeSettings {
+    pub fn from_env() -> Self {
+        Self {
+            default: TranscodeTarget::from_env(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn target_for(&self, entry_key: &str) -> TranscodeTarget {
+        self.overrides
+            .get(entry_key)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+impl GTexViewerApp {
+    /// Q cycles the transcode target of every KTX2, Shift+Q only the one under the cursor
+    pub fn handle_transcode_input(&mut self) {
+        if !is_key_pressed(KeyCode::Q) {
+            return;
+        }
+
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        if shift_down {
+            let mouse_screen = mouse_position();
+            let mouse_world = self.screen_to_world(vec2(mouse_screen.0, mouse_screen.1));
+            let Some(metadata) = self
+                .slot_at(mouse_world)
+                .and_then(|index| ktx2_metadata(&self.image_slots[index].state))
+            else {
+                return;
+            };
+
+            let key = metadata.entry_key();
+            let settings = &mut self.async_loader.transcode;
+            let target = settings.target_for(&key).next();
+            settings.overrides.insert(key.clone(), target);
+            log::info!(
+                "🗜️ Transcode target for {}: {}",
+                metadata.name,
+                target.name()
+            );
+            self.reload_ktx2_slots(|slot_key| slot_key == key);
+        } else {
+            let settings = &mut self.async_loader.transcode;
+            settings.default = settings.default.next();
+            settings.overrides.clear();
+            log::info!("🗜️ Transcode target: {}", settings.default.name());
+            self.reload_ktx2_slots(|_| true);
+        }
+    }
+
+    /// Decode loaded KTX2 slots again so a new transcode target takes effect
+    fn reload_ktx2_slots(&mut self, matches: impl Fn(&str) -> bool) {
+        let mut reload = Vec::new();
+
+        for slot in &mut self.image_slots {
+            let Some(metadata) = ktx2_metadata(&slot.state) else {
+                continue;
+            };
+            if !matches(&metadata.entry_key()) {
+                continue;
+            }
+
+            slot.state = ImageState::Placeholder {
+                original_metadata: metadata.clone(),
+                layout_metadata: Self::adjust_metadata_for_layout(&metadata),
+            };
+            reload.push(metadata);
+        }
+
+        if !reload.is_empty() {
+            self.async_loader.start_loading_batch(reload, &self.workers);
+            self.start_burst_rendering(std::time::Duration::from_millis(500));
+        }
+    }
+}
+
+/// Metadata of a loaded KTX2 slot
+fn ktx2_metadata(state: &ImageState) -> Option<EmbeddedMetadata> {
+    match state {
+        ImageState::Loaded { image } if image.metadata.format == imagesize::ImageType::Ktx2 => {
+            Some(image.metadata.clone())
+        }
+        _ => None,
+    }
+}
@@ -1,5 +1,6 @@
 use macroquad::math::Rect as MacroRect;
 use macroquad::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -7,8 +8,40 @@ use std::sync::mpsc;
 use std::time::Instant;
 use taffy::prelude::*;
 
-use crate::loading::{AsyncImageLoader, LoadedImage};
+use crate::analysis::{StreamingConfig, UnpremultiplyMode};
+use crate::color_profile::MonitorProfile;
+use crate::color_vision::ColorVision;
+use crate::credentials::PasswordPrompt;
+use crate::cubemap_view::CubemapLayout;
+use crate::display_transform::DisplayView;
+use crate::export::CropDrag;
+use crate::exposure::ExposureGamma;
+use crate::eyedropper::Eyedropper;
+use crate::false_color::FalseColor;
+use crate::focus_mode::FocusMode;
+use crate::gamut::GamutOverlay;
+use crate::guides::GuideOverlay;
+use crate::jump_palette::JumpPalette;
+use crate::levels::{LevelsCurve, LevelsHandle};
+use crate::lut::ColorLut;
+use crate::loading::{
+    AsyncImageLoader, ContainerWatcher, DirectoryScan, DroppedRoot, LoadedImage, WorkerTracker,
+};
+use crate::pixel_diff::{DiffResult, PixelDiff};
+use crate::pixel_inspector::PixelProbe;
+use crate::sampling::SamplingSettings;
+use crate::slot_filter::SlotFilter;
+use crate::slot_groups::SlotGroup;
+use crate::slideshow::Slideshow;
+use crate::slot_sort::SortMode;
+use crate::surface_preview::SurfacePreview;
+use crate::tabs::Session;
+use crate::sync_compare::SyncCompare;
+use crate::texture_cache::TextureCache;
 use crate::texture_pipeline::EmbeddedMetadata;
+use crate::timeline::FrameCompare;
+use crate::validation::{ValidationRules, Violation};
+use crate::workspace::WorkspaceLayout;
 
 #[derive(Clone)]
 pub struct ImageContext {
@@ -16,20 +49,85 @@ pub struct ImageContext {
     pub height: f32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum ChannelMode {
-    Normal, // RGBA
-    Red,    // Red channel only
-    Green,  // Green channel only
-    Blue,   // Blue channel only
-    Alpha,  // Alpha channel only
-    SwapRG, // Swap red and green channels
-    SwapRB, // Swap red and blue channels
-    SwapGB, // Swap green and blue channels
+    #[default]
+    Normal,    // RGBA
+    Red,       // Red channel only
+    Green,     // Green channel only
+    Blue,      // Blue channel only
+    Alpha,     // Alpha channel only
+    SwapRG,    // Swap red and green channels
+    SwapRB,    // Swap red and blue channels
+    SwapGB,    // Swap green and blue channels
+    Luminance, // Rec.709 weighted luminance
+    Invert,    // Inverted colour, alpha kept
+    Quadrants, // R, G, B and A side by side in a 2x2 split
+}
+
+impl ChannelMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            ChannelMode::Normal => "RGBA",
+            ChannelMode::Red => "Red",
+            ChannelMode::Green => "Green",
+            ChannelMode::Blue => "Blue",
+            ChannelMode::Alpha => "Alpha",
+            ChannelMode::SwapRG => "Swap R↔G",
+            ChannelMode::SwapRB => "Swap R↔B",
+            ChannelMode::SwapGB => "Swap G↔B",
+            ChannelMode::Luminance => "Luminance",
+            ChannelMode::Invert => "Inverted",
+            ChannelMode::Quadrants => "R|G/B|A",
+        }
+    }
+}
+
+/// Environment variable selecting the startup texture filter (auto, nearest or linear)
+pub const TEXTURE_FILTER_ENV: &str = "GTEXVIEWER_FILTER";
+
+/// Texture filtering applied to every image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterOverride {
+    Auto,    // Nearest at 0.5x zoom and above, Linear below
+    Nearest, // Always crisp pixels, e.g. for pixel art
+    Linear,  // Always smooth, e.g. for photos
+}
+
+impl FilterOverride {
+    /// Startup filter from `$GTEXVIEWER_FILTER`, Auto when unset or unknown
+    pub fn from_env() -> Self {
+        match std::env::var(TEXTURE_FILTER_ENV)
+            .map(|value| value.to_lowercase())
+            .as_deref()
+        {
+            Ok("nearest") => FilterOverride::Nearest,
+            Ok("linear") => FilterOverride::Linear,
+            _ => FilterOverride::Auto,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            FilterOverride::Auto => FilterOverride::Nearest,
+            FilterOverride::Nearest => FilterOverride::Linear,
+            FilterOverride::Linear => FilterOverride::Auto,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FilterOverride::Auto => "Auto",
+            FilterOverride::Nearest => "Nearest",
+            FilterOverride::Linear => "Linear",
+        }
+    }
 }
 
 pub struct GTexViewerApp {
     pub image_slots: Vec<ImageSlot>,
+    pub tabs: Vec<Session>,           // Every tab; the active one's state is in the fields here
+    pub active_tab: usize,            // Index of the tab shown
     pub initial_file_path: Option<PathBuf>,
     pub metadata_receivers: Vec<mpsc::Receiver<MetadataResult>>,
     pub async_loader: AsyncImageLoader,
@@ -42,12 +140,71 @@ pub struct GTexViewerApp {
     pub taffy_tree: TaffyTree<ImageContext>, // Layout engine
     pub channel_switch_material: Option<Material>, // Custom shader for RGBA channel switching
     pub channel_mode: ChannelMode,    // Current channel display mode
+    pub false_color: FalseColor,      // Colour map for single-channel modes
+    pub filter_override: FilterOverride, // Global Nearest/Linear override of the zoom heuristic
     pub hovered_image_info: Option<HoveredImageInfo>, // Info for image under mouse cursor
     pub ui_text_queue: Vec<UiText>,   // Queue UI text to minimize camera switches
     pub pending_metadata: Vec<EmbeddedMetadata>, // Store metadata until all arrive
     pub burst_render_until: Option<Instant>, // Force continuous rendering until this time
     pub ui_font: Option<Font>,        // Custom UI font
     pub metadata_cancel_flag: Arc<AtomicBool>, // Cancellation flag for metadata extraction
+    pub workers: WorkerTracker,       // Running scan/metadata/decode workers, awaited on shutdown
+    pub alpha_cutoff: f32,            // Alpha-test cutoff for mip coverage analysis
+    pub unpremultiply: UnpremultiplyMode, // Which premultiplied images are divided by alpha for display
+    pub levels: LevelsCurve,          // Display-only levels/curve adjustment
+    pub exposure_gamma: ExposureGamma, // Display-only exposure and gamma
+    pub color_lut: Option<ColorLut>, // Show LUT from a dropped .cube file or $GTEXVIEWER_LUT
+    pub levels_drag: Option<LevelsHandle>, // Levels handle currently being dragged
+    pub sampling: SamplingSettings,   // Anisotropy and LOD bias for the engine sampling preview
+    pub sampling_drag: bool,          // Whether the LOD bias slider is being dragged
+    pub minimap_dragging: bool,       // Whether the view is being dragged around the minimap
+    pub display_views: Vec<DisplayView>, // Selectable display/view transforms
+    pub display_view_index: usize,    // Index of the active display view
+    pub color_managed: bool, // Filter and blend in linear light; off shows stored values as-is
+    pub monitor_profile: Option<MonitorProfile>, // OS monitor ICC profile, if found
+    pub monitor_profile_enabled: bool, // Whether the monitor profile is applied
+    pub gamut_overlay: GamutOverlay,  // Out-of-gamut highlight settings
+    pub guides: GuideOverlay,         // Thirds/centre/grid guides over the hovered image
+    pub color_vision: ColorVision,    // Simulated colour vision deficiency
+    pub surface_preview: SurfacePreview, // Normal map lighting preview and its light direction
+    pub validation_rules: Option<ValidationRules>, // Asset lint rules, if a rules file was found
+    pub streaming_config: StreamingConfig, // Target engine streaming setup for budget estimates
+    pub directory_scan: Option<DirectoryScan>, // Background file discovery for dropped folders
+    pub container_watcher: Option<ContainerWatcher>, // Re-scans loaded containers edited on disk
+    pub dropped_roots: Vec<DroppedRoot>, // Paths dropped into the current session
+    pub highlighted_slots: Vec<usize>, // Slots flashed after a duplicate drop
+    pub highlight_until: Option<Instant>, // When the duplicate-drop highlight ends
+    pub selected_slots: Vec<usize>,   // Ctrl+clicked slots, in click order
+    pub focus: Option<FocusMode>,     // Double-clicked slot shown alone
+    pub last_click: Option<(Instant, Vec2)>, // Time and screen position of the last click, for double-clicks
+    pub slideshow: Option<Slideshow>, // Fullscreen one-at-a-time presentation
+    pub pixel_diff: Option<PixelDiff>, // Open |A−B| view of two selected images
+    pub pixel_diff_pending: Option<mpsc::Receiver<Result<DiffResult, String>>>, // Diff being computed
+    pub sync_compare: Option<SyncCompare>, // Side-by-side panes sharing zoom and pan
+    pub password_prompt: Option<PasswordPrompt>, // Password entry for an encrypted container
+    pub animation_focus: Option<usize>, // Animated slot controlled by the timeline scrubber
+    pub timeline_dragging: bool,      // Whether the scrubber playhead is being dragged
+    pub frame_compare: FrameCompare,  // Onion-skin/difference view for animated images
+    pub expand_frames: bool,          // Animated images shown as one slot per frame
+    pub cubemap_layout: CubemapLayout, // Single face, cross or equirectangular for cubemaps
+    pub offset_map_container: Option<PathBuf>, // Container shown in the offset map
+    pub histogram_target: Option<String>, // Entry key of the image shown in the histogram
+    pub details_target: Option<String>, // Entry key of the image shown in the details sidebar
+    pub breadcrumb_target: Option<String>, // Entry key of the image whose container path is shown
+    pub pixel_probe: PixelProbe, // CPU pixels of the hovered image for the pixel inspector
+    pub eyedropper: Eyedropper,  // Shift+I colour picker and the last copied colour
+    pub jump_palette: Option<JumpPalette>, // Ctrl+P "jump to texture" search
+    pub slot_filter: SlotFilter,      // `/` filter box narrowing the grid by name
+    pub sort_mode: SortMode,          // Order of slots in the grid
+    pub show_captions: bool,          // File names drawn under the thumbnails
+    pub slot_groups: Vec<SlotGroup>,  // Per-source header rows of the last layout, empty when flat
+    pub collapsed_groups: HashSet<String>, // Keys of groups shown as a stacked card
+    pub group_press: Option<(String, Vec2)>, // Group header pressed and where, until release
+    pub crop_drag: Option<CropDrag>,  // Shift+drag region being selected for crop export
+    pub workspace: WorkspaceLayout,   // Panel visibility, docking and background, persisted per machine
+    pub texture_cache: TextureCache,  // VRAM budget and least-recently-visible eviction
+    pub full_resolution_pending: HashSet<String>, // Proxied images being decoded at full resolution
+    pub placeholder_previews: HashMap<String, Texture2D>, // Coarse previews of images still decoding
 }
 
 // Implement Drop to clean up resources when the app is destroyed
@@ -104,6 +261,8 @@ pub struct ImageSlot {
     pub state: ImageState,
     pub position: Vec2,
     pub size: Vec2,
+    pub violations: Vec<Violation>, // Validation rule failures for the loaded image
+    pub hidden: bool,               // Filtered out by the `/` filter box
 }
 
 #[derive(Clone)]
@@ -112,7 +271,14 @@ pub struct HoveredImageInfo {
     pub dimensions: String,
     pub file_size: String,
     pub color_space: String,
-    pub mouse_pos: Vec2, // Screen position for tooltip placement
+    pub alpha_coverage: Option<String>, // Alpha-test coverage summary down the mip chain
+    pub premultiplied: Option<String>,  // Premultiplied alpha and how it was detected
+    pub pixel: Option<String>,          // Coordinate and exact value of the texel under the cursor
+    pub sampler: Option<String>,        // Wrap modes, when declared by the asset or previewed
+    pub violations: Vec<String>,        // Validation rule failures
+    pub streaming_cost: Option<String>, // Estimated streaming memory/disk cost
+    pub container_streaming_cost: Option<String>, // Same, summed over the texture's container
+    pub mouse_pos: Vec2,                // Screen position for tooltip placement
 }
 
 #[derive(Debug, Clone)]
@@ -1,6 +1,7 @@
 use macroquad::prelude::*;
 
-use crate::types::{ChannelMode, GTexViewerApp, HoveredImageInfo, ImageState};
+use crate::status_bar::STATUS_BAR_HEIGHT;
+use crate::types::{GTexViewerApp, HoveredImageInfo, ImageState};
 
 impl GTexViewerApp {
     pub fn draw_ui(&mut self) {
@@ -35,7 +36,7 @@ impl GTexViewerApp {
             draw_text_ex(main_text, main_text_x, main_text_y, main_text_params);
 
             // Draw supported formats info
-            let formats_text = "Supports: PNG, JPEG, WebP, BMP, TIFF, GIF, FF, EXR, HDR, ICO, QOI, TGA, PNM, AVIF, KTX2, GLB/GLTF, FBX";
+            let formats_text = "Supports: PNG, JPEG, WebP, BMP, TIFF, GIF, FF, EXR, HDR, ICO, QOI, TGA, PNM, AVIF, KTX2, GLB/GLTF, FBX, OBJ, PMX/PMD, 3DS, USDZ, APK, WAD, PK3, XCF, PDF, UASSET, ATLAS, ZIP, TAR, 7Z, ISO, PAK, VPK, ZST, GZ";
             let formats_text_size = 16.0;
             let formats_text_params = TextParams {
                 font: self.ui_font.as_ref(),
@@ -83,59 +84,71 @@ impl GTexViewerApp {
             );
         }
 
-        // Draw UI overlay with image count and zoom info if images are loaded
-        if !self.image_slots.is_empty() {
-            let loaded_count = self
-                .image_slots
-                .iter()
-                .filter(|slot| matches!(slot.state, ImageState::Loaded { .. }))
-                .count();
-            let total_count = self.image_slots.len();
-
-            let channel_mode_str = match self.channel_mode {
-                ChannelMode::Normal => "RGBA",
-                ChannelMode::Red => "Red",
-                ChannelMode::Green => "Green",
-                ChannelMode::Blue => "Blue",
-                ChannelMode::Alpha => "Alpha",
-                ChannelMode::SwapRG => "Swap R↔G",
-                ChannelMode::SwapRB => "Swap R↔B",
-                ChannelMode::SwapGB => "Swap G↔B",
-            };
+        // Draw the tab strip and the hovered image's container path along the top
+        self.draw_tabs();
+        self.draw_breadcrumb();
 
-            let info_text = format!(
-                "Images: {}/{} | Zoom: {:.1}x | Mode: {}",
-                loaded_count, total_count, self.camera.zoom.x, channel_mode_str
-            );
-            let info_text_size = 16.0;
+        // Draw the minimap while zoomed in past the content
+        self.draw_minimap();
 
-            // Draw semi-transparent background for text
-            let text_dims = measure_text(
-                &info_text,
-                self.ui_font.as_ref(),
-                info_text_size as u16,
-                1.0,
-            );
-            let info_text_params = TextParams {
-                font: self.ui_font.as_ref(),
-                font_size: info_text_size as u16,
-                color: WHITE,
-                ..Default::default()
-            };
-            draw_rectangle(
-                5.0,
-                5.0,
-                text_dims.width + 10.0,
-                25.0,
-                Color::new(0.0, 0.0, 0.0, 0.7),
-            );
-            draw_text_ex(&info_text, 10.0, 22.0, info_text_params);
-        }
+        // Draw file names under the thumbnails
+        self.draw_captions();
+
+        // Draw a header above each source's row when several were dropped
+        self.draw_group_headers();
+
+        // Draw alignment guides over the hovered image
+        self.draw_guides();
+
+        // Draw crop selection rectangle
+        self.draw_crop_selection();
+
+        // Draw container byte-layout bar
+        self.draw_offset_map();
+
+        // Draw file-discovery progress while scanning dropped folders
+        self.draw_scan_progress();
+
+        // Draw validation badges over failing images
+        self.draw_validation_badges();
+
+        // Draw levels/curve widget
+        self.draw_levels_widget();
+
+        // Draw engine sampling preview controls
+        self.draw_sampling_widget();
+
+        // Draw timeline scrubber for animated images
+        self.draw_timeline();
+
+        // Draw RGB(A) histogram of the hovered image
+        self.draw_histogram();
+
+        // Draw full details of the last hovered image
+        self.draw_details();
+
+        // Draw colour picker swatches next to the cursor
+        self.draw_eyedropper();
 
         // Draw hover image info panel
         if let Some(ref hover_info) = self.hovered_image_info {
             self.draw_hover_info_panel(hover_info);
         }
+
+        // Draw the |A−B| view of two selected images over the canvas
+        self.draw_pixel_diff();
+
+        // Draw cursor readouts and display state along the bottom
+        self.draw_status_bar();
+
+        // Draw the filter box while typing or filtering
+        self.draw_slot_filter();
+
+        // Draw jump-to-texture palette
+        self.draw_jump_palette();
+
+        // Draw password prompt above everything else
+        self.draw_password_prompt();
     }
 
     pub fn draw_hover_info_panel(&self, hover_info: &HoveredImageInfo) {
@@ -144,12 +157,33 @@ impl GTexViewerApp {
         let text_size = 14.0;
 
         // Prepare info lines
-        let info_lines = [
+        let mut info_lines = vec![
             format!("File: {}", hover_info.file_name),
             format!("Size: {}", hover_info.dimensions),
             format!("Color: {}", hover_info.color_space),
             format!("File Size: {}", hover_info.file_size),
         ];
+        if let Some(ref coverage) = hover_info.alpha_coverage {
+            info_lines.push(format!("Alpha Coverage: {coverage}"));
+        }
+        if let Some(ref premultiplied) = hover_info.premultiplied {
+            info_lines.push(format!("Alpha: {premultiplied}"));
+        }
+        if let Some(ref pixel) = hover_info.pixel {
+            info_lines.push(format!("Pixel: {pixel}"));
+        }
+        if let Some(ref sampler) = hover_info.sampler {
+            info_lines.push(format!("Sampler: {sampler}"));
+        }
+        if let Some(ref cost) = hover_info.streaming_cost {
+            info_lines.push(format!("Streaming: {cost}"));
+        }
+        if let Some(ref cost) = hover_info.container_streaming_cost {
+            info_lines.push(format!("Container: {cost}"));
+        }
+        for violation in &hover_info.violations {
+            info_lines.push(format!("Violation: {violation}"));
+        }
 
         // Calculate panel dimensions
         let max_text_width = info_lines
@@ -168,7 +202,7 @@ impl GTexViewerApp {
         if panel_x + panel_width > screen_width() {
             panel_x = hover_info.mouse_pos.x - panel_width - 15.0;
         }
-        if panel_y + panel_height > screen_height() {
+        if panel_y + panel_height > screen_height() - STATUS_BAR_HEIGHT {
             panel_y = hover_info.mouse_pos.y - panel_height - 15.0;
         }
 
@@ -219,7 +253,12 @@ impl GTexViewerApp {
         // Find which image (if any) is under the mouse cursor
         self.hovered_image_info = None;
 
-        for slot in self.image_slots.iter() {
+        // Exact texel value, once the hovered image's pixels are on the CPU
+        let pixel = self
+            .slot_at(mouse_world)
+            .and_then(|index| self.probe_pixel(index, mouse_world));
+
+        for slot in self.image_slots.iter().filter(|slot| !slot.hidden) {
             // Check if mouse is inside this image's bounds
             let left = slot.position.x;
             let right = slot.position.x + slot.size.x;
@@ -242,19 +281,62 @@ impl GTexViewerApp {
                             format!("{file_size_kb_val:.1} KB")
                         };
 
-                        // Extract filename from path
-                        let file_name = image
-                            .path
-                            .file_name()
-                            .and_then(|name| name.to_str())
-                            .unwrap_or("Unknown")
-                            .to_string();
+                        // File name, with the containers nested entries sit in
+                        let mut file_name = image.metadata.display_path();
+                        if let (Some(frame), Some(animation)) =
+                            (image.expanded_frame, &image.animation)
+                        {
+                            file_name.push_str(&format!(
+                                " · frame {}/{}",
+                                frame + 1,
+                                animation.frame_count()
+                            ));
+                        }
+                        if let Some(layers) = &image.layers {
+                            file_name.push_str(&format!(
+                                " · layer {}/{}",
+                                layers.current + 1,
+                                layers.layer_count()
+                            ));
+                        }
 
                         self.hovered_image_info = Some(HoveredImageInfo {
                             file_name,
                             dimensions: format!("{}×{}", image.info.width, image.info.height),
                             file_size: file_size_str,
                             color_space: image.info.color_space.clone(),
+                            alpha_coverage: image
+                                .analysis
+                                .alpha_coverage
+                                .as_ref()
+                                .map(|coverage| coverage.summary(self.alpha_cutoff)),
+                            premultiplied: image.analysis.premultiplied.map(|premultiplied| {
+                                let shown = if self.unpremultiply.applies_to(premultiplied) {
+                                    "shown straight"
+                                } else if premultiplied.is_authoritative() {
+                                    "shown as stored"
+                                } else {
+                                    "shown as stored, A to un-premultiply"
+                                };
+                                format!("{}, {shown}", premultiplied.name())
+                            }),
+                            pixel: pixel.map(|value| value.summary()),
+                            sampler: (image.sampler_preview.tiling
+                                || image.sampler_preview.asset_sampler.is_some())
+                            .then(|| image.sampler_preview.summary()),
+                            violations: slot
+                                .violations
+                                .iter()
+                                .map(|violation| violation.message.clone())
+                                .collect(),
+                            streaming_cost: Some(self.image_streaming_cost(image).summary()),
+                            container_streaming_cost: {
+                                let cost =
+                                    self.container_streaming_cost(&image.metadata.source_path);
+                                (cost.textures > 1).then(|| {
+                                    format!("{} ({} textures)", cost.summary(), cost.textures)
+                                })
+                            },
                             mouse_pos: vec2(mouse_screen.0, mouse_screen.1),
                         });
                     }
@@ -270,36 +352,43 @@ impl GTexViewerApp {
                             format!("{file_size_kb_val:.1} KB")
                         };
 
-                        // Extract filename from path
-                        let file_name = original_metadata
-                            .source_path
-                            .file_name()
-                            .and_then(|name| name.to_str())
-                            .unwrap_or("Unknown")
-                            .to_string();
+                        // File name, with the containers nested entries sit in
+                        let file_name = original_metadata.display_path();
 
                         let status = "Loading...";
 
+                        // Lazily listed entries aren't probed until they load
+                        let probed = !original_metadata.embedded_hint.deferred_probe();
+                        let dimensions = if probed && original_metadata.width > 0 {
+                            format!("{}×{}", original_metadata.width, original_metadata.height)
+                        } else {
+                            "?×?".to_string()
+                        };
+                        let format = if probed {
+                            format!("{:?}", original_metadata.format)
+                        } else {
+                            "Unknown format".to_string()
+                        };
+
                         self.hovered_image_info = Some(HoveredImageInfo {
                             file_name,
-                            dimensions: format!(
-                                "{}×{}",
-                                original_metadata.width, original_metadata.height
-                            ),
+                            dimensions,
                             file_size: file_size_str,
-                            color_space: format!("{:?} ({})", original_metadata.format, status),
+                            color_space: format!("{format} ({status})"),
+                            alpha_coverage: None,
+                            premultiplied: None,
+                            pixel: None,
+                            sampler: None,
+                            violations: Vec::new(),
+                            streaming_cost: None,
+                            container_streaming_cost: None,
                             mouse_pos: vec2(mouse_screen.0, mouse_screen.1),
                         });
                     }
                     ImageState::Failed { metadata, error } => {
                         // Show basic info for failed images
                         let (file_name, dimensions, file_size) = if let Some(metadata) = metadata {
-                            let file_name = metadata
-                                .source_path
-                                .file_name()
-                                .and_then(|name| name.to_str())
-                                .unwrap_or("Unknown")
-                                .to_string();
+                            let file_name = metadata.display_path();
                             let dimensions = format!("{}×{}", metadata.width, metadata.height);
                             let file_size_mb = metadata.file_size as f64 / (1024.0 * 1024.0);
                             let file_size = if file_size_mb >= 1.0 {
@@ -322,6 +411,13 @@ impl GTexViewerApp {
                             dimensions,
                             file_size,
                             color_space: format!("Error: {error}"),
+                            alpha_coverage: None,
+                            premultiplied: None,
+                            pixel: None,
+                            sampler: None,
+                            violations: Vec::new(),
+                            streaming_cost: None,
+                            container_streaming_cost: None,
                             mouse_pos: vec2(mouse_screen.0, mouse_screen.1),
                         });
                     }
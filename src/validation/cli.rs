@@ -0,0 +1,140 @@
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+use crate::analysis::TextureAnalysis;
+use crate::texture_pipeline::{EmbeddedMetadata, Pipeline, is_remote_url, is_stdin_path};
+use crate::validation::report::{EntryReport, ValidationReport};
+use crate::validation::{ValidationRules, Violation};
+
+/// Exit code when every entry passed
+pub const EXIT_OK: i32 = 0;
+/// Exit code when at least one entry violated a rule or failed to load, or no entry
+/// was found to check
+pub const EXIT_VIOLATIONS: i32 = 1;
+/// Exit code for usage or rules file errors, including paths that don't exist
+pub const EXIT_USAGE: i32 = 2;
+
+const USAGE: &str =
+    "Usage: gtexviewer validate [--rules <rules.toml>] [--report <report.json>] <paths>...";
+
+/// Headless `validate` subcommand: evaluate the rules against every entry found
+/// under the given paths, print violations and return the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let mut rules_path = None;
+    let mut report_path = None;
+    let mut paths = Vec::new();
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--rules" => rules_path = args_iter.next().map(PathBuf::from),
+            "--report" => report_path = args_iter.next().map(PathBuf::from),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return EXIT_OK;
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("{USAGE}");
+        return EXIT_USAGE;
+    }
+    if let Some(missing) = paths.iter().find(|path| {
+        !path.exists() && !is_stdin_path(path) && !is_remote_url(&path.to_string_lossy())
+    }) {
+        eprintln!("error: {} does not exist", missing.display());
+        return EXIT_USAGE;
+    }
+
+    let rules = match rules_path {
+        Some(path) => match ValidationRules::load(&path) {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("error: {e:#}");
+                return EXIT_USAGE;
+            }
+        },
+        None => match ValidationRules::load_default() {
+            Some(rules) => rules,
+            None => {
+                eprintln!("error: no rules file given and none found");
+                eprintln!("{USAGE}");
+                return EXIT_USAGE;
+            }
+        },
+    };
+
+    let metadata_list = Pipeline::new().extract_all_metadata_recursive(paths);
+    let entries: Vec<EntryReport> = metadata_list
+        .into_par_iter()
+        .map(|metadata| validate_entry(&rules, metadata))
+        .collect();
+
+    for entry in &entries {
+        for violation in &entry.violations {
+            println!(
+                "{}:{}: [{}] {}",
+                entry.source.display(),
+                entry.name,
+                violation.rule,
+                violation.message
+            );
+        }
+    }
+
+    let report = ValidationReport::new(rules.source.clone(), entries);
+    println!(
+        "{} of {} entries failed validation",
+        report.failed, report.checked
+    );
+
+    if let Some(path) = report_path
+        && let Err(e) = report.write_json(&path)
+    {
+        eprintln!("error: {e:#}");
+        return EXIT_USAGE;
+    }
+
+    // An empty folder or only unsupported files must not read as a clean pass
+    if report.checked == 0 {
+        eprintln!("error: no image entries found to validate");
+        return EXIT_VIOLATIONS;
+    }
+
+    if report.failed > 0 {
+        EXIT_VIOLATIONS
+    } else {
+        EXIT_OK
+    }
+}
+
+/// Decode a single entry on the CPU and evaluate the rules against it
+/// Entries that can't be decoded are reported as a `load` violation
+fn validate_entry(rules: &ValidationRules, mut metadata: EmbeddedMetadata) -> EntryReport {
+    let pipeline = Pipeline::new();
+
+    let violations = pipeline
+        .metadata_to_loaded_data(&metadata)
+        .and_then(|data| {
+            // Lazily listed entries are checked against their probed format
+            (metadata.format, metadata.width, metadata.height) =
+                (data.format, data.width, data.height);
+            let (image, info) = pipeline.parse_image_data(&data)?;
+            Ok((TextureAnalysis::analyze(&data, &image), info))
+        })
+        .map(|(analysis, info)| rules.evaluate(&metadata, &info, &analysis))
+        .unwrap_or_else(|e| {
+            vec![Violation {
+                rule: "load",
+                message: format!("Failed to load: {e}"),
+            }]
+        });
+
+    EntryReport {
+        source: metadata.source_path,
+        name: metadata.name,
+        violations,
+    }
+}
@@ -0,0 +1,359 @@
+pub mod cli;
+pub mod report;
+
+use anyhow::{Context, Result};
+use macroquad::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::analysis::TextureAnalysis;
+use crate::loading::LoadedImage;
+use crate::texture_pipeline::{EmbeddedMetadata, ImageInfo};
+use crate::types::{GTexViewerApp, ImageState};
+use report::{EntryReport, ValidationReport};
+
+/// Environment variable pointing at a rules file
+pub const RULES_ENV: &str = "GTEXVIEWER_RULES";
+
+/// Rules file picked up from the working directory when no override is given
+pub const DEFAULT_RULES_FILE: &str = "gtexviewer-rules.toml";
+
+/// Rules file as written by the user
+///
+/// ```toml
+/// max_width = 4096
+/// max_height = 4096
+/// require_power_of_two = true
+/// allowed_formats = ["png", "ktx2", "dds"]
+/// name_pattern = "^T_[A-Za-z0-9]+_(D|N|ORM)\\."
+///
+/// [albedo]
+/// name_pattern = "_(D|BaseColor|Albedo)\\."
+/// min = 30
+/// max = 240
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    max_width: Option<usize>,
+    max_height: Option<usize>,
+    #[serde(default)]
+    require_power_of_two: bool,
+    allowed_formats: Option<Vec<String>>,
+    name_pattern: Option<String>,
+    albedo: Option<AlbedoRuleFile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AlbedoRuleFile {
+    name_pattern: String,
+    #[serde(default = "default_albedo_min")]
+    min: u8,
+    #[serde(default = "default_albedo_max")]
+    max: u8,
+}
+
+fn default_albedo_min() -> u8 {
+    30
+}
+
+fn default_albedo_max() -> u8 {
+    240
+}
+
+/// Albedo brightness range, applied to entries whose name matches `name_pattern`
+/// Values are sRGB 0-255 and checked against the 1st/99th luma percentiles
+#[derive(Debug, Clone)]
+pub struct AlbedoRule {
+    pub name_pattern: Regex,
+    pub min: u8,
+    pub max: u8,
+}
+
+/// Compiled validation rules evaluated against every loaded entry
+#[derive(Debug, Clone)]
+pub struct ValidationRules {
+    pub source: PathBuf,
+    pub max_width: Option<usize>,
+    pub max_height: Option<usize>,
+    pub require_power_of_two: bool,
+    pub allowed_formats: Option<Vec<String>>,
+    pub name_pattern: Option<Regex>,
+    pub albedo: Option<AlbedoRule>,
+}
+
+/// A single failed rule for an entry
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl ValidationRules {
+    /// Load rules from `$GTEXVIEWER_RULES` or `gtexviewer-rules.toml` in the working directory
+    /// (or next to the executable in portable mode)
+    pub fn load_default() -> Option<Self> {
+        let path = crate::portable::resolve_config_path(
+            std::env::var(RULES_ENV)
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(DEFAULT_RULES_FILE)),
+        );
+        if !path.exists() {
+            return None;
+        }
+
+        match Self::load(&path) {
+            Ok(rules) => {
+                log::info!("📏 Validation rules loaded from {}", path.display());
+                Some(rules)
+            }
+            Err(e) => {
+                log::warn!(
+                    "⚠️ Failed to load validation rules {}: {e:#}",
+                    path.display()
+                );
+                None
+            }
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read rules file: {}", path.display()))?;
+        let file: RulesFile = toml::from_str(&content)
+            .with_context(|| format!("Invalid rules file: {}", path.display()))?;
+
+        let name_pattern = file
+            .name_pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .context("Invalid name_pattern")?;
+
+        let albedo = file
+            .albedo
+            .map(|albedo| -> Result<AlbedoRule> {
+                Ok(AlbedoRule {
+                    name_pattern: Regex::new(&albedo.name_pattern)
+                        .context("Invalid albedo.name_pattern")?,
+                    min: albedo.min,
+                    max: albedo.max,
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            source: path.to_path_buf(),
+            max_width: file.max_width,
+            max_height: file.max_height,
+            require_power_of_two: file.require_power_of_two,
+            allowed_formats: file
+                .allowed_formats
+                .map(|formats| formats.iter().map(|f| f.to_lowercase()).collect()),
+            name_pattern,
+            albedo,
+        })
+    }
+
+    /// Evaluate every rule against a decoded entry
+    pub fn evaluate(
+        &self,
+        metadata: &EmbeddedMetadata,
+        info: &ImageInfo,
+        analysis: &TextureAnalysis,
+    ) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let (width, height) = (info.width as usize, info.height as usize);
+
+        if let Some(max_width) = self.max_width
+            && width > max_width
+        {
+            violations.push(Violation {
+                rule: "max_width",
+                message: format!("Width {width} exceeds {max_width}"),
+            });
+        }
+
+        if let Some(max_height) = self.max_height
+            && height > max_height
+        {
+            violations.push(Violation {
+                rule: "max_height",
+                message: format!("Height {height} exceeds {max_height}"),
+            });
+        }
+
+        if self.require_power_of_two && !(width.is_power_of_two() && height.is_power_of_two()) {
+            violations.push(Violation {
+                rule: "require_power_of_two",
+                message: format!("{width}×{height} is not power-of-two"),
+            });
+        }
+
+        if let Some(ref allowed) = self.allowed_formats {
+            let format = format_name(metadata.format);
+            if !allowed.contains(&format) {
+                violations.push(Violation {
+                    rule: "allowed_formats",
+                    message: format!("Format {format} is not allowed"),
+                });
+            }
+        }
+
+        let file_name = entry_file_name(&metadata.name);
+
+        if let Some(ref pattern) = self.name_pattern
+            && !pattern.is_match(file_name)
+        {
+            violations.push(Violation {
+                rule: "name_pattern",
+                message: format!("Name does not match {}", pattern.as_str()),
+            });
+        }
+
+        if let Some(ref albedo) = self.albedo
+            && albedo.name_pattern.is_match(file_name)
+            && let Some(luminance) = analysis.luminance
+        {
+            if luminance.low < albedo.min {
+                violations.push(Violation {
+                    rule: "albedo",
+                    message: format!(
+                        "Albedo too dark: 1st percentile {} < {}",
+                        luminance.low, albedo.min
+                    ),
+                });
+            }
+            if luminance.high > albedo.max {
+                violations.push(Violation {
+                    rule: "albedo",
+                    message: format!(
+                        "Albedo too bright: 99th percentile {} > {}",
+                        luminance.high, albedo.max
+                    ),
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+/// Lowercase format name used by `allowed_formats` (e.g. "png", "ktx2", "heif")
+pub fn format_name(format: imagesize::ImageType) -> String {
+    let debug_name = format!("{format:?}");
+    debug_name
+        .split('(')
+        .next()
+        .unwrap_or(&debug_name)
+        .to_lowercase()
+}
+
+/// Last path component of an entry name (container entries may include folders)
+fn entry_file_name(name: &str) -> &str {
+    name.rsplit(['/', '\\']).next().unwrap_or(name)
+}
+
+impl GTexViewerApp {
+    /// Run the active rules against a freshly loaded image
+    pub fn validate_loaded_image(&self, image: &LoadedImage) -> Vec<Violation> {
+        let Some(ref rules) = self.validation_rules else {
+            return Vec::new();
+        };
+
+        let violations = rules.evaluate(&image.metadata, &image.info, &image.analysis);
+        for violation in &violations {
+            log::warn!(
+                "📏 {} [{}]: {}",
+                image.metadata.name,
+                violation.rule,
+                violation.message
+            );
+        }
+        violations
+    }
+
+    pub fn handle_report_export_input(&mut self) {
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        let shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+
+        // Ctrl+E exports the validation report for all loaded entries (Ctrl+Shift+E is the manifest)
+        if ctrl_down && !shift_down && is_key_pressed(KeyCode::E) {
+            self.export_validation_report(Path::new(report::DEFAULT_REPORT_FILE));
+        }
+    }
+
+    pub fn export_validation_report(&self, path: &Path) {
+        let Some(ref rules) = self.validation_rules else {
+            log::info!("📏 No validation rules loaded ({DEFAULT_RULES_FILE} or ${RULES_ENV})");
+            return;
+        };
+
+        let entries = self
+            .image_slots
+            .iter()
+            .filter_map(|slot| match &slot.state {
+                ImageState::Loaded { image } => Some(EntryReport {
+                    source: image.metadata.source_path.clone(),
+                    name: image.metadata.name.clone(),
+                    violations: slot.violations.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let report = ValidationReport::new(rules.source.clone(), entries);
+        match report.write_json(path) {
+            Ok(()) => log::info!(
+                "📏 Validation report written to {} ({}/{} entries failed)",
+                path.display(),
+                report.failed,
+                report.checked
+            ),
+            Err(e) => log::error!("Failed to export validation report: {e:#}"),
+        }
+    }
+
+    /// Red badge with the violation count at the top-right corner of each failing slot
+    pub fn draw_validation_badges(&self) {
+        let badge_radius = 10.0;
+        let text_size = 14.0;
+
+        for slot in &self.image_slots {
+            if slot.violations.is_empty() || slot.hidden {
+                continue;
+            }
+
+            let corner = self
+                .camera
+                .world_to_screen(vec2(slot.position.x + slot.size.x, slot.position.y));
+            let center = vec2(corner.x - badge_radius - 4.0, corner.y + badge_radius + 4.0);
+
+            draw_circle(
+                center.x,
+                center.y,
+                badge_radius,
+                Color::new(0.85, 0.1, 0.1, 0.95),
+            );
+
+            let text = slot.violations.len().to_string();
+            let dims = measure_text(&text, self.ui_font.as_ref(), text_size as u16, 1.0);
+            draw_text_ex(
+                &text,
+                (center.x - dims.width / 2.0).round(),
+                (center.y + dims.offset_y / 2.0).round(),
+                TextParams {
+                    font: self.ui_font.as_ref(),
+                    font_size: text_size as u16,
+                    color: WHITE,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::validation::Violation;
+
+/// Default file name for exported validation reports
+pub const DEFAULT_REPORT_FILE: &str = "gtexviewer-report.json";
+
+/// Validation results for one entry
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryReport {
+    pub source: PathBuf,
+    pub name: String,
+    pub violations: Vec<Violation>,
+}
+
+/// Exportable validation report
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub rules: PathBuf,
+    pub checked: usize,
+    pub failed: usize,
+    pub entries: Vec<EntryReport>,
+}
+
+impl ValidationReport {
+    pub fn new(rules: PathBuf, entries: Vec<EntryReport>) -> Self {
+        Self {
+            rules,
+            checked: entries.len(),
+            failed: entries
+                .iter()
+                .filter(|entry| !entry.violations.is_empty())
+                .count(),
+            entries,
+        }
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write report: {}", path.display()))
+    }
+}
@@ -0,0 +1,451 @@
+use anyhow::{Context, Result};
+use macroquad::math::Rect as MacroRect;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::background::Background;
+use crate::status_bar::STATUS_BAR_HEIGHT;
+use crate::types::GTexViewerApp;
+
+/// Environment variable pointing at the workspace layout file
+pub const WORKSPACE_CONFIG_ENV: &str = "GTEXVIEWER_WORKSPACE";
+
+const WORKSPACE_FILE: &str = "workspace.toml";
+
+/// Gap between panels and the window edge
+const MARGIN: f32 = 10.0;
+/// Top docks start below the breadcrumb row
+const TOP_START: f32 = 40.0;
+/// Width of the left/right dock columns
+const SIDE_WIDTH: f32 = 230.0;
+
+/// Screen edge a panel is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dock {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Dock {
+    pub fn name(self) -> &'static str {
+        match self {
+            Dock::Top => "top",
+            Dock::Bottom => "bottom",
+            Dock::Left => "left",
+            Dock::Right => "right",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Dock::Top => Dock::Right,
+            Dock::Right => Dock::Bottom,
+            Dock::Bottom => Dock::Left,
+            Dock::Left => Dock::Top,
+        }
+    }
+
+    fn is_side(self) -> bool {
+        matches!(self, Dock::Left | Dock::Right)
+    }
+}
+
+/// Panels that can be shown, hidden and docked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Levels,
+    Sampling,
+    Timeline,
+    OffsetMap,
+    Histogram,
+    Details,
+}
+
+impl Panel {
+    /// Stacking order within a dock, innermost (closest to the edge) first
+    pub const ALL: [Panel; 6] = [
+        Panel::Levels,
+        Panel::Sampling,
+        Panel::Timeline,
+        Panel::OffsetMap,
+        Panel::Histogram,
+        Panel::Details,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Panel::Levels => "Levels",
+            Panel::Sampling => "Sampling",
+            Panel::Timeline => "Timeline",
+            Panel::OffsetMap => "Offset map",
+            Panel::Histogram => "Histogram",
+            Panel::Details => "Details",
+        }
+    }
+
+    /// Natural size; width is ignored for panels that stretch along top/bottom docks
+    fn size(self) -> Vec2 {
+        match self {
+            Panel::Levels => crate::levels::LEVELS_WIDGET_SIZE,
+            Panel::Sampling => crate::sampling::SAMPLING_WIDGET_SIZE,
+            Panel::Timeline => vec2(SIDE_WIDTH, crate::timeline::TIMELINE_HEIGHT),
+            Panel::OffsetMap => vec2(SIDE_WIDTH, crate::offset_map::OFFSET_MAP_HEIGHT),
+            Panel::Histogram => vec2(SIDE_WIDTH, crate::histogram::HISTOGRAM_HEIGHT),
+            Panel::Details => vec2(SIDE_WIDTH, crate::details::DETAILS_HEIGHT),
+        }
+    }
+
+    fn stretches(self) -> bool {
+        !matches!(
+            self,
+            Panel::Levels | Panel::Sampling | Panel::Histogram | Panel::Details
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanelPlacement {
+    pub visible: bool,
+    pub dock: Dock,
+}
+
+/// Which panels are shown and where, plus the canvas background, persisted per machine
+///
+/// ```toml
+/// background = "gray"
+///
+/// [levels]
+/// visible = true
+/// dock = "right"
+///
+/// [sampling]
+/// visible = false
+/// dock = "right"
+///
+/// [timeline]
+/// visible = true
+/// dock = "bottom"
+///
+/// [offset_map]
+/// visible = false
+/// dock = "top"
+///
+/// [histogram]
+/// visible = true
+/// dock = "left"
+///
+/// [details]
+/// visible = false
+/// dock = "left"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceLayout {
+    pub background: Background, // Plain value, so it serializes ahead of the panel tables
+    pub levels: PanelPlacement,
+    pub sampling: PanelPlacement,
+    pub timeline: PanelPlacement,
+    pub offset_map: PanelPlacement,
+    pub histogram: PanelPlacement,
+    pub details: PanelPlacement,
+}
+
+impl Default for WorkspaceLayout {
+    fn default() -> Self {
+        Self {
+            background: Background::default(),
+            levels: PanelPlacement {
+                visible: false,
+                dock: Dock::Right,
+            },
+            sampling: PanelPlacement {
+                visible: false,
+                dock: Dock::Right,
+            },
+            timeline: PanelPlacement {
+                visible: true,
+                dock: Dock::Bottom,
+            },
+            offset_map: PanelPlacement {
+                visible: false,
+                dock: Dock::Top,
+            },
+            histogram: PanelPlacement {
+                visible: false,
+                dock: Dock::Right,
+            },
+            details: PanelPlacement {
+                visible: false,
+                dock: Dock::Left,
+            },
+        }
+    }
+}
+
+impl WorkspaceLayout {
+    /// Every panel hidden, full canvas for browsing
+    pub fn browse() -> Self {
+        let mut layout = Self::default();
+        for panel in Panel::ALL {
+            layout.placement_mut(panel).visible = false;
+        }
+        layout
+    }
+
+    /// Every panel shown in its default dock
+    pub fn inspect() -> Self {
+        let mut layout = Self::default();
+        for panel in Panel::ALL {
+            layout.placement_mut(panel).visible = true;
+        }
+        layout
+    }
+
+    pub fn placement(&self, panel: Panel) -> PanelPlacement {
+        match panel {
+            Panel::Levels => self.levels,
+            Panel::Sampling => self.sampling,
+            Panel::Timeline => self.timeline,
+            Panel::OffsetMap => self.offset_map,
+            Panel::Histogram => self.histogram,
+            Panel::Details => self.details,
+        }
+    }
+
+    pub fn placement_mut(&mut self, panel: Panel) -> &mut PanelPlacement {
+        match panel {
+            Panel::Levels => &mut self.levels,
+            Panel::Sampling => &mut self.sampling,
+            Panel::Timeline => &mut self.timeline,
+            Panel::OffsetMap => &mut self.offset_map,
+            Panel::Histogram => &mut self.histogram,
+            Panel::Details => &mut self.details,
+        }
+    }
+
+    /// Layout from `$GTEXVIEWER_WORKSPACE` or the per-user config directory, else defaults
+    pub fn load_default() -> Self {
+        let Some(path) = Self::config_path() else {
+            return Self::default();
+        };
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match Self::load(&path) {
+            Ok(layout) => {
+                log::info!("🪟 Workspace layout loaded from {}", path.display());
+                layout
+            }
+            Err(e) => {
+                log::warn!(
+                    "⚠️ Failed to load workspace layout {}: {e:#}",
+                    path.display()
+                );
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workspace layout: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid workspace layout: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write workspace layout: {}", path.display()))
+    }
+
+    /// Where the layout lives on this machine (next to the executable when portable)
+    pub fn config_path() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var(WORKSPACE_CONFIG_ENV) {
+            return Some(crate::portable::resolve_config_path(PathBuf::from(path)));
+        }
+
+        crate::portable::config_dir().map(|dir| dir.join(WORKSPACE_FILE))
+    }
+}
+
+impl GTexViewerApp {
+    /// Whether a panel currently takes up screen space
+    /// The timeline only appears while an animation is focused
+    pub fn panel_shown(&self, panel: Panel) -> bool {
+        self.workspace.placement(panel).visible
+            && (panel != Panel::Timeline || self.animation_focus.is_some())
+    }
+
+    /// Screen rect of a panel given where every shown panel is docked
+    /// Side docks stack upward from the bottom corner; top/bottom docks stack inward
+    /// and span the width left between the side columns
+    pub fn panel_rect(&self, target: Panel) -> MacroRect {
+        let shown = |panel: Panel| panel == target || self.panel_shown(panel);
+        let side_used = |dock: Dock| {
+            Panel::ALL
+                .into_iter()
+                .any(|panel| shown(panel) && self.workspace.placement(panel).dock == dock)
+        };
+
+        let left = MARGIN
+            + if side_used(Dock::Left) {
+                SIDE_WIDTH + MARGIN
+            } else {
+                0.0
+            };
+        let right = screen_width()
+            - MARGIN
+            - if side_used(Dock::Right) {
+                SIDE_WIDTH + MARGIN
+            } else {
+                0.0
+            };
+
+        // Bottom edges sit above the status bar
+        let floor = screen_height() - STATUS_BAR_HEIGHT - MARGIN;
+        let mut left_y = floor;
+        let mut right_y = floor;
+        let mut top_y = TOP_START;
+        let mut bottom_y = floor;
+        let mut rect = MacroRect::new(0.0, 0.0, 0.0, 0.0);
+
+        for panel in Panel::ALL.into_iter().filter(|&panel| shown(panel)) {
+            let size = panel.size();
+            let dock = self.workspace.placement(panel).dock;
+            let width = if dock.is_side() {
+                SIDE_WIDTH
+            } else if panel.stretches() {
+                (right - left).max(100.0)
+            } else {
+                size.x
+            };
+
+            rect = match dock {
+                Dock::Left => {
+                    left_y -= size.y;
+                    let rect = MacroRect::new(MARGIN, left_y, width, size.y);
+                    left_y -= MARGIN;
+                    rect
+                }
+                Dock::Right => {
+                    right_y -= size.y;
+                    let rect =
+                        MacroRect::new(screen_width() - MARGIN - width, right_y, width, size.y);
+                    right_y -= MARGIN;
+                    rect
+                }
+                Dock::Bottom => {
+                    bottom_y -= size.y;
+                    let rect = MacroRect::new(left, bottom_y, width, size.y);
+                    bottom_y -= MARGIN;
+                    rect
+                }
+                Dock::Top => {
+                    let rect = MacroRect::new(left, top_y, width, size.y);
+                    top_y += size.y + MARGIN;
+                    rect
+                }
+            };
+
+            if panel == target {
+                break;
+            }
+        }
+
+        rect
+    }
+
+    /// Show or hide a panel and remember the choice
+    pub fn toggle_panel(&mut self, panel: Panel) {
+        let placement = self.workspace.placement_mut(panel);
+        placement.visible = !placement.visible;
+        log::info!(
+            "🪟 {} {}",
+            panel.name(),
+            if placement.visible { "shown" } else { "hidden" }
+        );
+        self.save_workspace();
+    }
+
+    /// W switches between the Browse and Inspect presets,
+    /// Alt+L / Alt+S / Alt+T / Alt+M / Alt+I / Alt+D move the levels, sampling, timeline,
+    /// offset map, histogram and details sidebar to the next dock,
+    /// T toggles the timeline
+    pub fn handle_workspace_input(&mut self) {
+        let alt_down = is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt);
+
+        if alt_down {
+            for (key, panel) in [
+                (KeyCode::L, Panel::Levels),
+                (KeyCode::S, Panel::Sampling),
+                (KeyCode::T, Panel::Timeline),
+                (KeyCode::M, Panel::OffsetMap),
+                (KeyCode::I, Panel::Histogram),
+                (KeyCode::D, Panel::Details),
+            ] {
+                if is_key_pressed(key) {
+                    let placement = self.workspace.placement_mut(panel);
+                    placement.dock = placement.dock.next();
+                    placement.visible = true;
+                    log::info!("🪟 {} docked {}", panel.name(), placement.dock.name());
+                    self.save_workspace();
+                }
+            }
+            return;
+        }
+
+        // Ctrl+T and Ctrl+W open and close tabs
+        let ctrl_down = is_key_down(KeyCode::LeftControl)
+            || is_key_down(KeyCode::RightControl)
+            || is_key_down(KeyCode::LeftSuper)
+            || is_key_down(KeyCode::RightSuper);
+        if ctrl_down {
+            return;
+        }
+
+        if is_key_pressed(KeyCode::T) {
+            self.toggle_panel(Panel::Timeline);
+        }
+
+        if is_key_pressed(KeyCode::W) {
+            // Presets only cover panels; the background stays as chosen
+            let background = self.workspace.background;
+            let browse = WorkspaceLayout {
+                background,
+                ..WorkspaceLayout::browse()
+            };
+            let (name, layout) = if self.workspace == browse {
+                ("Inspect", WorkspaceLayout::inspect())
+            } else {
+                ("Browse", browse)
+            };
+            self.workspace = WorkspaceLayout {
+                background,
+                ..layout
+            };
+            log::info!("🪟 {name} workspace");
+            self.save_workspace();
+        }
+    }
+
+    pub(crate) fn save_workspace(&self) {
+        macroquad::miniquad::window::schedule_update();
+
+        let Some(path) = WorkspaceLayout::config_path() else {
+            return;
+        };
+        if let Err(e) = self.workspace.save(&path) {
+            log::warn!("⚠️ Failed to save workspace layout: {e:#}");
+        }
+    }
+}